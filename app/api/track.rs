@@ -1,7 +1,10 @@
-use std::collections::BTreeMap;
+use std::{
+	collections::{BTreeMap, HashMap},
+	convert::TryInto,
+};
 use tangram_app_common::{
 	error::{bad_request, service_unavailable},
-	model::get_model,
+	model::{get_model, get_model_data},
 	monitor_event::{
 		BinaryClassificationPredictOutput, MonitorEvent, MulticlassClassificationPredictOutput,
 		NumberOrString, PredictOutput, PredictionMonitorEvent, RegressionPredictOutput,
@@ -46,11 +49,22 @@ pub async fn post(
 		Err(_) => return Ok(service_unavailable()),
 	};
 	let mut models = BTreeMap::new();
+	let mut predict_models = BTreeMap::new();
 	for monitor_event in monitor_events {
 		match monitor_event {
 			MonitorEvent::Prediction(monitor_event) => {
-				let handle_prediction_result =
-					handle_prediction_monitor_event(&mut db, &mut models, monitor_event).await;
+				let predict_models = if context.options.production_feature_contributions_enabled {
+					Some(&mut predict_models)
+				} else {
+					None
+				};
+				let handle_prediction_result = handle_prediction_monitor_event(
+					&mut db,
+					&mut models,
+					predict_models,
+					monitor_event,
+				)
+				.await;
 				if handle_prediction_result.is_err() {
 					return Ok(bad_request());
 				}
@@ -72,26 +86,129 @@ pub async fn post(
 	Ok(response)
 }
 
+/// If `predict_models` is `Some`, the feature contributions for the prediction are computed and recorded in the production stats.
 async fn handle_prediction_monitor_event(
 	mut db: &mut sqlx::Transaction<'_, sqlx::Any>,
 	models: &mut BTreeMap<Id, tangram_core::model::Model>,
+	mut predict_models: Option<&mut BTreeMap<Id, tangram_core::predict::Model>>,
 	monitor_event: PredictionMonitorEvent,
 ) -> Result<()> {
 	let model_id = monitor_event.model_id;
-	let model = match models.get(&model_id) {
-		Some(model) => model,
-		None => {
-			let model = get_model(&mut db, model_id).await?;
+	let model_is_cached = models.contains_key(&model_id);
+	let predict_model_is_cached = predict_models
+		.as_ref()
+		.map(|predict_models| predict_models.contains_key(&model_id))
+		.unwrap_or(true);
+	// Retrieve the model's data once, and deserialize it for each cache that is missing the model.
+	if !model_is_cached || !predict_model_is_cached {
+		let data = get_model_data(&mut db, model_id).await?;
+		if !model_is_cached {
+			let model = tangram_core::model::Model::from_slice(&data)?;
 			models.insert(model_id, model);
-			models.get(&model_id).unwrap()
 		}
+		if let Some(predict_models) = predict_models.as_mut() {
+			if !predict_model_is_cached {
+				let predict_model = tangram_core::model::Model::from_slice(&data)?.try_into()?;
+				predict_models.insert(model_id, predict_model);
+			}
+		}
+	}
+	let model = models.get(&model_id).unwrap();
+	let feature_contributions = match predict_models {
+		Some(predict_models) => Some(compute_abs_feature_contributions(
+			predict_models.get(&model_id).unwrap(),
+			&monitor_event.input,
+		)?),
+		None => None,
 	};
 	write_prediction_monitor_event(&mut db, model_id, &monitor_event).await?;
-	insert_or_update_production_stats_for_monitor_event(&mut db, model_id, &model, monitor_event)
-		.await?;
+	insert_or_update_production_stats_for_monitor_event(
+		&mut db,
+		model_id,
+		&model,
+		monitor_event,
+		feature_contributions,
+	)
+	.await?;
 	Ok(())
 }
 
+/// Compute the absolute value of each feature's contribution to the model's prediction for `input`, in the same order as the model's features. For multiclass classifiers, this is the mean over the classes.
+fn compute_abs_feature_contributions(
+	model: &tangram_core::predict::Model,
+	input: &HashMap<String, serde_json::Value>,
+) -> Result<Vec<f32>> {
+	let input = input
+		.iter()
+		.map(|(column_name, value)| (column_name.clone(), value.clone()))
+		.collect();
+	let input = tangram_core::predict::PredictInput(vec![input]);
+	let output = tangram_core::predict::predict(model, input, None)?;
+	let abs_feature_contributions =
+		|feature_contributions: &tangram_core::predict::FeatureContributions| {
+			feature_contributions
+				.feature_contributions
+				.iter()
+				.map(|feature_contribution| feature_contribution_value(feature_contribution).abs())
+				.collect::<Vec<_>>()
+		};
+	let abs_feature_contributions = match output {
+		tangram_core::predict::PredictOutput::Regression(mut output) => output
+			.remove(0)
+			.feature_contributions
+			.as_ref()
+			.map(abs_feature_contributions),
+		tangram_core::predict::PredictOutput::BinaryClassification(mut output) => output
+			.remove(0)
+			.feature_contributions
+			.as_ref()
+			.map(abs_feature_contributions),
+		tangram_core::predict::PredictOutput::MulticlassClassification(mut output) => output
+			.remove(0)
+			.feature_contributions
+			.map(|feature_contributions| {
+				let n_classes = feature_contributions.len().to_f32().unwrap();
+				let mut mean_abs_feature_contributions = Vec::new();
+				for feature_contributions in feature_contributions.values() {
+					let abs_feature_contributions =
+						abs_feature_contributions(feature_contributions);
+					mean_abs_feature_contributions.resize(abs_feature_contributions.len(), 0.0);
+					for (mean, value) in mean_abs_feature_contributions
+						.iter_mut()
+						.zip(abs_feature_contributions)
+					{
+						*mean += value / n_classes;
+					}
+				}
+				mean_abs_feature_contributions
+			}),
+	};
+	abs_feature_contributions.ok_or_else(|| err!("failed to compute feature contributions"))
+}
+
+fn feature_contribution_value(
+	feature_contribution: &tangram_core::predict::FeatureContribution,
+) -> f32 {
+	match feature_contribution {
+		tangram_core::predict::FeatureContribution::Identity {
+			feature_contribution_value,
+			..
+		} => *feature_contribution_value,
+		tangram_core::predict::FeatureContribution::Normalized {
+			feature_contribution_value,
+			..
+		} => *feature_contribution_value,
+		tangram_core::predict::FeatureContribution::OneHotEncoded {
+			feature_contribution_value,
+			..
+		} => *feature_contribution_value,
+		tangram_core::predict::FeatureContribution::BagOfWords {
+			feature_contribution_value,
+			..
+		} => *feature_contribution_value,
+	}
+}
+
 async fn handle_true_value_monitor_event(
 	mut db: &mut sqlx::Transaction<'_, sqlx::Any>,
 	models: &mut BTreeMap<Id, tangram_core::model::Model>,
@@ -173,6 +290,7 @@ async fn insert_or_update_production_stats_for_monitor_event(
 	model_id: Id,
 	model: &tangram_core::model::Model,
 	monitor_event: PredictionMonitorEvent,
+	feature_contributions: Option<Vec<f32>>,
 ) -> Result<()> {
 	let date = monitor_event.date;
 	let hour = Utc
@@ -198,6 +316,9 @@ async fn insert_or_update_production_stats_for_monitor_event(
 		let data: Vec<u8> = base64::decode(data)?;
 		let mut production_stats: ProductionStats = serde_json::from_slice(&data)?;
 		production_stats.update(monitor_event);
+		if let Some(feature_contributions) = feature_contributions.as_ref() {
+			production_stats.update_feature_contributions(feature_contributions);
+		}
 		let data = serde_json::to_vec(&production_stats)?;
 		sqlx::query(
 			"
@@ -221,6 +342,9 @@ async fn insert_or_update_production_stats_for_monitor_event(
 		let end_date = hour + chrono::Duration::hours(1);
 		let mut production_stats = ProductionStats::new(&model, start_date, end_date);
 		production_stats.update(monitor_event);
+		if let Some(feature_contributions) = feature_contributions.as_ref() {
+			production_stats.update_feature_contributions(feature_contributions);
+		}
 		let data = serde_json::to_vec(&production_stats)?;
 		sqlx::query(
 			"
@@ -257,6 +381,7 @@ async fn insert_or_update_production_metrics_for_monitor_event(
 	let rows = sqlx::query(
 		"
 			select
+				predictions.input,
 				predictions.output
 			from
 				predictions
@@ -273,11 +398,22 @@ async fn insert_or_update_production_metrics_for_monitor_event(
 	let row = rows
 		.get(0)
 		.ok_or_else(|| err!("no prediction with identifier {}", identifier))?;
-	let output: String = row.get(0);
+	let input: String = row.get(0);
+	let input: Vec<u8> = base64::decode(input)?;
+	let input: HashMap<String, serde_json::Value> = serde_json::from_slice(input.as_slice())?;
+	let output: String = row.get(1);
 	let output: Vec<u8> = base64::decode(output)?;
 	let output: PredictOutput = serde_json::from_slice(output.as_slice())?;
+	let prediction_interval = match &output {
+		PredictOutput::Regression(RegressionPredictOutput {
+			lower: Some(lower),
+			upper: Some(upper),
+			..
+		}) => Some((*lower, *upper)),
+		_ => None,
+	};
 	let prediction = match output {
-		PredictOutput::Regression(RegressionPredictOutput { value }) => {
+		PredictOutput::Regression(RegressionPredictOutput { value, .. }) => {
 			NumberOrString::Number(value)
 		}
 		PredictOutput::BinaryClassification(BinaryClassificationPredictOutput {
@@ -315,7 +451,11 @@ async fn insert_or_update_production_metrics_for_monitor_event(
 		let data: String = row.get(0);
 		let data: Vec<u8> = base64::decode(data)?;
 		let mut production_metrics: ProductionMetrics = serde_json::from_slice(&data)?;
-		production_metrics.update((prediction, true_value));
+		production_metrics.update((prediction.clone(), true_value.clone()));
+		if let Some(prediction_interval) = prediction_interval {
+			production_metrics.update_prediction_interval(prediction_interval, &true_value);
+		}
+		production_metrics.update_fairness_metrics(&input, (prediction, true_value));
 		let data = serde_json::to_vec(&production_metrics)?;
 		sqlx::query(
 			"
@@ -338,7 +478,11 @@ async fn insert_or_update_production_metrics_for_monitor_event(
 		let start_date = hour;
 		let end_date = hour + chrono::Duration::hours(1);
 		let mut production_metrics = ProductionMetrics::new(&model, start_date, end_date);
-		production_metrics.update((prediction, true_value));
+		production_metrics.update((prediction.clone(), true_value.clone()));
+		if let Some(prediction_interval) = prediction_interval {
+			production_metrics.update_prediction_interval(prediction_interval, &true_value);
+		}
+		production_metrics.update_fairness_metrics(&input, (prediction, true_value));
 		let data = serde_json::to_vec(&production_metrics)?;
 		sqlx::query(
 			"
@@ -0,0 +1,506 @@
+/*!
+Alert rules watch a metric of a model's production data and fire when it goes above a threshold. After each hour ends, [`check_alert_rules`] computes the metric of every rule over that hour's production stats and metrics, records an alert for each rule whose threshold was exceeded, and notifies the rule's email address and webhook.
+*/
+
+use crate::{
+	drift::compute_column_drift,
+	email::send_email,
+	model::get_model,
+	production_metrics::{
+		ProductionMetrics, ProductionMetricsOutput, ProductionPredictionMetricsOutput,
+	},
+	production_stats::{ProductionColumnStatsOutput, ProductionStats, ProductionStatsOutput},
+};
+use std::{collections::BTreeMap, time::Duration};
+use tangram_deps::{
+	base64, chrono, chrono::prelude::*, num_traits::ToPrimitive, once_cell::sync::Lazy, reqwest,
+	serde_json, serde_json::json, sqlx, sqlx::prelude::*,
+};
+use tangram_metrics::StreamingMetric;
+use tangram_util::{err, error::Result, id::Id};
+
+/// Webhook requests are sent with a shared client, so connections are reused, and time out after this long, so an unresponsive webhook does not hold up the notifications for other rules.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+static WEBHOOK_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+	reqwest::Client::builder()
+		.timeout(WEBHOOK_TIMEOUT)
+		.build()
+		.unwrap()
+});
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type")]
+pub enum AlertMetric {
+	/// This is the fraction of predictions whose input was missing the column.
+	#[serde(rename = "absent_rate")]
+	AbsentRate { column_name: String },
+	/// This is the fraction of predictions whose input had a value for the column that is not valid for its type.
+	#[serde(rename = "invalid_rate")]
+	InvalidRate { column_name: String },
+	/// This is the population stability index of the column's production distribution against its training distribution.
+	#[serde(rename = "psi")]
+	Psi { column_name: String },
+	/// This is the training accuracy minus the production accuracy of a classifier.
+	#[serde(rename = "accuracy_drop")]
+	AccuracyDrop,
+	/// This is the production rmse minus the training rmse of a regressor.
+	#[serde(rename = "rmse_increase")]
+	RmseIncrease,
+}
+
+#[derive(Debug)]
+pub struct AlertRule {
+	pub id: Id,
+	pub model_id: Id,
+	pub metric: AlertMetric,
+	pub threshold: f32,
+	pub email: Option<String>,
+	pub webhook_url: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct Alert {
+	pub alert_rule_id: Id,
+	pub hour: DateTime<Utc>,
+	pub metric: AlertMetric,
+	pub threshold: f32,
+	pub value: f32,
+}
+
+impl AlertMetric {
+	pub fn title(&self) -> String {
+		match self {
+			AlertMetric::AbsentRate { column_name } => format!("Absent Rate of {}", column_name),
+			AlertMetric::InvalidRate { column_name } => format!("Invalid Rate of {}", column_name),
+			AlertMetric::Psi { column_name } => format!("PSI of {}", column_name),
+			AlertMetric::AccuracyDrop => "Accuracy Drop".to_owned(),
+			AlertMetric::RmseIncrease => "RMSE Increase".to_owned(),
+		}
+	}
+
+	/// Rates and accuracies are formatted as percents, and every other metric as a number.
+	pub fn format_value(&self, value: f32) -> String {
+		match self {
+			AlertMetric::AbsentRate { .. }
+			| AlertMetric::InvalidRate { .. }
+			| AlertMetric::AccuracyDrop => tangram_ui::format_percent(value),
+			AlertMetric::Psi { .. } | AlertMetric::RmseIncrease => tangram_ui::format_number(value),
+		}
+	}
+
+	/// Check that this metric applies to the model. Column metrics must name one of the model's columns, accuracy drop only applies to classifiers, and rmse increase only applies to regressors.
+	pub fn is_valid_for_model(&self, model: &tangram_core::model::Model) -> bool {
+		match self {
+			AlertMetric::AbsentRate { column_name }
+			| AlertMetric::InvalidRate { column_name }
+			| AlertMetric::Psi { column_name } => train_column_stats(model)
+				.iter()
+				.any(|column_stats| column_stats.column_name() == column_name),
+			AlertMetric::AccuracyDrop => matches!(
+				model,
+				tangram_core::model::Model::BinaryClassifier(_)
+					| tangram_core::model::Model::MulticlassClassifier(_)
+			),
+			AlertMetric::RmseIncrease => {
+				matches!(model, tangram_core::model::Model::Regressor(_))
+			}
+		}
+	}
+
+	/// Compute the value of this metric for an hour, or return `None` if the hour does not have the data the metric needs.
+	pub fn compute(
+		&self,
+		model: &tangram_core::model::Model,
+		production_stats: Option<&ProductionStatsOutput>,
+		production_metrics: Option<&ProductionMetricsOutput>,
+	) -> Option<f32> {
+		match self {
+			AlertMetric::AbsentRate { column_name } | AlertMetric::InvalidRate { column_name } => {
+				let production_stats = production_stats?;
+				if production_stats.row_count == 0 {
+					return None;
+				}
+				let column_stats = production_stats
+					.column_stats
+					.iter()
+					.find(|column_stats| column_stats.column_name() == column_name)?;
+				let (absent_count, invalid_count) = match column_stats {
+					ProductionColumnStatsOutput::Unknown(s) => (s.absent_count, s.invalid_count),
+					ProductionColumnStatsOutput::Number(s) => (s.absent_count, s.invalid_count),
+					ProductionColumnStatsOutput::Enum(s) => (s.absent_count, s.invalid_count),
+					ProductionColumnStatsOutput::Text(s) => (s.absent_count, s.invalid_count),
+				};
+				let count = match self {
+					AlertMetric::AbsentRate { .. } => absent_count,
+					_ => invalid_count,
+				};
+				Some(count.to_f32().unwrap() / production_stats.row_count.to_f32().unwrap())
+			}
+			AlertMetric::Psi { column_name } => {
+				let production_stats = production_stats?;
+				let train_column_stats = train_column_stats(model)
+					.iter()
+					.find(|column_stats| column_stats.column_name() == column_name)?;
+				let production_column_stats = production_stats
+					.column_stats
+					.iter()
+					.find(|column_stats| column_stats.column_name() == column_name)?;
+				compute_column_drift(train_column_stats, production_column_stats).psi
+			}
+			AlertMetric::AccuracyDrop => {
+				let production_metrics = production_metrics?.prediction_metrics.as_ref()?;
+				match (model, production_metrics) {
+					(
+						tangram_core::model::Model::BinaryClassifier(model),
+						ProductionPredictionMetricsOutput::BinaryClassification(production_metrics),
+					) => {
+						let training_accuracy = model
+							.test_metrics
+							.thresholds
+							.get(model.test_metrics.thresholds.len() / 2)?
+							.accuracy;
+						Some(training_accuracy - production_metrics.accuracy)
+					}
+					(
+						tangram_core::model::Model::MulticlassClassifier(model),
+						ProductionPredictionMetricsOutput::MulticlassClassification(
+							production_metrics,
+						),
+					) => Some(model.test_metrics.accuracy - production_metrics.accuracy),
+					_ => None,
+				}
+			}
+			AlertMetric::RmseIncrease => {
+				let production_metrics = production_metrics?.prediction_metrics.as_ref()?;
+				match (model, production_metrics) {
+					(
+						tangram_core::model::Model::Regressor(model),
+						ProductionPredictionMetricsOutput::Regression(production_metrics),
+					) => Some(production_metrics.rmse - model.test_metrics.rmse),
+					_ => None,
+				}
+			}
+		}
+	}
+}
+
+fn train_column_stats(model: &tangram_core::model::Model) -> &[tangram_core::model::ColumnStats] {
+	match model {
+		tangram_core::model::Model::Regressor(model) => model.train_column_stats.as_slice(),
+		tangram_core::model::Model::BinaryClassifier(model) => model.train_column_stats.as_slice(),
+		tangram_core::model::Model::MulticlassClassifier(model) => {
+			model.train_column_stats.as_slice()
+		}
+	}
+}
+
+/// Retrieves the alert rules for the model with the specified id.
+pub async fn get_alert_rules(
+	db: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+) -> Result<Vec<AlertRule>> {
+	let rows = sqlx::query(
+		"
+			select
+				id,
+				model_id,
+				metric,
+				threshold,
+				email,
+				webhook_url
+			from alert_rules
+			where
+				model_id = $1
+			order by created_at
+		",
+	)
+	.bind(&model_id.to_string())
+	.fetch_all(&mut *db)
+	.await?;
+	rows.iter().map(alert_rule_from_row).collect()
+}
+
+fn alert_rule_from_row(row: &sqlx::any::AnyRow) -> Result<AlertRule> {
+	let id: String = row.get(0);
+	let model_id: String = row.get(1);
+	let metric: String = row.get(2);
+	let threshold: f64 = row.get(3);
+	Ok(AlertRule {
+		id: id.parse()?,
+		model_id: model_id.parse()?,
+		metric: serde_json::from_str(&metric)?,
+		threshold: threshold.to_f32().unwrap(),
+		email: row.get(4),
+		webhook_url: row.get(5),
+	})
+}
+
+/// Retrieves the most recent alerts for the model with the specified id.
+pub async fn get_alerts(
+	db: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+	limit: i64,
+) -> Result<Vec<Alert>> {
+	let rows = sqlx::query(
+		"
+			select
+				alerts.alert_rule_id,
+				alerts.hour,
+				alert_rules.metric,
+				alert_rules.threshold,
+				alerts.value
+			from alerts
+			join alert_rules
+				on alert_rules.id = alerts.alert_rule_id
+			where
+				alerts.model_id = $1
+			order by alerts.hour desc
+			limit $2
+		",
+	)
+	.bind(&model_id.to_string())
+	.bind(&limit)
+	.fetch_all(&mut *db)
+	.await?;
+	rows.iter()
+		.map(|row| {
+			let alert_rule_id: String = row.get(0);
+			let hour: i64 = row.get(1);
+			let metric: String = row.get(2);
+			let threshold: f64 = row.get(3);
+			let value: f64 = row.get(4);
+			Ok(Alert {
+				alert_rule_id: alert_rule_id.parse()?,
+				hour: Utc.timestamp(hour, 0),
+				metric: serde_json::from_str(&metric)?,
+				threshold: threshold.to_f32().unwrap(),
+				value: value.to_f32().unwrap(),
+			})
+		})
+		.collect()
+}
+
+/// Check every alert rule that has not been checked for the last hour that has ended, and notify the rules that fire. Each model's rules are checked in their own transaction, and a model whose rules fail to be checked is logged and skipped so the other models' rules are still checked. Notification failures are logged rather than returned so that one broken webhook does not stop the other notifications from being sent.
+pub async fn check_alert_rules(
+	pool: &sqlx::AnyPool,
+	sendgrid_api_token: Option<&str>,
+) -> Result<()> {
+	let now = Utc::now();
+	let hour = Utc
+		.ymd(now.year(), now.month(), now.day())
+		.and_hms(now.hour(), 0, 0)
+		- chrono::Duration::hours(1);
+	let rows = sqlx::query(
+		"
+			select
+				id,
+				model_id,
+				metric,
+				threshold,
+				email,
+				webhook_url
+			from alert_rules
+			where
+				last_checked_hour is null or
+				last_checked_hour < $1
+		",
+	)
+	.bind(&hour.timestamp())
+	.fetch_all(pool)
+	.await?;
+	// Group the rules by model, so the model and its production data for the hour are retrieved once for all the model's rules.
+	let mut alert_rules_by_model: BTreeMap<Id, Vec<AlertRule>> = BTreeMap::new();
+	for row in rows.iter() {
+		match alert_rule_from_row(row) {
+			Ok(alert_rule) => alert_rules_by_model
+				.entry(alert_rule.model_id)
+				.or_insert_with(Vec::new)
+				.push(alert_rule),
+			Err(error) => eprintln!("{}", error),
+		}
+	}
+	for (model_id, alert_rules) in alert_rules_by_model {
+		let fired = match check_model_alert_rules(pool, model_id, alert_rules, hour).await {
+			Ok(fired) => fired,
+			Err(error) => {
+				eprintln!(
+					"failed to check the alert rules for model {}: {}",
+					model_id, error
+				);
+				continue;
+			}
+		};
+		// Notify only after the alerts are committed, so a failed commit does not send notifications for alerts that were not recorded.
+		for (alert_rule, value) in fired.iter() {
+			if let Err(error) = notify(alert_rule, *value, hour, sendgrid_api_token).await {
+				eprintln!("{}", error);
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Check the alert rules for one model in a transaction, and return the rules that fired with their values.
+async fn check_model_alert_rules(
+	pool: &sqlx::AnyPool,
+	model_id: Id,
+	alert_rules: Vec<AlertRule>,
+	hour: DateTime<Utc>,
+) -> Result<Vec<(AlertRule, f32)>> {
+	let mut db = pool.begin().await?;
+	let model = get_model(&mut db, model_id).await?;
+	let production_stats = get_hourly_production_stats(&mut db, &model, hour).await?;
+	let production_metrics = get_hourly_production_metrics(&mut db, &model, hour).await?;
+	let mut fired = Vec::new();
+	for alert_rule in alert_rules {
+		let value = alert_rule
+			.metric
+			.compute(
+				&model,
+				production_stats.as_ref(),
+				production_metrics.as_ref(),
+			)
+			.filter(|value| *value > alert_rule.threshold);
+		if let Some(value) = value {
+			sqlx::query(
+				"
+					insert into alerts (
+						id, alert_rule_id, model_id, hour, value
+					) values (
+						$1, $2, $3, $4, $5
+					)
+				",
+			)
+			.bind(&Id::new().to_string())
+			.bind(&alert_rule.id.to_string())
+			.bind(&alert_rule.model_id.to_string())
+			.bind(&hour.timestamp())
+			.bind(&value.to_f64().unwrap())
+			.execute(&mut *db)
+			.await?;
+		}
+		sqlx::query(
+			"
+				update alert_rules
+				set
+					last_checked_hour = $1
+				where
+					id = $2
+			",
+		)
+		.bind(&hour.timestamp())
+		.bind(&alert_rule.id.to_string())
+		.execute(&mut *db)
+		.await?;
+		if let Some(value) = value {
+			fired.push((alert_rule, value));
+		}
+	}
+	db.commit().await?;
+	Ok(fired)
+}
+
+async fn get_hourly_production_stats(
+	db: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model: &tangram_core::model::Model,
+	hour: DateTime<Utc>,
+) -> Result<Option<ProductionStatsOutput>> {
+	let row = sqlx::query(
+		"
+			select
+				data
+			from production_stats
+			where
+				model_id = $1 and
+				hour = $2
+		",
+	)
+	.bind(&model.id().to_string())
+	.bind(&hour.timestamp())
+	.fetch_optional(&mut *db)
+	.await?;
+	let row = match row {
+		Some(row) => row,
+		None => return Ok(None),
+	};
+	let data: String = row.get(0);
+	let data: Vec<u8> = base64::decode(data)?;
+	let production_stats: ProductionStats = serde_json::from_slice(&data)?;
+	Ok(Some(production_stats.finalize()))
+}
+
+async fn get_hourly_production_metrics(
+	db: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model: &tangram_core::model::Model,
+	hour: DateTime<Utc>,
+) -> Result<Option<ProductionMetricsOutput>> {
+	let row = sqlx::query(
+		"
+			select
+				data
+			from production_metrics
+			where
+				model_id = $1 and
+				hour = $2
+		",
+	)
+	.bind(&model.id().to_string())
+	.bind(&hour.timestamp())
+	.fetch_optional(&mut *db)
+	.await?;
+	let row = match row {
+		Some(row) => row,
+		None => return Ok(None),
+	};
+	let data: String = row.get(0);
+	let data: Vec<u8> = base64::decode(data)?;
+	let production_metrics: ProductionMetrics = serde_json::from_slice(&data)?;
+	Ok(Some(production_metrics.finalize()))
+}
+
+/// Send an alert to the rule's email address, if it has one and email is configured, and to the rule's webhook, if it has one. A failure to send the email is logged, and the webhook is still notified.
+async fn notify(
+	alert_rule: &AlertRule,
+	value: f32,
+	hour: DateTime<Utc>,
+	sendgrid_api_token: Option<&str>,
+) -> Result<()> {
+	let title = alert_rule.metric.title();
+	let value_string = alert_rule.metric.format_value(value);
+	let threshold_string = alert_rule.metric.format_value(alert_rule.threshold);
+	if let (Some(email), Some(sendgrid_api_token)) = (&alert_rule.email, sendgrid_api_token) {
+		let subject = format!("Tangram Alert: {}", title);
+		let body = format!(
+			"The {} of model {} was {} in the hour starting {}, which is above the alert threshold of {}.",
+			title, alert_rule.model_id, value_string, hour, threshold_string,
+		);
+		// A failure to send the email should not keep the webhook from being notified.
+		if let Err(error) = send_email(sendgrid_api_token, email, &subject, body).await {
+			eprintln!(
+				"failed to send the email for alert rule {}: {}",
+				alert_rule.id, error
+			);
+		}
+	}
+	if let Some(webhook_url) = &alert_rule.webhook_url {
+		let json = json!({
+			"alert_rule_id": alert_rule.id.to_string(),
+			"model_id": alert_rule.model_id.to_string(),
+			"metric": alert_rule.metric,
+			"title": title,
+			"threshold": alert_rule.threshold,
+			"value": value,
+			"hour": hour.to_rfc3339(),
+		});
+		let response = WEBHOOK_CLIENT.post(webhook_url).json(&json).send().await?;
+		if !response.status().is_success() {
+			return Err(err!(
+				"Non-2xx response from alert webhook {}: {:?}",
+				webhook_url,
+				response.status()
+			));
+		}
+	}
+	Ok(())
+}
@@ -1,11 +1,25 @@
+use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::fmt::Display;
+use tangram_deps::{chrono, chrono::prelude::*, chrono_tz::Tz};
+
+/// This is the largest number of intervals a date window can be split into.
+const MAX_N_INTERVALS: i64 = 1_000;
 
 #[derive(Clone, Debug)]
 pub enum DateWindow {
 	Today,
 	ThisMonth,
 	ThisYear,
+	/// The rolling windows end at the end of the current hour or day, so they include the most recent production data.
+	Last24Hours,
+	Last7Days,
+	Last30Days,
+	/// A custom date window starts at the beginning of `start_date` and ends at the end of `end_date`.
+	Custom {
+		start_date: NaiveDate,
+		end_date: NaiveDate,
+	},
 }
 
 impl Display for DateWindow {
@@ -14,6 +28,10 @@ impl Display for DateWindow {
 			DateWindow::Today => f.write_str("today"),
 			DateWindow::ThisMonth => f.write_str("this_month"),
 			DateWindow::ThisYear => f.write_str("this_year"),
+			DateWindow::Last24Hours => f.write_str("last_24_hours"),
+			DateWindow::Last7Days => f.write_str("last_7_days"),
+			DateWindow::Last30Days => f.write_str("last_30_days"),
+			DateWindow::Custom { .. } => f.write_str("custom"),
 		}
 	}
 }
@@ -22,26 +40,398 @@ impl Display for DateWindow {
 pub enum DateWindowInterval {
 	Hourly,
 	Daily,
+	Weekly,
 	Monthly,
 }
 
+impl Display for DateWindowInterval {
+	fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+		match *self {
+			DateWindowInterval::Hourly => f.write_str("hourly"),
+			DateWindowInterval::Daily => f.write_str("daily"),
+			DateWindowInterval::Weekly => f.write_str("weekly"),
+			DateWindowInterval::Monthly => f.write_str("monthly"),
+		}
+	}
+}
+
+impl DateWindow {
+	/// Compute an upper bound on the number of days the date window overlaps.
+	fn max_n_days(&self) -> i64 {
+		match self {
+			DateWindow::Today => 1,
+			DateWindow::ThisMonth => 31,
+			DateWindow::ThisYear => 366,
+			DateWindow::Last24Hours => 2,
+			DateWindow::Last7Days => 7,
+			DateWindow::Last30Days => 30,
+			DateWindow::Custom {
+				start_date,
+				end_date,
+			} => (*end_date - *start_date).num_days() + 1,
+		}
+	}
+
+	/// This is the interval used when the search params do not specify one.
+	fn default_interval(&self) -> DateWindowInterval {
+		match self {
+			DateWindow::Today | DateWindow::Last24Hours => DateWindowInterval::Hourly,
+			DateWindow::ThisMonth | DateWindow::Last7Days | DateWindow::Last30Days => {
+				DateWindowInterval::Daily
+			}
+			DateWindow::ThisYear => DateWindowInterval::Monthly,
+			DateWindow::Custom { .. } => match self.max_n_days() {
+				n_days if n_days <= 2 => DateWindowInterval::Hourly,
+				n_days if n_days <= 92 => DateWindowInterval::Daily,
+				n_days if n_days <= 366 => DateWindowInterval::Weekly,
+				_ => DateWindowInterval::Monthly,
+			},
+		}
+	}
+}
+
+/// Parse the date window and interval from the `date_window`, `start_date`, `end_date`, and `date_window_interval` search params. A custom date window without valid dates defaults to the last seven days in UTC. This returns `None` if the search params are invalid or the date window would be split into too many intervals.
 pub fn get_date_window_and_interval(
 	search_params: &Option<BTreeMap<String, String>>,
 ) -> Option<(DateWindow, DateWindowInterval)> {
-	let date_window = search_params
-		.as_ref()
-		.and_then(|query| query.get("date_window"));
-	let date_window = date_window.map_or("this_month", |dw| dw.as_str());
+	let get_search_param = |key: &str| {
+		search_params
+			.as_ref()
+			.and_then(|search_params| search_params.get(key))
+			.map(|value| value.as_str())
+			.filter(|value| !value.is_empty())
+	};
+	let date_window = get_search_param("date_window").unwrap_or("this_month");
 	let date_window = match date_window {
 		"today" => DateWindow::Today,
 		"this_month" => DateWindow::ThisMonth,
 		"this_year" => DateWindow::ThisYear,
+		"last_24_hours" => DateWindow::Last24Hours,
+		"last_7_days" => DateWindow::Last7Days,
+		"last_30_days" => DateWindow::Last30Days,
+		"custom" => {
+			let today = Utc::now().naive_utc().date();
+			let start_date = match get_search_param("start_date") {
+				Some(start_date) => parse_date(start_date)?,
+				None => today - chrono::Duration::days(6),
+			};
+			let end_date = match get_search_param("end_date") {
+				Some(end_date) => parse_date(end_date)?,
+				None => today,
+			};
+			if start_date > end_date {
+				return None;
+			}
+			DateWindow::Custom {
+				start_date,
+				end_date,
+			}
+		}
 		_ => return None,
 	};
-	let date_window_interval = match date_window {
-		DateWindow::Today => DateWindowInterval::Hourly,
-		DateWindow::ThisMonth => DateWindowInterval::Daily,
-		DateWindow::ThisYear => DateWindowInterval::Monthly,
+	let date_window_interval = match get_search_param("date_window_interval") {
+		None => date_window.default_interval(),
+		Some("hourly") => DateWindowInterval::Hourly,
+		Some("daily") => DateWindowInterval::Daily,
+		Some("weekly") => DateWindowInterval::Weekly,
+		Some("monthly") => DateWindowInterval::Monthly,
+		Some(_) => return None,
 	};
+	let max_n_days = date_window.max_n_days();
+	let max_n_intervals = match date_window_interval {
+		DateWindowInterval::Hourly => max_n_days * 24,
+		DateWindowInterval::Daily => max_n_days,
+		DateWindowInterval::Weekly => max_n_days / 7 + 1,
+		DateWindowInterval::Monthly => max_n_days / 28 + 1,
+	};
+	if max_n_intervals > MAX_N_INTERVALS {
+		return None;
+	}
 	Some((date_window, date_window_interval))
 }
+
+/// Produce the search params that select the date window and interval, for links between pages that show the same date window.
+pub fn date_window_search_params(
+	date_window: &DateWindow,
+	date_window_interval: &DateWindowInterval,
+) -> String {
+	match date_window {
+		DateWindow::Custom {
+			start_date,
+			end_date,
+		} => format!(
+			"date_window={}&start_date={}&end_date={}&date_window_interval={}",
+			date_window,
+			start_date.format("%Y-%m-%d"),
+			end_date.format("%Y-%m-%d"),
+			date_window_interval,
+		),
+		_ => format!(
+			"date_window={}&date_window_interval={}",
+			date_window, date_window_interval
+		),
+	}
+}
+
+/// Compute the start and end of the date window in `timezone`. The end is exclusive.
+pub fn compute_date_window_range(
+	date_window: &DateWindow,
+	timezone: Tz,
+) -> (DateTime<Tz>, DateTime<Tz>) {
+	let now: DateTime<Tz> = Utc::now().with_timezone(&timezone);
+	let today = now.naive_local().date();
+	match date_window {
+		DateWindow::Today => (
+			start_of_day(timezone, today),
+			start_of_day(timezone, today.succ()),
+		),
+		DateWindow::ThisMonth => {
+			let start_date = NaiveDate::from_ymd(today.year(), today.month(), 1);
+			(
+				start_of_day(timezone, start_date),
+				start_of_day(timezone, start_of_next_month(start_date)),
+			)
+		}
+		DateWindow::ThisYear => (
+			start_of_day(timezone, NaiveDate::from_ymd(today.year(), 1, 1)),
+			start_of_day(timezone, NaiveDate::from_ymd(today.year() + 1, 1, 1)),
+		),
+		DateWindow::Last24Hours => {
+			// Hourly production data is stored by utc hour, so round up to the end of the current utc hour.
+			let end_date = Utc::now()
+				.with_minute(0)
+				.unwrap()
+				.with_second(0)
+				.unwrap()
+				.with_nanosecond(0)
+				.unwrap() + chrono::Duration::hours(1);
+			let start_date = end_date - chrono::Duration::hours(24);
+			(
+				start_date.with_timezone(&timezone),
+				end_date.with_timezone(&timezone),
+			)
+		}
+		DateWindow::Last7Days => (
+			start_of_day(timezone, today - chrono::Duration::days(6)),
+			start_of_day(timezone, today.succ()),
+		),
+		DateWindow::Last30Days => (
+			start_of_day(timezone, today - chrono::Duration::days(29)),
+			start_of_day(timezone, today.succ()),
+		),
+		DateWindow::Custom {
+			start_date,
+			end_date,
+		} => (
+			start_of_day(timezone, *start_date),
+			start_of_day(timezone, end_date.succ()),
+		),
+	}
+}
+
+/// Split the range from `start_date` to `end_date` into intervals, returning the start and end of each. Daily and monthly intervals end at the start of the next day or month in the range's timezone, and weekly intervals end at the start of the day one week after they start. The last interval is cut off at `end_date`.
+pub fn compute_date_window_intervals(
+	start_date: DateTime<Tz>,
+	end_date: DateTime<Tz>,
+	date_window_interval: &DateWindowInterval,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+	let timezone = start_date.timezone();
+	let mut intervals = Vec::new();
+	let mut interval_start = start_date;
+	while interval_start < end_date {
+		let date = interval_start.naive_local().date();
+		let interval_end = match date_window_interval {
+			DateWindowInterval::Hourly => interval_start + chrono::Duration::hours(1),
+			DateWindowInterval::Daily => start_of_day(timezone, date.succ()),
+			DateWindowInterval::Weekly => start_of_day(timezone, date + chrono::Duration::days(7)),
+			DateWindowInterval::Monthly => start_of_day(timezone, start_of_next_month(date)),
+		};
+		let interval_end = interval_end.min(end_date);
+		intervals.push((
+			interval_start.with_timezone(&Utc),
+			interval_end.with_timezone(&Utc),
+		));
+		interval_start = interval_end;
+	}
+	intervals
+}
+
+/// Find the index of the interval that contains `date`, if any. The intervals must be sorted and must not overlap.
+pub fn find_interval(
+	intervals: &[(DateTime<Utc>, DateTime<Utc>)],
+	date: DateTime<Utc>,
+) -> Option<usize> {
+	intervals
+		.binary_search_by(|(start, end)| {
+			if *end <= date {
+				Ordering::Less
+			} else if *start > date {
+				Ordering::Greater
+			} else {
+				Ordering::Equal
+			}
+		})
+		.ok()
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+	NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+/// Compute the start of `date` in `timezone`. If a daylight saving transition makes midnight occur twice, the earlier one is used, and if it skips midnight, the day starts at the first local time that exists.
+fn start_of_day(timezone: Tz, date: NaiveDate) -> DateTime<Tz> {
+	let midnight = date.and_hms(0, 0, 0);
+	timezone
+		.from_local_datetime(&midnight)
+		.earliest()
+		.unwrap_or_else(|| {
+			(1..=24 * 60)
+				.find_map(|minutes| {
+					timezone
+						.from_local_datetime(&(midnight + chrono::Duration::minutes(minutes)))
+						.earliest()
+				})
+				.unwrap()
+		})
+}
+
+fn start_of_next_month(date: NaiveDate) -> NaiveDate {
+	if date.month() == 12 {
+		NaiveDate::from_ymd(date.year() + 1, 1, 1)
+	} else {
+		NaiveDate::from_ymd(date.year(), date.month() + 1, 1)
+	}
+}
+
+#[cfg(test)]
+fn utc(value: &str) -> DateTime<Utc> {
+	value.parse().unwrap()
+}
+
+#[test]
+fn test_start_of_day() {
+	let date = NaiveDate::from_ymd(2020, 3, 8);
+	assert_eq!(
+		start_of_day(tangram_deps::chrono_tz::UTC, date).with_timezone(&Utc),
+		utc("2020-03-08T00:00:00Z")
+	);
+	// In Sao Paulo, clocks moved forward from midnight to 1am on 2018-11-04.
+	let date = NaiveDate::from_ymd(2018, 11, 4);
+	assert_eq!(
+		start_of_day(tangram_deps::chrono_tz::America::Sao_Paulo, date).with_timezone(&Utc),
+		utc("2018-11-04T03:00:00Z")
+	);
+	// In Havana, clocks moved back from 1am to midnight on 2020-11-01, so midnight occurred twice.
+	let date = NaiveDate::from_ymd(2020, 11, 1);
+	assert_eq!(
+		start_of_day(tangram_deps::chrono_tz::America::Havana, date).with_timezone(&Utc),
+		utc("2020-11-01T04:00:00Z")
+	);
+}
+
+#[test]
+fn test_compute_date_window_intervals() {
+	let timezone = tangram_deps::chrono_tz::UTC;
+	let start_date = start_of_day(timezone, NaiveDate::from_ymd(2020, 1, 1));
+	let end_date = start_of_day(timezone, NaiveDate::from_ymd(2020, 1, 2));
+	let intervals =
+		compute_date_window_intervals(start_date, end_date, &DateWindowInterval::Hourly);
+	assert_eq!(intervals.len(), 24);
+	assert_eq!(
+		intervals[0],
+		(utc("2020-01-01T00:00:00Z"), utc("2020-01-01T01:00:00Z"))
+	);
+	// Monthly intervals have the length of their month.
+	let end_date = start_of_day(timezone, NaiveDate::from_ymd(2021, 1, 1));
+	let intervals =
+		compute_date_window_intervals(start_date, end_date, &DateWindowInterval::Monthly);
+	assert_eq!(intervals.len(), 12);
+	assert_eq!(
+		intervals[1],
+		(utc("2020-02-01T00:00:00Z"), utc("2020-03-01T00:00:00Z"))
+	);
+	assert_eq!(intervals[11].1, utc("2021-01-01T00:00:00Z"));
+	// The last weekly interval is cut off at the end of the range.
+	let end_date = start_of_day(timezone, NaiveDate::from_ymd(2020, 1, 10));
+	let intervals =
+		compute_date_window_intervals(start_date, end_date, &DateWindowInterval::Weekly);
+	assert_eq!(
+		intervals,
+		vec![
+			(utc("2020-01-01T00:00:00Z"), utc("2020-01-08T00:00:00Z")),
+			(utc("2020-01-08T00:00:00Z"), utc("2020-01-10T00:00:00Z")),
+		]
+	);
+	// Daily intervals follow local days across a daylight saving transition, so the day clocks move forward is 23 hours long.
+	let timezone = tangram_deps::chrono_tz::America::Sao_Paulo;
+	let start_date = start_of_day(timezone, NaiveDate::from_ymd(2018, 11, 3));
+	let end_date = start_of_day(timezone, NaiveDate::from_ymd(2018, 11, 6));
+	let intervals = compute_date_window_intervals(start_date, end_date, &DateWindowInterval::Daily);
+	assert_eq!(
+		intervals,
+		vec![
+			(utc("2018-11-03T03:00:00Z"), utc("2018-11-04T03:00:00Z")),
+			(utc("2018-11-04T03:00:00Z"), utc("2018-11-05T02:00:00Z")),
+			(utc("2018-11-05T02:00:00Z"), utc("2018-11-06T02:00:00Z")),
+		]
+	);
+}
+
+#[test]
+fn test_find_interval() {
+	let start_date = start_of_day(
+		tangram_deps::chrono_tz::UTC,
+		NaiveDate::from_ymd(2020, 1, 1),
+	);
+	let end_date = start_of_day(
+		tangram_deps::chrono_tz::UTC,
+		NaiveDate::from_ymd(2020, 1, 4),
+	);
+	let intervals = compute_date_window_intervals(start_date, end_date, &DateWindowInterval::Daily);
+	assert_eq!(
+		find_interval(&intervals, utc("2020-01-01T00:00:00Z")),
+		Some(0)
+	);
+	assert_eq!(
+		find_interval(&intervals, utc("2020-01-02T12:00:00Z")),
+		Some(1)
+	);
+	// Intervals include their start and exclude their end.
+	assert_eq!(
+		find_interval(&intervals, utc("2020-01-03T00:00:00Z")),
+		Some(2)
+	);
+	assert_eq!(find_interval(&intervals, utc("2020-01-04T00:00:00Z")), None);
+	assert_eq!(find_interval(&intervals, utc("2019-12-31T23:59:59Z")), None);
+}
+
+#[test]
+fn test_max_n_intervals() {
+	let search_params = |start_date: &str, end_date: &str, date_window_interval: &str| {
+		let mut search_params = BTreeMap::new();
+		search_params.insert("date_window".to_owned(), "custom".to_owned());
+		search_params.insert("start_date".to_owned(), start_date.to_owned());
+		search_params.insert("end_date".to_owned(), end_date.to_owned());
+		search_params.insert(
+			"date_window_interval".to_owned(),
+			date_window_interval.to_owned(),
+		);
+		Some(search_params)
+	};
+	// 41 days is 984 hourly intervals, and 42 days is 1008.
+	assert!(
+		get_date_window_and_interval(&search_params("2020-01-01", "2020-02-10", "hourly"))
+			.is_some()
+	);
+	assert!(
+		get_date_window_and_interval(&search_params("2020-01-01", "2020-02-11", "hourly"))
+			.is_none()
+	);
+	assert!(
+		get_date_window_and_interval(&search_params("2000-01-01", "2020-01-01", "daily")).is_none()
+	);
+	assert!(
+		get_date_window_and_interval(&search_params("2000-01-01", "2020-01-01", "monthly"))
+			.is_some()
+	);
+}
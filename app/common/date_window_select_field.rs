@@ -1,14 +1,26 @@
-use crate::date_window::DateWindow;
+use crate::date_window::{DateWindow, DateWindowInterval};
 use tangram_deps::html::{self, component, html};
 use tangram_ui as ui;
 
 #[component]
-pub fn DateWindowSelectField(date_window: DateWindow) {
+pub fn DateWindowSelectField(date_window: DateWindow, date_window_interval: DateWindowInterval) {
 	let date_window_select_field_options = vec![
 		ui::SelectFieldOption {
 			text: "Today".to_owned(),
 			value: "today".to_owned(),
 		},
+		ui::SelectFieldOption {
+			text: "Last 24 Hours".to_owned(),
+			value: "last_24_hours".to_owned(),
+		},
+		ui::SelectFieldOption {
+			text: "Last 7 Days".to_owned(),
+			value: "last_7_days".to_owned(),
+		},
+		ui::SelectFieldOption {
+			text: "Last 30 Days".to_owned(),
+			value: "last_30_days".to_owned(),
+		},
 		ui::SelectFieldOption {
 			text: "This Month".to_owned(),
 			value: "this_month".to_owned(),
@@ -17,17 +29,93 @@ pub fn DateWindowSelectField(date_window: DateWindow) {
 			text: "This Year".to_owned(),
 			value: "this_year".to_owned(),
 		},
+		ui::SelectFieldOption {
+			text: "Custom".to_owned(),
+			value: "custom".to_owned(),
+		},
+	];
+	let date_window_interval_select_field_options = vec![
+		ui::SelectFieldOption {
+			text: "Hourly".to_owned(),
+			value: "hourly".to_owned(),
+		},
+		ui::SelectFieldOption {
+			text: "Daily".to_owned(),
+			value: "daily".to_owned(),
+		},
+		ui::SelectFieldOption {
+			text: "Weekly".to_owned(),
+			value: "weekly".to_owned(),
+		},
+		ui::SelectFieldOption {
+			text: "Monthly".to_owned(),
+			value: "monthly".to_owned(),
+		},
 	];
+	// The start and end date fields are only shown for a custom date window, along with a button to apply them.
+	let custom_date_fields = match date_window {
+		DateWindow::Custom {
+			start_date,
+			end_date,
+		} => Some(html! {
+			<>
+				<ui::TextField
+					autocomplete={None}
+					disabled={None}
+					label={"Start Date".to_owned()}
+					name={"start_date".to_owned()}
+					placeholder={"YYYY-MM-DD".to_owned()}
+					readonly={None}
+					required={true}
+					value={start_date.format("%Y-%m-%d").to_string()}
+				/>
+				<ui::TextField
+					autocomplete={None}
+					disabled={None}
+					label={"End Date".to_owned()}
+					name={"end_date".to_owned()}
+					placeholder={"YYYY-MM-DD".to_owned()}
+					readonly={None}
+					required={true}
+					value={end_date.format("%Y-%m-%d").to_string()}
+				/>
+				<ui::Button
+					color={None}
+					disabled={None}
+					href={None}
+					id={None}
+					download={None}
+					button_type={ui::ButtonType::Submit}
+				>
+					{"Apply"}
+				</ui::Button>
+			</>
+		}),
+		_ => None,
+	};
 	html! {
-		<ui::SelectField
-			disabled={None}
-			placeholder={None}
-			required={None}
-			id={"date_window_select_field".to_owned()}
-			label={"Date Window".to_owned()}
-			name={"date_window".to_owned()}
-			options={date_window_select_field_options}
-			value={date_window.to_string()}
-		/>
+		<>
+			<ui::SelectField
+				disabled={None}
+				placeholder={None}
+				required={None}
+				id={"date_window_select_field".to_owned()}
+				label={"Date Window".to_owned()}
+				name={"date_window".to_owned()}
+				options={date_window_select_field_options}
+				value={date_window.to_string()}
+			/>
+			<ui::SelectField
+				disabled={None}
+				placeholder={None}
+				required={None}
+				id={"date_window_interval_select_field".to_owned()}
+				label={"Interval".to_owned()}
+				name={"date_window_interval".to_owned()}
+				options={date_window_interval_select_field_options}
+				value={date_window_interval.to_string()}
+			/>
+			{custom_date_fields}
+		</>
 	}
 }
@@ -34,3 +34,7 @@ pub const PRECISION_RECALL: &str =
 // 	"Recall is the percentage of positive examples that were labeled as positive."
 pub const RECEIVER_OPERATING_CHARACTERISTIC: &str =
 	"The Receiver Operating Characteristic Curve shows the True Positive Rate v. False Positive Rate at various thresholds in binary classification.";
+pub const DEMOGRAPHIC_PARITY_DIFFERENCE: &str =
+	"The demographic parity difference is the difference between the largest and smallest percentage of examples predicted as the positive class among the groups of the sensitive column. A model that predicts the positive class equally often for every group has a demographic parity difference of 0.";
+pub const EQUAL_OPPORTUNITY_DIFFERENCE: &str =
+	"The equal opportunity difference is the difference between the largest and smallest true positive rate among the groups of the sensitive column. A model that correctly predicts the same percentage of positive examples as positive for every group has an equal opportunity difference of 0.";
@@ -0,0 +1,453 @@
+/*!
+This module measures how far the distribution of a column in production has drifted from its distribution in the training dataset.
+
+* The population stability index compares the fraction of values in each bin. Enum columns use one bin for each option seen in training, and number columns use the bins between the training quartiles.
+* The Kullback-Leibler and Jensen-Shannon divergences compare the distributions of the options of enum columns and of the top tokens of text columns. They use the natural logarithm, so the Jensen-Shannon divergence is between 0 and ln 2.
+* The Kolmogorov-Smirnov statistic is the largest difference between the training and production cdfs of a number column.
+* The feature importance divergence is the Jensen-Shannon divergence between the model's feature importances from training and the mean absolute feature contributions of the predictions tracked in production, after normalizing both to sum to one. It measures how much the model's reasoning in production differs from its reasoning in training.
+
+The production distribution of a number column is estimated from its reservoir of samples. The training cdf is exact if the column has a histogram, and is otherwise interpolated linearly between the column's min, quartiles, and max.
+*/
+
+use crate::production_stats::{NumberStatsOutput, ProductionColumnStatsOutput, Token};
+use tangram_deps::num_traits::ToPrimitive;
+
+/// Bins with a fraction smaller than this are treated as having this fraction, so the logarithms in the drift metrics stay finite.
+const MIN_BIN_FRACTION: f64 = 1e-4;
+
+/// Each metric is `None` if it does not apply to the column's type or the column has no valid values in production.
+#[derive(Clone, Debug, Default)]
+pub struct ColumnDrift {
+	pub psi: Option<f32>,
+	pub kl_divergence: Option<f32>,
+	pub js_divergence: Option<f32>,
+	pub ks_statistic: Option<f32>,
+}
+
+pub fn compute_column_drift(
+	train_column_stats: &tangram_core::model::ColumnStats,
+	production_column_stats: &ProductionColumnStatsOutput,
+) -> ColumnDrift {
+	match (train_column_stats, production_column_stats) {
+		(
+			tangram_core::model::ColumnStats::Number(train_column_stats),
+			ProductionColumnStatsOutput::Number(production_column_stats),
+		) => match production_column_stats.stats.as_ref() {
+			Some(production_stats) if !production_stats.samples.is_empty() => ColumnDrift {
+				psi: Some(compute_number_psi(train_column_stats, production_stats)),
+				ks_statistic: Some(compute_ks_statistic(train_column_stats, production_stats)),
+				..Default::default()
+			},
+			_ => ColumnDrift::default(),
+		},
+		(
+			tangram_core::model::ColumnStats::Enum(train_column_stats),
+			ProductionColumnStatsOutput::Enum(production_column_stats),
+		) => {
+			let train_counts = train_column_stats
+				.histogram
+				.iter()
+				.map(|(_, count)| *count)
+				.collect::<Vec<_>>();
+			let production_counts = train_column_stats
+				.histogram
+				.iter()
+				.map(|(option, _)| {
+					production_column_stats
+						.histogram
+						.iter()
+						.find(|(production_option, _)| production_option == option)
+						.map(|(_, count)| *count)
+						.unwrap_or(0)
+				})
+				.collect::<Vec<_>>();
+			match (normalize(&train_counts), normalize(&production_counts)) {
+				(Some(expected), Some(actual)) => ColumnDrift {
+					psi: Some(psi(&expected, &actual)),
+					kl_divergence: Some(kl_divergence(&actual, &expected)),
+					js_divergence: Some(js_divergence(&actual, &expected)),
+					..Default::default()
+				},
+				_ => ColumnDrift::default(),
+			}
+		}
+		(
+			tangram_core::model::ColumnStats::Text(train_column_stats),
+			ProductionColumnStatsOutput::Text(production_column_stats),
+		) => {
+			let train_counts = train_column_stats
+				.top_tokens
+				.iter()
+				.map(|token_stats| token_stats.occurrence_count)
+				.collect::<Vec<_>>();
+			let production_counts = train_column_stats
+				.top_tokens
+				.iter()
+				.map(|token_stats| {
+					let token: Token = token_stats.token.clone().into();
+					production_column_stats
+						.token_histogram
+						.iter()
+						.find(|(production_token, _)| *production_token == token)
+						.map(|(_, count)| *count)
+						.unwrap_or(0)
+				})
+				.collect::<Vec<_>>();
+			match (normalize(&train_counts), normalize(&production_counts)) {
+				(Some(expected), Some(actual)) => ColumnDrift {
+					kl_divergence: Some(kl_divergence(&actual, &expected)),
+					js_divergence: Some(js_divergence(&actual, &expected)),
+					..Default::default()
+				},
+				_ => ColumnDrift::default(),
+			}
+		}
+		_ => ColumnDrift::default(),
+	}
+}
+
+/// Normalize feature importances or mean absolute feature contributions so they sum to one, or return `None` if they are all zero.
+pub fn normalize_feature_importances(feature_importances: &[f32]) -> Option<Vec<f32>> {
+	let total = feature_importances.iter().sum::<f32>();
+	if total <= 0.0 {
+		return None;
+	}
+	Some(
+		feature_importances
+			.iter()
+			.map(|feature_importance| feature_importance / total)
+			.collect(),
+	)
+}
+
+pub fn compute_feature_importance_divergence(
+	training_feature_importances: &[f32],
+	production_mean_abs_feature_contributions: &[f32],
+) -> Option<f32> {
+	let expected = normalize_feature_importances(training_feature_importances)?;
+	let actual = normalize_feature_importances(production_mean_abs_feature_contributions)?;
+	let expected = expected
+		.iter()
+		.map(|value| value.to_f64().unwrap())
+		.collect::<Vec<_>>();
+	let actual = actual
+		.iter()
+		.map(|value| value.to_f64().unwrap())
+		.collect::<Vec<_>>();
+	Some(js_divergence(&actual, &expected))
+}
+
+/// Convert counts to fractions, or return `None` if the counts are all zero.
+fn normalize(counts: &[u64]) -> Option<Vec<f64>> {
+	let total = counts.iter().sum::<u64>();
+	if total == 0 {
+		return None;
+	}
+	let total = total.to_f64().unwrap();
+	Some(
+		counts
+			.iter()
+			.map(|count| count.to_f64().unwrap() / total)
+			.collect(),
+	)
+}
+
+fn psi(expected: &[f64], actual: &[f64]) -> f32 {
+	expected
+		.iter()
+		.zip(actual.iter())
+		.map(|(expected, actual)| {
+			let expected = expected.max(MIN_BIN_FRACTION);
+			let actual = actual.max(MIN_BIN_FRACTION);
+			(actual - expected) * (actual / expected).ln()
+		})
+		.sum::<f64>()
+		.to_f32()
+		.unwrap()
+}
+
+fn kl_divergence(p: &[f64], q: &[f64]) -> f32 {
+	kl_divergence_f64(p, q).to_f32().unwrap()
+}
+
+fn kl_divergence_f64(p: &[f64], q: &[f64]) -> f64 {
+	p.iter()
+		.zip(q.iter())
+		.filter(|(p, _)| **p > 0.0)
+		.map(|(p, q)| p * (p / q.max(MIN_BIN_FRACTION)).ln())
+		.sum::<f64>()
+}
+
+fn js_divergence(p: &[f64], q: &[f64]) -> f32 {
+	let m = p
+		.iter()
+		.zip(q.iter())
+		.map(|(p, q)| (p + q) / 2.0)
+		.collect::<Vec<_>>();
+	(0.5 * kl_divergence_f64(p, &m) + 0.5 * kl_divergence_f64(q, &m))
+		.to_f32()
+		.unwrap()
+}
+
+fn compute_number_psi(
+	train_column_stats: &tangram_core::model::NumberColumnStats,
+	production_stats: &NumberStatsOutput,
+) -> f32 {
+	let mut split_values = vec![
+		train_column_stats.p25,
+		train_column_stats.p50,
+		train_column_stats.p75,
+	];
+	split_values.dedup();
+	let mut expected = Vec::with_capacity(split_values.len() + 1);
+	let mut actual = Vec::with_capacity(split_values.len() + 1);
+	let (mut previous_expected, mut previous_actual) = (0.0, 0.0);
+	for split_value in split_values {
+		let current_expected = train_cdf(train_column_stats, split_value);
+		let current_actual = production_cdf(&production_stats.samples, split_value);
+		expected.push(current_expected - previous_expected);
+		actual.push(current_actual - previous_actual);
+		previous_expected = current_expected;
+		previous_actual = current_actual;
+	}
+	expected.push(1.0 - previous_expected);
+	actual.push(1.0 - previous_actual);
+	psi(&expected, &actual)
+}
+
+/// The cdfs are compared at every production sample, both at the sample and just below it, because the production cdf jumps at each sample.
+fn compute_ks_statistic(
+	train_column_stats: &tangram_core::model::NumberColumnStats,
+	production_stats: &NumberStatsOutput,
+) -> f32 {
+	let samples = &production_stats.samples;
+	let n = samples.len().to_f64().unwrap();
+	let mut ks_statistic: f64 = 0.0;
+	for value in samples.iter() {
+		let production_le = count_le(samples, *value).to_f64().unwrap() / n;
+		let production_lt = count_lt(samples, *value).to_f64().unwrap() / n;
+		let train_le = train_cdf(train_column_stats, *value);
+		let train_lt = train_cdf_lt(train_column_stats, *value);
+		ks_statistic = ks_statistic
+			.max((production_le - train_le).abs())
+			.max((production_lt - train_lt).abs());
+	}
+	ks_statistic.to_f32().unwrap()
+}
+
+/// Compute the fraction of training values less than or equal to `value`.
+fn train_cdf(train_column_stats: &tangram_core::model::NumberColumnStats, value: f32) -> f64 {
+	match &train_column_stats.histogram {
+		Some(histogram) => histogram_fraction(histogram, |bin_value| bin_value <= value),
+		None => interpolate_train_cdf(train_column_stats, value),
+	}
+}
+
+/// Compute the fraction of training values less than `value`.
+fn train_cdf_lt(train_column_stats: &tangram_core::model::NumberColumnStats, value: f32) -> f64 {
+	match &train_column_stats.histogram {
+		Some(histogram) => histogram_fraction(histogram, |bin_value| bin_value < value),
+		None => interpolate_train_cdf(train_column_stats, value),
+	}
+}
+
+fn histogram_fraction(histogram: &[(f32, u64)], predicate: impl Fn(f32) -> bool) -> f64 {
+	let total = histogram.iter().map(|(_, count)| count).sum::<u64>();
+	if total == 0 {
+		return 0.0;
+	}
+	let count = histogram
+		.iter()
+		.filter(|(bin_value, _)| predicate(*bin_value))
+		.map(|(_, count)| count)
+		.sum::<u64>();
+	count.to_f64().unwrap() / total.to_f64().unwrap()
+}
+
+fn interpolate_train_cdf(
+	train_column_stats: &tangram_core::model::NumberColumnStats,
+	value: f32,
+) -> f64 {
+	let points = [
+		(train_column_stats.min, 0.0),
+		(train_column_stats.p25, 0.25),
+		(train_column_stats.p50, 0.5),
+		(train_column_stats.p75, 0.75),
+		(train_column_stats.max, 1.0),
+	];
+	if value < points[0].0 {
+		return 0.0;
+	}
+	for window in points.windows(2) {
+		let (left_value, left_fraction) = window[0];
+		let (right_value, right_fraction) = window[1];
+		if value < right_value {
+			let t = ((value - left_value) / (right_value - left_value))
+				.to_f64()
+				.unwrap();
+			return left_fraction + t * (right_fraction - left_fraction);
+		}
+	}
+	1.0
+}
+
+/// Compute the fraction of the sorted `samples` less than or equal to `value`.
+fn production_cdf(samples: &[f32], value: f32) -> f64 {
+	count_le(samples, value).to_f64().unwrap() / samples.len().to_f64().unwrap()
+}
+
+/// Count the sorted `samples` less than or equal to `value` with a binary search.
+fn count_le(samples: &[f32], value: f32) -> usize {
+	let (mut low, mut high) = (0, samples.len());
+	while low < high {
+		let mid = (low + high) / 2;
+		if samples[mid] <= value {
+			low = mid + 1;
+		} else {
+			high = mid;
+		}
+	}
+	low
+}
+
+/// Count the sorted `samples` less than `value` with a binary search.
+fn count_lt(samples: &[f32], value: f32) -> usize {
+	let (mut low, mut high) = (0, samples.len());
+	while low < high {
+		let mid = (low + high) / 2;
+		if samples[mid] < value {
+			low = mid + 1;
+		} else {
+			high = mid;
+		}
+	}
+	low
+}
+
+#[test]
+fn test_normalize() {
+	assert_eq!(normalize(&[1, 3]), Some(vec![0.25, 0.75]));
+	assert_eq!(normalize(&[0, 0]), None);
+	assert_eq!(
+		normalize_feature_importances(&[1.0, 3.0]),
+		Some(vec![0.25, 0.75])
+	);
+	assert_eq!(normalize_feature_importances(&[0.0, 0.0]), None);
+}
+
+#[test]
+fn test_psi() {
+	// (0.25 - 0.5) * ln(0.25 / 0.5) + (0.75 - 0.5) * ln(0.75 / 0.5) = 0.25 * ln(3)
+	let expected = 0.25 * f32::ln(3.0);
+	assert!(f32::abs(psi(&[0.5, 0.5], &[0.25, 0.75]) - expected) < f32::EPSILON);
+	assert!(f32::abs(psi(&[0.5, 0.5], &[0.5, 0.5])) < f32::EPSILON);
+}
+
+#[test]
+fn test_kl_divergence() {
+	// 0.5 * ln(0.5 / 0.25) + 0.5 * ln(0.5 / 0.75) = 0.5 * ln(4 / 3)
+	let expected = 0.5 * f32::ln(4.0 / 3.0);
+	assert!(f32::abs(kl_divergence(&[0.5, 0.5], &[0.25, 0.75]) - expected) < f32::EPSILON);
+	// Bins where p is zero contribute nothing.
+	let expected = f32::ln(2.0);
+	assert!(f32::abs(kl_divergence(&[1.0, 0.0], &[0.5, 0.5]) - expected) < f32::EPSILON);
+}
+
+#[test]
+fn test_js_divergence() {
+	assert!(f32::abs(js_divergence(&[0.25, 0.75], &[0.25, 0.75])) < f32::EPSILON);
+	// Disjoint distributions have the maximum divergence, ln(2).
+	let expected = f32::ln(2.0);
+	assert!(f32::abs(js_divergence(&[1.0, 0.0], &[0.0, 1.0]) - expected) < f32::EPSILON);
+	assert!(f32::abs(js_divergence(&[0.0, 1.0], &[1.0, 0.0]) - expected) < f32::EPSILON);
+	let divergence = compute_feature_importance_divergence(&[2.0, 0.0], &[0.0, 5.0]).unwrap();
+	assert!(f32::abs(divergence - expected) < f32::EPSILON);
+}
+
+#[test]
+fn test_count_le_and_count_lt() {
+	let samples = [1.0, 2.0, 2.0, 3.0];
+	assert_eq!(count_le(&samples, 0.0), 0);
+	assert_eq!(count_le(&samples, 2.0), 3);
+	assert_eq!(count_le(&samples, 3.0), 4);
+	assert_eq!(count_lt(&samples, 2.0), 1);
+	assert_eq!(count_lt(&samples, 4.0), 4);
+	assert!((production_cdf(&samples, 2.0) - 0.75).abs() < f64::EPSILON);
+}
+
+#[cfg(test)]
+fn test_train_column_stats(
+	histogram: Option<Vec<(f32, u64)>>,
+	quantiles: [f32; 5],
+) -> tangram_core::model::NumberColumnStats {
+	let [min, p25, p50, p75, max] = quantiles;
+	tangram_core::model::NumberColumnStats {
+		column_name: "x".to_owned(),
+		invalid_count: 0,
+		unique_count: 0,
+		histogram,
+		min,
+		max,
+		mean: 0.0,
+		variance: 0.0,
+		std: 0.0,
+		p25,
+		p50,
+		p75,
+	}
+}
+
+#[cfg(test)]
+fn test_production_stats(samples: Vec<f32>) -> NumberStatsOutput {
+	NumberStatsOutput {
+		n: samples.len().to_u64().unwrap(),
+		min: samples[0],
+		max: samples[samples.len() - 1],
+		mean: 0.0,
+		variance: 0.0,
+		std: 0.0,
+		p25: 0.0,
+		p50: 0.0,
+		p75: 0.0,
+		samples,
+	}
+}
+
+#[test]
+fn test_interpolate_train_cdf() {
+	let train_column_stats = test_train_column_stats(None, [0.0, 1.0, 2.0, 4.0, 8.0]);
+	assert!(interpolate_train_cdf(&train_column_stats, -1.0).abs() < f64::EPSILON);
+	assert!((interpolate_train_cdf(&train_column_stats, 1.0) - 0.25).abs() < f64::EPSILON);
+	// 3 is halfway between p50 = 2 and p75 = 4.
+	assert!((interpolate_train_cdf(&train_column_stats, 3.0) - 0.625).abs() < f64::EPSILON);
+	assert!((interpolate_train_cdf(&train_column_stats, 6.0) - 0.875).abs() < f64::EPSILON);
+	assert!((interpolate_train_cdf(&train_column_stats, 8.0) - 1.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_compute_ks_statistic() {
+	let histogram = vec![(1.0, 1), (2.0, 1), (3.0, 1), (4.0, 1)];
+	let train_column_stats = test_train_column_stats(Some(histogram), [1.0, 1.0, 2.0, 3.0, 4.0]);
+	let production_stats = test_production_stats(vec![3.0, 4.0]);
+	// Just below 3, the production cdf is 0 and the train cdf is 0.5.
+	let ks_statistic = compute_ks_statistic(&train_column_stats, &production_stats);
+	assert!(f32::abs(ks_statistic - 0.5) < f32::EPSILON);
+	let production_stats = test_production_stats(vec![1.0, 2.0, 3.0, 4.0]);
+	let ks_statistic = compute_ks_statistic(&train_column_stats, &production_stats);
+	assert!(f32::abs(ks_statistic) < f32::EPSILON);
+}
+
+#[test]
+fn test_compute_number_psi() {
+	let histogram = vec![(1.0, 1), (2.0, 1), (3.0, 1), (4.0, 1)];
+	let train_column_stats = test_train_column_stats(Some(histogram), [1.0, 1.0, 2.0, 3.0, 4.0]);
+	// The bins split at p25 = 1, p50 = 2, and p75 = 3, so the expected fractions are [0.25, 0.25, 0.25, 0.25] and the actual fractions are [0, 0, 0.5, 0.5], where the empty bins are clamped to the minimum bin fraction.
+	let production_stats = test_production_stats(vec![3.0, 4.0]);
+	let expected = 2.0 * (MIN_BIN_FRACTION - 0.25) * f64::ln(MIN_BIN_FRACTION / 0.25)
+		+ 2.0 * 0.25 * f64::ln(2.0);
+	let psi = compute_number_psi(&train_column_stats, &production_stats);
+	assert!((psi.to_f64().unwrap() - expected).abs() < 1e-5);
+	let production_stats = test_production_stats(vec![1.0, 2.0, 3.0, 4.0]);
+	let psi = compute_number_psi(&train_column_stats, &production_stats);
+	assert!(f32::abs(psi) < f32::EPSILON);
+}
@@ -0,0 +1,55 @@
+use tangram_deps::{http, reqwest, serde_json::json};
+use tangram_util::{err, error::Result};
+
+/// Send a plain text email from Tangram with the SendGrid api.
+pub async fn send_email(
+	sendgrid_api_token: &str,
+	email: &str,
+	subject: &str,
+	body: String,
+) -> Result<()> {
+	let json = json!({
+		"personalizations": [
+			{
+				"to": [
+					{
+						"email": email,
+					}
+				]
+			}
+		],
+		"from": {
+			"email": "noreply@tangramhq.com",
+			"name": "Tangram"
+		},
+		"subject": subject,
+		"tracking_settings": {
+			"click_tracking": {
+				"enable": false
+			}
+		},
+		"content": [
+			{
+				"type": "text/plain",
+				"value": body,
+			}
+		]
+	});
+	let client = reqwest::Client::new();
+	let response = client
+		.post("https://api.sendgrid.com/v3/mail/send")
+		.header(
+			http::header::AUTHORIZATION,
+			format!("Bearer {}", sendgrid_api_token),
+		)
+		.json(&json)
+		.send()
+		.await?;
+	if !response.status().is_success() {
+		return Err(err!(
+			"Non-2xx response from sengrid: {:?}",
+			response.text().await?
+		));
+	}
+	Ok(())
+}
@@ -1,10 +1,13 @@
 use tangram_deps::{sqlx, url};
 
+pub mod alerts;
 pub mod class_select_field;
 pub mod cookies;
 pub mod date_window;
 pub mod date_window_select_field;
 pub mod definitions;
+pub mod drift;
+pub mod email;
 pub mod error;
 pub mod logo;
 pub mod metrics_row;
@@ -28,6 +31,8 @@ pub struct Options {
 	pub database_max_connections: Option<u32>,
 	pub host: std::net::IpAddr,
 	pub port: u16,
+	/// If `production_feature_contributions_enabled` is true, the app computes the feature contributions for each tracked prediction so it can compare the features' importances in production to their importances in training. This makes tracking predictions slower.
+	pub production_feature_contributions_enabled: bool,
 	pub sendgrid_api_token: Option<String>,
 	pub stripe_publishable_key: Option<String>,
 	pub stripe_secret_key: Option<String>,
@@ -6,6 +6,16 @@ pub async fn get_model(
 	db: &mut sqlx::Transaction<'_, sqlx::Any>,
 	model_id: Id,
 ) -> Result<tangram_core::model::Model> {
+	let data = get_model_data(db, model_id).await?;
+	let model = tangram_core::model::Model::from_slice(&data.as_slice())?;
+	Ok(model)
+}
+
+/// Retrieves the serialized data of the model with the specified id, for callers that deserialize it more than once.
+pub async fn get_model_data(
+	db: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+) -> Result<Vec<u8>> {
 	let row = sqlx::query(
 		"
 			select
@@ -20,6 +30,44 @@ pub async fn get_model(
 	.await?;
 	let data: String = row.get(0);
 	let data: Vec<u8> = base64::decode(data)?;
-	let model = tangram_core::model::Model::from_slice(&data.as_slice())?;
-	Ok(model)
+	Ok(data)
+}
+
+/// Computes the display name of each of the features produced by the feature groups.
+pub fn compute_feature_names(feature_groups: &[tangram_core::model::FeatureGroup]) -> Vec<String> {
+	feature_groups
+		.iter()
+		.flat_map(|feature_group| match feature_group {
+			tangram_core::model::FeatureGroup::Identity(feature_group) => {
+				vec![feature_group.source_column_name.clone()]
+			}
+			tangram_core::model::FeatureGroup::Normalized(feature_group) => {
+				vec![feature_group.source_column_name.clone()]
+			}
+			tangram_core::model::FeatureGroup::OneHotEncoded(feature_group) => {
+				vec!["OOV".to_owned()]
+					.iter()
+					.chain(feature_group.options.iter())
+					.map(|option| {
+						format!(
+							"{} = {}",
+							feature_group.source_column_name.clone(),
+							option.clone(),
+						)
+					})
+					.collect()
+			}
+			tangram_core::model::FeatureGroup::BagOfWords(feature_group) => feature_group
+				.tokens
+				.iter()
+				.map(|token| {
+					format!(
+						"{} contains {}",
+						feature_group.source_column_name.clone(),
+						token.token,
+					)
+				})
+				.collect(),
+		})
+		.collect()
 }
@@ -43,6 +43,9 @@ pub enum PredictOutput {
 #[serde(rename_all = "camelCase")]
 pub struct RegressionPredictOutput {
 	pub value: f32,
+	/// The bounds of the prediction interval are present if the model was trained with the prediction intervals config.
+	pub lower: Option<f32>,
+	pub upper: Option<f32>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -59,7 +62,7 @@ pub struct MulticlassClassificationPredictOutput {
 	pub probabilities: Option<HashMap<String, f32>>,
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(untagged)]
 pub enum NumberOrString {
 	Number(f32),
@@ -1,5 +1,4 @@
 use crate::tokens::{EnumColumnToken, NumberColumnToken, TextColumnToken, UnknownColumnToken};
-use std::convert::TryInto;
 use tangram_charts::{
 	bar_chart::{BarChartPoint, BarChartSeries},
 	components::{BarChart, FeatureContributionsChart},
@@ -14,6 +13,9 @@ use tangram_deps::{
 };
 use tangram_ui as ui;
 
+/// The prediction result shows at most this many of the largest feature interactions.
+const MAX_FEATURE_INTERACTIONS: usize = 10;
+
 #[derive(Clone)]
 pub struct PredictionResultProps {
 	pub input_table: InputTable,
@@ -50,7 +52,16 @@ pub enum Prediction {
 #[derive(Clone)]
 pub struct RegressionPredictionResultProps {
 	value: f32,
+	prediction_interval: Option<PredictionInterval>,
 	feature_contributions_chart_series: Vec<FeatureContributionsChartSeriesData>,
+	feature_interactions: Vec<FeatureInteractionData>,
+}
+
+#[derive(Clone)]
+pub struct PredictionInterval {
+	lower: f32,
+	upper: f32,
+	coverage: f32,
 }
 
 #[derive(Clone)]
@@ -58,6 +69,7 @@ pub struct BinaryClassificationPredictionResultProps {
 	class_name: String,
 	probability: f32,
 	feature_contributions_chart_series: Vec<FeatureContributionsChartSeriesData>,
+	feature_interactions: Vec<FeatureInteractionData>,
 }
 
 #[derive(Clone)]
@@ -66,6 +78,7 @@ pub struct MulticlassClassificationPredictionResultProps {
 	probability: f32,
 	probabilities: Vec<(String, f32)>,
 	feature_contributions_chart_series: Vec<FeatureContributionsChartSeriesData>,
+	feature_interactions: Vec<FeatureInteractionData>,
 }
 
 #[derive(Clone)]
@@ -84,13 +97,23 @@ pub struct FeatureContributionsChartValueData {
 	value: f32,
 }
 
+#[derive(Clone)]
+pub struct FeatureInteractionData {
+	feature_a: String,
+	feature_b: String,
+	value: f32,
+}
+
 pub fn predict(
-	model: tangram_core::model::Model,
+	model: &tangram_core::predict::Model,
 	example: serde_json::Map<String, serde_json::Value>,
 ) -> Prediction {
-	let predict_model: tangram_core::predict::Model = model.try_into().unwrap();
 	let examples = tangram_core::predict::PredictInput(vec![example]);
-	let output = tangram_core::predict::predict(&predict_model, examples, None);
+	let options = tangram_core::predict::PredictOptions {
+		compute_feature_interactions: true,
+		..Default::default()
+	};
+	let output = tangram_core::predict::predict(model, examples, Some(options)).unwrap();
 	let predict_output: Prediction = match output {
 		tangram_core::predict::PredictOutput::Regression(mut output) => {
 			let output = output.remove(0);
@@ -107,9 +130,28 @@ pub fn predict(
 					.map(compute_feature_contributions_chart_value)
 					.collect(),
 			}];
+			let feature_interactions =
+				compute_feature_interactions_data(output.feature_interactions.unwrap());
+			let coverage = match model {
+				tangram_core::predict::Model::Regressor(model) => model
+					.prediction_intervals
+					.as_ref()
+					.map(|prediction_intervals| prediction_intervals.coverage),
+				_ => unreachable!(),
+			};
+			let prediction_interval = match (output.lower, output.upper, coverage) {
+				(Some(lower), Some(upper), Some(coverage)) => Some(PredictionInterval {
+					lower,
+					upper,
+					coverage,
+				}),
+				_ => None,
+			};
 			let prediction = RegressionPredictionResultProps {
 				feature_contributions_chart_series: feature_contributions_chart_data,
+				feature_interactions,
 				value: output.value,
+				prediction_interval,
 			};
 			Prediction::Regression(prediction)
 		}
@@ -128,10 +170,13 @@ pub fn predict(
 					.map(compute_feature_contributions_chart_value)
 					.collect(),
 			}];
+			let feature_interactions =
+				compute_feature_interactions_data(output.feature_interactions.unwrap());
 			let prediction = BinaryClassificationPredictionResultProps {
 				class_name: output.class_name,
 				probability: output.probability,
 				feature_contributions_chart_series: feature_contributions_chart_data,
+				feature_interactions,
 			};
 			Prediction::BinaryClassification(prediction)
 		}
@@ -156,11 +201,20 @@ pub fn predict(
 					},
 				)
 				.collect();
+			// Only the interactions for the predicted class are shown.
+			let feature_interactions = compute_feature_interactions_data(
+				output
+					.feature_interactions
+					.unwrap()
+					.remove(&output.class_name)
+					.unwrap(),
+			);
 			let prediction = MulticlassClassificationPredictionResultProps {
 				class_name: output.class_name,
 				probability: output.probability,
 				probabilities: output.probabilities.into_iter().collect::<Vec<_>>(),
 				feature_contributions_chart_series: feature_contributions_chart_data,
+				feature_interactions,
 			};
 			Prediction::MulticlassClassification(prediction)
 		}
@@ -222,6 +276,50 @@ fn compute_feature_contributions_chart_value(
 	}
 }
 
+fn compute_feature_interactions_data(
+	feature_interactions: Vec<tangram_core::predict::FeatureInteraction>,
+) -> Vec<FeatureInteractionData> {
+	feature_interactions
+		.into_iter()
+		.take(MAX_FEATURE_INTERACTIONS)
+		.map(|feature_interaction| FeatureInteractionData {
+			feature_a: compute_feature_name(feature_interaction.feature_a),
+			feature_b: compute_feature_name(feature_interaction.feature_b),
+			value: feature_interaction.feature_interaction_value,
+		})
+		.collect()
+}
+
+fn compute_feature_name(feature: tangram_core::predict::Feature) -> String {
+	match feature {
+		tangram_core::predict::Feature::Identity { column_name } => column_name,
+		tangram_core::predict::Feature::Normalized { column_name } => column_name,
+		tangram_core::predict::Feature::OneHotEncoded {
+			column_name,
+			option,
+			feature_value,
+		} => {
+			let predicate = if feature_value { "is" } else { "is not" };
+			let option = option
+				.map(|option| format!("\"{}\"", option))
+				.unwrap_or_else(|| "invalid".to_owned());
+			format!("{} {} {}", column_name, predicate, option)
+		}
+		tangram_core::predict::Feature::BagOfWords {
+			column_name,
+			token,
+			feature_value,
+		} => {
+			let predicate = if feature_value {
+				"contains"
+			} else {
+				"does not contain"
+			};
+			format!("{} {} \"{}\"", column_name, predicate, token)
+		}
+	}
+}
+
 #[component]
 pub fn PredictionResult(props: PredictionResultProps) {
 	let inner = match props.prediction {
@@ -343,6 +441,18 @@ pub fn RegressionPrediction(props: RegressionPredictionResultProps) {
 					value={props.value.to_string()}
 				/>
 			</ui::Card>
+			{props.prediction_interval.map(|prediction_interval| html! {
+				<ui::Card>
+					<ui::NumberChart
+						title={format!("{} Prediction Interval", ui::format_percent_with_precision(prediction_interval.coverage, 0))}
+						value={format!(
+							"{} to {}",
+							ui::format_number(prediction_interval.lower),
+							ui::format_number(prediction_interval.upper),
+						)}
+					/>
+				</ui::Card>
+			})}
 			<ui::H2 center={false}>{"Explanation"}</ui::H2>
 			<ui::P>
 				{"This chart shows how the input values influenced the model's output."}
@@ -360,6 +470,7 @@ pub fn RegressionPrediction(props: RegressionPredictionResultProps) {
 					series={series}
 				/>
 			</ui::Card>
+			<FeatureInteractionsTable feature_interactions={props.feature_interactions} />
 		</ui::S2>
 	}
 }
@@ -414,6 +525,7 @@ pub fn BinaryClassificationPrediction(props: BinaryClassificationPredictionResul
 					series={series}
 				/>
 			</ui::Card>
+			<FeatureInteractionsTable feature_interactions={props.feature_interactions} />
 		</ui::S2>
 	}
 }
@@ -500,6 +612,65 @@ pub fn MulticlassClassificationPrediction(props: MulticlassClassificationPredict
 					title={None}
 				/>
 			</ui::Card>
+			<FeatureInteractionsTable feature_interactions={props.feature_interactions} />
 		</ui::S2>
 	}
 }
+
+/// Linear models have no interactions between features, so nothing is shown for them.
+#[component]
+fn FeatureInteractionsTable(feature_interactions: Vec<FeatureInteractionData>) {
+	if feature_interactions.is_empty() {
+		return html! { <></> };
+	}
+	html! {
+		<>
+			<ui::H2 center={false}>{"Interactions"}</ui::H2>
+			<ui::P>
+				{"This table shows the pairs of input values whose combined influence on the model's output differed the most from the sum of their influences alone."}
+			</ui::P>
+			<ui::Table width={"100%".to_owned()}>
+				<ui::TableHeader>
+					<ui::TableRow color={None}>
+						<ui::TableHeaderCell
+							color={None}
+							expand={None}
+							text_align={None}
+						>
+							{"Feature A"}
+						</ui::TableHeaderCell>
+						<ui::TableHeaderCell
+							color={None}
+							expand={None}
+							text_align={None}
+						>
+							{"Feature B"}
+						</ui::TableHeaderCell>
+						<ui::TableHeaderCell
+							color={None}
+							expand={None}
+							text_align={None}
+						>
+							{"Interaction Value"}
+						</ui::TableHeaderCell>
+					</ui::TableRow>
+				</ui::TableHeader>
+				<ui::TableBody>
+				{feature_interactions.into_iter().map(|feature_interaction| html! {
+					<ui::TableRow color={None}>
+						<ui::TableCell color={None} expand={None}>
+							{feature_interaction.feature_a}
+						</ui::TableCell>
+						<ui::TableCell color={None} expand={None}>
+							{feature_interaction.feature_b}
+						</ui::TableCell>
+						<ui::TableCell color={None} expand={None}>
+							{feature_interaction.value.to_string()}
+						</ui::TableCell>
+					</ui::TableRow>
+				}).collect::<Vec<_>>()}
+				</ui::TableBody>
+			</ui::Table>
+		</>
+	}
+}
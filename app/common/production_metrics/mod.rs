@@ -11,10 +11,14 @@ use self::{
 		RegressionProductionPredictionMetrics, RegressionProductionPredictionMetricsOutput,
 	},
 };
-use crate::date_window::{DateWindow, DateWindowInterval};
+use crate::date_window::{
+	compute_date_window_intervals, compute_date_window_range, find_interval, DateWindow,
+	DateWindowInterval,
+};
 use crate::monitor_event::NumberOrString;
+use std::collections::HashMap;
 use tangram_deps::{
-	base64, chrono, chrono::prelude::*, chrono_tz::Tz, num_traits::ToPrimitive, serde_json, sqlx,
+	base64, chrono::prelude::*, chrono_tz::Tz, num_traits::ToPrimitive, serde_json, sqlx,
 	sqlx::prelude::*,
 };
 use tangram_metrics::StreamingMetric;
@@ -30,6 +34,15 @@ pub struct ProductionMetrics {
 	pub end_date: DateTime<Utc>,
 	pub true_values_count: u64,
 	pub prediction_metrics: ProductionPredictionMetrics,
+	/// This is `Some` for binary classifiers trained with a sensitive column in the fairness config.
+	pub fairness_metrics: Option<ProductionFairnessMetrics>,
+}
+
+/// This struct contains the prediction metrics for each option of a binary classifier's sensitive column.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct ProductionFairnessMetrics {
+	pub sensitive_column_name: String,
+	pub groups: Vec<(String, BinaryClassificationProductionPredictionMetrics)>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
@@ -45,6 +58,25 @@ pub struct ProductionMetricsOutput {
 	pub end_date: DateTime<Utc>,
 	pub true_values_count: u64,
 	pub prediction_metrics: Option<ProductionPredictionMetricsOutput>,
+	pub fairness_metrics: Option<ProductionFairnessMetricsOutput>,
+}
+
+#[derive(Debug)]
+pub struct ProductionFairnessMetricsOutput {
+	pub sensitive_column_name: String,
+	pub groups: Vec<ProductionFairnessGroupMetricsOutput>,
+	/// This is the difference between the largest and smallest positive rate among the groups with true values, or `None` if there are none.
+	pub demographic_parity_difference: Option<f32>,
+	/// This is the difference between the largest and smallest recall among the groups with true values of the positive class, or `None` if there are none.
+	pub equal_opportunity_difference: Option<f32>,
+}
+
+#[derive(Debug)]
+pub struct ProductionFairnessGroupMetricsOutput {
+	pub group: String,
+	/// This is the fraction of the group's predictions with true values that were the positive class.
+	pub positive_rate: Option<f32>,
+	pub prediction_metrics: Option<BinaryClassificationProductionPredictionMetricsOutput>,
 }
 
 #[derive(Debug)]
@@ -61,15 +93,144 @@ impl ProductionMetrics {
 		end_date: DateTime<Utc>,
 	) -> ProductionMetrics {
 		let prediction_metrics = ProductionPredictionMetrics::new(model);
+		let fairness_metrics = ProductionFairnessMetrics::new(model);
 		ProductionMetrics {
 			start_date,
 			end_date,
 			true_values_count: 0,
 			prediction_metrics,
+			fairness_metrics,
+		}
+	}
+
+	/// Record whether the true value was inside the prediction interval of a regressor's prediction.
+	pub fn update_prediction_interval(
+		&mut self,
+		prediction_interval: (f32, f32),
+		true_value: &NumberOrString,
+	) {
+		if let ProductionPredictionMetrics::Regression(prediction_metrics) =
+			&mut self.prediction_metrics
+		{
+			prediction_metrics.update_prediction_interval(prediction_interval, true_value);
+		}
+	}
+
+	/// Update the prediction metrics of the group of the sensitive column that the prediction's input belongs to, if the model has a sensitive column.
+	pub fn update_fairness_metrics(
+		&mut self,
+		input: &HashMap<String, serde_json::Value>,
+		value: (NumberOrString, NumberOrString),
+	) {
+		if let Some(fairness_metrics) = self.fairness_metrics.as_mut() {
+			fairness_metrics.update(input, value);
+		}
+	}
+}
+
+impl ProductionFairnessMetrics {
+	pub fn new(model: &tangram_core::model::Model) -> Option<ProductionFairnessMetrics> {
+		let model = match model {
+			tangram_core::model::Model::BinaryClassifier(model) => model,
+			_ => return None,
+		};
+		let sensitive_column_name = &model.fairness_metrics.as_ref()?.sensitive_column_name;
+		let column_stats = model
+			.overall_column_stats
+			.iter()
+			.find(|column_stats| column_stats.column_name() == sensitive_column_name)?
+			.as_enum()?;
+		let groups = column_stats
+			.histogram
+			.iter()
+			.map(|(group, _)| {
+				(
+					group.clone(),
+					BinaryClassificationProductionPredictionMetrics::new(
+						model.negative_class.clone(),
+						model.positive_class.clone(),
+					),
+				)
+			})
+			.collect();
+		Some(ProductionFairnessMetrics {
+			sensitive_column_name: sensitive_column_name.clone(),
+			groups,
+		})
+	}
+
+	fn update(
+		&mut self,
+		input: &HashMap<String, serde_json::Value>,
+		value: (NumberOrString, NumberOrString),
+	) {
+		let group = match input.get(&self.sensitive_column_name) {
+			Some(serde_json::Value::String(group)) => group.clone(),
+			Some(serde_json::Value::Number(group)) => group.to_string(),
+			Some(serde_json::Value::Bool(group)) => group.to_string(),
+			_ => return,
+		};
+		if let Some((_, metrics)) = self.groups.iter_mut().find(|(g, _)| *g == group) {
+			metrics.update(value);
+		}
+	}
+
+	fn merge(&mut self, other: ProductionFairnessMetrics) {
+		for ((_, metrics), (_, other_metrics)) in self.groups.iter_mut().zip(other.groups) {
+			metrics.merge(other_metrics);
+		}
+	}
+
+	fn finalize(self) -> ProductionFairnessMetricsOutput {
+		let groups = self
+			.groups
+			.into_iter()
+			.map(|(group, metrics)| {
+				let prediction_metrics = metrics.finalize();
+				let positive_rate = prediction_metrics.as_ref().map(|metrics| {
+					let n_examples = metrics.true_positives
+						+ metrics.false_positives
+						+ metrics.true_negatives
+						+ metrics.false_negatives;
+					(metrics.true_positives + metrics.false_positives)
+						.to_f32()
+						.unwrap() / n_examples.to_f32().unwrap()
+				});
+				ProductionFairnessGroupMetricsOutput {
+					group,
+					positive_rate,
+					prediction_metrics,
+				}
+			})
+			.collect::<Vec<_>>();
+		let demographic_parity_difference =
+			compute_max_difference(groups.iter().filter_map(|group| group.positive_rate));
+		let equal_opportunity_difference = compute_max_difference(
+			groups
+				.iter()
+				.filter_map(|group| group.prediction_metrics.as_ref())
+				.map(|metrics| metrics.recall),
+		);
+		ProductionFairnessMetricsOutput {
+			sensitive_column_name: self.sensitive_column_name,
+			groups,
+			demographic_parity_difference,
+			equal_opportunity_difference,
 		}
 	}
 }
 
+/// Compute the difference between the largest and smallest finite values, or `None` if there are none.
+fn compute_max_difference(values: impl Iterator<Item = f32>) -> Option<f32> {
+	values
+		.filter(|value| value.is_finite())
+		.fold(None, |min_max, value| match min_max {
+			None => Some((value, value)),
+			Some((min, max)) => Some((value.min(min), value.max(max))),
+		})
+		.map(|(min, max)| max - min)
+}
+
 impl StreamingMetric<'_> for ProductionMetrics {
 	type Input = (NumberOrString, NumberOrString);
 	type Output = ProductionMetricsOutput;
@@ -84,6 +245,11 @@ impl StreamingMetric<'_> for ProductionMetrics {
 		self.end_date = self.end_date.max(other.end_date);
 		self.prediction_metrics.merge(other.prediction_metrics);
 		self.true_values_count += other.true_values_count;
+		match (self.fairness_metrics.as_mut(), other.fairness_metrics) {
+			(Some(fairness_metrics), Some(other)) => fairness_metrics.merge(other),
+			(None, Some(other)) => self.fairness_metrics = Some(other),
+			_ => {}
+		}
 	}
 
 	fn finalize(self) -> Self::Output {
@@ -92,6 +258,9 @@ impl StreamingMetric<'_> for ProductionMetrics {
 			end_date: self.end_date,
 			true_values_count: self.true_values_count,
 			prediction_metrics: self.prediction_metrics.finalize(),
+			fairness_metrics: self
+				.fairness_metrics
+				.map(|fairness_metrics| fairness_metrics.finalize()),
 		}
 	}
 }
@@ -186,29 +355,9 @@ pub async fn get_production_metrics(
 	date_window_interval: DateWindowInterval,
 	timezone: Tz,
 ) -> Result<GetProductionMetricsOutput> {
-	/*
-	Compute the start date given the date window.
-	* For today, use the start of this UTC day.
-	* For this month, use the start of this UTC month.
-	* For this year, use the start of this UTC year.
-	*/
-	let now: DateTime<Tz> = Utc::now().with_timezone(&timezone);
-	let start_date = match date_window {
-		DateWindow::Today => timezone
-			.ymd(now.year(), now.month(), now.day())
-			.and_hms(0, 0, 0),
-		DateWindow::ThisMonth => timezone.ymd(now.year(), now.month(), 1).and_hms(0, 0, 0),
-		DateWindow::ThisYear => timezone.ymd(now.year(), 1, 1).and_hms(0, 0, 0),
-	};
-	let end_date = match date_window {
-		DateWindow::Today => start_date + chrono::Duration::days(1),
-		DateWindow::ThisMonth => {
-			start_date
-				+ chrono::Duration::days(n_days_in_month(start_date.year(), start_date.month()))
-		}
-		DateWindow::ThisYear => timezone.ymd(now.year() + 1, 1, 1).and_hms(0, 0, 0),
-	};
-	let start_date = &start_date.with_timezone(&Utc);
+	// Compute the start and end dates of the date window and split it into intervals.
+	let (start_date, end_date) = compute_date_window_range(&date_window, timezone);
+	let interval_dates = compute_date_window_intervals(start_date, end_date, &date_window_interval);
 	let rows = sqlx::query(
 		"
 			select
@@ -217,67 +366,31 @@ pub async fn get_production_metrics(
 			from production_metrics
 			where
 				model_id = $1 and
-				hour >= $2
+				hour >= $2 and
+				hour < $3
 			order by hour
 		",
 	)
 	.bind(&model.id().to_string())
 	.bind(&start_date.timestamp())
+	.bind(&end_date.timestamp())
 	.fetch_all(&mut *db)
 	.await?;
-	/*
-	 Compute the number of intervals.
-	 * For today, use 24.
-	 * For this month, use the number of days in this month.
-	 * For this year, use 12.
-	*/
-	let n_intervals: usize = match date_window_interval {
-		DateWindowInterval::Hourly => 24,
-		DateWindowInterval::Daily => n_days_in_month(start_date.year(), start_date.month())
-			.to_usize()
-			.unwrap(),
-		DateWindowInterval::Monthly => 12,
-	};
-	let mut intervals: Vec<ProductionMetrics> = (0..n_intervals.to_u32().unwrap())
-		.map(|i| {
-			// Determine the start and end dates for the interval.
-			let start = match date_window_interval {
-				DateWindowInterval::Hourly => start_date.with_hour(i).unwrap(),
-				DateWindowInterval::Daily => start_date.with_day0(i).unwrap(),
-				DateWindowInterval::Monthly => start_date.with_month0(i).unwrap(),
-			};
-			let end = match date_window_interval {
-				DateWindowInterval::Hourly => start + chrono::Duration::hours(1),
-				DateWindowInterval::Daily => start + chrono::Duration::days(1),
-				DateWindowInterval::Monthly => {
-					start + chrono::Duration::days(n_days_in_month(start.year(), start.month()))
-				}
-			};
-			ProductionMetrics::new(&model, start.with_timezone(&Utc), end.with_timezone(&Utc))
-		})
+	let mut intervals: Vec<ProductionMetrics> = interval_dates
+		.iter()
+		.map(|(start, end)| ProductionMetrics::new(&model, *start, *end))
 		.collect();
 	// Merge each hourly production metrics entry into its corresponding interval.
 	for row in rows {
 		let data: String = row.get(0);
 		let data: Vec<u8> = base64::decode(data)?;
 		let hour: i64 = row.get(1);
-		let hour = timezone.timestamp(hour, 0);
-		let interval = match date_window_interval {
-			DateWindowInterval::Hourly => {
-				let hour = hour.hour().to_usize().unwrap();
-				intervals.get_mut(hour).unwrap()
-			}
-			DateWindowInterval::Daily => {
-				let day = hour.day0().to_usize().unwrap();
-				intervals.get_mut(day).unwrap()
-			}
-			DateWindowInterval::Monthly => {
-				let month = hour.month0().to_usize().unwrap();
-				intervals.get_mut(month).unwrap()
-			}
+		let interval_index = match find_interval(&interval_dates, Utc.timestamp(hour, 0)) {
+			Some(interval_index) => interval_index,
+			None => continue,
 		};
 		let hourly_production_metrics: ProductionMetrics = serde_json::from_slice(&data).unwrap();
-		interval.merge(hourly_production_metrics);
+		intervals[interval_index].merge(hourly_production_metrics);
 	}
 	let overall = intervals
 		.iter()
@@ -304,14 +417,3 @@ pub async fn get_production_metrics(
 		intervals,
 	})
 }
-
-fn n_days_in_month(year: i32, month: u32) -> i64 {
-	let (end_year, end_month) = if month == 12 {
-		(year + 1, 1)
-	} else {
-		(year, month + 1)
-	};
-	let start = NaiveDate::from_ymd(year, month, 1);
-	let end = NaiveDate::from_ymd(end_year, end_month, 1);
-	(end - start).num_days()
-}
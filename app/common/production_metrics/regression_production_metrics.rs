@@ -7,6 +7,12 @@ pub struct RegressionProductionPredictionMetrics {
 	stats: Option<NumberStats>,
 	absolute_error: f64,
 	squared_error: f64,
+	/// This is the number of true values whose prediction had a prediction interval.
+	#[serde(default)]
+	prediction_intervals_count: u64,
+	/// This is the number of true values that were inside their prediction's interval.
+	#[serde(default)]
+	prediction_intervals_covered_count: u64,
 }
 
 #[derive(Debug)]
@@ -17,12 +23,29 @@ pub struct RegressionProductionPredictionMetricsOutput {
 	pub r2: f32,
 	pub baseline_mse: f32,
 	pub baseline_rmse: f32,
+	/// This is the fraction of true values that were inside their prediction's interval, or `None` if no predictions had an interval.
+	pub prediction_interval_coverage: Option<f32>,
 }
 
 impl RegressionProductionPredictionMetrics {
 	pub fn new() -> RegressionProductionPredictionMetrics {
 		RegressionProductionPredictionMetrics::default()
 	}
+
+	pub fn update_prediction_interval(
+		&mut self,
+		(lower, upper): (f32, f32),
+		label: &NumberOrString,
+	) {
+		let label = match label.as_number() {
+			Ok(value) => value,
+			Err(_) => return,
+		};
+		self.prediction_intervals_count += 1;
+		if lower <= label && label <= upper {
+			self.prediction_intervals_covered_count += 1;
+		}
+	}
 }
 
 impl Default for RegressionProductionPredictionMetrics {
@@ -31,6 +54,8 @@ impl Default for RegressionProductionPredictionMetrics {
 			stats: None,
 			absolute_error: 0.0,
 			squared_error: 0.0,
+			prediction_intervals_count: 0,
+			prediction_intervals_covered_count: 0,
 		}
 	}
 }
@@ -71,6 +96,8 @@ impl StreamingMetric<'_> for RegressionProductionPredictionMetrics {
 		};
 		self.absolute_error += other.absolute_error;
 		self.squared_error += other.squared_error;
+		self.prediction_intervals_count += other.prediction_intervals_count;
+		self.prediction_intervals_covered_count += other.prediction_intervals_covered_count;
 	}
 
 	fn finalize(self) -> Self::Output {
@@ -85,6 +112,14 @@ impl StreamingMetric<'_> for RegressionProductionPredictionMetrics {
 					- self.squared_error.to_f32().unwrap() / (variance * stats.n.to_f32().unwrap()); // Sum of Squared Error = variance * n
 				let baseline_mse = variance;
 				let baseline_rmse = baseline_mse.sqrt();
+				let prediction_interval_coverage = if self.prediction_intervals_count > 0 {
+					Some(
+						self.prediction_intervals_covered_count.to_f32().unwrap()
+							/ self.prediction_intervals_count.to_f32().unwrap(),
+					)
+				} else {
+					None
+				};
 				Some(RegressionProductionPredictionMetricsOutput {
 					mae,
 					mse,
@@ -92,6 +127,7 @@ impl StreamingMetric<'_> for RegressionProductionPredictionMetrics {
 					rmse,
 					baseline_mse,
 					baseline_rmse,
+					prediction_interval_coverage,
 				})
 			}
 			None => None,
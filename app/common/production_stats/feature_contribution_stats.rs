@@ -0,0 +1,68 @@
+use tangram_deps::num_traits::ToPrimitive;
+use tangram_metrics::StreamingMetric;
+
+/// `ProductionFeatureContributionStats` sums the absolute value of each feature's contribution over the predictions whose feature contributions were computed when they were tracked. The sums are in the same order as the model's features, and are empty until the first contributions are recorded.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default)]
+pub struct ProductionFeatureContributionStats {
+	pub count: u64,
+	pub abs_feature_contribution_sums: Vec<f64>,
+}
+
+#[derive(Debug)]
+pub struct ProductionFeatureContributionStatsOutput {
+	pub count: u64,
+	/// This is the mean absolute contribution of each feature, or `None` if no predictions had their feature contributions computed.
+	pub mean_abs_feature_contributions: Option<Vec<f32>>,
+}
+
+impl<'a> StreamingMetric<'a> for ProductionFeatureContributionStats {
+	type Input = &'a [f32];
+	type Output = ProductionFeatureContributionStatsOutput;
+
+	fn update(&mut self, value: &'a [f32]) {
+		if self.abs_feature_contribution_sums.len() < value.len() {
+			self.abs_feature_contribution_sums.resize(value.len(), 0.0);
+		}
+		for (sum, feature_contribution) in self
+			.abs_feature_contribution_sums
+			.iter_mut()
+			.zip(value.iter())
+		{
+			*sum += feature_contribution.abs().to_f64().unwrap();
+		}
+		self.count += 1;
+	}
+
+	fn merge(&mut self, other: Self) {
+		if self.abs_feature_contribution_sums.len() < other.abs_feature_contribution_sums.len() {
+			self.abs_feature_contribution_sums
+				.resize(other.abs_feature_contribution_sums.len(), 0.0);
+		}
+		for (sum, other_sum) in self
+			.abs_feature_contribution_sums
+			.iter_mut()
+			.zip(other.abs_feature_contribution_sums.iter())
+		{
+			*sum += other_sum;
+		}
+		self.count += other.count;
+	}
+
+	fn finalize(self) -> Self::Output {
+		let count = self.count;
+		let mean_abs_feature_contributions = if count == 0 {
+			None
+		} else {
+			Some(
+				self.abs_feature_contribution_sums
+					.iter()
+					.map(|sum| (sum / count.to_f64().unwrap()).to_f32().unwrap())
+					.collect(),
+			)
+		};
+		ProductionFeatureContributionStatsOutput {
+			count,
+			mean_abs_feature_contributions,
+		}
+	}
+}
@@ -1,17 +1,19 @@
-use crate::date_window::{DateWindow, DateWindowInterval};
-use crate::monitor_event::PredictionMonitorEvent;
-use tangram_deps::{
-	base64, chrono, chrono::prelude::*, chrono_tz::Tz, num_traits::ToPrimitive, serde_json, sqlx,
-	sqlx::prelude::*,
+use crate::date_window::{
+	compute_date_window_intervals, compute_date_window_range, find_interval, DateWindow,
+	DateWindowInterval,
 };
+use crate::monitor_event::PredictionMonitorEvent;
+use tangram_deps::{base64, chrono::prelude::*, chrono_tz::Tz, serde_json, sqlx, sqlx::prelude::*};
 use tangram_metrics::StreamingMetric;
 use tangram_util::{error::Result, zip};
 
 mod column_stats;
+mod feature_contribution_stats;
 mod number_stats;
 mod prediction_stats;
 
 pub use column_stats::*;
+pub use feature_contribution_stats::*;
 pub use number_stats::*;
 pub use prediction_stats::*;
 
@@ -22,6 +24,8 @@ pub struct ProductionStats {
 	pub row_count: u64,
 	pub column_stats: Vec<ProductionColumnStats>,
 	pub prediction_stats: ProductionPredictionStats,
+	#[serde(default)]
+	pub feature_contribution_stats: ProductionFeatureContributionStats,
 }
 
 #[derive(Debug)]
@@ -31,6 +35,7 @@ pub struct ProductionStatsOutput {
 	pub row_count: u64,
 	pub column_stats: Vec<ProductionColumnStatsOutput>,
 	pub prediction_stats: ProductionPredictionStatsOutput,
+	pub feature_contribution_stats: ProductionFeatureContributionStatsOutput,
 }
 
 impl ProductionStats {
@@ -59,8 +64,15 @@ impl ProductionStats {
 			row_count: 0,
 			column_stats,
 			prediction_stats,
+			feature_contribution_stats: ProductionFeatureContributionStats::default(),
 		}
 	}
+
+	/// Record the feature contributions computed for a prediction, in the same order as the model's features.
+	pub fn update_feature_contributions(&mut self, feature_contributions: &[f32]) {
+		self.feature_contribution_stats
+			.update(feature_contributions);
+	}
 }
 
 impl StreamingMetric<'_> for ProductionStats {
@@ -84,6 +96,8 @@ impl StreamingMetric<'_> for ProductionStats {
 			this.merge(other)
 		}
 		self.prediction_stats.merge(other.prediction_stats);
+		self.feature_contribution_stats
+			.merge(other.feature_contribution_stats);
 	}
 
 	fn finalize(self) -> ProductionStatsOutput {
@@ -97,6 +111,7 @@ impl StreamingMetric<'_> for ProductionStats {
 				.map(|c| c.finalize())
 				.collect(),
 			prediction_stats: self.prediction_stats.finalize(),
+			feature_contribution_stats: self.feature_contribution_stats.finalize(),
 		}
 	}
 }
@@ -116,27 +131,9 @@ pub async fn get_production_stats(
 	date_window_interval: DateWindowInterval,
 	timezone: Tz,
 ) -> Result<GetProductionStatsOutput> {
-	// Compute the start date given the date window.
-	// * For today, use the start of this utc day.
-	// * For this month, use the start of this utc month.
-	// * For this year, use the start of this utc year.
-	let now: DateTime<Tz> = Utc::now().with_timezone(&timezone);
-	let start_date = match date_window {
-		DateWindow::Today => timezone
-			.ymd(now.year(), now.month(), now.day())
-			.and_hms(0, 0, 0),
-		DateWindow::ThisMonth => timezone.ymd(now.year(), now.month(), 1).and_hms(0, 0, 0),
-		DateWindow::ThisYear => timezone.ymd(now.year(), 1, 1).and_hms(0, 0, 0),
-	};
-	let end_date = match date_window {
-		DateWindow::Today => start_date + chrono::Duration::days(1),
-		DateWindow::ThisMonth => {
-			start_date
-				+ chrono::Duration::days(n_days_in_month(start_date.year(), start_date.month()))
-		}
-		DateWindow::ThisYear => timezone.ymd(now.year() + 1, 1, 1).and_hms(0, 0, 0),
-	};
-	let start_date = &start_date.with_timezone(&Utc);
+	// Compute the start and end dates of the date window and split it into intervals.
+	let (start_date, end_date) = compute_date_window_range(&date_window, timezone);
+	let interval_dates = compute_date_window_intervals(start_date, end_date, &date_window_interval);
 	// Retrieve the production stats for the date window.
 	let rows = sqlx::query(
 		"
@@ -146,66 +143,32 @@ pub async fn get_production_stats(
 			from production_stats
 			where
 				model_id = $1 and
-				hour >= $2
+				hour >= $2 and
+				hour < $3
 			order by hour
 		",
 	)
 	.bind(&model.id().to_string())
 	.bind(&start_date.timestamp())
+	.bind(&end_date.timestamp())
 	.fetch_all(&mut *db)
 	.await?;
-	// Compute the number of intervals.
-	// * For today, use 24.
-	// * For this month, use the number of days in this month.
-	// * For this year, use 12.
-	let n_intervals: usize = match date_window_interval {
-		DateWindowInterval::Hourly => 24,
-		DateWindowInterval::Daily => n_days_in_month(start_date.year(), start_date.month())
-			.to_usize()
-			.unwrap(),
-		DateWindowInterval::Monthly => 12,
-	};
 	// Initialize the intervals with start and end dates.
-	let mut intervals: Vec<ProductionStats> = (0..n_intervals.to_u32().unwrap())
-		.map(|i| {
-			// Determine the start and end dates for the interval.
-			let start = match date_window_interval {
-				DateWindowInterval::Hourly => start_date.with_hour(i).unwrap(),
-				DateWindowInterval::Daily => start_date.with_day0(i).unwrap(),
-				DateWindowInterval::Monthly => start_date.with_month0(i).unwrap(),
-			};
-			let end = match date_window_interval {
-				DateWindowInterval::Hourly => start + chrono::Duration::hours(1),
-				DateWindowInterval::Daily => start + chrono::Duration::days(1),
-				DateWindowInterval::Monthly => {
-					start + chrono::Duration::days(n_days_in_month(start.year(), start.month()))
-				}
-			};
-			ProductionStats::new(&model, start.with_timezone(&Utc), end.with_timezone(&Utc))
-		})
+	let mut intervals: Vec<ProductionStats> = interval_dates
+		.iter()
+		.map(|(start, end)| ProductionStats::new(&model, *start, *end))
 		.collect();
 	// Merge each hourly production stats entry into its corresponding interval.
 	for row in rows {
 		let data: String = row.get(0);
 		let data: Vec<u8> = base64::decode(data)?;
 		let hour: i64 = row.get(1);
-		let hour = timezone.timestamp(hour, 0);
-		let interval = match date_window_interval {
-			DateWindowInterval::Hourly => {
-				let hour = hour.hour().to_usize().unwrap();
-				intervals.get_mut(hour).unwrap()
-			}
-			DateWindowInterval::Daily => {
-				let day = hour.day0().to_usize().unwrap();
-				intervals.get_mut(day).unwrap()
-			}
-			DateWindowInterval::Monthly => {
-				let month = hour.month0().to_usize().unwrap();
-				intervals.get_mut(month).unwrap()
-			}
+		let interval_index = match find_interval(&interval_dates, Utc.timestamp(hour, 0)) {
+			Some(interval_index) => interval_index,
+			None => continue,
 		};
 		let hourly_production_stats = serde_json::from_slice(&data)?;
-		interval.merge(hourly_production_stats);
+		intervals[interval_index].merge(hourly_production_stats);
 	}
 	// Compute the overall production stats by merging all the intervals together.
 	let overall = intervals
@@ -234,14 +197,3 @@ pub async fn get_production_stats(
 		intervals,
 	})
 }
-
-fn n_days_in_month(year: i32, month: u32) -> i64 {
-	let (end_year, end_month) = if month == 12 {
-		(year + 1, 1)
-	} else {
-		(year, month + 1)
-	};
-	let start = NaiveDate::from_ymd(year, month, 1);
-	let end = NaiveDate::from_ymd(end_year, end_month, 1);
-	(end - start).num_days()
-}
@@ -26,6 +26,8 @@ pub struct NumberStatsOutput {
 	pub p25: f32,
 	pub p50: f32,
 	pub p75: f32,
+	/// These are the reservoir's samples in ascending order, which are used to estimate the distribution of the values.
+	pub samples: Vec<f32>,
 }
 
 impl NumberStats {
@@ -119,6 +121,7 @@ impl StreamingMetric<'_> for NumberStats {
 			std: tangram_metrics::m2_to_variance(self.m2, NonZeroU64::new(self.n).unwrap()).sqrt(),
 			min: self.min,
 			max: self.max,
+			samples,
 		}
 	}
 }
@@ -7,6 +7,13 @@ pub fn format_date_window(date: DateTime<Utc>, date_window: &DateWindow, timezon
 		DateWindow::Today => format_day(date),
 		DateWindow::ThisMonth => format_month(date),
 		DateWindow::ThisYear => format_year(date),
+		DateWindow::Last24Hours => "Last 24 Hours".to_owned(),
+		DateWindow::Last7Days => "Last 7 Days".to_owned(),
+		DateWindow::Last30Days => "Last 30 Days".to_owned(),
+		DateWindow::Custom {
+			start_date,
+			end_date,
+		} => format_date_range(*start_date, *end_date),
 	}
 }
 
@@ -19,6 +26,7 @@ pub fn format_date_window_interval(
 	match date_window_interval {
 		DateWindowInterval::Hourly => format_hour(date),
 		DateWindowInterval::Daily => format_day_of_month(date),
+		DateWindowInterval::Weekly => format!("Week of {}", format_day_of_month(date)),
 		DateWindowInterval::Monthly => format_month(date),
 	}
 }
@@ -27,6 +35,7 @@ pub fn interval_chart_title(date_window_interval: &DateWindowInterval, title: St
 	match date_window_interval {
 		DateWindowInterval::Hourly => format!("Hourly {}", title),
 		DateWindowInterval::Daily => format!("Daily {}", title),
+		DateWindowInterval::Weekly => format!("Weekly {}", title),
 		DateWindowInterval::Monthly => format!("Monthly {}", title),
 	}
 }
@@ -36,9 +45,28 @@ pub fn overall_chart_title(date_window: &DateWindow, title: String) -> String {
 		DateWindow::Today => format!("Today's {}", title),
 		DateWindow::ThisMonth => format!("This Month's {}", title),
 		DateWindow::ThisYear => format!("This Year's {}", title),
+		DateWindow::Last24Hours => format!("{} for the Last 24 Hours", title),
+		DateWindow::Last7Days => format!("{} for the Last 7 Days", title),
+		DateWindow::Last30Days => format!("{} for the Last 30 Days", title),
+		DateWindow::Custom {
+			start_date,
+			end_date,
+		} => format!(
+			"{} from {}",
+			title,
+			format_date_range(*start_date, *end_date)
+		),
 	}
 }
 
+pub fn format_date_range(start_date: NaiveDate, end_date: NaiveDate) -> String {
+	format!(
+		"{} to {}",
+		start_date.format("%b %d %Y"),
+		end_date.format("%b %d %Y")
+	)
+}
+
 pub fn format_hour(date: DateTime<Tz>) -> String {
 	date.format("%-l%P").to_string()
 }
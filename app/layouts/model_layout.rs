@@ -205,11 +205,14 @@ pub enum ModelSideNavItem {
 	TrainingStats,
 	TrainingMetrics,
 	TrainingImportances,
+	TrainedModel,
 	Prediction,
 	Tuning,
 	ProductionPredictions,
 	ProductionStats,
 	ProductionMetrics,
+	ProductionImportances,
+	Alerts,
 }
 
 #[component]
@@ -361,11 +364,17 @@ pub fn ModelSideNav(repo_id: String, model_id: String, selected_item: ModelSideN
 					{"Training Metrics"}
 				</ui::SideNavItem>
 				<ui::SideNavItem
-					href={format!("/repos/{}/models/{}/training_importances", repo_id, model_id)}
+					href={format!("/repos/{}/models/{}/training_importances/", repo_id, model_id)}
 					selected={Some(selected_item == ModelSideNavItem::TrainingImportances)}
 				>
 					{"Training Importances"}
 				</ui::SideNavItem>
+				<ui::SideNavItem
+					href={format!("/repos/{}/models/{}/trained_model", repo_id, model_id)}
+					selected={Some(selected_item == ModelSideNavItem::TrainedModel)}
+				>
+					{"Trained Model"}
+				</ui::SideNavItem>
 				<ui::SideNavItem
 					href={format!("/repos/{}/models/{}/prediction", repo_id, model_id)}
 					selected={Some(selected_item == ModelSideNavItem::Prediction)}
@@ -398,6 +407,18 @@ pub fn ModelSideNav(repo_id: String, model_id: String, selected_item: ModelSideN
 				>
 					{"Production Metrics"}
 				</ui::SideNavItem>
+				<ui::SideNavItem
+					href={format!("/repos/{}/models/{}/production_importances", repo_id, model_id)}
+					selected={Some(selected_item == ModelSideNavItem::ProductionImportances)}
+				>
+					{"Production Importances"}
+				</ui::SideNavItem>
+				<ui::SideNavItem
+					href={format!("/repos/{}/models/{}/alerts", repo_id, model_id)}
+					selected={Some(selected_item == ModelSideNavItem::Alerts)}
+				>
+					{"Alerts"}
+				</ui::SideNavItem>
 			</ui::SideNavSection>
 		</ui::SideNav>
 	}
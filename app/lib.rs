@@ -1,6 +1,6 @@
 use std::path::{Path, PathBuf};
 use std::{collections::BTreeMap, sync::Arc};
-use tangram_app_common::Context;
+use tangram_app_common::{alerts::check_alert_rules, Context};
 use tangram_deps::{futures::FutureExt, http, hyper, sqlx, tokio, url};
 use tangram_util::{err, error::Result, serve::serve};
 
@@ -46,6 +46,19 @@ async fn run_inner(options: Options) -> Result<()> {
 		.await?;
 	// Run any pending migrations.
 	self::migrations::run(&pool).await?;
+	// Check the alert rules every minute, so each rule is checked soon after each hour ends.
+	let alerts_pool = pool.clone();
+	let sendgrid_api_token = options.sendgrid_api_token.clone();
+	tokio::spawn(async move {
+		let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+		loop {
+			interval.tick().await;
+			let result = check_alert_rules(&alerts_pool, sendgrid_api_token.as_deref()).await;
+			if let Err(error) = result {
+				eprintln!("{}", error);
+			}
+		}
+	});
 	// Start the server.
 	let host = options.host;
 	let port = options.port;
@@ -187,15 +200,32 @@ async fn request_handler(
 				request,
 				model_id,
 				column_name,
+				search_params,
 			).boxed()
 		}
-		(&http::Method::GET, &["repos", _repo_id, "models", model_id, "training_importances"]) => {
-			tangram_app_pages_repos_repo_id_models_model_id_training_importances::get(
+		(&http::Method::GET, &["repos", _repo_id, "models", model_id, "training_importances", ""]) => {
+			tangram_app_pages_repos_repo_id_models_model_id_training_importances_index::get(
 				context,
 				request,
 				model_id,
 			).boxed()
 		}
+		(&http::Method::GET, &["repos", _repo_id, "models", model_id, "training_importances", "shap"]) => {
+			tangram_app_pages_repos_repo_id_models_model_id_training_importances_shap::get(
+				context,
+				request,
+				model_id,
+				search_params,
+			).boxed()
+		}
+		(&http::Method::GET, &["repos", _repo_id, "models", model_id, "trained_model"]) => {
+			tangram_app_pages_repos_repo_id_models_model_id_trained_model::get(
+				context,
+				request,
+				model_id,
+				search_params,
+			).boxed()
+		}
 		(&http::Method::GET, &["repos", _repo_id, "models", model_id, "prediction"]) => {
 			tangram_app_pages_repos_repo_id_models_model_id_prediction::get(
 				context,
@@ -219,6 +249,13 @@ async fn request_handler(
 				search_params,
 			).boxed()
 		}
+		(&http::Method::GET, &["repos", _repo_id, "models", model_id, "training_metrics", "fairness"]) => {
+			tangram_app_pages_repos_repo_id_models_model_id_training_metrics_fairness::get(
+				context,
+				request,
+				model_id,
+			).boxed()
+		}
 		(&http::Method::GET, &["repos", _repo_id, "models", model_id, "training_metrics", "precision_recall"]) => {
 			tangram_app_pages_repos_repo_id_models_model_id_training_metrics_precision_recall::get(
 				context,
@@ -233,6 +270,13 @@ async fn request_handler(
 				model_id,
 			).boxed()
 		}
+		(&http::Method::GET, &["repos", _repo_id, "models", model_id, "training_metrics", "slices"]) => {
+			tangram_app_pages_repos_repo_id_models_model_id_training_metrics_slices::get(
+				context,
+				request,
+				model_id,
+			).boxed()
+		}
 		(&http::Method::GET, &["repos", _repo_id, "models", model_id, "tuning"]) => {
 			tangram_app_pages_repos_repo_id_models_model_id_tuning::get(
 				context,
@@ -296,6 +340,28 @@ async fn request_handler(
 				search_params,
 			).boxed()
 		}
+		(&http::Method::GET, &["repos", _repo_id, "models", model_id, "production_importances"]) => {
+			tangram_app_pages_repos_repo_id_models_model_id_production_importances::get(
+				context,
+				request,
+				model_id,
+				search_params,
+			).boxed()
+		}
+		(&http::Method::GET, &["repos", _repo_id, "models", model_id, "alerts"]) => {
+			tangram_app_pages_repos_repo_id_models_model_id_alerts::get(
+				context,
+				request,
+				model_id,
+			).boxed()
+		}
+		(&http::Method::POST, &["repos", _repo_id, "models", model_id, "alerts"]) => {
+			tangram_app_pages_repos_repo_id_models_model_id_alerts::post(
+				context,
+				request,
+				model_id,
+			).boxed()
+		}
 		(&http::Method::GET, &["user"]) =>{
 			tangram_app_pages_user::get(
 				context,
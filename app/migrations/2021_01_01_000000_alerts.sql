@@ -0,0 +1,20 @@
+create table alert_rules (
+	id char(32) primary key,
+	model_id char(32) references models (id) on delete cascade not null,
+	created_at bigint not null,
+	/* json describing the metric the rule checks */
+	metric text not null,
+	threshold double precision not null,
+	email varchar(320),
+	webhook_url text,
+	/* the start of the last hour this rule was checked for */
+	last_checked_hour bigint
+);
+
+create table alerts (
+	id char(32) primary key,
+	alert_rule_id char(32) references alert_rules (id) on delete cascade not null,
+	model_id char(32) references models (id) on delete cascade not null,
+	hour bigint not null,
+	value double precision not null
+);
@@ -8,6 +8,10 @@ fn migrations() -> BTreeMap<&'static str, &'static str> {
 		"2020-01-01-000000-init.sql",
 		include_str!("./2020_01_01_000000_init.sql"),
 	);
+	migrations.insert(
+		"2021-01-01-000000-alerts.sql",
+		include_str!("./2021_01_01_000000_alerts.sql"),
+	);
 	migrations
 }
 
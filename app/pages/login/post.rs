@@ -1,14 +1,14 @@
 use super::page::{render, Props};
 use tangram_app_common::{
+	email::send_email,
 	error::{bad_request, service_unavailable},
 	Context,
 };
 use tangram_app_layouts::document::PageInfo;
 use tangram_deps::{
-	chrono::prelude::*, http, hyper, rand, rand::Rng, reqwest, serde_json::json, serde_urlencoded,
-	sqlx, sqlx::prelude::*,
+	chrono::prelude::*, http, hyper, rand, rand::Rng, serde_urlencoded, sqlx, sqlx::prelude::*,
 };
-use tangram_util::{err, error::Result, id::Id};
+use tangram_util::{error::Result, id::Id};
 
 #[derive(serde::Deserialize)]
 struct Action {
@@ -214,48 +214,11 @@ fn set_cookie_header_value(token: Id, domain: Option<&str>) -> String {
 }
 
 async fn send_code_email(email: String, code: String, sendgrid_api_token: String) -> Result<()> {
-	let json = json!({
-		"personalizations": [
-			{
-				"to": [
-					{
-						"email": email,
-					}
-				]
-			}
-		],
-		"from": {
-			"email": "noreply@tangramhq.com",
-			"name": "Tangram"
-		},
-		"subject": "Tangram Login Code",
-		"tracking_settings": {
-			"click_tracking": {
-				"enable": false
-			}
-		},
-		"content": [
-			{
-				"type": "text/plain",
-				"value": format!("Your Tangram login code is {}.", code),
-			}
-		]
-	});
-	let client = reqwest::Client::new();
-	let response = client
-		.post("https://api.sendgrid.com/v3/mail/send")
-		.header(
-			http::header::AUTHORIZATION,
-			format!("Bearer {}", sendgrid_api_token),
-		)
-		.json(&json)
-		.send()
-		.await?;
-	if !response.status().is_success() {
-		return Err(err!(
-			"Non-2xx response from sengrid: {:?}",
-			response.text().await?
-		));
-	}
-	Ok(())
+	send_email(
+		&sendgrid_api_token,
+		&email,
+		"Tangram Login Code",
+		format!("Your Tangram login code is {}.", code),
+	)
+	.await
 }
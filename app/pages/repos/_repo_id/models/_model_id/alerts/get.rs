@@ -0,0 +1,117 @@
+use crate::page::{render, Alert, AlertRule, MetricOption, Props};
+use tangram_app_common::{
+	alerts::{get_alert_rules, get_alerts},
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	model::get_model,
+	timezone::get_timezone,
+	user::{authorize_user, authorize_user_for_model},
+	Context,
+};
+use tangram_app_layouts::{document::PageInfo, model_layout::get_model_layout_info};
+use tangram_deps::{chrono::prelude::*, chrono_tz::Tz, http, hyper};
+use tangram_util::{error::Result, id::Id};
+
+/// This is the number of most recent alerts shown on the page.
+const N_ALERTS: i64 = 100;
+
+pub async fn get(
+	context: &Context,
+	request: http::Request<hyper::Body>,
+	model_id: &str,
+) -> Result<http::Response<hyper::Body>> {
+	let timezone = get_timezone(&request);
+	let mut db = match context.pool.begin().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user = match authorize_user(&request, &mut db, context.options.auth_enabled).await? {
+		Ok(user) => user,
+		Err(_) => return Ok(redirect_to_login()),
+	};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let model = get_model(&mut db, model_id).await?;
+	let train_column_stats = match &model {
+		tangram_core::model::Model::Regressor(model) => &model.train_column_stats,
+		tangram_core::model::Model::BinaryClassifier(model) => &model.train_column_stats,
+		tangram_core::model::Model::MulticlassClassifier(model) => &model.train_column_stats,
+	};
+	let column_names = train_column_stats
+		.iter()
+		.map(|column_stats| column_stats.column_name().to_owned())
+		.collect();
+	let mut metric_options = vec![
+		MetricOption {
+			text: "Absent Rate".to_owned(),
+			value: "absent_rate".to_owned(),
+		},
+		MetricOption {
+			text: "Invalid Rate".to_owned(),
+			value: "invalid_rate".to_owned(),
+		},
+		MetricOption {
+			text: "PSI".to_owned(),
+			value: "psi".to_owned(),
+		},
+	];
+	metric_options.push(match &model {
+		tangram_core::model::Model::Regressor(_) => MetricOption {
+			text: "RMSE Increase".to_owned(),
+			value: "rmse_increase".to_owned(),
+		},
+		tangram_core::model::Model::BinaryClassifier(_)
+		| tangram_core::model::Model::MulticlassClassifier(_) => MetricOption {
+			text: "Accuracy Drop".to_owned(),
+			value: "accuracy_drop".to_owned(),
+		},
+	});
+	let alert_rules = get_alert_rules(&mut db, model_id)
+		.await?
+		.into_iter()
+		.map(|alert_rule| AlertRule {
+			id: alert_rule.id.to_string(),
+			title: alert_rule.metric.title(),
+			threshold: alert_rule.metric.format_value(alert_rule.threshold),
+			email: alert_rule.email,
+			webhook_url: alert_rule.webhook_url,
+		})
+		.collect();
+	let alerts = get_alerts(&mut db, model_id, N_ALERTS)
+		.await?
+		.into_iter()
+		.map(|alert| {
+			let hour: DateTime<Tz> = alert.hour.with_timezone(&timezone);
+			Alert {
+				hour: hour.to_string(),
+				title: alert.metric.title(),
+				value: alert.metric.format_value(alert.value),
+				threshold: alert.metric.format_value(alert.threshold),
+			}
+		})
+		.collect();
+	let model_layout_info = get_model_layout_info(&mut db, context, model_id).await?;
+	let props = Props {
+		alert_rules,
+		alerts,
+		column_names,
+		email_enabled: context.options.sendgrid_api_token.is_some(),
+		id: model_id.to_string(),
+		metric_options,
+		model_layout_info,
+	};
+	db.commit().await?;
+	let page_info = PageInfo {
+		client_wasm_js_src: None,
+	};
+	let html = render(props, page_info);
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.body(hyper::Body::from(html))
+		.unwrap();
+	Ok(response)
+}
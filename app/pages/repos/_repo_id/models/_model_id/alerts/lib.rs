@@ -0,0 +1,6 @@
+mod get;
+mod page;
+mod post;
+
+pub use get::get;
+pub use post::post;
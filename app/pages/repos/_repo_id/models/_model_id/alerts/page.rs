@@ -0,0 +1,255 @@
+use tangram_app_layouts::{
+	document::PageInfo,
+	model_layout::{ModelLayout, ModelLayoutInfo, ModelSideNavItem},
+};
+use tangram_deps::html::{self, html};
+use tangram_ui as ui;
+
+pub struct Props {
+	pub alert_rules: Vec<AlertRule>,
+	pub alerts: Vec<Alert>,
+	pub column_names: Vec<String>,
+	/// Alert rules can only notify by email if the app has a SendGrid api token.
+	pub email_enabled: bool,
+	pub id: String,
+	pub metric_options: Vec<MetricOption>,
+	pub model_layout_info: ModelLayoutInfo,
+}
+
+pub struct AlertRule {
+	pub id: String,
+	pub title: String,
+	pub threshold: String,
+	pub email: Option<String>,
+	pub webhook_url: Option<String>,
+}
+
+pub struct Alert {
+	pub hour: String,
+	pub title: String,
+	pub value: String,
+	pub threshold: String,
+}
+
+pub struct MetricOption {
+	pub text: String,
+	pub value: String,
+}
+
+pub fn render(props: Props, page_info: PageInfo) -> String {
+	let alert_rules = if props.alert_rules.is_empty() {
+		html! {
+			<ui::Card>
+				<ui::P>{"This model has no alert rules."}</ui::P>
+			</ui::Card>
+		}
+	} else {
+		html! {
+			<ui::Table width={"100%".to_owned()}>
+				<ui::TableHeader>
+					<ui::TableRow color={None}>
+						<ui::TableHeaderCell color={None} expand={None} text_align={None}>
+							{"Metric"}
+						</ui::TableHeaderCell>
+						<ui::TableHeaderCell color={None} expand={None} text_align={None}>
+							{"Threshold"}
+						</ui::TableHeaderCell>
+						<ui::TableHeaderCell color={None} expand={None} text_align={None}>
+							{"Email"}
+						</ui::TableHeaderCell>
+						<ui::TableHeaderCell color={None} expand={None} text_align={None}>
+							{"Webhook"}
+						</ui::TableHeaderCell>
+						<ui::TableHeaderCell color={None} expand={None} text_align={None}>
+						</ui::TableHeaderCell>
+					</ui::TableRow>
+				</ui::TableHeader>
+				<ui::TableBody>
+					{props.alert_rules.into_iter().map(|alert_rule| html! {
+						<ui::TableRow color={None}>
+							<ui::TableCell color={None} expand={None}>
+								{alert_rule.title}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{alert_rule.threshold}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{alert_rule.email.unwrap_or_default()}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{alert_rule.webhook_url.unwrap_or_default()}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={false}>
+								<form method="post">
+									<input
+										name="action"
+										type="hidden"
+										value="delete_alert_rule"
+									/>
+									<input name="alert_rule_id" type="hidden" value={alert_rule.id} />
+									<ui::Button
+										button_type={ui::ButtonType::Submit}
+										disabled={None}
+										download={None}
+										href={None}
+										id={None}
+										color={"var(--red)".to_owned()}
+									>
+										{"Delete"}
+									</ui::Button>
+								</form>
+							</ui::TableCell>
+						</ui::TableRow>
+					}).collect::<Vec<_>>()}
+				</ui::TableBody>
+			</ui::Table>
+		}
+	};
+	let alerts = if props.alerts.is_empty() {
+		html! {
+			<ui::Card>
+				<ui::P>{"No alerts have fired for this model."}</ui::P>
+			</ui::Card>
+		}
+	} else {
+		html! {
+			<ui::Table width={"100%".to_owned()}>
+				<ui::TableHeader>
+					<ui::TableRow color={None}>
+						<ui::TableHeaderCell color={None} expand={None} text_align={None}>
+							{"Hour"}
+						</ui::TableHeaderCell>
+						<ui::TableHeaderCell color={None} expand={None} text_align={None}>
+							{"Metric"}
+						</ui::TableHeaderCell>
+						<ui::TableHeaderCell color={None} expand={None} text_align={None}>
+							{"Value"}
+						</ui::TableHeaderCell>
+						<ui::TableHeaderCell color={None} expand={None} text_align={None}>
+							{"Threshold"}
+						</ui::TableHeaderCell>
+					</ui::TableRow>
+				</ui::TableHeader>
+				<ui::TableBody>
+					{props.alerts.into_iter().map(|alert| html! {
+						<ui::TableRow color={None}>
+							<ui::TableCell color={None} expand={None}>
+								{alert.hour}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{alert.title}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{alert.value}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{alert.threshold}
+							</ui::TableCell>
+						</ui::TableRow>
+					}).collect::<Vec<_>>()}
+				</ui::TableBody>
+			</ui::Table>
+		}
+	};
+	let html = html! {
+		<ModelLayout
+			info={props.model_layout_info}
+			page_info={page_info}
+			selected_item={ModelSideNavItem::Alerts}
+		>
+			<ui::S1>
+				<ui::H1 center={false}>{"Alerts"}</ui::H1>
+				<ui::P>
+					{"After each hour ends, every alert rule computes its metric from that hour's production stats and metrics, and fires if the metric is above its threshold. Rates and accuracy drops are fractions, so a threshold of 0.05 fires above 5%. PSI compares the column's production distribution to its training distribution, and values above 0.2 usually indicate significant drift."}
+				</ui::P>
+				<ui::S2>
+					<ui::H2 center={false}>{"Alert Rules"}</ui::H2>
+					{alert_rules}
+				</ui::S2>
+				<ui::S2>
+					<ui::H2 center={false}>{"New Alert Rule"}</ui::H2>
+					<ui::Form
+						action={None}
+						autocomplete={None}
+						enc_type={None}
+						id={None}
+						post={true}
+					>
+						<input name="action" type="hidden" value="add_alert_rule" />
+						<ui::SelectField
+							disabled={None}
+							id={None}
+							placeholder={None}
+							label={"Metric".to_owned()}
+							name={"metric".to_owned()}
+							options={props.metric_options.into_iter().map(|metric_option| ui::SelectFieldOption {
+								text: metric_option.text,
+								value: metric_option.value,
+							}).collect::<Vec<_>>()}
+							required={true}
+							value={None}
+						/>
+						<ui::SelectField
+							disabled={None}
+							id={None}
+							placeholder={None}
+							label={"Column (for absent rate, invalid rate, and PSI)".to_owned()}
+							name={"column_name".to_owned()}
+							options={props.column_names.into_iter().map(|column_name| ui::SelectFieldOption {
+								text: column_name.clone(),
+								value: column_name,
+							}).collect::<Vec<_>>()}
+							required={None}
+							value={None}
+						/>
+						<ui::TextField
+							autocomplete={None}
+							disabled={None}
+							placeholder={None}
+							readonly={None}
+							required={true}
+							value={None}
+							label={"Threshold".to_owned()}
+							name={"threshold".to_owned()}
+						/>
+						<ui::TextField
+							autocomplete={None}
+							disabled={!props.email_enabled}
+							placeholder={None}
+							readonly={None}
+							required={None}
+							value={None}
+							label={"Email".to_owned()}
+							name={"email".to_owned()}
+						/>
+						<ui::TextField
+							autocomplete={None}
+							disabled={None}
+							placeholder={None}
+							readonly={None}
+							required={None}
+							value={None}
+							label={"Webhook URL".to_owned()}
+							name={"webhook_url".to_owned()}
+						/>
+						<ui::Button
+							id={None}
+							color={None}
+							disabled={None}
+							download={None}
+							href={None}
+							button_type={ui::ButtonType::Submit}
+						>
+							{"Add Alert Rule"}
+						</ui::Button>
+					</ui::Form>
+				</ui::S2>
+				<ui::S2>
+					<ui::H2 center={false}>{"Recent Alerts"}</ui::H2>
+					{alerts}
+				</ui::S2>
+			</ui::S1>
+		</ModelLayout>
+	};
+	html.render_to_string()
+}
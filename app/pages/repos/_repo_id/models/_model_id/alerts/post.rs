@@ -0,0 +1,143 @@
+use tangram_app_common::{
+	alerts::AlertMetric,
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	model::get_model,
+	user::{authorize_user, authorize_user_for_model},
+	Context,
+};
+use tangram_deps::{
+	chrono::prelude::*, http, hyper, num_traits::ToPrimitive, serde_json, serde_urlencoded, sqlx,
+	url,
+};
+use tangram_util::{error::Result, id::Id};
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "action")]
+enum Action {
+	#[serde(rename = "add_alert_rule")]
+	AddAlertRule(AddAlertRuleAction),
+	#[serde(rename = "delete_alert_rule")]
+	DeleteAlertRule(DeleteAlertRuleAction),
+}
+
+#[derive(serde::Deserialize)]
+struct AddAlertRuleAction {
+	metric: String,
+	column_name: Option<String>,
+	threshold: String,
+	email: Option<String>,
+	webhook_url: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct DeleteAlertRuleAction {
+	alert_rule_id: String,
+}
+
+pub async fn post(
+	context: &Context,
+	mut request: http::Request<hyper::Body>,
+	model_id: &str,
+) -> Result<http::Response<hyper::Body>> {
+	let data = match hyper::body::to_bytes(request.body_mut()).await {
+		Ok(data) => data,
+		Err(_) => return Ok(bad_request()),
+	};
+	let action: Action = match serde_urlencoded::from_bytes(&data) {
+		Ok(action) => action,
+		Err(_) => return Ok(bad_request()),
+	};
+	let mut db = match context.pool.begin().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user = match authorize_user(&request, &mut db, context.options.auth_enabled).await? {
+		Ok(user) => user,
+		Err(_) => return Ok(redirect_to_login()),
+	};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	match action {
+		Action::AddAlertRule(action) => {
+			let column_name = action.column_name.filter(|c| !c.is_empty());
+			let metric = match (action.metric.as_str(), column_name) {
+				("absent_rate", Some(column_name)) => AlertMetric::AbsentRate { column_name },
+				("invalid_rate", Some(column_name)) => AlertMetric::InvalidRate { column_name },
+				("psi", Some(column_name)) => AlertMetric::Psi { column_name },
+				("accuracy_drop", _) => AlertMetric::AccuracyDrop,
+				("rmse_increase", _) => AlertMetric::RmseIncrease,
+				_ => return Ok(bad_request()),
+			};
+			let model = get_model(&mut db, model_id).await?;
+			if !metric.is_valid_for_model(&model) {
+				return Ok(bad_request());
+			}
+			let threshold: f32 = match action.threshold.trim().parse() {
+				Ok(threshold) => threshold,
+				Err(_) => return Ok(bad_request()),
+			};
+			// Values are never greater than a NaN threshold, so a rule with one would never fire.
+			if !threshold.is_finite() {
+				return Ok(bad_request());
+			}
+			let email = action.email.filter(|email| !email.is_empty());
+			let webhook_url = action
+				.webhook_url
+				.filter(|webhook_url| !webhook_url.is_empty());
+			if let Some(webhook_url) = &webhook_url {
+				match url::Url::parse(webhook_url) {
+					Ok(url) if url.scheme() == "http" || url.scheme() == "https" => {}
+					_ => return Ok(bad_request()),
+				}
+			}
+			sqlx::query(
+				"
+					insert into alert_rules (
+						id, model_id, created_at, metric, threshold, email, webhook_url
+					) values (
+						$1, $2, $3, $4, $5, $6, $7
+					)
+				",
+			)
+			.bind(&Id::new().to_string())
+			.bind(&model_id.to_string())
+			.bind(&Utc::now().timestamp())
+			.bind(&serde_json::to_string(&metric)?)
+			.bind(&threshold.to_f64().unwrap())
+			.bind(&email)
+			.bind(&webhook_url)
+			.execute(&mut *db)
+			.await?;
+		}
+		Action::DeleteAlertRule(DeleteAlertRuleAction { alert_rule_id }) => {
+			let alert_rule_id: Id = match alert_rule_id.parse() {
+				Ok(alert_rule_id) => alert_rule_id,
+				Err(_) => return Ok(bad_request()),
+			};
+			sqlx::query(
+				"
+					delete from alert_rules
+					where
+						id = $1 and
+						model_id = $2
+				",
+			)
+			.bind(&alert_rule_id.to_string())
+			.bind(&model_id.to_string())
+			.execute(&mut *db)
+			.await?;
+		}
+	}
+	db.commit().await?;
+	let response = http::Response::builder()
+		.status(http::StatusCode::SEE_OTHER)
+		.header(http::header::LOCATION, "alerts")
+		.body(hyper::Body::empty())
+		.unwrap();
+	Ok(response)
+}
@@ -23,6 +23,11 @@ pub struct BinaryClassifierInnerMetrics {
 	pub accuracy: f32,
 	pub precision: f32,
 	pub recall: f32,
+	/// The margins are half the widths of the test metrics' confidence intervals, which are `None` if they were not computed.
+	pub auc_roc_margin: Option<f32>,
+	pub accuracy_margin: Option<f32>,
+	pub precision_margin: Option<f32>,
+	pub recall_margin: Option<f32>,
 }
 
 pub fn binary_classifier_index_page(props: BinaryClassifierProps) -> html::Node {
@@ -82,26 +87,26 @@ pub fn binary_classifier_index_page(props: BinaryClassifierProps) -> html::Node
 				<ui::Card>
 					<ui::NumberChart
 						title="AUC ROC"
-						value={ui::format_number(props.metrics.auc_roc)}
+						value={ui::format_number_with_margin(props.metrics.auc_roc, props.metrics.auc_roc_margin)}
 					/>
 				</ui::Card>
 				<ui::Card>
 					<ui::NumberChart
 						title="Accuracy"
-						value={ui::format_number(props.metrics.accuracy)}
+						value={ui::format_number_with_margin(props.metrics.accuracy, props.metrics.accuracy_margin)}
 					/>
 				</ui::Card>
 				<MetricsRow>
 					<ui::Card>
 						<ui::NumberChart
 							title="Precision"
-							value={ui::format_number(props.metrics.precision)}
+							value={ui::format_number_with_margin(props.metrics.precision, props.metrics.precision_margin)}
 						/>
 					</ui::Card>
 					<ui::Card>
 						<ui::NumberChart
 							title="Recall"
-							value={ui::format_number(props.metrics.recall)}
+							value={ui::format_number_with_margin(props.metrics.recall, props.metrics.recall_margin)}
 						/>
 					</ui::Card>
 				</MetricsRow>
@@ -61,6 +61,7 @@ pub async fn get(
 				.thresholds
 				.get(model.baseline_metrics.thresholds.len() / 2)
 				.unwrap();
+			let confidence_intervals = model.test_metrics_confidence_intervals.as_ref();
 			Inner::BinaryClassifier(BinaryClassifierProps {
 				id: model_id.to_string(),
 				metrics: BinaryClassifierInnerMetrics {
@@ -69,6 +70,15 @@ pub async fn get(
 					accuracy: default_threshold_test_metrics.accuracy,
 					precision: default_threshold_test_metrics.precision,
 					recall: default_threshold_test_metrics.recall,
+					auc_roc_margin: confidence_intervals
+						.map(|confidence_intervals| compute_margin(&confidence_intervals.auc_roc)),
+					accuracy_margin: confidence_intervals
+						.map(|confidence_intervals| compute_margin(&confidence_intervals.accuracy)),
+					precision_margin: confidence_intervals.map(|confidence_intervals| {
+						compute_margin(&confidence_intervals.precision)
+					}),
+					recall_margin: confidence_intervals
+						.map(|confidence_intervals| compute_margin(&confidence_intervals.recall)),
 				},
 				losses_chart_series: match &model.model {
 					tangram_core::model::BinaryClassificationModel::Linear(model) => {
@@ -225,3 +235,8 @@ fn model_type_name(model: &tangram_core::model::Model) -> String {
 		},
 	}
 }
+
+/// The margin of a confidence interval is half its width, which is shown as "value ± margin".
+fn compute_margin(confidence_interval: &tangram_core::model::ConfidenceInterval) -> f32 {
+	(confidence_interval.upper - confidence_interval.lower) / 2.0
+}
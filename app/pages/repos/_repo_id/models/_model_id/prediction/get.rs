@@ -1,17 +1,18 @@
 use super::page::{
-	render, Column, EnumFieldProps, Inner, NumberFieldProps, PredictForm, PredictionFormProps,
-	Props, TextFieldProps, UnknownFieldProps,
+	render, Column, CounterfactualChangeProps, CounterfactualProps, CounterfactualsProps,
+	DesiredOutcomeField, EnumFieldProps, Inner, NumberFieldProps, PredictForm, PredictionFormProps,
+	PredictionResultInner, Props, TextFieldProps, UnknownFieldProps,
 };
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, convert::TryInto};
 use tangram_app_common::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
 	model::get_model,
-	predict::{ColumnType, InputTable, InputTableRow, Prediction, PredictionResultProps},
+	predict::{ColumnType, InputTable, InputTableRow, PredictionResultProps},
 	user::{authorize_user, authorize_user_for_model},
 	Context,
 };
 use tangram_app_layouts::{document::PageInfo, model_layout::get_model_layout_info};
-use tangram_deps::{http, hyper, lexical, serde_json};
+use tangram_deps::{http, hyper, lexical, num_traits::ToPrimitive, serde_json};
 use tangram_util::{client, err, error::Result, id::Id};
 
 pub async fn get(
@@ -107,8 +108,15 @@ pub async fn get(
 		})
 		.collect();
 	let model_layout_info = get_model_layout_info(&mut db, context, model_id).await?;
+	let model: tangram_core::predict::Model = model.try_into()?;
 	let inner = if let Some(search_params) = search_params {
-		let prediction = predict(model, columns.as_slice(), search_params)?;
+		let example = compute_example(columns.as_slice(), &search_params)?;
+		let counterfactuals =
+			match compute_counterfactuals(&model, &columns, &example, &search_params) {
+				Some(counterfactuals) => counterfactuals,
+				None => return Ok(bad_request()),
+			};
+		let prediction = tangram_app_common::predict::predict(&model, example);
 		let input_table_rows = columns
 			.into_iter()
 			.map(|column| {
@@ -125,11 +133,14 @@ pub async fn get(
 				}
 			})
 			.collect();
-		Inner::PredictionResult(PredictionResultProps {
-			input_table: InputTable {
-				rows: input_table_rows,
+		Inner::PredictionResult(PredictionResultInner {
+			prediction_result: PredictionResultProps {
+				input_table: InputTable {
+					rows: input_table_rows,
+				},
+				prediction,
 			},
-			prediction,
+			counterfactuals,
 		})
 	} else {
 		Inner::PredictionForm(PredictionFormProps {
@@ -153,11 +164,10 @@ pub async fn get(
 	Ok(response)
 }
 
-fn predict(
-	model: tangram_core::model::Model,
+fn compute_example(
 	columns: &[Column],
-	search_params: BTreeMap<String, String>,
-) -> Result<Prediction> {
+	search_params: &BTreeMap<String, String>,
+) -> Result<serde_json::Map<String, serde_json::Value>> {
 	let mut column_lookup = BTreeMap::new();
 	for column in columns.iter() {
 		match column {
@@ -174,7 +184,8 @@ fn predict(
 		}
 	}
 	let mut example: serde_json::Map<String, serde_json::Value> = serde_json::Map::new();
-	for (key, value) in search_params.into_iter() {
+	for (key, value) in search_params.iter() {
+		let key = key.clone();
 		let column = match column_lookup.get(&key) {
 			Some(column) => column,
 			None => continue,
@@ -184,16 +195,16 @@ fn predict(
 				return Err(err!("encountered unknown column"));
 			}
 			Column::Text(_) => {
-				example.insert(key, serde_json::Value::String(value));
+				example.insert(key, serde_json::Value::String(value.clone()));
 			}
 			Column::Enum(_) => {
-				example.insert(key, serde_json::Value::String(value));
+				example.insert(key, serde_json::Value::String(value.clone()));
 			}
 			Column::Number(_) => {
 				if value == "" {
 					continue;
 				}
-				let value = lexical::parse::<f64, _>(&value)
+				let value = lexical::parse::<f64, _>(value)
 					.map_err(|_| err!("unable to parse \"{}\" as a number", value))?;
 				example.insert(
 					key,
@@ -202,6 +213,110 @@ fn predict(
 			}
 		}
 	}
-	let prediction = tangram_app_common::predict::predict(model, example);
-	Ok(prediction)
+	Ok(example)
+}
+
+/// Build the props for the counterfactuals section, searching for counterfactuals if the `desired_outcome` search param is set. `None` is returned if the desired outcome or mutable columns are invalid.
+fn compute_counterfactuals(
+	model: &tangram_core::predict::Model,
+	columns: &[Column],
+	example: &serde_json::Map<String, serde_json::Value>,
+	search_params: &BTreeMap<String, String>,
+) -> Option<CounterfactualsProps> {
+	let desired_outcome_value = search_params.get("desired_outcome").cloned();
+	let mutable_columns = search_params
+		.get("mutable_columns")
+		.cloned()
+		.unwrap_or_else(|| "".to_owned());
+	let desired_outcome_field = match model.classes() {
+		Some(classes) => DesiredOutcomeField::Class {
+			value: desired_outcome_value
+				.clone()
+				.unwrap_or_else(|| classes[0].to_owned()),
+			classes: classes.iter().map(|class| class.to_string()).collect(),
+		},
+		None => DesiredOutcomeField::Value {
+			value: desired_outcome_value
+				.clone()
+				.unwrap_or_else(|| "".to_owned()),
+		},
+	};
+	let counterfactuals = match desired_outcome_value.filter(|value| !value.is_empty()) {
+		Some(desired_outcome_value) => {
+			let desired_outcome = match model.classes() {
+				Some(_) => tangram_core::counterfactual::DesiredOutcome::Class {
+					class: desired_outcome_value,
+				},
+				None => tangram_core::counterfactual::DesiredOutcome::Value {
+					value: lexical::parse::<f32, _>(&desired_outcome_value).ok()?,
+				},
+			};
+			let mutable_columns = mutable_columns
+				.split(',')
+				.map(|column_name| column_name.trim().to_owned())
+				.filter(|column_name| !column_name.is_empty())
+				.collect::<Vec<_>>();
+			let options = tangram_core::counterfactual::CounterfactualOptions {
+				mutable_columns: if mutable_columns.is_empty() {
+					None
+				} else {
+					Some(mutable_columns)
+				},
+				..Default::default()
+			};
+			let counterfactuals = tangram_core::counterfactual::compute_counterfactuals(
+				model,
+				example,
+				desired_outcome,
+				Some(options),
+			)
+			.ok()?;
+			let counterfactuals = counterfactuals
+				.into_iter()
+				.map(|counterfactual| CounterfactualProps {
+					changes: counterfactual
+						.changes
+						.into_iter()
+						.map(|change| CounterfactualChangeProps {
+							column_name: change.column_name,
+							value: format_value(change.value),
+							counterfactual_value: format_value(change.counterfactual_value),
+						})
+						.collect(),
+					cost: counterfactual.cost,
+					output_value: counterfactual.output_value,
+				})
+				.collect();
+			Some(counterfactuals)
+		}
+		None => None,
+	};
+	let example = columns
+		.iter()
+		.filter_map(|column| {
+			let (name, value) = match column {
+				Column::Unknown(_) => return None,
+				Column::Number(column) => (&column.name, &column.value),
+				Column::Enum(column) => (&column.name, &column.value),
+				Column::Text(column) => (&column.name, &column.value),
+			};
+			Some((name.clone(), value.clone()))
+		})
+		.collect();
+	Some(CounterfactualsProps {
+		example,
+		desired_outcome: desired_outcome_field,
+		mutable_columns,
+		counterfactuals,
+	})
+}
+
+fn format_value(value: serde_json::Value) -> String {
+	match value {
+		serde_json::Value::Null => "missing".to_owned(),
+		serde_json::Value::String(value) => value,
+		// Numbers are stored as f32, so printing them as f32 avoids showing digits that are not significant.
+		serde_json::Value::Number(value) => value.as_f64().unwrap().to_f32().unwrap().to_string(),
+		value => value.to_string(),
+	}
 }
@@ -25,7 +25,13 @@ pub struct Props {
 #[derive(Clone)]
 pub enum Inner {
 	PredictionForm(PredictionFormProps),
-	PredictionResult(PredictionResultProps),
+	PredictionResult(PredictionResultInner),
+}
+
+#[derive(Clone)]
+pub struct PredictionResultInner {
+	pub prediction_result: PredictionResultProps,
+	pub counterfactuals: CounterfactualsProps,
 }
 
 #[derive(Clone)]
@@ -77,13 +83,48 @@ pub struct TextFieldProps {
 	pub value: String,
 }
 
+#[derive(Clone)]
+pub struct CounterfactualsProps {
+	/// These are the name and value of each column in the input, which the form to search for counterfactuals submits again along with the desired outcome.
+	pub example: Vec<(String, String)>,
+	pub desired_outcome: DesiredOutcomeField,
+	pub mutable_columns: String,
+	/// This is `None` if no desired outcome was submitted.
+	pub counterfactuals: Option<Vec<CounterfactualProps>>,
+}
+
+#[derive(Clone)]
+pub enum DesiredOutcomeField {
+	Class { classes: Vec<String>, value: String },
+	Value { value: String },
+}
+
+#[derive(Clone)]
+pub struct CounterfactualProps {
+	pub changes: Vec<CounterfactualChangeProps>,
+	pub cost: f32,
+	pub output_value: f32,
+}
+
+#[derive(Clone)]
+pub struct CounterfactualChangeProps {
+	pub column_name: String,
+	pub value: String,
+	pub counterfactual_value: String,
+}
+
 pub fn render(props: Props, page_info: PageInfo) -> String {
 	let inner = match props.inner {
 		Inner::PredictionForm(inner) => {
 			html! {<PredictionFormPage form={inner.form} />}
 		}
 		Inner::PredictionResult(inner) => {
-			html! {<PredictionResult props={inner}/>}
+			html! {
+				<>
+					<PredictionResult props={inner.prediction_result}/>
+					<Counterfactuals props={inner.counterfactuals} />
+				</>
+			}
 		}
 	};
 	let html = html! {
@@ -303,3 +344,177 @@ fn TextField(field: TextFieldProps) {
 		</>
 	}
 }
+
+#[component]
+fn Counterfactuals(props: CounterfactualsProps) {
+	let is_classifier = matches!(props.desired_outcome, DesiredOutcomeField::Class { .. });
+	let desired_outcome_field = match props.desired_outcome {
+		DesiredOutcomeField::Class { classes, value } => html! {
+			<ui::SelectField
+				id={None}
+				required={None}
+				disabled={None}
+				placeholder={None}
+				label={"Desired Class".to_owned()}
+				name={"desired_outcome".to_owned()}
+				options={classes.into_iter().map(|class| ui::SelectFieldOption {
+					text: class.clone(),
+					value: class,
+				}).collect::<Vec<_>>()}
+				value={value}
+			/>
+		},
+		DesiredOutcomeField::Value { value } => html! {
+			<ui::TextField
+				autocomplete={None}
+				disabled={None}
+				required={true}
+				readonly={None}
+				placeholder={None}
+				label={"Desired Value".to_owned()}
+				name={"desired_outcome".to_owned()}
+				value={value}
+			/>
+		},
+	};
+	html! {
+		<ui::S2>
+			<ui::H2 center={false}>{"Counterfactuals"}</ui::H2>
+			<ui::P>
+				{"A counterfactual is a small change to the input's number and enum columns that would change the model's prediction to the desired outcome. Number columns are only changed to values within the range seen in the training dataset. Leave the mutable columns empty to allow every number and enum column to change, or list the columns that may change, separated by commas."}
+			</ui::P>
+			<ui::Form
+				action={None}
+				autocomplete={None}
+				enc_type={None}
+				id={None}
+				post={None}
+			>
+				{props.example.into_iter().map(|(name, value)| html! {
+					<input
+						name={name}
+						type={"hidden".to_owned()}
+						value={value}
+					/>
+				}).collect::<Vec<_>>()}
+				{desired_outcome_field}
+				<ui::TextField
+					autocomplete={None}
+					disabled={None}
+					required={None}
+					readonly={None}
+					placeholder={Some("All number and enum columns".to_owned())}
+					label={"Mutable Columns".to_owned()}
+					name={"mutable_columns".to_owned()}
+					value={props.mutable_columns}
+				/>
+				<ui::Button
+					button_type={ui::ButtonType::Submit}
+					color={None}
+					disabled={None}
+					download={None}
+					href={None}
+					id={None}
+				>
+					{"Search"}
+				</ui::Button>
+			</ui::Form>
+			{props.counterfactuals.map(|counterfactuals| {
+				if counterfactuals.is_empty() {
+					html! {
+						<ui::P>
+							{"No counterfactual was found that reaches the desired outcome by changing at most three columns."}
+						</ui::P>
+					}
+				} else {
+					html! {
+						<>
+							{counterfactuals.into_iter().enumerate().map(|(index, counterfactual)| html! {
+								<Counterfactual
+									index={index}
+									counterfactual={counterfactual}
+									is_classifier={is_classifier}
+								/>
+							}).collect::<Vec<_>>()}
+						</>
+					}
+				}
+			})}
+		</ui::S2>
+	}
+}
+
+#[component]
+fn Counterfactual(index: usize, counterfactual: CounterfactualProps, is_classifier: bool) {
+	let output = if is_classifier {
+		format!(
+			"probability of the desired class: {}",
+			ui::format_percent(counterfactual.output_value)
+		)
+	} else {
+		format!("predicted value: {}", counterfactual.output_value)
+	};
+	let description = if counterfactual.changes.is_empty() {
+		"The model already predicts the desired outcome for this input.".to_owned()
+	} else {
+		format!(
+			"Counterfactual {} has a cost of {:.3} and a {}.",
+			index + 1,
+			counterfactual.cost,
+			output
+		)
+	};
+	html! {
+		<>
+			<ui::P>{description}</ui::P>
+			{if counterfactual.changes.is_empty() {
+				None
+			} else {
+				Some(html! {
+					<ui::Table width={"100%".to_owned()}>
+						<ui::TableHeader>
+							<ui::TableRow color={None}>
+								<ui::TableHeaderCell
+									color={None}
+									expand={None}
+									text_align={None}
+								>
+									{"Column Name"}
+								</ui::TableHeaderCell>
+								<ui::TableHeaderCell
+									color={None}
+									expand={None}
+									text_align={None}
+								>
+									{"Value"}
+								</ui::TableHeaderCell>
+								<ui::TableHeaderCell
+									color={None}
+									expand={None}
+									text_align={None}
+								>
+									{"Counterfactual Value"}
+								</ui::TableHeaderCell>
+							</ui::TableRow>
+						</ui::TableHeader>
+						<ui::TableBody>
+						{counterfactual.changes.into_iter().map(|change| html! {
+							<ui::TableRow color={None}>
+								<ui::TableCell color={None} expand={None}>
+									{change.column_name}
+								</ui::TableCell>
+								<ui::TableCell color={None} expand={None}>
+									{change.value}
+								</ui::TableCell>
+								<ui::TableCell color={None} expand={None}>
+									{change.counterfactual_value}
+								</ui::TableCell>
+							</ui::TableRow>
+						}).collect::<Vec<_>>()}
+						</ui::TableBody>
+					</ui::Table>
+				})
+			}}
+		</>
+	}
+}
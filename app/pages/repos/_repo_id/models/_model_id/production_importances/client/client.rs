@@ -0,0 +1,26 @@
+use tangram_charts::{bar_chart::BarChart, hydrate_chart, line_chart::LineChart};
+use tangram_ui as ui;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(start)]
+pub fn start() {
+	console_error_panic_hook::set_once();
+	let document = web_sys::window().unwrap().document().unwrap();
+	ui::select_field_submit_on_change("date_window_select_field".to_owned());
+	ui::select_field_submit_on_change("date_window_interval_select_field".to_owned());
+	if document.get_element_by_id("feature_importances").is_some() {
+		hydrate_chart::<BarChart>("feature_importances");
+	}
+	if document
+		.get_element_by_id("feature_importance_divergence_intervals")
+		.is_some()
+	{
+		hydrate_chart::<LineChart>("feature_importance_divergence_intervals");
+	}
+	if document
+		.get_element_by_id("feature_importance_intervals")
+		.is_some()
+	{
+		hydrate_chart::<LineChart>("feature_importance_intervals");
+	}
+}
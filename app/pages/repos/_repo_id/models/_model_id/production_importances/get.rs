@@ -0,0 +1,240 @@
+use crate::page::{
+	render, DivergenceIntervalEntry, FeatureImportance, FeatureImportanceIntervalChart,
+	FeatureImportanceIntervalSeries, Inner, Props,
+};
+use std::{cmp::Ordering, collections::BTreeMap};
+use tangram_app_common::{
+	date_window::get_date_window_and_interval,
+	drift::{compute_feature_importance_divergence, normalize_feature_importances},
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	model::{compute_feature_names, get_model},
+	production_stats::get_production_stats,
+	time::format_date_window_interval,
+	timezone::get_timezone,
+	user::{authorize_user, authorize_user_for_model},
+	Context,
+};
+use tangram_app_layouts::{document::PageInfo, model_layout::get_model_layout_info};
+use tangram_deps::{http, hyper};
+use tangram_util::{client, error::Result, id::Id, zip};
+
+const MAX_FEATURE_IMPORTANCES: usize = 1_000;
+/// This is the number of features whose importance is charted for each interval.
+const N_INTERVAL_CHART_FEATURES: usize = 5;
+
+pub async fn get(
+	context: &Context,
+	request: http::Request<hyper::Body>,
+	model_id: &str,
+	search_params: Option<BTreeMap<String, String>>,
+) -> Result<http::Response<hyper::Body>> {
+	let (date_window, date_window_interval) = match get_date_window_and_interval(&search_params) {
+		Some((date_window, date_window_interval)) => (date_window, date_window_interval),
+		None => return Ok(bad_request()),
+	};
+	let timezone = get_timezone(&request);
+	let mut db = match context.pool.begin().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user = match authorize_user(&request, &mut db, context.options.auth_enabled).await? {
+		Ok(user) => user,
+		Err(_) => return Ok(redirect_to_login()),
+	};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let model = get_model(&mut db, model_id).await?;
+	let production_stats = get_production_stats(
+		&mut db,
+		&model,
+		date_window.clone(),
+		date_window_interval.clone(),
+		timezone,
+	)
+	.await?;
+	let (feature_names, training_feature_importances) = training_feature_importances(&model);
+	let feature_contribution_stats = &production_stats.overall.feature_contribution_stats;
+	let inner = match feature_contribution_stats
+		.mean_abs_feature_contributions
+		.as_ref()
+		.and_then(|mean_abs_feature_contributions| {
+			normalize_feature_importances(mean_abs_feature_contributions)
+		}) {
+		Some(production_feature_importances) => {
+			let divergence = compute_feature_importance_divergence(
+				&training_feature_importances,
+				&production_feature_importances,
+			);
+			let normalized_training_feature_importances =
+				normalize_feature_importances(&training_feature_importances)
+					.unwrap_or_else(|| vec![0.0; training_feature_importances.len()]);
+			// Show the features whose importance changed the most first.
+			let mut feature_importances = zip!(
+				feature_names.iter(),
+				normalized_training_feature_importances.iter(),
+				production_feature_importances.iter()
+			)
+			.enumerate()
+			.map(
+				|(feature_index, (feature_name, training_value, production_value))| {
+					(
+						feature_index,
+						FeatureImportance {
+							feature_name: feature_name.clone(),
+							training_value: *training_value,
+							production_value: *production_value,
+						},
+					)
+				},
+			)
+			.collect::<Vec<_>>();
+			feature_importances.sort_by(|(_, a), (_, b)| {
+				let a = (a.production_value - a.training_value).abs();
+				let b = (b.production_value - b.training_value).abs();
+				b.partial_cmp(&a).unwrap_or(Ordering::Equal)
+			});
+			feature_importances.truncate(MAX_FEATURE_IMPORTANCES);
+			let interval_production_feature_importances = production_stats
+				.intervals
+				.iter()
+				.map(|interval| {
+					interval
+						.feature_contribution_stats
+						.mean_abs_feature_contributions
+						.as_ref()
+						.and_then(|mean_abs_feature_contributions| {
+							normalize_feature_importances(mean_abs_feature_contributions)
+						})
+				})
+				.collect::<Vec<_>>();
+			let labels = production_stats
+				.intervals
+				.iter()
+				.map(|interval| {
+					format_date_window_interval(
+						interval.start_date,
+						&date_window_interval,
+						timezone,
+					)
+				})
+				.collect::<Vec<_>>();
+			let divergence_intervals = zip!(
+				labels.iter(),
+				interval_production_feature_importances.iter()
+			)
+			.map(
+				|(label, production_feature_importances)| DivergenceIntervalEntry {
+					label: label.clone(),
+					divergence: production_feature_importances.as_ref().and_then(
+						|production_feature_importances| {
+							compute_feature_importance_divergence(
+								&training_feature_importances,
+								production_feature_importances,
+							)
+						},
+					),
+				},
+			)
+			.collect();
+			let feature_importance_interval_chart = FeatureImportanceIntervalChart {
+				labels,
+				features: feature_importances
+					.iter()
+					.take(N_INTERVAL_CHART_FEATURES)
+					.map(
+						|(feature_index, feature_importance)| FeatureImportanceIntervalSeries {
+							feature_name: feature_importance.feature_name.clone(),
+							training_value: feature_importance.training_value,
+							production_values: interval_production_feature_importances
+								.iter()
+								.map(|production_feature_importances| {
+									production_feature_importances.as_ref().and_then(
+										|production_feature_importances| {
+											production_feature_importances
+												.get(*feature_index)
+												.cloned()
+										},
+									)
+								})
+								.collect(),
+						},
+					)
+					.collect(),
+			};
+			Some(Inner {
+				divergence,
+				divergence_intervals,
+				feature_contributions_count: feature_contribution_stats.count,
+				feature_importance_interval_chart,
+				feature_importances: feature_importances
+					.into_iter()
+					.map(|(_, feature_importance)| feature_importance)
+					.collect(),
+				n_features: feature_names.len(),
+			})
+		}
+		None => None,
+	};
+	let model_layout_info = get_model_layout_info(&mut db, context, model_id).await?;
+	db.commit().await?;
+	let props = Props {
+		date_window,
+		date_window_interval,
+		inner,
+		model_layout_info,
+	};
+	let page_info = PageInfo {
+		client_wasm_js_src: Some(client!()),
+	};
+	let html = render(props, page_info);
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.body(hyper::Body::from(html))
+		.unwrap();
+	Ok(response)
+}
+
+/// Retrieve the name and training feature importance of each of the model's features.
+fn training_feature_importances(model: &tangram_core::model::Model) -> (Vec<String>, Vec<f32>) {
+	let (feature_groups, feature_importances) = match model {
+		tangram_core::model::Model::Regressor(model) => match &model.model {
+			tangram_core::model::RegressionModel::Linear(inner_model) => (
+				&inner_model.feature_groups,
+				&inner_model.feature_importances,
+			),
+			tangram_core::model::RegressionModel::Tree(inner_model) => (
+				&inner_model.feature_groups,
+				&inner_model.feature_importances,
+			),
+		},
+		tangram_core::model::Model::BinaryClassifier(model) => match &model.model {
+			tangram_core::model::BinaryClassificationModel::Linear(inner_model) => (
+				&inner_model.feature_groups,
+				&inner_model.feature_importances,
+			),
+			tangram_core::model::BinaryClassificationModel::Tree(inner_model) => (
+				&inner_model.feature_groups,
+				&inner_model.feature_importances,
+			),
+		},
+		tangram_core::model::Model::MulticlassClassifier(model) => match &model.model {
+			tangram_core::model::MulticlassClassificationModel::Linear(inner_model) => (
+				&inner_model.feature_groups,
+				&inner_model.feature_importances,
+			),
+			tangram_core::model::MulticlassClassificationModel::Tree(inner_model) => (
+				&inner_model.feature_groups,
+				&inner_model.feature_importances,
+			),
+		},
+	};
+	(
+		compute_feature_names(feature_groups),
+		feature_importances.clone(),
+	)
+}
@@ -0,0 +1,363 @@
+use tangram_app_common::{
+	date_window::{DateWindow, DateWindowInterval},
+	date_window_select_field::DateWindowSelectField,
+	metrics_row::MetricsRow,
+	time::{interval_chart_title, overall_chart_title},
+	tokens::{PRODUCTION_COLOR, TRAINING_COLOR},
+};
+use tangram_app_layouts::{
+	document::PageInfo,
+	model_layout::{ModelLayout, ModelLayoutInfo, ModelSideNavItem},
+};
+use tangram_charts::{
+	bar_chart::{BarChartPoint, BarChartSeries},
+	common::GridLineInterval,
+	components::{BarChart, LineChart},
+	line_chart::{LineChartPoint, LineChartSeries, LineStyle, PointStyle},
+};
+use tangram_deps::{
+	html::{self, component, html},
+	num_traits::ToPrimitive,
+};
+use tangram_ui as ui;
+
+/// This is the number of features shown in the bar chart comparing training and production feature importances.
+const N_BAR_CHART_FEATURES: usize = 10;
+
+pub struct Props {
+	pub date_window: DateWindow,
+	pub date_window_interval: DateWindowInterval,
+	/// This is `None` if none of the predictions tracked in the date window had their feature contributions computed.
+	pub inner: Option<Inner>,
+	pub model_layout_info: ModelLayoutInfo,
+}
+
+#[derive(Clone)]
+pub struct Inner {
+	pub divergence: Option<f32>,
+	pub divergence_intervals: Vec<DivergenceIntervalEntry>,
+	pub feature_contributions_count: u64,
+	pub feature_importance_interval_chart: FeatureImportanceIntervalChart,
+	/// These are sorted by how much each feature's importance changed between training and production, largest first.
+	pub feature_importances: Vec<FeatureImportance>,
+	pub n_features: usize,
+}
+
+#[derive(Clone)]
+pub struct DivergenceIntervalEntry {
+	pub label: String,
+	pub divergence: Option<f32>,
+}
+
+/// Both importances are normalized so the importances of all the features sum to one.
+#[derive(Clone)]
+pub struct FeatureImportance {
+	pub feature_name: String,
+	pub training_value: f32,
+	pub production_value: f32,
+}
+
+#[derive(Clone)]
+pub struct FeatureImportanceIntervalChart {
+	pub labels: Vec<String>,
+	pub features: Vec<FeatureImportanceIntervalSeries>,
+}
+
+#[derive(Clone)]
+pub struct FeatureImportanceIntervalSeries {
+	pub feature_name: String,
+	pub training_value: f32,
+	pub production_values: Vec<Option<f32>>,
+}
+
+pub fn render(props: Props, page_info: PageInfo) -> String {
+	let inner = match props.inner {
+		Some(inner) => html! {
+			<ProductionImportances
+				date_window={props.date_window.clone()}
+				date_window_interval={props.date_window_interval.clone()}
+				inner={inner}
+			/>
+		},
+		None => html! {
+			<ui::Card>
+				<ui::P>
+					{"None of the predictions tracked in this date window had their feature contributions computed. Run the app with the --production-feature-contributions flag to compute the feature contributions for each prediction as it is tracked."}
+				</ui::P>
+			</ui::Card>
+		},
+	};
+	let html = html! {
+		<ModelLayout
+			info={props.model_layout_info}
+			page_info={page_info}
+			selected_item={ModelSideNavItem::ProductionImportances}
+		>
+			<ui::S1>
+				<ui::H1 center={false}>{"Production Feature Importances"}</ui::H1>
+				<ui::P>
+					{"The importance of each feature in production is its mean absolute contribution to the predictions tracked in production. It is compared to the feature's importance in training after normalizing the importances of all the features to sum to one. The divergence is the Jensen-Shannon divergence between the training and production importances, which is zero when the model's reasoning in production matches its reasoning in training and at most ln 2."}
+				</ui::P>
+				<ui::Form
+					action={None}
+					autocomplete={None}
+					post={None}
+					enc_type={None}
+					id={None}
+				>
+					<DateWindowSelectField
+						date_window={props.date_window}
+						date_window_interval={props.date_window_interval}
+					/>
+					<noscript>
+						<ui::Button
+							color={None}
+							disabled={None}
+							href={None}
+							id={None}
+							download={None}
+							button_type={ui::ButtonType::Submit}
+						>
+							{"Submit"}
+						</ui::Button>
+					</noscript>
+				</ui::Form>
+				{inner}
+			</ui::S1>
+		</ModelLayout>
+	};
+	html.render_to_string()
+}
+
+#[component]
+fn ProductionImportances(
+	date_window: DateWindow,
+	date_window_interval: DateWindowInterval,
+	inner: Inner,
+) {
+	let divergence_overall_title =
+		overall_chart_title(&date_window, "Feature Importance Divergence".to_owned());
+	let divergence_interval_chart_title = interval_chart_title(
+		&date_window_interval,
+		"Feature Importance Divergence".to_owned(),
+	);
+	let feature_importance_interval_chart_title =
+		interval_chart_title(&date_window_interval, "Feature Importance".to_owned());
+	let divergence_labels = inner
+		.divergence_intervals
+		.iter()
+		.map(|interval| interval.label.clone())
+		.collect::<Vec<_>>();
+	let divergence_series = vec![LineChartSeries {
+		color: PRODUCTION_COLOR.to_owned(),
+		data: inner
+			.divergence_intervals
+			.iter()
+			.enumerate()
+			.filter_map(|(index, interval)| {
+				interval.divergence.map(|divergence| LineChartPoint {
+					x: index.to_f64().unwrap(),
+					y: divergence.to_f64().unwrap(),
+				})
+			})
+			.collect(),
+		line_style: None,
+		point_style: None,
+		title: Some("Feature Importance Divergence".to_owned()),
+	}];
+	let color_options = vec![
+		ui::colors::BLUE,
+		ui::colors::GREEN,
+		ui::colors::ORANGE,
+		ui::colors::PURPLE,
+		ui::colors::RED,
+	];
+	let n_intervals = inner.feature_importance_interval_chart.labels.len();
+	let feature_importance_interval_series = inner
+		.feature_importance_interval_chart
+		.features
+		.iter()
+		.enumerate()
+		.flat_map(|(index, feature)| {
+			let color = color_options[index % color_options.len()].to_owned();
+			vec![
+				LineChartSeries {
+					color: color.clone(),
+					data: (0..n_intervals)
+						.map(|index| LineChartPoint {
+							x: index.to_f64().unwrap(),
+							y: feature.training_value.to_f64().unwrap(),
+						})
+						.collect(),
+					line_style: Some(LineStyle::Dashed),
+					point_style: Some(PointStyle::Hidden),
+					title: Some(format!("{} (Training)", feature.feature_name)),
+				},
+				LineChartSeries {
+					color,
+					data: feature
+						.production_values
+						.iter()
+						.enumerate()
+						.filter_map(|(index, value)| {
+							value.map(|value| LineChartPoint {
+								x: index.to_f64().unwrap(),
+								y: value.to_f64().unwrap(),
+							})
+						})
+						.collect(),
+					line_style: None,
+					point_style: None,
+					title: Some(feature.feature_name.clone()),
+				},
+			]
+		})
+		.collect::<Vec<_>>();
+	let bar_chart_features = inner
+		.feature_importances
+		.iter()
+		.take(N_BAR_CHART_FEATURES)
+		.collect::<Vec<_>>();
+	let bar_chart_series = vec![
+		BarChartSeries {
+			color: TRAINING_COLOR.to_owned(),
+			data: bar_chart_features
+				.iter()
+				.enumerate()
+				.map(|(index, feature_importance)| BarChartPoint {
+					label: feature_importance.feature_name.clone(),
+					x: index.to_f64().unwrap(),
+					y: Some(feature_importance.training_value.to_f64().unwrap()),
+				})
+				.collect(),
+			title: Some("Training".to_owned()),
+		},
+		BarChartSeries {
+			color: PRODUCTION_COLOR.to_owned(),
+			data: bar_chart_features
+				.iter()
+				.enumerate()
+				.map(|(index, feature_importance)| BarChartPoint {
+					label: feature_importance.feature_name.clone(),
+					x: index.to_f64().unwrap(),
+					y: Some(feature_importance.production_value.to_f64().unwrap()),
+				})
+				.collect(),
+			title: Some("Production".to_owned()),
+		},
+	];
+	html! {
+		<>
+			<MetricsRow>
+				<ui::Card>
+					<ui::NumberChart
+						title={"Predictions Explained".to_owned()}
+						value={inner.feature_contributions_count.to_string()}
+					/>
+				</ui::Card>
+				<ui::Card>
+					<ui::NumberChart
+						title={divergence_overall_title}
+						value={inner.divergence.map(ui::format_number).unwrap_or_else(|| "-".to_owned())}
+					/>
+				</ui::Card>
+			</MetricsRow>
+			<ui::Card>
+				<LineChart
+					class={None}
+					hide_legend={None}
+					id={"feature_importance_divergence_intervals".to_owned()}
+					labels={divergence_labels.clone()}
+					series={divergence_series}
+					should_draw_x_axis_labels={None}
+					should_draw_y_axis_labels={None}
+					title={divergence_interval_chart_title}
+					x_axis_grid_line_interval={Some(GridLineInterval { k: 1.0, p: 0.0 })}
+					x_axis_title={None}
+					x_max={None}
+					x_min={None}
+					y_axis_grid_line_interval={None}
+					y_axis_title={None}
+					y_max={None}
+					y_min={0.0}
+				/>
+			</ui::Card>
+			<ui::Card>
+				<LineChart
+					class={None}
+					hide_legend={None}
+					id={"feature_importance_intervals".to_owned()}
+					labels={divergence_labels}
+					series={feature_importance_interval_series}
+					should_draw_x_axis_labels={None}
+					should_draw_y_axis_labels={None}
+					title={feature_importance_interval_chart_title}
+					x_axis_grid_line_interval={Some(GridLineInterval { k: 1.0, p: 0.0 })}
+					x_axis_title={None}
+					x_max={None}
+					x_min={None}
+					y_axis_grid_line_interval={None}
+					y_axis_title={None}
+					y_max={None}
+					y_min={0.0}
+				/>
+			</ui::Card>
+			<ui::Card>
+				<BarChart
+					class={None}
+					group_gap={None}
+					hide_legend={None}
+					id={"feature_importances".to_owned()}
+					series={bar_chart_series}
+					should_draw_x_axis_labels={false}
+					should_draw_y_axis_labels={None}
+					title={"Features with the Largest Change in Importance".to_owned()}
+					x_axis_title={"Feature Name".to_owned()}
+					y_axis_grid_line_interval={None}
+					y_axis_title={"Feature Importance Value".to_owned()}
+					y_min={None}
+					y_max={None}
+				/>
+			</ui::Card>
+			<ui::P>
+				{format!("Your model had a total of {} features.", inner.n_features)}
+			</ui::P>
+			<ui::Table width={"100%".to_owned()}>
+				<ui::TableHeader>
+					<ui::TableRow color={None}>
+						<ui::TableHeaderCell color={None} expand={None} text_align={None}>
+							{"Feature Name"}
+						</ui::TableHeaderCell>
+						<ui::TableHeaderCell color={None} expand={None} text_align={None}>
+							{"Training Importance"}
+						</ui::TableHeaderCell>
+						<ui::TableHeaderCell color={None} expand={None} text_align={None}>
+							{"Production Importance"}
+						</ui::TableHeaderCell>
+						<ui::TableHeaderCell color={None} expand={None} text_align={None}>
+							{"Change"}
+						</ui::TableHeaderCell>
+					</ui::TableRow>
+				</ui::TableHeader>
+				<ui::TableBody>
+					{inner.feature_importances.iter().map(|feature_importance| html! {
+						<ui::TableRow color={None}>
+							<ui::TableCell color={None} expand={None}>
+								{feature_importance.feature_name.clone()}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{ui::format_percent(feature_importance.training_value)}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{ui::format_percent(feature_importance.production_value)}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{ui::format_percent(feature_importance.production_value - feature_importance.training_value)}
+							</ui::TableCell>
+						</ui::TableRow>
+					}).collect::<Vec<_>>()}
+				</ui::TableBody>
+			</ui::Table>
+		</>
+	}
+}
@@ -228,7 +228,10 @@ pub fn render(props: Props, page_info: PageInfo) -> String {
 				id={None}
 				post={None}
 			>
-				<DateWindowSelectField date_window={props.date_window} />
+				<DateWindowSelectField
+					date_window={props.date_window}
+					date_window_interval={props.date_window_interval}
+				/>
 				<ClassSelectField class={props.class.clone()} classes={props.classes.clone()} />
 				<noscript>
 					<ui::Button
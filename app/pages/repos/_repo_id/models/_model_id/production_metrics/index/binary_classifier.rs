@@ -2,7 +2,7 @@ use crate::page::{AccuracyChart, TrainingProductionMetrics, TrueValuesCountChart
 use tangram_app_common::{
 	date_window::{DateWindow, DateWindowInterval},
 	date_window_select_field::DateWindowSelectField,
-	definitions::ACCURACY,
+	definitions::{ACCURACY, DEMOGRAPHIC_PARITY_DIFFERENCE, EQUAL_OPPORTUNITY_DIFFERENCE},
 	metrics_row::MetricsRow,
 	time::interval_chart_title,
 	tokens::{PRODUCTION_COLOR, TRAINING_COLOR},
@@ -26,6 +26,8 @@ pub struct BinaryClassifierProductionMetricsProps {
 	pub overall: BinaryClassificationOverallProductionMetrics,
 	pub id: String,
 	pub accuracy_chart: AccuracyChart,
+	/// This is `None` if the model was trained without a sensitive column in the fairness config.
+	pub fairness: Option<ProductionFairnessProps>,
 }
 
 #[derive(Clone)]
@@ -36,6 +38,23 @@ pub struct BinaryClassificationOverallProductionMetrics {
 	pub true_values_count: u64,
 }
 
+#[derive(Clone)]
+pub struct ProductionFairnessProps {
+	pub sensitive_column_name: String,
+	pub demographic_parity_difference: TrainingProductionMetrics,
+	pub equal_opportunity_difference: TrainingProductionMetrics,
+	pub groups: Vec<ProductionFairnessGroup>,
+}
+
+#[derive(Clone)]
+pub struct ProductionFairnessGroup {
+	pub group: String,
+	pub true_values_count: u64,
+	pub positive_rate: TrainingProductionMetrics,
+	pub accuracy: TrainingProductionMetrics,
+	pub recall: TrainingProductionMetrics,
+}
+
 #[component]
 pub fn BinaryClassifierProductionMetrics(props: BinaryClassifierProductionMetricsProps) {
 	let chart_labels = props
@@ -90,7 +109,10 @@ pub fn BinaryClassifierProductionMetrics(props: BinaryClassifierProductionMetric
 					id={None}
 					post={None}
 				>
-					<DateWindowSelectField date_window={props.date_window} />
+					<DateWindowSelectField
+						date_window={props.date_window}
+						date_window_interval={props.date_window_interval}
+					/>
 					<noscript>
 						<ui::Button
 							disabled={None}
@@ -156,6 +178,114 @@ pub fn BinaryClassifierProductionMetrics(props: BinaryClassifierProductionMetric
 					/>
 				</ui::Card>
 			</ui::S2>
+			{props.fairness.map(|fairness| html! {
+				<ProductionFairness fairness={fairness} />
+			})}
 		</ui::S1>
 	}
 }
+
+#[component]
+fn ProductionFairness(fairness: ProductionFairnessProps) {
+	let format_metric = |value: Option<f32>| {
+		value
+			.map(ui::format_percent)
+			.unwrap_or_else(|| "N/A".to_owned())
+	};
+	html! {
+		<ui::S2>
+			<ui::H2 center={false}>{"Fairness"}</ui::H2>
+			<ui::P>
+				{format!("These are the metrics for each group of predictions with the same value of the sensitive column \"{}\", computed from the predictions with true values.", fairness.sensitive_column_name)}
+			</ui::P>
+			<MetricsRow>
+				<ui::Card>
+					<ui::NumberComparisonChart
+						id={None}
+						color_a={TRAINING_COLOR.to_owned()}
+						color_b={PRODUCTION_COLOR.to_owned()}
+						title={"Demographic Parity Difference".to_owned()}
+						value_a={fairness.demographic_parity_difference.training}
+						value_a_title={"Training".to_owned()}
+						value_b={fairness.demographic_parity_difference.production}
+						value_b_title={"Production".to_owned()}
+					/>
+				</ui::Card>
+				<ui::Card>
+					<ui::NumberComparisonChart
+						id={None}
+						color_a={TRAINING_COLOR.to_owned()}
+						color_b={PRODUCTION_COLOR.to_owned()}
+						title={"Equal Opportunity Difference".to_owned()}
+						value_a={fairness.equal_opportunity_difference.training}
+						value_a_title={"Training".to_owned()}
+						value_b={fairness.equal_opportunity_difference.production}
+						value_b_title={"Production".to_owned()}
+					/>
+				</ui::Card>
+			</MetricsRow>
+			<ui::P>{DEMOGRAPHIC_PARITY_DIFFERENCE}</ui::P>
+			<ui::P>{EQUAL_OPPORTUNITY_DIFFERENCE}</ui::P>
+			<ui::Table width={Some("100%".to_owned())}>
+				<ui::TableHeader>
+					<ui::TableRow color={None}>
+						<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+							{"Group"}
+						</ui::TableHeaderCell>
+						<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+							{"True Value Count"}
+						</ui::TableHeaderCell>
+						<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+							{"Training Positive Rate"}
+						</ui::TableHeaderCell>
+						<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+							{"Production Positive Rate"}
+						</ui::TableHeaderCell>
+						<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+							{"Training Accuracy"}
+						</ui::TableHeaderCell>
+						<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+							{"Production Accuracy"}
+						</ui::TableHeaderCell>
+						<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+							{"Training Recall"}
+						</ui::TableHeaderCell>
+						<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+							{"Production Recall"}
+						</ui::TableHeaderCell>
+					</ui::TableRow>
+				</ui::TableHeader>
+				<ui::TableBody>
+					{fairness.groups.into_iter().map(|group| html! {
+						<ui::TableRow color={None}>
+							<ui::TableCell color={None} expand={None}>
+								{group.group}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{group.true_values_count.to_string()}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{ui::format_percent(group.positive_rate.training)}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{format_metric(group.positive_rate.production)}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{ui::format_percent(group.accuracy.training)}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{format_metric(group.accuracy.production)}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{ui::format_percent(group.recall.training)}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{format_metric(group.recall.production)}
+							</ui::TableCell>
+						</ui::TableRow>
+					}).collect::<Vec<_>>()}
+				</ui::TableBody>
+			</ui::Table>
+		</ui::S2>
+	}
+}
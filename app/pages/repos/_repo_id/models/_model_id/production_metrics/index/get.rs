@@ -2,8 +2,8 @@ use crate::page::{
 	render, AccuracyChart, AccuracyChartEntry, BinaryClassificationOverallProductionMetrics,
 	BinaryClassifierProductionMetricsProps, ClassMetricsTableEntry, Inner, MSEChart, MSEChartEntry,
 	MulticlassClassificationOverallProductionMetrics, MulticlassClassifierProductionMetricsProps,
-	Props, RegressionProductionMetrics, RegressorProductionMetricsProps, TrainingProductionMetrics,
-	TrueValuesCountChartEntry,
+	ProductionFairnessGroup, ProductionFairnessProps, Props, RegressionProductionMetrics,
+	RegressorProductionMetricsProps, TrainingProductionMetrics, TrueValuesCountChartEntry,
 };
 use std::collections::BTreeMap;
 use tangram_app_common::{
@@ -77,6 +77,14 @@ pub async fn get(
 					production: overall_production_metrics.as_ref().map(|m| m.rmse),
 					training: training_metrics.rmse,
 				},
+				prediction_interval_coverage: model.prediction_intervals.as_ref().map(
+					|prediction_intervals| TrainingProductionMetrics {
+						production: overall_production_metrics
+							.as_ref()
+							.and_then(|m| m.prediction_interval_coverage),
+						training: prediction_intervals.coverage,
+					},
+				),
 				true_values_count,
 			};
 			let mse_chart = {
@@ -219,6 +227,76 @@ pub async fn get(
 				},
 				true_values_count,
 			};
+			let production_fairness_metrics = production_metrics.overall.fairness_metrics;
+			let fairness = model
+				.fairness_metrics
+				.as_ref()
+				.map(|training_fairness_metrics| {
+					let production_groups = production_fairness_metrics
+						.as_ref()
+						.map(|fairness_metrics| fairness_metrics.groups.as_slice())
+						.unwrap_or(&[]);
+					let groups = training_fairness_metrics
+						.groups
+						.iter()
+						.map(|training_group| {
+							let production_group =
+								production_groups.iter().find(|production_group| {
+									production_group.group == training_group.group
+								});
+							let production_prediction_metrics =
+								production_group.and_then(|production_group| {
+									production_group.prediction_metrics.as_ref()
+								});
+							let default_threshold_test_metrics = &training_group.metrics.thresholds
+								[training_group.metrics.thresholds.len() / 2];
+							ProductionFairnessGroup {
+								group: training_group.group.clone(),
+								true_values_count: production_prediction_metrics
+									.map(|metrics| {
+										metrics.true_positives
+											+ metrics.false_positives + metrics.true_negatives
+											+ metrics.false_negatives
+									})
+									.unwrap_or(0),
+								positive_rate: TrainingProductionMetrics {
+									production: production_group.and_then(|production_group| {
+										production_group.positive_rate
+									}),
+									training: training_group.positive_rate,
+								},
+								accuracy: TrainingProductionMetrics {
+									production: production_prediction_metrics
+										.map(|metrics| metrics.accuracy),
+									training: default_threshold_test_metrics.accuracy,
+								},
+								recall: TrainingProductionMetrics {
+									production: production_prediction_metrics
+										.map(|metrics| metrics.recall),
+									training: default_threshold_test_metrics.recall,
+								},
+							}
+						})
+						.collect();
+					ProductionFairnessProps {
+						sensitive_column_name: training_fairness_metrics
+							.sensitive_column_name
+							.clone(),
+						demographic_parity_difference: TrainingProductionMetrics {
+							production: production_fairness_metrics
+								.as_ref()
+								.and_then(|metrics| metrics.demographic_parity_difference),
+							training: training_fairness_metrics.demographic_parity_difference,
+						},
+						equal_opportunity_difference: TrainingProductionMetrics {
+							production: production_fairness_metrics
+								.as_ref()
+								.and_then(|metrics| metrics.equal_opportunity_difference),
+							training: training_fairness_metrics.equal_opportunity_difference,
+						},
+						groups,
+					}
+				});
 			Inner::BinaryClassifier(BinaryClassifierProductionMetricsProps {
 				date_window,
 				date_window_interval,
@@ -226,6 +304,7 @@ pub async fn get(
 				id: model_id.to_string(),
 				accuracy_chart,
 				overall,
+				fairness,
 			})
 		}
 		tangram_core::model::Model::MulticlassClassifier(model) => {
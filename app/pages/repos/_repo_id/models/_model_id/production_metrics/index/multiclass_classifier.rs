@@ -2,7 +2,7 @@ use crate::page::{
 	AccuracyChart, ClassMetricsTableEntry, TrainingProductionMetrics, TrueValuesCountChartEntry,
 };
 use tangram_app_common::{
-	date_window::{DateWindow, DateWindowInterval},
+	date_window::{date_window_search_params, DateWindow, DateWindowInterval},
 	date_window_select_field::DateWindowSelectField,
 	definitions::{ACCURACY, PRECISION_RECALL},
 	metrics_row::MetricsRow,
@@ -89,7 +89,7 @@ pub fn MulticlassClassifierProductionMetrics(props: MulticlassClassifierProducti
 					{"Overview"}
 				</ui::TabLink>
 				<ui::TabLink
-					href={format!("class_metrics?date_window={}", props.date_window)}
+					href={format!("class_metrics?{}", date_window_search_params(&props.date_window, &props.date_window_interval))}
 					selected={false}
 					disabled={None}
 				>
@@ -104,7 +104,10 @@ pub fn MulticlassClassifierProductionMetrics(props: MulticlassClassifierProducti
 					id={None}
 					post={None}
 				>
-					<DateWindowSelectField date_window={props.date_window} />
+					<DateWindowSelectField
+						date_window={props.date_window}
+						date_window_interval={props.date_window_interval}
+					/>
 					<noscript>
 						<ui::Button
 							disabled={None}
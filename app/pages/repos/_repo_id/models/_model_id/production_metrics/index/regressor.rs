@@ -42,6 +42,8 @@ pub struct MSEChartEntry {
 pub struct RegressionProductionMetrics {
 	pub mse: TrainingProductionMetrics,
 	pub rmse: TrainingProductionMetrics,
+	/// This is `Some` if the model was trained with the prediction intervals config. The training value is the coverage the intervals were calibrated for.
+	pub prediction_interval_coverage: Option<TrainingProductionMetrics>,
 	pub true_values_count: u64,
 }
 
@@ -96,7 +98,10 @@ pub fn RegressorProductionMetrics(props: RegressorProductionMetricsProps) {
 				id={None}
 				post={None}
 			>
-				<DateWindowSelectField date_window={props.date_window} />
+				<DateWindowSelectField
+					date_window={props.date_window}
+					date_window_interval={props.date_window_interval}
+				/>
 				<noscript>
 					<ui::Button
 						disabled={None}
@@ -171,6 +176,25 @@ pub fn RegressorProductionMetrics(props: RegressorProductionMetricsProps) {
 					/>
 				</ui::Card>
 			</MetricsRow>
+			{props.overall.prediction_interval_coverage.map(|prediction_interval_coverage| html! {
+				<>
+					<ui::Card>
+						<ui::NumberComparisonChart
+							id={None}
+							color_a={TRAINING_COLOR.to_owned()}
+							color_b={PRODUCTION_COLOR.to_owned()}
+							title={"Prediction Interval Coverage".to_owned()}
+							value_a={prediction_interval_coverage.training}
+							value_a_title={"Calibrated".to_owned()}
+							value_b={prediction_interval_coverage.production}
+							value_b_title={"Production".to_owned()}
+						/>
+					</ui::Card>
+					<ui::P>
+						{"The prediction interval coverage is the fraction of true values that were inside their prediction's interval. If it is much lower than the coverage the intervals were calibrated for, the production data may have drifted away from the training data."}
+					</ui::P>
+				</>
+			})}
 		</ui::S2>
 	</ui::S1>
 	}
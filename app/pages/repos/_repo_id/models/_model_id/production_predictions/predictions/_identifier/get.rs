@@ -1,5 +1,5 @@
 use super::page::{render, Found, Inner, Props};
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, convert::TryInto};
 use tangram_app_common::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
 	model::get_model,
@@ -192,7 +192,8 @@ fn predict(
 			})
 		}
 	}
-	let prediction = tangram_app_common::predict::predict(model, example);
+	let model: tangram_core::predict::Model = model.try_into().unwrap();
+	let prediction = tangram_app_common::predict::predict(&model, example);
 	PredictionOutput {
 		input_table: InputTable {
 			rows: input_table_rows,
@@ -1,4 +1,6 @@
-use tangram_charts::{bar_chart::BarChart, box_chart::BoxChart, hydrate_chart};
+use tangram_charts::{
+	bar_chart::BarChart, box_chart::BoxChart, hydrate_chart, line_chart::LineChart,
+};
 use tangram_ui as ui;
 use wasm_bindgen::prelude::*;
 
@@ -7,6 +9,7 @@ pub fn start() {
 	console_error_panic_hook::set_once();
 	let document = web_sys::window().unwrap().document().unwrap();
 	ui::select_field_submit_on_change("date_window_select_field".to_owned());
+	ui::select_field_submit_on_change("date_window_interval_select_field".to_owned());
 	if document.get_element_by_id("number_intervals").is_some() {
 		hydrate_chart::<BoxChart>("number_intervals");
 	}
@@ -19,4 +22,7 @@ pub fn start() {
 	if document.get_element_by_id("text_overall").is_some() {
 		hydrate_chart::<BarChart>("text_overall");
 	}
+	if document.get_element_by_id("drift_intervals").is_some() {
+		hydrate_chart::<LineChart>("drift_intervals");
+	}
 }
@@ -0,0 +1,146 @@
+use tangram_app_common::{
+	date_window::{DateWindow, DateWindowInterval},
+	drift::ColumnDrift,
+	metrics_row::MetricsRow,
+	time::{interval_chart_title, overall_chart_title},
+};
+use tangram_charts::{
+	common::GridLineInterval,
+	components::LineChart,
+	line_chart::{LineChartPoint, LineChartSeries},
+};
+use tangram_deps::{
+	html::{self, component, html},
+	num_traits::ToPrimitive,
+};
+use tangram_ui as ui;
+
+#[derive(Clone)]
+pub struct DriftProps {
+	pub date_window: DateWindow,
+	pub date_window_interval: DateWindowInterval,
+	pub overall: ColumnDrift,
+	pub intervals: Vec<DriftIntervalEntry>,
+}
+
+#[derive(Clone)]
+pub struct DriftIntervalEntry {
+	pub label: String,
+	pub drift: ColumnDrift,
+}
+
+struct DriftMetric {
+	title: &'static str,
+	color: &'static str,
+	value: fn(&ColumnDrift) -> Option<f32>,
+}
+
+fn drift_metrics() -> Vec<DriftMetric> {
+	vec![
+		DriftMetric {
+			title: "PSI",
+			color: ui::colors::BLUE,
+			value: |drift| drift.psi,
+		},
+		DriftMetric {
+			title: "KS Statistic",
+			color: ui::colors::GREEN,
+			value: |drift| drift.ks_statistic,
+		},
+		DriftMetric {
+			title: "KL Divergence",
+			color: ui::colors::ORANGE,
+			value: |drift| drift.kl_divergence,
+		},
+		DriftMetric {
+			title: "JS Divergence",
+			color: ui::colors::PURPLE,
+			value: |drift| drift.js_divergence,
+		},
+	]
+}
+
+#[component]
+pub fn Drift(props: DriftProps) {
+	// Only show the metrics that apply to the column's type and have a value for the date window.
+	let metrics = drift_metrics()
+		.into_iter()
+		.filter(|metric| (metric.value)(&props.overall).is_some())
+		.collect::<Vec<_>>();
+	if metrics.is_empty() {
+		return html! {
+			<ui::S2>
+				<ui::H2 center={false}>{"Drift"}</ui::H2>
+				<ui::Card>
+					<ui::P>{"There are no production values to compare to the training distribution."}</ui::P>
+				</ui::Card>
+			</ui::S2>
+		};
+	}
+	let labels = props
+		.intervals
+		.iter()
+		.map(|interval| interval.label.clone())
+		.collect::<Vec<_>>();
+	let series = metrics
+		.iter()
+		.map(|metric| LineChartSeries {
+			color: metric.color.to_owned(),
+			data: props
+				.intervals
+				.iter()
+				.enumerate()
+				.filter_map(|(index, interval)| {
+					(metric.value)(&interval.drift).map(|value| LineChartPoint {
+						x: index.to_f64().unwrap(),
+						y: value.to_f64().unwrap(),
+					})
+				})
+				.collect(),
+			line_style: None,
+			point_style: None,
+			title: Some(metric.title.to_owned()),
+		})
+		.collect::<Vec<_>>();
+	let drift_interval_chart_title =
+		interval_chart_title(&props.date_window_interval, "Drift".to_owned());
+	let drift_overall_title = overall_chart_title(&props.date_window, "Drift".to_owned());
+	html! {
+		<ui::S2>
+			<ui::H2 center={false}>{drift_overall_title}</ui::H2>
+			<ui::P>
+				{"These metrics compare the distribution of this column in production to its distribution in the training dataset, and are zero when the distributions are the same. A PSI above 0.1 indicates moderate drift and above 0.2 indicates significant drift."}
+			</ui::P>
+			<MetricsRow>
+				{metrics.iter().map(|metric| html! {
+					<ui::Card>
+						<ui::NumberChart
+							title={metric.title.to_owned()}
+							value={ui::format_number((metric.value)(&props.overall).unwrap())}
+						/>
+					</ui::Card>
+				}).collect::<Vec<_>>()}
+			</MetricsRow>
+			<ui::Card>
+				<LineChart
+					class={None}
+					hide_legend={None}
+					id={"drift_intervals".to_owned()}
+					labels={labels}
+					series={series}
+					should_draw_x_axis_labels={None}
+					should_draw_y_axis_labels={None}
+					title={drift_interval_chart_title}
+					x_axis_grid_line_interval={Some(GridLineInterval { k: 1.0, p: 0.0 })}
+					x_axis_title={None}
+					x_max={None}
+					x_min={None}
+					y_axis_grid_line_interval={None}
+					y_axis_title={None}
+					y_max={None}
+					y_min={0.0}
+				/>
+			</ui::Card>
+		</ui::S2>
+	}
+}
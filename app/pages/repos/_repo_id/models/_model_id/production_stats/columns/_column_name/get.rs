@@ -1,11 +1,13 @@
 use super::page::{
-	render, EnumColumnProps, EnumOverallHistogramEntry, Inner, IntervalBoxChartDataPoint,
-	IntervalBoxChartDataPointStats, NumberColumnProps, NumberTrainingProductionComparison,
-	OverallBoxChartData, OverallBoxChartDataStats, Props, TextColumnProps,
+	render, DriftIntervalEntry, DriftProps, EnumColumnProps, EnumOverallHistogramEntry, Inner,
+	IntervalBoxChartDataPoint, IntervalBoxChartDataPointStats, NumberColumnProps,
+	NumberTrainingProductionComparison, OverallBoxChartData, OverallBoxChartDataStats, Props,
+	TextColumnProps,
 };
 use std::collections::BTreeMap;
 use tangram_app_common::{
 	date_window::{get_date_window_and_interval, DateWindow, DateWindowInterval},
+	drift::{compute_column_drift, ColumnDrift},
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
 	model::get_model,
 	production_stats::ProductionColumnStatsOutput,
@@ -70,13 +72,21 @@ pub async fn get(
 		.iter()
 		.find(|column| column.column_name() == column_name)
 		.unwrap();
+	let drift = drift_props(
+		&get_production_stats_output,
+		&model,
+		column_name,
+		date_window.clone(),
+		date_window_interval.clone(),
+		timezone,
+	);
 	let inner = match train_column_stats {
 		tangram_core::model::ColumnStats::Number(train_column_stats) => {
 			Inner::Number(number_props(
 				get_production_stats_output,
 				train_column_stats,
 				date_window.clone(),
-				date_window_interval,
+				date_window_interval.clone(),
 				timezone,
 			))
 		}
@@ -85,14 +95,14 @@ pub async fn get(
 			train_column_stats,
 			train_row_count,
 			date_window.clone(),
-			date_window_interval,
+			date_window_interval.clone(),
 			timezone,
 		)),
 		tangram_core::model::ColumnStats::Text(train_column_stats) => Inner::Text(text_props(
 			get_production_stats_output,
 			train_column_stats,
 			date_window.clone(),
-			date_window_interval,
+			date_window_interval.clone(),
 			timezone,
 		)),
 		_ => return Ok(bad_request()),
@@ -100,7 +110,9 @@ pub async fn get(
 	db.commit().await?;
 	let props = Props {
 		date_window,
+		date_window_interval,
 		column_name: column_name.to_owned(),
+		drift,
 		id: model_id.to_string(),
 		inner,
 		model_layout_info,
@@ -116,6 +128,54 @@ pub async fn get(
 	Ok(response)
 }
 
+/// Compute the drift of the column from the training dataset's column stats, which are the stats the production stats are compared to.
+fn drift_props(
+	get_production_stats_output: &GetProductionStatsOutput,
+	model: &tangram_core::model::Model,
+	column_name: &str,
+	date_window: DateWindow,
+	date_window_interval: DateWindowInterval,
+	timezone: Tz,
+) -> DriftProps {
+	let train_column_stats = match model {
+		tangram_core::model::Model::Regressor(model) => &model.train_column_stats,
+		tangram_core::model::Model::BinaryClassifier(model) => &model.train_column_stats,
+		tangram_core::model::Model::MulticlassClassifier(model) => &model.train_column_stats,
+	}
+	.iter()
+	.find(|column_stats| column_stats.column_name() == column_name);
+	let compute_drift = |column_stats: &[ProductionColumnStatsOutput]| -> ColumnDrift {
+		let production_column_stats = column_stats
+			.iter()
+			.find(|column_stats| column_stats.column_name() == column_name);
+		match (train_column_stats, production_column_stats) {
+			(Some(train_column_stats), Some(production_column_stats)) => {
+				compute_column_drift(train_column_stats, production_column_stats)
+			}
+			_ => ColumnDrift::default(),
+		}
+	};
+	let overall = compute_drift(&get_production_stats_output.overall.column_stats);
+	let intervals = get_production_stats_output
+		.intervals
+		.iter()
+		.map(|interval| DriftIntervalEntry {
+			label: format_date_window_interval(
+				interval.start_date,
+				&date_window_interval,
+				timezone,
+			),
+			drift: compute_drift(&interval.column_stats),
+		})
+		.collect();
+	DriftProps {
+		date_window,
+		date_window_interval,
+		overall,
+		intervals,
+	}
+}
+
 fn number_props(
 	get_production_stats_output: GetProductionStatsOutput,
 	train_column_stats: &tangram_core::model::NumberColumnStats,
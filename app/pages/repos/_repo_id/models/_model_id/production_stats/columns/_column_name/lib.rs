@@ -1,3 +1,4 @@
+mod drift;
 mod enum_column;
 mod get;
 mod number_column;
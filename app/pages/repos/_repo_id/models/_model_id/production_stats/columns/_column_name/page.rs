@@ -1,4 +1,4 @@
-use tangram_app_common::date_window::DateWindow;
+use tangram_app_common::date_window::{DateWindow, DateWindowInterval};
 use tangram_app_common::date_window_select_field::DateWindowSelectField;
 use tangram_app_layouts::{
 	document::PageInfo,
@@ -7,13 +7,16 @@ use tangram_app_layouts::{
 use tangram_deps::html::{self, html};
 use tangram_ui as ui;
 
+pub use crate::drift::*;
 pub use crate::enum_column::*;
 pub use crate::number_column::*;
 pub use crate::text_column::*;
 
 pub struct Props {
 	pub date_window: DateWindow,
+	pub date_window_interval: DateWindowInterval,
 	pub column_name: String,
+	pub drift: DriftProps,
 	pub id: String,
 	pub inner: Inner,
 	pub model_layout_info: ModelLayoutInfo,
@@ -77,7 +80,10 @@ pub fn render(props: Props, page_info: PageInfo) -> String {
 					autocomplete={None}
 					action={None}
 				>
-					<DateWindowSelectField date_window={props.date_window} />
+					<DateWindowSelectField
+						date_window={props.date_window}
+						date_window_interval={props.date_window_interval}
+					/>
 					<noscript>
 						<ui::Button
 							disabled={None}
@@ -92,6 +98,7 @@ pub fn render(props: Props, page_info: PageInfo) -> String {
 					</noscript>
 				</ui::Form>
 				{inner}
+				<Drift props={props.drift} />
 			</ui::S1>
 		</ModelLayout>
 	};
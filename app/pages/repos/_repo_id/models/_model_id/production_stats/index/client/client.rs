@@ -7,6 +7,8 @@ pub fn start() {
 	console_error_panic_hook::set_once();
 	let document = web_sys::window().unwrap().document().unwrap();
 	ui::select_field_submit_on_change("date_window_select_field".to_owned());
+	ui::select_field_submit_on_change("date_window_interval_select_field".to_owned());
+	ui::select_field_submit_on_change("sort_select_field".to_owned());
 	if document.get_element_by_id("prediction_count").is_some() {
 		hydrate_chart::<BarChart>("prediction_count");
 	}
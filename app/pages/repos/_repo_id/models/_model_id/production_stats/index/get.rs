@@ -1,11 +1,13 @@
 use super::page::{
-	render, ClassificationChartEntry, ColumnType, OverallColumnStats, PredictionCountChartEntry,
-	PredictionStatsChart, PredictionStatsIntervalChart, ProductionTrainingHistogram,
-	ProductionTrainingQuantiles, Props, Quantiles, RegressionChartEntry,
+	render, ClassificationChartEntry, ColumnType, ColumnsSort, OverallColumnStats,
+	PredictionCountChartEntry, PredictionStatsChart, PredictionStatsIntervalChart,
+	ProductionTrainingHistogram, ProductionTrainingQuantiles, Props, Quantiles,
+	RegressionChartEntry,
 };
-use std::collections::BTreeMap;
+use std::{cmp::Ordering, collections::BTreeMap};
 use tangram_app_common::{
 	date_window::get_date_window_and_interval,
+	drift::compute_column_drift,
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
 	model::get_model,
 	production_stats::get_production_stats,
@@ -35,6 +37,17 @@ pub async fn get(
 		Some((date_window, date_window_interval)) => (date_window, date_window_interval),
 		None => return Ok(bad_request()),
 	};
+	let sort = match search_params
+		.as_ref()
+		.and_then(|search_params| search_params.get("sort"))
+		.map(|sort| sort.as_str())
+	{
+		None | Some("column") => ColumnsSort::Column,
+		Some("psi") => ColumnsSort::Psi,
+		Some("ks_statistic") => ColumnsSort::KsStatistic,
+		Some("js_divergence") => ColumnsSort::JsDivergence,
+		Some(_) => return Ok(bad_request()),
+	};
 	let timezone = get_timezone(&request);
 	let mut db = match context.pool.begin().await {
 		Ok(db) => db,
@@ -60,19 +73,36 @@ pub async fn get(
 		timezone,
 	)
 	.await?;
-	let target_column_stats = match model {
-		tangram_core::model::Model::Regressor(model) => model.overall_target_column_stats,
-		tangram_core::model::Model::BinaryClassifier(model) => model.overall_target_column_stats,
-		tangram_core::model::Model::MulticlassClassifier(model) => {
-			model.overall_target_column_stats
-		}
+	let (target_column_stats, train_column_stats) = match &model {
+		tangram_core::model::Model::Regressor(model) => (
+			&model.overall_target_column_stats,
+			&model.train_column_stats,
+		),
+		tangram_core::model::Model::BinaryClassifier(model) => (
+			&model.overall_target_column_stats,
+			&model.train_column_stats,
+		),
+		tangram_core::model::Model::MulticlassClassifier(model) => (
+			&model.overall_target_column_stats,
+			&model.train_column_stats,
+		),
 	};
 	let row_count = production_stats.overall.row_count;
-	let overall_column_stats_table = production_stats
+	let mut overall_column_stats_table = production_stats
 		.overall
 		.column_stats
 		.iter()
-		.map(|column_stats| match column_stats {
+		.map(|column_stats| {
+			let drift = train_column_stats
+				.iter()
+				.find(|train_column_stats| {
+					train_column_stats.column_name() == column_stats.column_name()
+				})
+				.map(|train_column_stats| compute_column_drift(train_column_stats, column_stats))
+				.unwrap_or_default();
+			(column_stats, drift)
+		})
+		.map(|(column_stats, drift)| match column_stats {
 			ProductionColumnStatsOutput::Unknown(column_stats) => OverallColumnStats {
 				absent_count: column_stats.absent_count,
 				invalid_count: column_stats.invalid_count,
@@ -83,6 +113,9 @@ pub async fn get(
 				),
 				name: column_stats.column_name.clone(),
 				column_type: ColumnType::Unknown,
+				psi: drift.psi,
+				ks_statistic: drift.ks_statistic,
+				js_divergence: drift.js_divergence,
 			},
 			ProductionColumnStatsOutput::Text(column_stats) => OverallColumnStats {
 				absent_count: column_stats.absent_count,
@@ -94,6 +127,9 @@ pub async fn get(
 				),
 				name: column_stats.column_name.clone(),
 				column_type: ColumnType::Text,
+				psi: drift.psi,
+				ks_statistic: drift.ks_statistic,
+				js_divergence: drift.js_divergence,
 			},
 			ProductionColumnStatsOutput::Number(column_stats) => OverallColumnStats {
 				absent_count: column_stats.absent_count,
@@ -105,6 +141,9 @@ pub async fn get(
 				),
 				name: column_stats.column_name.clone(),
 				column_type: ColumnType::Number,
+				psi: drift.psi,
+				ks_statistic: drift.ks_statistic,
+				js_divergence: drift.js_divergence,
 			},
 			ProductionColumnStatsOutput::Enum(column_stats) => OverallColumnStats {
 				absent_count: column_stats.absent_count,
@@ -116,9 +155,13 @@ pub async fn get(
 				),
 				name: column_stats.column_name.clone(),
 				column_type: ColumnType::Enum,
+				psi: drift.psi,
+				ks_statistic: drift.ks_statistic,
+				js_divergence: drift.js_divergence,
 			},
 		})
-		.collect();
+		.collect::<Vec<_>>();
+	sort_columns(&mut overall_column_stats_table, sort);
 	let prediction_count_chart = production_stats
 		.intervals
 		.iter()
@@ -270,6 +313,7 @@ pub async fn get(
 		prediction_stats_chart,
 		prediction_stats_interval_chart,
 		model_layout_info,
+		sort,
 	};
 	let page_info = PageInfo {
 		client_wasm_js_src: Some(client!()),
@@ -308,6 +352,24 @@ fn compute_production_training_quantiles(
 	}
 }
 
+fn sort_columns(columns: &mut [OverallColumnStats], sort: ColumnsSort) {
+	if sort == ColumnsSort::Column {
+		return;
+	}
+	let key = |column: &OverallColumnStats| match sort {
+		ColumnsSort::Column => None,
+		ColumnsSort::Psi => column.psi,
+		ColumnsSort::KsStatistic => column.ks_statistic,
+		ColumnsSort::JsDivergence => column.js_divergence,
+	};
+	columns.sort_by(|a, b| match (key(a), key(b)) {
+		(Some(a), Some(b)) => b.partial_cmp(&a).unwrap_or(Ordering::Equal),
+		(Some(_), None) => Ordering::Less,
+		(None, Some(_)) => Ordering::Greater,
+		(None, None) => Ordering::Equal,
+	});
+}
+
 fn alert_message(count: u64, absent_count: u64, invalid_count: u64) -> Option<String> {
 	let invalid_ratio = invalid_count.to_f32().unwrap() / count.to_f32().unwrap();
 	let absent_ratio = absent_count.to_f32().unwrap() / count.to_f32().unwrap();
@@ -30,6 +30,7 @@ pub struct Props {
 	pub prediction_stats_chart: PredictionStatsChart,
 	pub prediction_stats_interval_chart: PredictionStatsIntervalChart,
 	pub model_layout_info: ModelLayoutInfo,
+	pub sort: ColumnsSort,
 }
 
 #[derive(Clone)]
@@ -39,6 +40,18 @@ pub struct OverallColumnStats {
 	pub alert: Option<String>,
 	pub name: String,
 	pub column_type: ColumnType,
+	pub psi: Option<f32>,
+	pub ks_statistic: Option<f32>,
+	pub js_divergence: Option<f32>,
+}
+
+/// The drift sorts put the columns with the most drift first, and the columns the metric does not apply to last.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ColumnsSort {
+	Column,
+	Psi,
+	KsStatistic,
+	JsDivergence,
 }
 
 #[derive(Clone)]
@@ -123,14 +136,14 @@ pub fn render(props: Props, page_info: PageInfo) -> String {
 		PredictionStatsIntervalChart::Regression(data) => html! {
 				<RegressionProductionStatsIntervalChart
 					chart_data={data}
-					date_window_interval={props.date_window_interval}
+					date_window_interval={props.date_window_interval.clone()}
 				/>
 		},
 		PredictionStatsIntervalChart::BinaryClassification(data) => {
 			html! {
 				<ClassificationProductionStatsIntervalChart
 					chart_data={data}
-					date_window_interval={props.date_window_interval}
+					date_window_interval={props.date_window_interval.clone()}
 				/>
 			}
 		}
@@ -138,7 +151,7 @@ pub fn render(props: Props, page_info: PageInfo) -> String {
 			html! {
 				<ClassificationProductionStatsIntervalChart
 					chart_data={data}
-					date_window_interval={props.date_window_interval}
+					date_window_interval={props.date_window_interval.clone()}
 				/>
 			}
 		}
@@ -163,6 +176,12 @@ pub fn render(props: Props, page_info: PageInfo) -> String {
 			/>
 		},
 	};
+	let sort = match props.sort {
+		ColumnsSort::Column => "column",
+		ColumnsSort::Psi => "psi",
+		ColumnsSort::KsStatistic => "ks_statistic",
+		ColumnsSort::JsDivergence => "js_divergence",
+	};
 	let html = html! {
 		<ModelLayout
 			info={props.model_layout_info}
@@ -178,7 +197,37 @@ pub fn render(props: Props, page_info: PageInfo) -> String {
 					enc_type={None}
 					id={None}
 				>
-					<DateWindowSelectField date_window={props.date_window} />
+					<DateWindowSelectField
+						date_window={props.date_window}
+						date_window_interval={props.date_window_interval}
+					/>
+					<ui::SelectField
+						disabled={None}
+						placeholder={None}
+						required={None}
+						id={"sort_select_field".to_owned()}
+						label={"Sort Columns By".to_owned()}
+						name={"sort".to_owned()}
+						options={vec![
+							ui::SelectFieldOption {
+								text: "Column".to_owned(),
+								value: "column".to_owned(),
+							},
+							ui::SelectFieldOption {
+								text: "PSI".to_owned(),
+								value: "psi".to_owned(),
+							},
+							ui::SelectFieldOption {
+								text: "KS Statistic".to_owned(),
+								value: "ks_statistic".to_owned(),
+							},
+							ui::SelectFieldOption {
+								text: "JS Divergence".to_owned(),
+								value: "js_divergence".to_owned(),
+							},
+						]}
+						value={sort.to_owned()}
+					/>
 					<noscript>
 						<ui::Button
 							color={None}
@@ -253,6 +302,27 @@ pub fn render(props: Props, page_info: PageInfo) -> String {
 							>
 								{"Invalid Count"}
 							</ui::TableHeaderCell>
+							<ui::TableHeaderCell
+								color={None}
+								expand={None}
+								text_align={None}
+							>
+								{"PSI"}
+							</ui::TableHeaderCell>
+							<ui::TableHeaderCell
+								color={None}
+								expand={None}
+								text_align={None}
+							>
+								{"KS Statistic"}
+							</ui::TableHeaderCell>
+							<ui::TableHeaderCell
+								color={None}
+								expand={None}
+								text_align={None}
+							>
+								{"JS Divergence"}
+							</ui::TableHeaderCell>
 						</ui::TableRow>
 					</ui::TableHeader>
 					<ui::TableBody>
@@ -297,6 +367,15 @@ pub fn render(props: Props, page_info: PageInfo) -> String {
 							<ui::TableCell color={None} expand={None}>
 								{column.invalid_count.to_string()}
 							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{format_drift(column.psi)}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{format_drift(column.ks_statistic)}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{format_drift(column.js_divergence)}
+							</ui::TableCell>
 						</ui::TableRow>
 					}).collect::<Vec<_>>()}
 					</ui::TableBody>
@@ -526,6 +605,12 @@ fn ClassificationProductionStatsIntervalChart(
 	}
 }
 
+fn format_drift(value: Option<f32>) -> String {
+	value
+		.map(ui::format_number)
+		.unwrap_or_else(|| "-".to_owned())
+}
+
 fn column_type_token(column_type: &ColumnType) -> html::Node {
 	match column_type {
 		ColumnType::Unknown => html! {
@@ -0,0 +1,17 @@
+use tangram_ui as ui;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(start)]
+pub fn start() {
+	console_error_panic_hook::set_once();
+	let document = web_sys::window().unwrap().document().unwrap();
+	for id in &[
+		"class-select-field",
+		"tree-select-field",
+		"sort-select-field",
+	] {
+		if document.get_element_by_id(id).is_some() {
+			ui::select_field_submit_on_change(id.to_string());
+		}
+	}
+}
@@ -0,0 +1,343 @@
+use crate::page::{
+	render, Inner, LinearProps, Props, TreeBranchNode, TreeLeafNode, TreeNode, TreeProps, Weight,
+	WeightsSort,
+};
+use std::collections::BTreeMap;
+use tangram_app_common::{
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	model::{compute_feature_names, get_model},
+	user::{authorize_user, authorize_user_for_model},
+	Context,
+};
+use tangram_app_layouts::{document::PageInfo, model_layout::get_model_layout_info};
+use tangram_deps::{http, hyper};
+use tangram_util::{client, error::Result, id::Id};
+
+pub async fn get(
+	context: &Context,
+	request: http::Request<hyper::Body>,
+	model_id: &str,
+	search_params: Option<BTreeMap<String, String>>,
+) -> Result<http::Response<hyper::Body>> {
+	let mut db = match context.pool.begin().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user = match authorize_user(&request, &mut db, context.options.auth_enabled).await? {
+		Ok(user) => user,
+		Err(_) => return Ok(redirect_to_login()),
+	};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let model = get_model(&mut db, model_id).await?;
+	let search_params = search_params.unwrap_or_default();
+	let tree_index = match search_params.get("tree") {
+		Some(tree_index) => match tree_index.parse::<usize>() {
+			Ok(tree_index) => tree_index,
+			Err(_) => return Ok(bad_request()),
+		},
+		None => 0,
+	};
+	let sort = match search_params.get("sort").map(|sort| sort.as_str()) {
+		None | Some("abs_weight") => WeightsSort::AbsWeight,
+		Some("weight") => WeightsSort::Weight,
+		Some("feature_name") => WeightsSort::FeatureName,
+		Some(_) => return Ok(bad_request()),
+	};
+	let inner = match &model {
+		tangram_core::model::Model::Regressor(model) => match &model.model {
+			tangram_core::model::RegressionModel::Linear(inner_model) => {
+				Inner::Linear(compute_linear_props(
+					&inner_model.feature_groups,
+					inner_model.bias,
+					&inner_model.weights,
+					None,
+					None,
+					sort,
+				))
+			}
+			tangram_core::model::RegressionModel::Tree(inner_model) => {
+				let trees = inner_model.trees.iter().collect::<Vec<_>>();
+				match compute_tree_props(
+					&inner_model.feature_groups,
+					&model.overall_column_stats,
+					inner_model.bias,
+					&trees,
+					tree_index,
+					None,
+					None,
+				) {
+					Some(props) => Inner::Tree(props),
+					None => return Ok(bad_request()),
+				}
+			}
+		},
+		tangram_core::model::Model::BinaryClassifier(model) => match &model.model {
+			tangram_core::model::BinaryClassificationModel::Linear(inner_model) => {
+				Inner::Linear(compute_linear_props(
+					&inner_model.feature_groups,
+					inner_model.bias,
+					&inner_model.weights,
+					Some(model.positive_class.clone()),
+					None,
+					sort,
+				))
+			}
+			tangram_core::model::BinaryClassificationModel::Tree(inner_model) => {
+				let trees = inner_model.trees.iter().collect::<Vec<_>>();
+				match compute_tree_props(
+					&inner_model.feature_groups,
+					&model.overall_column_stats,
+					inner_model.bias,
+					&trees,
+					tree_index,
+					Some(model.positive_class.clone()),
+					None,
+				) {
+					Some(props) => Inner::Tree(props),
+					None => return Ok(bad_request()),
+				}
+			}
+		},
+		tangram_core::model::Model::MulticlassClassifier(model) => {
+			let class_index = match search_params.get("class") {
+				Some(class) => match model.classes.iter().position(|c| c == class) {
+					Some(class_index) => class_index,
+					None => return Ok(bad_request()),
+				},
+				None => 0,
+			};
+			let class = Some(model.classes[class_index].clone());
+			let classes = Some(model.classes.clone());
+			match &model.model {
+				tangram_core::model::MulticlassClassificationModel::Linear(inner_model) => {
+					// The weights are stored with shape (n_features, n_classes) in row major order.
+					let weights = inner_model
+						.weights
+						.iter()
+						.skip(class_index)
+						.step_by(inner_model.n_classes)
+						.cloned()
+						.collect::<Vec<_>>();
+					Inner::Linear(compute_linear_props(
+						&inner_model.feature_groups,
+						inner_model.biases[class_index],
+						&weights,
+						class,
+						classes,
+						sort,
+					))
+				}
+				tangram_core::model::MulticlassClassificationModel::Tree(inner_model) => {
+					// The trees are stored round by round, with one tree for each class in each round.
+					let trees = inner_model
+						.trees
+						.iter()
+						.skip(class_index)
+						.step_by(inner_model.n_classes)
+						.collect::<Vec<_>>();
+					match compute_tree_props(
+						&inner_model.feature_groups,
+						&model.overall_column_stats,
+						inner_model.biases[class_index],
+						&trees,
+						tree_index,
+						class,
+						classes,
+					) {
+						Some(props) => Inner::Tree(props),
+						None => return Ok(bad_request()),
+					}
+				}
+			}
+		}
+	};
+	let model_layout_info = get_model_layout_info(&mut db, context, model_id).await?;
+	let props = Props {
+		id: model_id.to_string(),
+		inner,
+		model_layout_info,
+	};
+	db.commit().await?;
+	let page_info = PageInfo {
+		client_wasm_js_src: Some(client!()),
+	};
+	let html = render(props, page_info);
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.body(hyper::Body::from(html))
+		.unwrap();
+	Ok(response)
+}
+
+fn compute_linear_props(
+	feature_groups: &[tangram_core::model::FeatureGroup],
+	bias: f32,
+	weights: &[f32],
+	class: Option<String>,
+	classes: Option<Vec<String>>,
+	sort: WeightsSort,
+) -> LinearProps {
+	let mut weights = compute_feature_names(feature_groups)
+		.into_iter()
+		.zip(weights.iter())
+		.map(|(feature_name, weight)| Weight {
+			feature_name,
+			weight: *weight,
+		})
+		.collect::<Vec<_>>();
+	match sort {
+		WeightsSort::AbsWeight => weights.sort_by(|a, b| {
+			a.weight
+				.abs()
+				.partial_cmp(&b.weight.abs())
+				.unwrap()
+				.reverse()
+		}),
+		WeightsSort::Weight => {
+			weights.sort_by(|a, b| a.weight.partial_cmp(&b.weight).unwrap().reverse())
+		}
+		WeightsSort::FeatureName => weights.sort_by(|a, b| a.feature_name.cmp(&b.feature_name)),
+	}
+	LinearProps {
+		class,
+		classes,
+		bias,
+		sort,
+		weights,
+	}
+}
+
+/// Compute the props for the tree at `tree_index` in `trees`, or return `None` if there is no such tree.
+fn compute_tree_props(
+	feature_groups: &[tangram_core::model::FeatureGroup],
+	column_stats: &[tangram_core::model::ColumnStats],
+	bias: f32,
+	trees: &[&tangram_core::model::Tree],
+	tree_index: usize,
+	class: Option<String>,
+	classes: Option<Vec<String>>,
+) -> Option<TreeProps> {
+	let nodes = if trees.is_empty() {
+		Vec::new()
+	} else {
+		let feature_names = compute_feature_names(feature_groups);
+		let feature_options = compute_feature_options(feature_groups, column_stats);
+		trees
+			.get(tree_index)?
+			.nodes
+			.iter()
+			.map(|node| compute_tree_node(node, &feature_names, &feature_options))
+			.collect()
+	};
+	Some(TreeProps {
+		class,
+		classes,
+		bias,
+		n_trees: trees.len(),
+		tree_index,
+		nodes,
+	})
+}
+
+/// Tree models use identity feature groups for enum columns, whose feature values are the indexes of the columns' options plus one, with zero for invalid values. This function retrieves the options for each feature that comes from an enum column, and `None` for every other feature.
+fn compute_feature_options(
+	feature_groups: &[tangram_core::model::FeatureGroup],
+	column_stats: &[tangram_core::model::ColumnStats],
+) -> Vec<Option<Vec<String>>> {
+	feature_groups
+		.iter()
+		.flat_map(|feature_group| match feature_group {
+			tangram_core::model::FeatureGroup::Identity(feature_group) => {
+				let options = column_stats
+					.iter()
+					.find(|column_stats| {
+						column_stats.column_name() == feature_group.source_column_name
+					})
+					.and_then(|column_stats| column_stats.as_enum())
+					.map(|column_stats| {
+						column_stats
+							.histogram
+							.iter()
+							.map(|(option, _)| option.clone())
+							.collect()
+					});
+				vec![options]
+			}
+			tangram_core::model::FeatureGroup::Normalized(_) => vec![None],
+			tangram_core::model::FeatureGroup::OneHotEncoded(feature_group) => {
+				vec![None; feature_group.options.len() + 1]
+			}
+			tangram_core::model::FeatureGroup::BagOfWords(feature_group) => {
+				vec![None; feature_group.tokens.len()]
+			}
+		})
+		.collect()
+}
+
+fn compute_tree_node(
+	node: &tangram_core::model::Node,
+	feature_names: &[String],
+	feature_options: &[Option<Vec<String>>],
+) -> TreeNode {
+	match node {
+		tangram_core::model::Node::Branch(node) => {
+			let (left_condition, right_condition) = match &node.split {
+				// Invalid values are sent right at continuous splits because they are not <= any split value.
+				tangram_core::model::BranchSplit::Continuous(split) => {
+					let feature_name = &feature_names[split.feature_index];
+					(
+						format!("{} <= {}", feature_name, split.split_value),
+						format!("{} > {} or invalid", feature_name, split.split_value),
+					)
+				}
+				tangram_core::model::BranchSplit::Discrete(split) => {
+					let feature_name = &feature_names[split.feature_index];
+					let options = feature_options[split.feature_index].as_ref();
+					let option_name = |option_index: usize| -> String {
+						if option_index == 0 {
+							"OOV".to_owned()
+						} else {
+							options
+								.and_then(|options| options.get(option_index - 1))
+								.cloned()
+								.unwrap_or_else(|| option_index.to_string())
+						}
+					};
+					let mut left_options = Vec::new();
+					let mut right_options = Vec::new();
+					for (option_index, direction) in split.directions.iter().enumerate() {
+						match direction {
+							tangram_core::model::SplitDirection::Left => {
+								left_options.push(option_name(option_index))
+							}
+							tangram_core::model::SplitDirection::Right => {
+								right_options.push(option_name(option_index))
+							}
+						}
+					}
+					(
+						format!("{} is one of {}", feature_name, left_options.join(", ")),
+						format!("{} is one of {}", feature_name, right_options.join(", ")),
+					)
+				}
+			};
+			TreeNode::Branch(TreeBranchNode {
+				left_child_index: node.left_child_index,
+				right_child_index: node.right_child_index,
+				left_condition,
+				right_condition,
+				examples_fraction: node.examples_fraction,
+			})
+		}
+		tangram_core::model::Node::Leaf(node) => TreeNode::Leaf(TreeLeafNode {
+			value: node.value,
+			examples_fraction: node.examples_fraction,
+		}),
+	}
+}
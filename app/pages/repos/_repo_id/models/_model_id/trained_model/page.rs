@@ -0,0 +1,316 @@
+use tangram_app_common::class_select_field::ClassSelectField;
+use tangram_app_layouts::{
+	document::PageInfo,
+	model_layout::{ModelLayout, ModelLayoutInfo, ModelSideNavItem},
+};
+use tangram_deps::html::{self, component, html};
+use tangram_ui as ui;
+
+pub struct Props {
+	pub id: String,
+	pub inner: Inner,
+	pub model_layout_info: ModelLayoutInfo,
+}
+
+pub enum Inner {
+	Linear(LinearProps),
+	Tree(TreeProps),
+}
+
+pub struct LinearProps {
+	/// This is the class whose weights are shown, which is `None` for regressors.
+	pub class: Option<String>,
+	/// This is `Some` only for multiclass classifiers, which show a select field to choose the class.
+	pub classes: Option<Vec<String>>,
+	pub bias: f32,
+	pub sort: WeightsSort,
+	pub weights: Vec<Weight>,
+}
+
+#[derive(Clone)]
+pub struct Weight {
+	pub feature_name: String,
+	pub weight: f32,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum WeightsSort {
+	AbsWeight,
+	Weight,
+	FeatureName,
+}
+
+pub struct TreeProps {
+	/// This is the class whose trees are shown, which is `None` for regressors.
+	pub class: Option<String>,
+	/// This is `Some` only for multiclass classifiers, which show a select field to choose the class.
+	pub classes: Option<Vec<String>>,
+	pub bias: f32,
+	pub n_trees: usize,
+	pub tree_index: usize,
+	/// These are the nodes of the selected tree, with the root at index 0.
+	pub nodes: Vec<TreeNode>,
+}
+
+#[derive(Clone)]
+pub enum TreeNode {
+	Branch(TreeBranchNode),
+	Leaf(TreeLeafNode),
+}
+
+#[derive(Clone)]
+pub struct TreeBranchNode {
+	pub left_child_index: usize,
+	pub right_child_index: usize,
+	pub left_condition: String,
+	pub right_condition: String,
+	pub examples_fraction: f32,
+}
+
+#[derive(Clone)]
+pub struct TreeLeafNode {
+	pub value: f64,
+	pub examples_fraction: f32,
+}
+
+pub fn render(props: Props, page_info: PageInfo) -> String {
+	let inner = match props.inner {
+		Inner::Linear(inner) => linear_page(inner),
+		Inner::Tree(inner) => tree_page(inner),
+	};
+	let html = html! {
+		<ModelLayout
+			info={props.model_layout_info}
+			page_info={page_info}
+			selected_item={ModelSideNavItem::TrainedModel}
+		>
+			<ui::S1>
+				<ui::H1 center={false}>{"Trained Model"}</ui::H1>
+				{inner}
+			</ui::S1>
+		</ModelLayout>
+	};
+	html.render_to_string()
+}
+
+fn linear_page(props: LinearProps) -> html::Node {
+	let description = match &props.class {
+		None => "The model is a linear model. Its prediction is the bias plus the sum of each feature's value multiplied by the feature's weight.".to_owned(),
+		Some(class) => format!("The model is a linear model. The log odds it predicts for the class \"{}\" is the bias plus the sum of each feature's value multiplied by the feature's weight.", class),
+	};
+	let sort = match props.sort {
+		WeightsSort::AbsWeight => "abs_weight",
+		WeightsSort::Weight => "weight",
+		WeightsSort::FeatureName => "feature_name",
+	};
+	let class = props.class;
+	html! {
+		<>
+			<ui::P>{description}</ui::P>
+			<ui::Form
+				enc_type={None}
+				post={None}
+				id={None}
+				action={None}
+				autocomplete={None}
+			>
+				{props.classes.map(|classes| html! {
+					<ClassSelectField class={class.unwrap()} classes={classes} />
+				})}
+				<ui::SelectField
+					disabled={None}
+					placeholder={None}
+					required={None}
+					id={"sort-select-field".to_owned()}
+					label={"Sort By".to_owned()}
+					name={"sort".to_owned()}
+					options={vec![
+						ui::SelectFieldOption {
+							text: "Absolute Weight".to_owned(),
+							value: "abs_weight".to_owned(),
+						},
+						ui::SelectFieldOption {
+							text: "Weight".to_owned(),
+							value: "weight".to_owned(),
+						},
+						ui::SelectFieldOption {
+							text: "Feature Name".to_owned(),
+							value: "feature_name".to_owned(),
+						},
+					]}
+					value={sort.to_owned()}
+				/>
+				<noscript>
+					<ui::Button
+						color={None}
+						disabled={None}
+						button_type={ui::ButtonType::Submit}
+						download={None}
+						href={None}
+						id={None}
+					>
+						{"Submit"}
+					</ui::Button>
+				</noscript>
+			</ui::Form>
+			<ui::S2>
+				<ui::H2 center={false}>{"Weights"}</ui::H2>
+				<ui::P>
+					{"One hot encoded enum columns have a feature for each option and one for values that are not one of the options, and text columns have a feature for each token."}
+				</ui::P>
+				<ui::Table width={Some("100%".to_owned())}>
+					<ui::TableHeader>
+						<ui::TableRow color={None}>
+							<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+								{"Feature"}
+							</ui::TableHeaderCell>
+							<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+								{"Weight"}
+							</ui::TableHeaderCell>
+						</ui::TableRow>
+					</ui::TableHeader>
+					<ui::TableBody>
+						<ui::TableRow color={None}>
+							<ui::TableCell color={None} expand={None}>
+								{"Bias"}
+							</ui::TableCell>
+							<ui::TableCell color={None} expand={None}>
+								{props.bias.to_string()}
+							</ui::TableCell>
+						</ui::TableRow>
+						{props.weights.into_iter().map(|weight| html! {
+							<ui::TableRow color={None}>
+								<ui::TableCell color={None} expand={None}>
+									{weight.feature_name}
+								</ui::TableCell>
+								<ui::TableCell color={None} expand={None}>
+									{weight.weight.to_string()}
+								</ui::TableCell>
+							</ui::TableRow>
+						}).collect::<Vec<_>>()}
+					</ui::TableBody>
+				</ui::Table>
+			</ui::S2>
+		</>
+	}
+}
+
+fn tree_page(props: TreeProps) -> html::Node {
+	let description = match &props.class {
+		None => format!("The model is a gradient boosted tree model with {} trees. Its prediction is the bias, {}, plus the sum of the values of the leaves each tree sends the example to.", props.n_trees, props.bias),
+		Some(class) => format!("The model is a gradient boosted tree model with {} trees for the class \"{}\". The log odds it predicts for the class is the bias, {}, plus the sum of the values of the leaves each tree sends the example to.", props.n_trees, class, props.bias),
+	};
+	let tree = if props.nodes.is_empty() {
+		None
+	} else {
+		Some(html! {
+			<ui::S2>
+				<ui::H2 center={false}>{format!("Tree {}", props.tree_index + 1)}</ui::H2>
+				<ui::P>
+					{"Click a node to show or hide its children. Each node shows the condition an example must meet to be sent to it and the percent of the training examples that were sent to it."}
+				</ui::P>
+				<ui::Card>
+					<div class="trained-model-tree">
+						{tree_node(&props.nodes, 0, "All examples".to_owned(), 0)}
+					</div>
+				</ui::Card>
+			</ui::S2>
+		})
+	};
+	html! {
+		<>
+			<ui::P>{description}</ui::P>
+			<TreeForm
+				class={props.class}
+				classes={props.classes}
+				n_trees={props.n_trees}
+				tree_index={props.tree_index}
+			/>
+			{tree}
+		</>
+	}
+}
+
+#[component]
+fn TreeForm(
+	class: Option<String>,
+	classes: Option<Vec<String>>,
+	n_trees: usize,
+	tree_index: usize,
+) {
+	html! {
+		<ui::Form
+			enc_type={None}
+			post={None}
+			id={None}
+			action={None}
+			autocomplete={None}
+		>
+			{classes.map(|classes| html! {
+				<ClassSelectField class={class.unwrap()} classes={classes} />
+			})}
+			{if n_trees > 0 {
+				Some(html! {
+					<ui::SelectField
+						disabled={None}
+						placeholder={None}
+						required={None}
+						id={"tree-select-field".to_owned()}
+						label={"Select Tree".to_owned()}
+						name={"tree".to_owned()}
+						options={(0..n_trees).map(|tree_index| ui::SelectFieldOption {
+							text: format!("Tree {}", tree_index + 1),
+							value: tree_index.to_string(),
+						}).collect::<Vec<_>>()}
+						value={tree_index.to_string()}
+					/>
+				})
+			} else {
+				None
+			}}
+			<noscript>
+				<ui::Button
+					color={None}
+					disabled={None}
+					button_type={ui::ButtonType::Submit}
+					download={None}
+					href={None}
+					id={None}
+				>
+					{"Submit"}
+				</ui::Button>
+			</noscript>
+		</ui::Form>
+	}
+}
+
+/// Render the node at `node_index` and its descendants. Branch nodes are collapsible, and only the nodes near the root are expanded at first.
+fn tree_node(nodes: &[TreeNode], node_index: usize, condition: String, depth: usize) -> html::Node {
+	match &nodes[node_index] {
+		TreeNode::Branch(node) => html! {
+			<details class="trained-model-tree-node" open={depth < 2}>
+				<summary class="trained-model-tree-node-summary">
+					{condition}
+					<span class="trained-model-tree-node-fraction">
+						{ui::format_percent(node.examples_fraction)}
+					</span>
+				</summary>
+				<div class="trained-model-tree-node-children">
+					{tree_node(nodes, node.left_child_index, node.left_condition.clone(), depth + 1)}
+					{tree_node(nodes, node.right_child_index, node.right_condition.clone(), depth + 1)}
+				</div>
+			</details>
+		},
+		TreeNode::Leaf(node) => html! {
+			<div class="trained-model-tree-leaf">
+				{condition}
+				<span class="trained-model-tree-leaf-value">
+					{format!("Leaf Value: {}", node.value)}
+				</span>
+				<span class="trained-model-tree-node-fraction">
+					{ui::format_percent(node.examples_fraction)}
+				</span>
+			</div>
+		},
+	}
+}
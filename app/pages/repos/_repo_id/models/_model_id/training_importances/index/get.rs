@@ -1,7 +1,7 @@
 use crate::page::{render, FeatureImportance, Props};
 use tangram_app_common::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
-	model::get_model,
+	model::{compute_feature_names, get_model},
 	user::{authorize_user, authorize_user_for_model},
 	Context,
 };
@@ -184,41 +184,3 @@ pub async fn get(
 		.unwrap();
 	Ok(response)
 }
-
-fn compute_feature_names(feature_groups: &[tangram_core::model::FeatureGroup]) -> Vec<String> {
-	feature_groups
-		.iter()
-		.flat_map(|feature_group| match feature_group {
-			tangram_core::model::FeatureGroup::Identity(feature_group) => {
-				vec![feature_group.source_column_name.clone()]
-			}
-			tangram_core::model::FeatureGroup::Normalized(feature_group) => {
-				vec![feature_group.source_column_name.clone()]
-			}
-			tangram_core::model::FeatureGroup::OneHotEncoded(feature_group) => {
-				vec!["OOV".to_owned()]
-					.iter()
-					.chain(feature_group.options.iter())
-					.map(|option| {
-						format!(
-							"{} = {}",
-							feature_group.source_column_name.clone(),
-							option.clone(),
-						)
-					})
-					.collect()
-			}
-			tangram_core::model::FeatureGroup::BagOfWords(feature_group) => feature_group
-				.tokens
-				.iter()
-				.map(|token| {
-					format!(
-						"{} contains {}",
-						feature_group.source_column_name.clone(),
-						token.token,
-					)
-				})
-				.collect(),
-		})
-		.collect()
-}
@@ -34,6 +34,22 @@ pub fn render(props: Props, page_info: PageInfo) -> String {
 				<ui::H1 center={false}>
 					{"Training Feature Importances"}
 				</ui::H1>
+				<ui::TabBar>
+					<ui::TabLink
+						disabled={false}
+						href={"./".to_owned()}
+						selected={true}
+					>
+						{"Overview"}
+					</ui::TabLink>
+					<ui::TabLink
+						disabled={false}
+						href={"shap".to_owned()}
+						selected={false}
+					>
+						{"SHAP Values"}
+					</ui::TabLink>
+				</ui::TabBar>
 				<ui::P>
 					{format!("Your model had a total of {} features.", props.n_features)}
 				</ui::P>
@@ -0,0 +1,26 @@
+use tangram_charts::{bar_chart::BarChart, hydrate_chart, line_chart::LineChart};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::window;
+
+#[wasm_bindgen(start)]
+pub fn start() {
+	console_error_panic_hook::set_once();
+	let window = window().unwrap();
+	let document = window.document().unwrap();
+	if document.get_element_by_id("mean_abs_shap_values").is_none() {
+		return;
+	}
+	hydrate_chart::<BarChart>("mean_abs_shap_values");
+	hydrate_chart::<LineChart>("shap_values");
+	let dependence_charts_query = document
+		.query_selector_all(".dependence-chart[data-chart-type='line']")
+		.unwrap();
+	for index in 0..dependence_charts_query.length() {
+		let item = dependence_charts_query
+			.item(index)
+			.unwrap()
+			.dyn_into::<web_sys::Element>()
+			.unwrap();
+		hydrate_chart::<LineChart>(&item.id());
+	}
+}
@@ -0,0 +1,140 @@
+use crate::page::{render, FeatureShapValues, MeanAbsShapValue, Props};
+use std::collections::BTreeMap;
+use tangram_app_common::{
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	model::{compute_feature_names, get_model},
+	user::{authorize_user, authorize_user_for_model},
+	Context,
+};
+use tangram_app_layouts::{document::PageInfo, model_layout::get_model_layout_info};
+use tangram_deps::{http, hyper};
+use tangram_util::{client, error::Result, id::Id};
+
+const MAX_MEAN_ABS_SHAP_VALUES: usize = 20;
+
+pub async fn get(
+	context: &Context,
+	request: http::Request<hyper::Body>,
+	model_id: &str,
+	search_params: Option<BTreeMap<String, String>>,
+) -> Result<http::Response<hyper::Body>> {
+	let mut db = match context.pool.begin().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user = match authorize_user(&request, &mut db, context.options.auth_enabled).await? {
+		Ok(user) => user,
+		Err(_) => return Ok(redirect_to_login()),
+	};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let model = get_model(&mut db, model_id).await?;
+	let (feature_groups, shap_summary, class, classes) = match model {
+		tangram_core::model::Model::Regressor(model) => {
+			let feature_groups = match model.model {
+				tangram_core::model::RegressionModel::Linear(model) => model.feature_groups,
+				tangram_core::model::RegressionModel::Tree(model) => model.feature_groups,
+			};
+			(feature_groups, model.shap_summary, None, None)
+		}
+		tangram_core::model::Model::BinaryClassifier(model) => {
+			let feature_groups = match model.model {
+				tangram_core::model::BinaryClassificationModel::Linear(model) => {
+					model.feature_groups
+				}
+				tangram_core::model::BinaryClassificationModel::Tree(model) => model.feature_groups,
+			};
+			(
+				feature_groups,
+				model.shap_summary,
+				Some(model.positive_class),
+				None,
+			)
+		}
+		tangram_core::model::Model::MulticlassClassifier(model) => {
+			let feature_groups = match model.model {
+				tangram_core::model::MulticlassClassificationModel::Linear(model) => {
+					model.feature_groups
+				}
+				tangram_core::model::MulticlassClassificationModel::Tree(model) => {
+					model.feature_groups
+				}
+			};
+			let class_index = match search_params.as_ref().and_then(|s| s.get("class")) {
+				Some(class) => match model.classes.iter().position(|c| c == class) {
+					Some(class_index) => class_index,
+					None => return Ok(bad_request()),
+				},
+				None => 0,
+			};
+			let shap_summary = model
+				.shap_summaries
+				.and_then(|shap_summaries| shap_summaries.into_iter().nth(class_index));
+			let class = model.classes[class_index].clone();
+			(
+				feature_groups,
+				shap_summary,
+				Some(class),
+				Some(model.classes),
+			)
+		}
+	};
+	let feature_names = compute_feature_names(&feature_groups);
+	let n_features = feature_names.len();
+	let (n_examples, mean_abs_shap_values, features) = match shap_summary {
+		Some(shap_summary) => {
+			let mut mean_abs_shap_values = shap_summary
+				.mean_abs_shap_values
+				.iter()
+				.zip(feature_names.iter())
+				.map(|(value, feature_name)| MeanAbsShapValue {
+					feature_name: feature_name.clone(),
+					value: *value,
+				})
+				.collect::<Vec<_>>();
+			mean_abs_shap_values.sort_by(|a, b| a.value.partial_cmp(&b.value).unwrap().reverse());
+			mean_abs_shap_values.truncate(MAX_MEAN_ABS_SHAP_VALUES);
+			let features = shap_summary
+				.features
+				.into_iter()
+				.map(|feature| FeatureShapValues {
+					feature_name: feature_names[feature.feature_index].clone(),
+					feature_values: feature.feature_values,
+					shap_values: feature.shap_values,
+				})
+				.collect();
+			(
+				Some(shap_summary.n_examples),
+				mean_abs_shap_values,
+				features,
+			)
+		}
+		None => (None, Vec::new(), Vec::new()),
+	};
+	let model_layout_info = get_model_layout_info(&mut db, context, model_id).await?;
+	let props = Props {
+		id: model_id.to_string(),
+		class,
+		classes,
+		n_examples,
+		n_features,
+		mean_abs_shap_values,
+		features,
+		model_layout_info,
+	};
+	db.commit().await?;
+	let page_info = PageInfo {
+		client_wasm_js_src: Some(client!()),
+	};
+	let html = render(props, page_info);
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.body(hyper::Body::from(html))
+		.unwrap();
+	Ok(response)
+}
@@ -0,0 +1,299 @@
+use tangram_app_common::class_select_field::ClassSelectField;
+use tangram_app_layouts::{
+	document::PageInfo,
+	model_layout::{ModelLayout, ModelLayoutInfo, ModelSideNavItem},
+};
+use tangram_charts::{
+	bar_chart::{BarChartPoint, BarChartSeries},
+	components::{BarChart, LineChart},
+	line_chart::{LineChartPoint, LineChartSeries, LineStyle, PointStyle},
+};
+use tangram_deps::html::{self, component, html};
+use tangram_deps::num_traits::ToPrimitive;
+use tangram_ui as ui;
+
+/// The chart of the distributions of SHAP values shows at most this many features.
+const MAX_SHAP_VALUES_CHART_FEATURES: usize = 10;
+
+const SHAP_VALUES_CHART_COLORS: [&str; MAX_SHAP_VALUES_CHART_FEATURES] = [
+	ui::colors::BLUE,
+	ui::colors::GREEN,
+	ui::colors::ORANGE,
+	ui::colors::PURPLE,
+	ui::colors::RED,
+	ui::colors::TEAL,
+	ui::colors::INDIGO,
+	ui::colors::YELLOW,
+	ui::colors::PINK,
+	ui::colors::GRAY,
+];
+
+pub struct Props {
+	pub id: String,
+	/// This is the class whose SHAP values are shown, which is `None` for regressors.
+	pub class: Option<String>,
+	/// This is `Some` only for multiclass classifiers, which show a select field to choose the class.
+	pub classes: Option<Vec<String>>,
+	/// This is `None` if SHAP values were not computed for this model.
+	pub n_examples: Option<u64>,
+	pub n_features: usize,
+	pub mean_abs_shap_values: Vec<MeanAbsShapValue>,
+	pub features: Vec<FeatureShapValues>,
+	pub model_layout_info: ModelLayoutInfo,
+}
+
+#[derive(Clone)]
+pub struct MeanAbsShapValue {
+	pub feature_name: String,
+	pub value: f32,
+}
+
+#[derive(Clone)]
+pub struct FeatureShapValues {
+	pub feature_name: String,
+	pub feature_values: Vec<f32>,
+	pub shap_values: Vec<f32>,
+}
+
+pub fn render(props: Props, page_info: PageInfo) -> String {
+	let description = match &props.class {
+		None => "A feature's SHAP value for an example is how much the feature changed the model's prediction for the example, in the units of the target column.".to_owned(),
+		Some(class) => format!("A feature's SHAP value for an example is how much the feature changed the log odds the model predicted for the class \"{}\" for the example.", class),
+	};
+	let class = props.class;
+	let html = html! {
+		<ModelLayout
+			info={props.model_layout_info}
+			page_info={page_info}
+			selected_item={ModelSideNavItem::TrainingImportances}
+		>
+			<ui::S1>
+				<ui::H1 center={false}>{"Training Feature Importances"}</ui::H1>
+				<ui::TabBar>
+					<ui::TabLink
+						disabled={false}
+						href={"./".to_owned()}
+						selected={false}
+					>
+						{"Overview"}
+					</ui::TabLink>
+					<ui::TabLink
+						disabled={false}
+						href={"shap".to_owned()}
+						selected={true}
+					>
+						{"SHAP Values"}
+					</ui::TabLink>
+				</ui::TabBar>
+				{props.classes.map(|classes| html! {
+					<ui::Form
+						enc_type={None}
+						post={None}
+						id={None}
+						action={None}
+						autocomplete={None}
+					>
+						<ClassSelectField class={class.unwrap()} classes={classes} />
+						<noscript>
+							<ui::Button
+								color={None}
+								disabled={None}
+								button_type={ui::ButtonType::Submit}
+								download={None}
+								href={None}
+								id={None}
+							>
+								{"Submit"}
+							</ui::Button>
+						</noscript>
+					</ui::Form>
+				})}
+				{match props.n_examples {
+					None => html! {
+						<ui::P>
+							{"SHAP values were not computed for this model because it was imported or trained with an earlier version of tangram."}
+						</ui::P>
+					},
+					Some(0) => html! {
+						<ui::P>
+							{"SHAP values were not computed for this model because it was not trained with a test dataset."}
+						</ui::P>
+					},
+					Some(n_examples) => html! {
+						<>
+							<ui::P>
+								{format!("{} These SHAP values were computed for {} examples from the test dataset.", description, n_examples)}
+							</ui::P>
+							<MeanAbsShapValuesChart
+								mean_abs_shap_values={props.mean_abs_shap_values}
+								n_features={props.n_features}
+							/>
+							<ShapValuesChart features={props.features.clone()} />
+							<DependenceCharts features={props.features} />
+						</>
+					},
+				}}
+			</ui::S1>
+		</ModelLayout>
+	};
+	html.render_to_string()
+}
+
+#[component]
+fn MeanAbsShapValuesChart(mean_abs_shap_values: Vec<MeanAbsShapValue>, n_features: usize) {
+	let series = vec![BarChartSeries {
+		color: ui::colors::BLUE.to_owned(),
+		data: mean_abs_shap_values
+			.iter()
+			.enumerate()
+			.map(|(index, mean_abs_shap_value)| BarChartPoint {
+				label: mean_abs_shap_value.feature_name.clone(),
+				x: index.to_f64().unwrap(),
+				y: Some(mean_abs_shap_value.value.to_f64().unwrap()),
+			})
+			.collect(),
+		title: Some("Mean Absolute SHAP Value".to_owned()),
+	}];
+	html! {
+		<ui::S2>
+			<ui::H2 center={false}>{"Mean Absolute SHAP Values"}</ui::H2>
+			<ui::P>
+				{format!("The mean of the absolute value of a feature's SHAP values measures how much the feature affects the model's predictions. This chart shows the {} features with the largest mean absolute SHAP values out of {} features.", mean_abs_shap_values.len(), n_features)}
+			</ui::P>
+			<ui::Card>
+				<BarChart
+					class={None}
+					group_gap={None}
+					hide_legend={true}
+					id={"mean_abs_shap_values".to_owned()}
+					series={series}
+					should_draw_x_axis_labels={false}
+					should_draw_y_axis_labels={None}
+					title={"Mean Absolute SHAP Values".to_owned()}
+					x_axis_title={"Feature Name".to_owned()}
+					y_axis_grid_line_interval={None}
+					y_axis_title={"Mean Absolute SHAP Value".to_owned()}
+					y_min={Some(0.0)}
+					y_max={None}
+				/>
+			</ui::Card>
+		</ui::S2>
+	}
+}
+
+/// This chart plots each example's SHAP value for each feature. Each feature has its own row, with the feature with the largest mean absolute SHAP value at the top, and the points in each row are spread vertically so they do not overlap as much.
+#[component]
+fn ShapValuesChart(features: Vec<FeatureShapValues>) {
+	let features = &features[..features.len().min(MAX_SHAP_VALUES_CHART_FEATURES)];
+	let n_features = features.len();
+	let series = features
+		.iter()
+		.enumerate()
+		.map(|(feature_index, feature)| {
+			let row = (n_features - feature_index).to_f64().unwrap();
+			LineChartSeries {
+				color: SHAP_VALUES_CHART_COLORS[feature_index].to_owned(),
+				data: feature
+					.shap_values
+					.iter()
+					.enumerate()
+					.map(|(example_index, shap_value)| LineChartPoint {
+						x: shap_value.to_f64().unwrap(),
+						y: row + jitter(example_index),
+					})
+					.collect(),
+				line_style: Some(LineStyle::Hidden),
+				point_style: Some(PointStyle::Circle),
+				title: Some(feature.feature_name.clone()),
+			}
+		})
+		.collect::<Vec<_>>();
+	html! {
+		<ui::S2>
+			<ui::H2 center={false}>{"SHAP Values"}</ui::H2>
+			<ui::P>
+				{"Each point is an example's SHAP value for a feature. Each feature has its own row, and the features are ordered by their mean absolute SHAP value, with the largest at the top."}
+			</ui::P>
+			<ui::Card>
+				<LineChart
+					class={None}
+					hide_legend={false}
+					id={"shap_values".to_owned()}
+					labels={None}
+					series={series}
+					should_draw_x_axis_labels={None}
+					should_draw_y_axis_labels={false}
+					title={"SHAP Values".to_owned()}
+					x_axis_grid_line_interval={None}
+					x_axis_title={"SHAP Value".to_owned()}
+					x_max={None}
+					x_min={None}
+					y_axis_grid_line_interval={None}
+					y_axis_title={None}
+					y_max={Some(n_features.to_f64().unwrap() + 0.5)}
+					y_min={Some(0.5)}
+				/>
+			</ui::Card>
+		</ui::S2>
+	}
+}
+
+#[component]
+fn DependenceCharts(features: Vec<FeatureShapValues>) {
+	html! {
+		<ui::S2>
+			<ui::H2 center={false}>{"Dependence"}</ui::H2>
+			<ui::P>
+				{"These charts plot each example's SHAP value for a feature against the feature's value, which shows how the feature's effect on the model's predictions depends on its value. The values of enum features are the numbers of their options, in the order they appear on the training stats page, and invalid values are not shown for number features."}
+			</ui::P>
+			{features.into_iter().enumerate().map(|(feature_index, feature)| {
+				let data = feature
+					.feature_values
+					.iter()
+					.zip(feature.shap_values.iter())
+					.filter(|(feature_value, _)| feature_value.is_finite())
+					.map(|(feature_value, shap_value)| LineChartPoint {
+						x: feature_value.to_f64().unwrap(),
+						y: shap_value.to_f64().unwrap(),
+					})
+					.collect();
+				let series = vec![LineChartSeries {
+					color: ui::colors::BLUE.to_owned(),
+					data,
+					line_style: Some(LineStyle::Hidden),
+					point_style: Some(PointStyle::Circle),
+					title: Some(feature.feature_name.clone()),
+				}];
+				html! {
+					<ui::Card>
+						<LineChart
+							class={Some("dependence-chart".to_owned())}
+							hide_legend={true}
+							id={format!("dependence_{}", feature_index)}
+							labels={None}
+							series={series}
+							should_draw_x_axis_labels={None}
+							should_draw_y_axis_labels={None}
+							title={format!("Dependence for {}", feature.feature_name)}
+							x_axis_grid_line_interval={None}
+							x_axis_title={feature.feature_name}
+							x_max={None}
+							x_min={None}
+							y_axis_grid_line_interval={None}
+							y_axis_title={"SHAP Value".to_owned()}
+							y_max={None}
+							y_min={None}
+						/>
+					</ui::Card>
+				}
+			}).collect::<Vec<_>>()}
+		</ui::S2>
+	}
+}
+
+/// Retrieve a vertical offset for the point for the example at `example_index` in the chart of SHAP values. The offsets are spread evenly between -0.3 and 0.3 in an order that looks random.
+fn jitter(example_index: usize) -> f64 {
+	let golden_ratio_conjugate = 0.618_033_988_749_895;
+	let fraction = (example_index.to_f64().unwrap() * golden_ratio_conjugate).fract();
+	(fraction - 0.5) * 0.6
+}
@@ -55,6 +55,7 @@ pub async fn get(
 	let model_layout_info = get_model_layout_info(&mut db, context, model_id).await?;
 	db.commit().await?;
 	let props = Props {
+		has_slice_metrics: model.slice_metrics.is_some(),
 		id: model_id.to_string(),
 		model_layout_info,
 		class,
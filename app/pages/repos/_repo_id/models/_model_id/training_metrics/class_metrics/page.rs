@@ -23,6 +23,7 @@ pub struct Props {
 	pub recall: f32,
 	pub true_negatives: u64,
 	pub true_positives: u64,
+	pub has_slice_metrics: bool,
 }
 
 pub fn render(props: Props, page_info: PageInfo) -> String {
@@ -49,6 +50,19 @@ pub fn render(props: Props, page_info: PageInfo) -> String {
 					>
 					{"Class Metrics"}
 					</ui::TabLink>
+					{if props.has_slice_metrics {
+						Some(html! {
+							<ui::TabLink
+								disabled={None}
+								href={"slices".to_owned()}
+								selected={false}
+							>
+								{"Slices"}
+							</ui::TabLink>
+						})
+					} else {
+						None
+					}}
 				</ui::TabBar>
 				<ui::Form
 					enc_type={None}
@@ -0,0 +1,17 @@
+use tangram_charts::{hydrate_chart, line_chart::LineChart};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(start)]
+pub fn start() {
+	console_error_panic_hook::set_once();
+	let document = web_sys::window().unwrap().document().unwrap();
+	if document.get_element_by_id("fairness_roc").is_some() {
+		hydrate_chart::<LineChart>("fairness_roc");
+	}
+	if document
+		.get_element_by_id("fairness_precision_recall")
+		.is_some()
+	{
+		hydrate_chart::<LineChart>("fairness_precision_recall");
+	}
+}
@@ -0,0 +1,102 @@
+use super::page::{
+	render, FairnessMetricsProps, GroupMetrics, PrecisionRecallPoint, Props, ROCCurvePoint,
+};
+use tangram_app_common::{
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	model::get_model,
+	user::{authorize_user, authorize_user_for_model},
+	Context,
+};
+use tangram_app_layouts::{document::PageInfo, model_layout::get_model_layout_info};
+use tangram_deps::{http, hyper};
+use tangram_util::{client, error::Result, id::Id};
+
+pub async fn get(
+	context: &Context,
+	request: http::Request<hyper::Body>,
+	model_id: &str,
+) -> Result<http::Response<hyper::Body>> {
+	let mut db = match context.pool.begin().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user = match authorize_user(&request, &mut db, context.options.auth_enabled).await? {
+		Ok(user) => user,
+		Err(_) => return Ok(redirect_to_login()),
+	};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let model = get_model(&mut db, model_id).await?;
+	let model = match model {
+		tangram_core::model::Model::BinaryClassifier(model) => model,
+		_ => return Ok(bad_request()),
+	};
+	let fairness_metrics = model
+		.fairness_metrics
+		.map(|fairness_metrics| FairnessMetricsProps {
+			sensitive_column_name: fairness_metrics.sensitive_column_name,
+			excluded_from_features: fairness_metrics.excluded_from_features,
+			demographic_parity_difference: fairness_metrics.demographic_parity_difference,
+			equal_opportunity_difference: fairness_metrics.equal_opportunity_difference,
+			groups: fairness_metrics
+				.groups
+				.into_iter()
+				.map(|group| {
+					// The middle threshold is the default threshold of 0.5.
+					let default_threshold_metrics =
+						&group.metrics.thresholds[group.metrics.thresholds.len() / 2];
+					GroupMetrics {
+						accuracy: default_threshold_metrics.accuracy,
+						precision: default_threshold_metrics.precision,
+						recall: default_threshold_metrics.recall,
+						false_positive_rate: default_threshold_metrics.false_positive_rate,
+						roc_curve: group
+							.metrics
+							.thresholds
+							.iter()
+							.map(|threshold_metrics| ROCCurvePoint {
+								false_positive_rate: threshold_metrics.false_positive_rate,
+								true_positive_rate: threshold_metrics.true_positive_rate,
+							})
+							.collect(),
+						precision_recall_curve: group
+							.metrics
+							.thresholds
+							.iter()
+							.map(|threshold_metrics| PrecisionRecallPoint {
+								precision: threshold_metrics.precision,
+								recall: threshold_metrics.recall,
+							})
+							.collect(),
+						auc_roc: group.metrics.auc_roc,
+						group: group.group,
+						n_examples: group.n_examples,
+						positive_rate: group.positive_rate,
+					}
+				})
+				.collect(),
+		});
+	let model_layout_info = get_model_layout_info(&mut db, context, model_id).await?;
+	let props = Props {
+		has_slice_metrics: model.slice_metrics.is_some(),
+		id: model_id.to_string(),
+		class: model.positive_class,
+		fairness_metrics,
+		model_layout_info,
+	};
+	db.commit().await?;
+	let page_info = PageInfo {
+		client_wasm_js_src: Some(client!()),
+	};
+	let html = render(props, page_info);
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.body(hyper::Body::from(html))
+		.unwrap();
+	Ok(response)
+}
@@ -0,0 +1,325 @@
+use tangram_app_common::{
+	definitions::{DEMOGRAPHIC_PARITY_DIFFERENCE, EQUAL_OPPORTUNITY_DIFFERENCE},
+	metrics_row::MetricsRow,
+};
+use tangram_app_layouts::{
+	document::PageInfo,
+	model_layout::{ModelLayout, ModelLayoutInfo, ModelSideNavItem},
+};
+use tangram_charts::{
+	components::LineChart,
+	line_chart::{LineChartPoint, LineChartSeries, LineStyle, PointStyle},
+};
+use tangram_deps::html::{self, component, html};
+use tangram_deps::num_traits::ToPrimitive;
+use tangram_ui as ui;
+
+const GROUP_COLORS: [&str; 8] = [
+	ui::colors::BLUE,
+	ui::colors::GREEN,
+	ui::colors::ORANGE,
+	ui::colors::PURPLE,
+	ui::colors::RED,
+	ui::colors::TEAL,
+	ui::colors::INDIGO,
+	ui::colors::PINK,
+];
+
+pub struct Props {
+	pub id: String,
+	pub class: String,
+	/// This is `None` if the model was trained without a sensitive column in the fairness config.
+	pub fairness_metrics: Option<FairnessMetricsProps>,
+	pub model_layout_info: ModelLayoutInfo,
+	pub has_slice_metrics: bool,
+}
+
+#[derive(Clone)]
+pub struct FairnessMetricsProps {
+	pub sensitive_column_name: String,
+	pub excluded_from_features: bool,
+	pub demographic_parity_difference: f32,
+	pub equal_opportunity_difference: f32,
+	pub groups: Vec<GroupMetrics>,
+}
+
+#[derive(Clone)]
+pub struct GroupMetrics {
+	pub group: String,
+	pub n_examples: u64,
+	pub positive_rate: f32,
+	pub accuracy: f32,
+	pub precision: f32,
+	pub recall: f32,
+	pub false_positive_rate: f32,
+	pub auc_roc: f32,
+	pub roc_curve: Vec<ROCCurvePoint>,
+	pub precision_recall_curve: Vec<PrecisionRecallPoint>,
+}
+
+#[derive(Clone)]
+pub struct ROCCurvePoint {
+	pub false_positive_rate: f32,
+	pub true_positive_rate: f32,
+}
+
+#[derive(Clone)]
+pub struct PrecisionRecallPoint {
+	pub precision: f32,
+	pub recall: f32,
+}
+
+pub fn render(props: Props, page_info: PageInfo) -> String {
+	let inner = match props.fairness_metrics {
+		Some(fairness_metrics) => html! {
+			<FairnessMetrics class={props.class} fairness_metrics={fairness_metrics} />
+		},
+		None => html! {
+			<ui::P>
+				{"Fairness metrics were not computed for this model. To slice the test metrics by a sensitive enum column, set \"fairness\": { \"sensitive_column_name\": \"<column name>\" } in the config used for training."}
+			</ui::P>
+		},
+	};
+	let html = html! {
+		<ModelLayout
+			info={props.model_layout_info}
+			page_info={page_info}
+			selected_item={ModelSideNavItem::TrainingMetrics}
+		>
+			<ui::S1>
+				<ui::H1 center={false}>{"Training Metrics"}</ui::H1>
+				<ui::TabBar>
+					<ui::TabLink
+						disabled={false}
+						href={"./".to_owned()}
+						selected={false}
+					>
+						{"Overview"}
+					</ui::TabLink>
+					<ui::TabLink
+						disabled={false}
+						href={"precision_recall".to_owned()}
+						selected={false}
+					>
+						{"PR Curve"}
+					</ui::TabLink>
+					<ui::TabLink
+						disabled={false}
+						href={"roc".to_owned()}
+						selected={false}
+					>
+						{"ROC Curve"}
+					</ui::TabLink>
+					<ui::TabLink
+						disabled={false}
+						href={"fairness".to_owned()}
+						selected={true}
+					>
+						{"Fairness"}
+					</ui::TabLink>
+					{if props.has_slice_metrics {
+						Some(html! {
+							<ui::TabLink
+								disabled={false}
+								href={"slices".to_owned()}
+								selected={false}
+							>
+								{"Slices"}
+							</ui::TabLink>
+						})
+					} else {
+						None
+					}}
+				</ui::TabBar>
+				{inner}
+			</ui::S1>
+		</ModelLayout>
+	};
+	html.render_to_string()
+}
+
+#[component]
+fn FairnessMetrics(class: String, fairness_metrics: FairnessMetricsProps) {
+	let description = format!(
+		"These are the test metrics for each group of examples with the same value of the sensitive column \"{}\", with the positive class \"{}\" and the default threshold of 0.5. {}",
+		fairness_metrics.sensitive_column_name,
+		class,
+		if fairness_metrics.excluded_from_features {
+			"The sensitive column was not used to compute the model's features."
+		} else {
+			"The sensitive column was used to compute the model's features."
+		},
+	);
+	let roc_series = fairness_metrics
+		.groups
+		.iter()
+		.enumerate()
+		.map(|(group_index, group)| LineChartSeries {
+			color: GROUP_COLORS[group_index % GROUP_COLORS.len()].to_owned(),
+			data: group
+				.roc_curve
+				.iter()
+				.map(|point| LineChartPoint {
+					x: point.false_positive_rate.to_f64().unwrap(),
+					y: point.true_positive_rate.to_f64().unwrap(),
+				})
+				.collect(),
+			line_style: Some(LineStyle::Solid),
+			point_style: Some(PointStyle::Circle),
+			title: Some(group.group.clone()),
+		})
+		.collect::<Vec<_>>();
+	let precision_recall_series = fairness_metrics
+		.groups
+		.iter()
+		.enumerate()
+		.map(|(group_index, group)| LineChartSeries {
+			color: GROUP_COLORS[group_index % GROUP_COLORS.len()].to_owned(),
+			data: group
+				.precision_recall_curve
+				.iter()
+				.filter(|point| point.precision.is_finite())
+				.map(|point| LineChartPoint {
+					x: point.recall.to_f64().unwrap(),
+					y: point.precision.to_f64().unwrap(),
+				})
+				.collect(),
+			line_style: Some(LineStyle::Solid),
+			point_style: Some(PointStyle::Circle),
+			title: Some(group.group.clone()),
+		})
+		.collect::<Vec<_>>();
+	html! {
+		<>
+			<ui::S2>
+				<ui::H2 center={false}>{"Fairness"}</ui::H2>
+				<ui::P>{description}</ui::P>
+				<MetricsRow>
+					<ui::Card>
+						<ui::NumberChart
+							title={"Demographic Parity Difference".to_owned()}
+							value={ui::format_percent(fairness_metrics.demographic_parity_difference)}
+						/>
+					</ui::Card>
+					<ui::Card>
+						<ui::NumberChart
+							title={"Equal Opportunity Difference".to_owned()}
+							value={ui::format_percent(fairness_metrics.equal_opportunity_difference)}
+						/>
+					</ui::Card>
+				</MetricsRow>
+				<ui::P>{DEMOGRAPHIC_PARITY_DIFFERENCE}</ui::P>
+				<ui::P>{EQUAL_OPPORTUNITY_DIFFERENCE}</ui::P>
+			</ui::S2>
+			<ui::S2>
+				<ui::H2 center={false}>{"Group Metrics"}</ui::H2>
+				<ui::Table width={Some("100%".to_owned())}>
+					<ui::TableHeader>
+						<ui::TableRow color={None}>
+							<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+								{"Group"}
+							</ui::TableHeaderCell>
+							<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+								{"Test Examples"}
+							</ui::TableHeaderCell>
+							<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+								{"Positive Rate"}
+							</ui::TableHeaderCell>
+							<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+								{"Accuracy"}
+							</ui::TableHeaderCell>
+							<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+								{"Precision"}
+							</ui::TableHeaderCell>
+							<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+								{"Recall"}
+							</ui::TableHeaderCell>
+							<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+								{"False Positive Rate"}
+							</ui::TableHeaderCell>
+							<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+								{"AUC"}
+							</ui::TableHeaderCell>
+						</ui::TableRow>
+					</ui::TableHeader>
+					<ui::TableBody>
+						{fairness_metrics.groups.iter().map(|group| html! {
+							<ui::TableRow color={None}>
+								<ui::TableCell color={None} expand={None}>
+									{group.group.clone()}
+								</ui::TableCell>
+								<ui::TableCell color={None} expand={None}>
+									{group.n_examples.to_string()}
+								</ui::TableCell>
+								<ui::TableCell color={None} expand={None}>
+									{ui::format_percent(group.positive_rate)}
+								</ui::TableCell>
+								<ui::TableCell color={None} expand={None}>
+									{ui::format_percent(group.accuracy)}
+								</ui::TableCell>
+								<ui::TableCell color={None} expand={None}>
+									{ui::format_percent(group.precision)}
+								</ui::TableCell>
+								<ui::TableCell color={None} expand={None}>
+									{ui::format_percent(group.recall)}
+								</ui::TableCell>
+								<ui::TableCell color={None} expand={None}>
+									{ui::format_percent(group.false_positive_rate)}
+								</ui::TableCell>
+								<ui::TableCell color={None} expand={None}>
+									{ui::format_number(group.auc_roc)}
+								</ui::TableCell>
+							</ui::TableRow>
+						}).collect::<Vec<_>>()}
+					</ui::TableBody>
+				</ui::Table>
+			</ui::S2>
+			<ui::S2>
+				<ui::H2 center={false}>{"Receiver Operating Characteristic Curves"}</ui::H2>
+				<ui::Card>
+					<LineChart
+						class={None}
+						labels={None}
+						should_draw_x_axis_labels={None}
+						should_draw_y_axis_labels={None}
+						x_axis_grid_line_interval={None}
+						y_axis_grid_line_interval={None}
+						hide_legend={false}
+						id={"fairness_roc".to_owned()}
+						series={roc_series}
+						title={"Receiver Operating Characteristic Curves".to_owned()}
+						x_axis_title={"False Positive Rate".to_owned()}
+						x_max={1.0}
+						x_min={0.0}
+						y_axis_title={"True Positive Rate".to_owned()}
+						y_max={1.0}
+						y_min={0.0}
+					/>
+				</ui::Card>
+			</ui::S2>
+			<ui::S2>
+				<ui::H2 center={false}>{"Precision Recall Curves"}</ui::H2>
+				<ui::Card>
+					<LineChart
+						class={None}
+						labels={None}
+						should_draw_x_axis_labels={None}
+						should_draw_y_axis_labels={None}
+						x_axis_grid_line_interval={None}
+						y_axis_grid_line_interval={None}
+						hide_legend={false}
+						id={"fairness_precision_recall".to_owned()}
+						series={precision_recall_series}
+						title={"Precision Recall Curves".to_owned()}
+						x_axis_title={"Recall".to_owned()}
+						x_max={1.0}
+						x_min={0.0}
+						y_axis_title={"Precision".to_owned()}
+						y_max={1.0}
+						y_min={0.0}
+					/>
+				</ui::Card>
+			</ui::S2>
+		</>
+	}
+}
@@ -34,6 +34,26 @@ pub fn BinaryClassifierTrainingMetricsIndexPage(props: BinaryClassifierProps) {
 			>
 				{"ROC Curve"}
 			</ui::TabLink>
+			<ui::TabLink
+				disabled={None}
+				href={"fairness".to_owned()}
+				selected={false}
+			>
+				{"Fairness"}
+			</ui::TabLink>
+			{if props.has_slice_metrics {
+				Some(html! {
+					<ui::TabLink
+						disabled={None}
+						href={"slices".to_owned()}
+						selected={false}
+					>
+						{"Slices"}
+					</ui::TabLink>
+				})
+			} else {
+				None
+			}}
 		</ui::TabBar>
 		<ui::S2>
 			<ui::P>
@@ -57,30 +77,38 @@ pub fn BinaryClassifierTrainingMetricsIndexPage(props: BinaryClassifierProps) {
 					value_b_title={"Training".to_owned()}
 				/>
 			</ui::Card>
+			{props.accuracy_margin.map(|accuracy_margin| html! {
+				<ui::P>
+					{format!(
+						"The 95% confidence interval for the accuracy from bootstrapping the test dataset is {}.",
+						ui::format_number_with_margin(props.accuracy, Some(accuracy_margin)),
+					)}
+				</ui::P>
+			})}
 		</ui::S2>
 		<ui::Card>
 			<ui::NumberChart
 				title={"AUC ROC".to_owned()}
-				value={ui::format_number(props.auc_roc)}
+				value={ui::format_number_with_margin(props.auc_roc, props.auc_roc_margin)}
 			/>
 		</ui::Card>
 		<MetricsRow>
 			<ui::Card>
 				<ui::NumberChart
 					title={"Precision".to_owned()}
-					value={ui::format_number(props.precision)}
+					value={ui::format_number_with_margin(props.precision, props.precision_margin)}
 				/>
 			</ui::Card>
 			<ui::Card>
 				<ui::NumberChart
 					title={"Recall".to_owned()}
-					value={ui::format_number(props.recall)}
+					value={ui::format_number_with_margin(props.recall, props.recall_margin)}
 				/>
 			</ui::Card>
 			<ui::Card>
 				<ui::NumberChart
 					title={"F1 Score".to_owned()}
-					value={ui::format_number(props.f1_score)}
+					value={ui::format_number_with_margin(props.f1_score, props.f1_score_margin)}
 				/>
 			</ui::Card>
 		</MetricsRow>
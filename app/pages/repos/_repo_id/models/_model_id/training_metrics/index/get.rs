@@ -64,11 +64,20 @@ pub async fn get(
 
 fn build_inner_regressor(model: tangram_core::model::Regressor) -> RegressorProps {
 	RegressorProps {
+		has_slice_metrics: model.slice_metrics.is_some(),
 		id: model.id,
 		rmse: model.test_metrics.rmse,
 		baseline_rmse: model.baseline_metrics.rmse,
 		mse: model.test_metrics.mse,
 		baseline_mse: model.baseline_metrics.mse,
+		mse_margin: model
+			.test_metrics_confidence_intervals
+			.as_ref()
+			.map(|confidence_intervals| compute_margin(&confidence_intervals.mse)),
+		rmse_margin: model
+			.test_metrics_confidence_intervals
+			.as_ref()
+			.map(|confidence_intervals| compute_margin(&confidence_intervals.rmse)),
 	}
 }
 
@@ -86,7 +95,9 @@ fn build_inner_binary_classifier(
 		.get(model.baseline_metrics.thresholds.len() / 2)
 		.unwrap()
 		.accuracy;
+	let confidence_intervals = model.test_metrics_confidence_intervals.as_ref();
 	BinaryClassifierProps {
+		has_slice_metrics: model.slice_metrics.is_some(),
 		accuracy: default_threshold_test_metrics.accuracy,
 		baseline_accuracy,
 		auc_roc: model.test_metrics.auc_roc,
@@ -94,6 +105,16 @@ fn build_inner_binary_classifier(
 		precision: default_threshold_test_metrics.precision,
 		recall: default_threshold_test_metrics.recall,
 		f1_score: default_threshold_test_metrics.f1_score,
+		accuracy_margin: confidence_intervals
+			.map(|confidence_intervals| compute_margin(&confidence_intervals.accuracy)),
+		auc_roc_margin: confidence_intervals
+			.map(|confidence_intervals| compute_margin(&confidence_intervals.auc_roc)),
+		precision_margin: confidence_intervals
+			.map(|confidence_intervals| compute_margin(&confidence_intervals.precision)),
+		recall_margin: confidence_intervals
+			.map(|confidence_intervals| compute_margin(&confidence_intervals.recall)),
+		f1_score_margin: confidence_intervals
+			.map(|confidence_intervals| compute_margin(&confidence_intervals.f1_score)),
 	}
 }
 
@@ -111,10 +132,20 @@ fn build_inner_multiclass_classifier(
 		})
 		.collect::<Vec<ClassMetrics>>();
 	MulticlassClassifierProps {
+		has_slice_metrics: model.slice_metrics.is_some(),
 		id: model.id.to_string(),
 		accuracy: model.test_metrics.accuracy,
+		accuracy_margin: model
+			.test_metrics_confidence_intervals
+			.as_ref()
+			.map(|confidence_intervals| compute_margin(&confidence_intervals.accuracy)),
 		baseline_accuracy: model.baseline_metrics.accuracy,
 		class_metrics,
 		classes,
 	}
 }
+
+/// The margin of a confidence interval is half its width, which is shown as "value ± margin".
+fn compute_margin(confidence_interval: &tangram_core::model::ConfidenceInterval) -> f32 {
+	(confidence_interval.upper - confidence_interval.lower) / 2.0
+}
@@ -27,6 +27,19 @@ pub fn MulticlassClassifierTrainingMetricsIndexPage(props: MulticlassClassifierP
 			>
 				{"Class Metrics"}
 			</ui::TabLink>
+			{if props.has_slice_metrics {
+				Some(html! {
+					<ui::TabLink
+						disabled={None}
+						href={"slices".to_owned()}
+						selected={false}
+					>
+						{"Slices"}
+					</ui::TabLink>
+				})
+			} else {
+				None
+			}}
 		</ui::TabBar>
 		<ui::S2>
 			<ui::P>
@@ -50,6 +63,14 @@ pub fn MulticlassClassifierTrainingMetricsIndexPage(props: MulticlassClassifierP
 					value_b_title={"Accuracy".to_owned()}
 				/>
 			</ui::Card>
+			{props.accuracy_margin.map(|accuracy_margin| html! {
+				<ui::P>
+					{format!(
+						"The 95% confidence interval for the accuracy from bootstrapping the test dataset is {}.",
+						ui::format_number_with_margin(props.accuracy, Some(accuracy_margin)),
+					)}
+				</ui::P>
+			})}
 		</ui::S2>
 		<ui::S2>
 			<ui::H2 center={false}>{"Precision and Recall"}</ui::H2>
@@ -25,7 +25,11 @@ pub struct RegressorProps {
 	pub baseline_rmse: f32,
 	pub mse: f32,
 	pub rmse: f32,
+	/// The margins are half the widths of the test metrics' confidence intervals, which are `None` if they were not computed.
+	pub mse_margin: Option<f32>,
+	pub rmse_margin: Option<f32>,
 	pub id: String,
+	pub has_slice_metrics: bool,
 }
 
 #[derive(Clone)]
@@ -37,15 +41,25 @@ pub struct BinaryClassifierProps {
 	pub precision: f32,
 	pub recall: f32,
 	pub f1_score: f32,
+	/// The margins are half the widths of the test metrics' confidence intervals, which are `None` if they were not computed.
+	pub accuracy_margin: Option<f32>,
+	pub auc_roc_margin: Option<f32>,
+	pub precision_margin: Option<f32>,
+	pub recall_margin: Option<f32>,
+	pub f1_score_margin: Option<f32>,
+	pub has_slice_metrics: bool,
 }
 
 #[derive(Clone)]
 pub struct MulticlassClassifierProps {
 	pub accuracy: f32,
+	/// This is half the width of the accuracy's confidence interval, which is `None` if it was not computed.
+	pub accuracy_margin: Option<f32>,
 	pub baseline_accuracy: f32,
 	pub class_metrics: Vec<ClassMetrics>,
 	pub classes: Vec<String>,
 	pub id: String,
+	pub has_slice_metrics: bool,
 }
 
 #[derive(Clone)]
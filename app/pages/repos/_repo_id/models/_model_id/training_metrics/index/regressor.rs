@@ -11,6 +11,28 @@ pub fn RegressorTrainingMetricsIndexPage(props: RegressorProps) {
 	html! {
 		<ui::S1>
 			<ui::H1 center={false}>{"Training Metrics"}</ui::H1>
+			<ui::TabBar>
+				<ui::TabLink
+					disabled={None}
+					href={"./".to_owned()}
+					selected={true}
+				>
+					{"Overview"}
+				</ui::TabLink>
+				{if props.has_slice_metrics {
+					Some(html! {
+						<ui::TabLink
+							disabled={None}
+							href={"slices".to_owned()}
+							selected={false}
+						>
+							{"Slices"}
+						</ui::TabLink>
+					})
+				} else {
+					None
+				}}
+			</ui::TabBar>
 			<ui::S2>
 				<ui::P>
 					{
@@ -45,6 +67,15 @@ pub fn RegressorTrainingMetricsIndexPage(props: RegressorProps) {
 						/>
 					</ui::Card>
 				</MetricsRow>
+				{props.rmse_margin.map(|rmse_margin| html! {
+					<ui::P>
+						{format!(
+							"The 95% confidence intervals from bootstrapping the test dataset are {} for the root mean squared error and {} for the mean squared error.",
+							ui::format_number_with_margin(props.rmse, Some(rmse_margin)),
+							ui::format_number_with_margin(props.mse, props.mse_margin),
+						)}
+					</ui::P>
+				})}
 			</ui::S2>
 		</ui::S1>
 	}
@@ -46,6 +46,7 @@ pub async fn get(
 		.collect();
 	let model_layout_info = get_model_layout_info(&mut db, context, model_id).await?;
 	let props = Props {
+		has_slice_metrics: model.slice_metrics.is_some(),
 		class: model.positive_class,
 		precision_recall_curve_series,
 		id: model_id.to_string(),
@@ -16,6 +16,7 @@ pub struct Props {
 	pub precision_recall_curve_series: Vec<PrecisionRecallPoint>,
 	pub id: String,
 	pub model_layout_info: ModelLayoutInfo,
+	pub has_slice_metrics: bool,
 }
 
 pub struct PrecisionRecallPoint {
@@ -102,6 +103,26 @@ pub fn render(props: Props, page_info: PageInfo) -> String {
 					>
 						{"ROC Curve"}
 					</ui::TabLink>
+					<ui::TabLink
+						disabled={false}
+						href={"fairness".to_owned()}
+						selected={false}
+					>
+						{"Fairness"}
+					</ui::TabLink>
+					{if props.has_slice_metrics {
+						Some(html! {
+							<ui::TabLink
+								disabled={false}
+								href={"slices".to_owned()}
+								selected={false}
+							>
+								{"Slices"}
+							</ui::TabLink>
+						})
+					} else {
+						None
+					}}
 				</ui::TabBar>
 				<ui::S2>
 					<ui::H2 center={false}>{"Parametric Precision Recall Curve"}</ui::H2>
@@ -45,6 +45,7 @@ pub async fn get(
 			let model_layout_info = get_model_layout_info(&mut db, context, model_id).await?;
 			db.commit().await?;
 			Props {
+				has_slice_metrics: model.slice_metrics.is_some(),
 				id: model_id.to_string(),
 				class: model.positive_class,
 				roc_curve_data,
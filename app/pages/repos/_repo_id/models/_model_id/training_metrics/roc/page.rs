@@ -17,6 +17,7 @@ pub struct Props {
 	pub model_layout_info: ModelLayoutInfo,
 	pub class: String,
 	pub auc_roc: f32,
+	pub has_slice_metrics: bool,
 }
 
 pub struct ROCCurveData {
@@ -82,6 +83,26 @@ pub fn render(props: Props, page_info: PageInfo) -> String {
 					>
 						{"ROC Curve"}
 					</ui::TabLink>
+					<ui::TabLink
+						disabled={false}
+						href={"fairness".to_owned()}
+						selected={false}
+					>
+						{"Fairness"}
+					</ui::TabLink>
+					{if props.has_slice_metrics {
+						Some(html! {
+							<ui::TabLink
+								disabled={false}
+								href={"slices".to_owned()}
+								selected={false}
+							>
+								{"Slices"}
+							</ui::TabLink>
+						})
+					} else {
+						None
+					}}
 				</ui::TabBar>
 				<ui::S2>
 					<ui::H2 center={false}>{"Area Under the Receiver Operating Characteristic"}</ui::H2>
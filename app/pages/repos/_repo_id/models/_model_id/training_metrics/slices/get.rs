@@ -0,0 +1,117 @@
+use super::page::{render, Props, Slice, SliceMetric, Task};
+use tangram_app_common::{
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	model::get_model,
+	user::{authorize_user, authorize_user_for_model},
+	Context,
+};
+use tangram_app_layouts::{document::PageInfo, model_layout::get_model_layout_info};
+use tangram_deps::{http, hyper};
+use tangram_util::{error::Result, id::Id};
+
+pub async fn get(
+	context: &Context,
+	request: http::Request<hyper::Body>,
+	model_id: &str,
+) -> Result<http::Response<hyper::Body>> {
+	let mut db = match context.pool.begin().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user = match authorize_user(&request, &mut db, context.options.auth_enabled).await? {
+		Ok(user) => user,
+		Err(_) => return Ok(redirect_to_login()),
+	};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let model = get_model(&mut db, model_id).await?;
+	let (task, overall_value, slice_metrics) = match &model {
+		tangram_core::model::Model::Regressor(model) => (
+			Task::Regressor,
+			model.test_metrics.mae,
+			&model.slice_metrics,
+		),
+		tangram_core::model::Model::BinaryClassifier(model) => {
+			// The middle threshold is the default threshold of 0.5.
+			let default_threshold_test_metrics =
+				&model.test_metrics.thresholds[model.test_metrics.thresholds.len() / 2];
+			(
+				Task::BinaryClassifier,
+				default_threshold_test_metrics.accuracy,
+				&model.slice_metrics,
+			)
+		}
+		tangram_core::model::Model::MulticlassClassifier(model) => (
+			Task::MulticlassClassifier,
+			model.test_metrics.accuracy,
+			&model.slice_metrics,
+		),
+	};
+	let slice_metrics = match slice_metrics {
+		Some(slice_metrics) => slice_metrics,
+		None => return Ok(not_found()),
+	};
+	let metric = match slice_metrics.metric {
+		tangram_core::model::SliceMetric::Accuracy => SliceMetric::Accuracy,
+		tangram_core::model::SliceMetric::MeanAbsoluteError => SliceMetric::MeanAbsoluteError,
+	};
+	let slices = slice_metrics
+		.slices
+		.iter()
+		.map(compute_slice)
+		.collect::<Vec<_>>();
+	let worst_slices = slice_metrics
+		.worst_slice_indexes
+		.iter()
+		.map(|slice_index| slices[*slice_index].clone())
+		.collect();
+	let model_layout_info = get_model_layout_info(&mut db, context, model_id).await?;
+	let props = Props {
+		id: model_id.to_string(),
+		task,
+		metric,
+		overall_value,
+		worst_slices,
+		slices,
+		model_layout_info,
+	};
+	db.commit().await?;
+	let page_info = PageInfo {
+		client_wasm_js_src: None,
+	};
+	let html = render(props, page_info);
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.body(hyper::Body::from(html))
+		.unwrap();
+	Ok(response)
+}
+
+fn compute_slice(slice: &tangram_core::model::Slice) -> Slice {
+	let column_name = &slice.column_name;
+	let description = match &slice.filter {
+		tangram_core::model::SliceFilter::Invalid => format!("{} is invalid", column_name),
+		tangram_core::model::SliceFilter::Enum(option) => {
+			format!("{} is {}", column_name, option)
+		}
+		tangram_core::model::SliceFilter::Number(filter) => match (filter.start, filter.end) {
+			(None, None) => format!("{} is valid", column_name),
+			(None, Some(end)) => format!("{} <= {}", column_name, end),
+			(Some(start), None) => format!("{} > {}", column_name, start),
+			(Some(start), Some(end)) => format!("{} < {} <= {}", start, column_name, end),
+		},
+	};
+	Slice {
+		column_name: slice.column_name.clone(),
+		description,
+		n_examples: slice.n_examples,
+		value: slice.value,
+		confidence_interval_lower: slice.confidence_interval_lower,
+		confidence_interval_upper: slice.confidence_interval_upper,
+	}
+}
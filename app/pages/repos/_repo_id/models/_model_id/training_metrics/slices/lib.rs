@@ -0,0 +1,4 @@
+mod get;
+mod page;
+
+pub use get::get;
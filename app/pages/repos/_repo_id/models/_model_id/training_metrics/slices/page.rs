@@ -0,0 +1,185 @@
+use tangram_app_layouts::{
+	document::PageInfo,
+	model_layout::{ModelLayout, ModelLayoutInfo, ModelSideNavItem},
+};
+use tangram_deps::html::{self, component, html};
+use tangram_ui as ui;
+
+pub struct Props {
+	pub id: String,
+	pub task: Task,
+	pub metric: SliceMetric,
+	/// This is the value of the metric on the whole test dataset.
+	pub overall_value: f32,
+	pub worst_slices: Vec<Slice>,
+	pub slices: Vec<Slice>,
+	pub model_layout_info: ModelLayoutInfo,
+}
+
+#[derive(Clone, Copy)]
+pub enum Task {
+	Regressor,
+	BinaryClassifier,
+	MulticlassClassifier,
+}
+
+#[derive(Clone, Copy)]
+pub enum SliceMetric {
+	Accuracy,
+	MeanAbsoluteError,
+}
+
+#[derive(Clone)]
+pub struct Slice {
+	pub column_name: String,
+	pub description: String,
+	pub n_examples: u64,
+	pub value: f32,
+	pub confidence_interval_lower: f32,
+	pub confidence_interval_upper: f32,
+}
+
+pub fn render(props: Props, page_info: PageInfo) -> String {
+	let (metric_name, description) = match props.metric {
+		SliceMetric::Accuracy => (
+			"Accuracy",
+			format!("Each slice is the test examples with one option of an enum column, the test examples in one quartile of a number column as computed on the train dataset, or the test examples with an invalid value for either. These are the slices with the lowest accuracy, out of the slices with at least 10 test examples. The accuracy on the whole test dataset is {}.", ui::format_percent(props.overall_value)),
+		),
+		SliceMetric::MeanAbsoluteError => (
+			"Mean Absolute Error",
+			format!("Each slice is the test examples with one option of an enum column, the test examples in one quartile of a number column as computed on the train dataset, or the test examples with an invalid value for either. These are the slices with the highest mean absolute error, out of the slices with at least 10 test examples. The mean absolute error on the whole test dataset is {}.", ui::format_number(props.overall_value)),
+		),
+	};
+	let worst_slices = if props.worst_slices.is_empty() {
+		html! {
+			<ui::P>{"There are no slices with at least 10 test examples."}</ui::P>
+		}
+	} else {
+		html! {
+			<SlicesTable
+				metric={props.metric}
+				metric_name={metric_name.to_owned()}
+				slices={props.worst_slices}
+			/>
+		}
+	};
+	let html = html! {
+		<ModelLayout
+			info={props.model_layout_info}
+			page_info={page_info}
+			selected_item={ModelSideNavItem::TrainingMetrics}
+		>
+			<ui::S1>
+				<ui::H1 center={false}>{"Training Metrics"}</ui::H1>
+				<TrainingMetricsTabBar task={props.task} />
+				<ui::S2>
+					<ui::H2 center={false}>{"Worst Slices"}</ui::H2>
+					<ui::P>{description}</ui::P>
+					<ui::P>
+						{"The confidence interval is the range that the slice's true value is in with 95% probability. Slices with wide intervals have too few examples for their value to be reliable."}
+					</ui::P>
+					{worst_slices}
+				</ui::S2>
+				<ui::S2>
+					<ui::H2 center={false}>{"All Slices"}</ui::H2>
+					<SlicesTable
+						metric={props.metric}
+						metric_name={metric_name.to_owned()}
+						slices={props.slices}
+					/>
+				</ui::S2>
+			</ui::S1>
+		</ModelLayout>
+	};
+	html.render_to_string()
+}
+
+#[component]
+fn TrainingMetricsTabBar(task: Task) {
+	let tabs: Vec<(&str, &str)> = match task {
+		Task::Regressor => vec![("./", "Overview")],
+		Task::BinaryClassifier => vec![
+			("./", "Overview"),
+			("precision_recall", "PR Curve"),
+			("roc", "ROC Curve"),
+			("fairness", "Fairness"),
+		],
+		Task::MulticlassClassifier => vec![("./", "Overview"), ("class_metrics", "Class Metrics")],
+	};
+	html! {
+		<ui::TabBar>
+			{tabs.into_iter().map(|(href, title)| html! {
+				<ui::TabLink
+					disabled={None}
+					href={href.to_owned()}
+					selected={false}
+				>
+					{title}
+				</ui::TabLink>
+			}).collect::<Vec<_>>()}
+			<ui::TabLink
+				disabled={None}
+				href={"slices".to_owned()}
+				selected={true}
+			>
+				{"Slices"}
+			</ui::TabLink>
+		</ui::TabBar>
+	}
+}
+
+#[component]
+fn SlicesTable(metric: SliceMetric, metric_name: String, slices: Vec<Slice>) {
+	let format_value = |value: f32| match metric {
+		SliceMetric::Accuracy => ui::format_percent(value),
+		SliceMetric::MeanAbsoluteError => ui::format_number(value),
+	};
+	html! {
+		<ui::Table width={Some("100%".to_owned())}>
+			<ui::TableHeader>
+				<ui::TableRow color={None}>
+					<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+						{"Column"}
+					</ui::TableHeaderCell>
+					<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+						{"Slice"}
+					</ui::TableHeaderCell>
+					<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+						{"Test Examples"}
+					</ui::TableHeaderCell>
+					<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+						{metric_name}
+					</ui::TableHeaderCell>
+					<ui::TableHeaderCell color={None} text_align={None} expand={None}>
+						{"95% Confidence Interval"}
+					</ui::TableHeaderCell>
+				</ui::TableRow>
+			</ui::TableHeader>
+			<ui::TableBody>
+				{slices.into_iter().map(|slice| html! {
+					<ui::TableRow color={None}>
+						<ui::TableCell color={None} expand={None}>
+							{slice.column_name}
+						</ui::TableCell>
+						<ui::TableCell color={None} expand={None}>
+							{slice.description}
+						</ui::TableCell>
+						<ui::TableCell color={None} expand={None}>
+							{slice.n_examples.to_string()}
+						</ui::TableCell>
+						<ui::TableCell color={None} expand={None}>
+							{format_value(slice.value)}
+						</ui::TableCell>
+						<ui::TableCell color={None} expand={None}>
+							{format!(
+								"{} to {}",
+								format_value(slice.confidence_interval_lower),
+								format_value(slice.confidence_interval_upper),
+							)}
+						</ui::TableCell>
+					</ui::TableRow>
+				}).collect::<Vec<_>>()}
+			</ui::TableBody>
+		</ui::Table>
+	}
+}
@@ -1,4 +1,6 @@
-use tangram_charts::{bar_chart::BarChart, box_chart::BoxChart, hydrate_chart};
+use tangram_charts::{
+	bar_chart::BarChart, box_chart::BoxChart, hydrate_chart, line_chart::LineChart,
+};
 use wasm_bindgen::prelude::*;
 use web_sys::window;
 
@@ -19,4 +21,7 @@ pub fn start() {
 	if document.get_element_by_id("token_histogram").is_some() {
 		hydrate_chart::<BarChart>("token_histogram");
 	}
+	if document.get_element_by_id("partial_dependence").is_some() {
+		hydrate_chart::<LineChart>("partial_dependence");
+	}
 }
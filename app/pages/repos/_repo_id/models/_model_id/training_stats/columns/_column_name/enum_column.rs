@@ -1,4 +1,5 @@
 use super::page::EnumProps;
+use super::partial_dependence::PartialDependence;
 use tangram_app_common::metrics_row::MetricsRow;
 use tangram_charts::{
 	bar_chart::{BarChartPoint, BarChartSeries},
@@ -30,9 +31,12 @@ pub fn EnumColumn(props: EnumProps) {
 					</ui::Card>
 				</MetricsRow>
 				{props.histogram.map(|histogram| html! {
-					<EnumColumnHistogram name={name} histogram />
+					<EnumColumnHistogram name={name.clone()} histogram />
 				})}
 			</ui::S2>
+			{props.partial_dependence.map(|partial_dependence| html! {
+				<PartialDependence column_name={name} props={partial_dependence} />
+			})}
 		</ui::S1>
 	}
 }
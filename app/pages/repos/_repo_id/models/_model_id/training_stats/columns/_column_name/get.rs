@@ -1,4 +1,8 @@
-use super::page::{render, EnumProps, Inner, NumberProps, Props, TextProps, TokenStats};
+use super::page::{
+	render, EnumProps, Inner, NumberProps, PartialDependenceProps, PartialDependenceValues, Props,
+	TextProps, TokenStats,
+};
+use std::collections::BTreeMap;
 use tangram_app_common::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
 	model::get_model,
@@ -16,6 +20,7 @@ pub async fn get(
 	request: http::Request<hyper::Body>,
 	model_id: &str,
 	column_name: &str,
+	search_params: Option<BTreeMap<String, String>>,
 ) -> Result<http::Response<hyper::Body>> {
 	let mut db = match context.pool.begin().await {
 		Ok(db) => db,
@@ -33,20 +38,74 @@ pub async fn get(
 		return Ok(not_found());
 	}
 	let model = get_model(&mut db, model_id).await?;
-	let (mut column_stats, target_column_stats) = match model {
+	let class = search_params.and_then(|s| s.get("class").map(|class| class.to_owned()));
+	let (
+		mut column_stats,
+		target_column_stats,
+		target_column_name,
+		partial_dependences,
+		class,
+		classes,
+	) = match model {
 		tangram_core::model::Model::Regressor(model) => (
 			model.overall_column_stats,
 			model.overall_target_column_stats,
+			model.target_column_name,
+			model.partial_dependences,
+			None,
+			None,
 		),
 		tangram_core::model::Model::BinaryClassifier(model) => (
 			model.overall_column_stats,
 			model.overall_target_column_stats,
+			model.target_column_name,
+			model.partial_dependences,
+			Some(model.positive_class),
+			None,
 		),
-		tangram_core::model::Model::MulticlassClassifier(model) => (
-			model.overall_column_stats,
-			model.overall_target_column_stats,
-		),
+		tangram_core::model::Model::MulticlassClassifier(model) => {
+			let class_index = match class {
+				Some(class) => match model.classes.iter().position(|c| *c == class) {
+					Some(class_index) => class_index,
+					None => return Ok(bad_request()),
+				},
+				None => 0,
+			};
+			let partial_dependences = model
+				.partial_dependences
+				.and_then(|partial_dependences| partial_dependences.into_iter().nth(class_index));
+			let class = model.classes[class_index].clone();
+			(
+				model.overall_column_stats,
+				model.overall_target_column_stats,
+				model.target_column_name,
+				partial_dependences,
+				Some(class),
+				Some(model.classes),
+			)
+		}
 	};
+	let partial_dependence = partial_dependences
+		.and_then(|partial_dependences| {
+			partial_dependences
+				.into_iter()
+				.find(|partial_dependence| partial_dependence.column_name == column_name)
+		})
+		.map(|partial_dependence| PartialDependenceProps {
+			class,
+			classes,
+			target_column_name,
+			values: match partial_dependence.values {
+				tangram_core::model::PartialDependenceValues::Number(values) => {
+					PartialDependenceValues::Number(values)
+				}
+				tangram_core::model::PartialDependenceValues::Enum(options) => {
+					PartialDependenceValues::Enum(options)
+				}
+			},
+			mean_predictions: partial_dependence.mean_predictions,
+			example_predictions: partial_dependence.example_predictions,
+		});
 	let column_index = column_stats
 		.iter()
 		.position(|column_stats| column_stats.column_name() == column_name);
@@ -74,12 +133,14 @@ pub async fn get(
 			p75: column.p75,
 			std: column.std,
 			unique_count: column.unique_count,
+			partial_dependence,
 		}),
 		tangram_core::model::ColumnStats::Enum(column) => Inner::Enum(EnumProps {
 			histogram: Some(column.histogram),
 			invalid_count: column.invalid_count,
 			name: column.column_name.clone(),
 			unique_count: column.unique_count,
+			partial_dependence,
 		}),
 		tangram_core::model::ColumnStats::Text(column) => {
 			let n_tokens = column.top_tokens.len();
@@ -2,6 +2,7 @@ mod enum_column;
 mod get;
 mod number_column;
 mod page;
+mod partial_dependence;
 mod text_column;
 
 pub use get::get;
@@ -1,4 +1,5 @@
 use super::page::NumberProps;
+use super::partial_dependence::PartialDependence;
 use tangram_app_common::metrics_row::MetricsRow;
 use tangram_charts::{
 	box_chart::BoxChartPoint,
@@ -26,6 +27,7 @@ pub fn NumberColumn(props: NumberProps) {
 		}],
 		title: Some("quartiles".to_owned()),
 	}];
+	let name = props.name.clone();
 	html! {
 		<ui::S1>
 			<ui::H1 center={None}>{props.name.clone()}</ui::H1>
@@ -106,6 +108,9 @@ pub fn NumberColumn(props: NumberProps) {
 				/>
 				</ui::Card>
 			</ui::S2>
+			{props.partial_dependence.map(|partial_dependence| html! {
+				<PartialDependence column_name={name} props={partial_dependence} />
+			})}
 		</ui::S1>
 	}
 }
@@ -30,6 +30,7 @@ pub struct NumberProps {
 	pub p75: f32,
 	pub std: f32,
 	pub unique_count: u64,
+	pub partial_dependence: Option<PartialDependenceProps>,
 }
 
 #[derive(Clone)]
@@ -38,6 +39,25 @@ pub struct EnumProps {
 	pub invalid_count: u64,
 	pub name: String,
 	pub unique_count: u64,
+	pub partial_dependence: Option<PartialDependenceProps>,
+}
+
+#[derive(Clone)]
+pub struct PartialDependenceProps {
+	/// This is the class whose predicted probability is shown, which is `None` for regressors.
+	pub class: Option<String>,
+	/// This is `Some` only for multiclass classifiers, which show a select field to choose the class.
+	pub classes: Option<Vec<String>>,
+	pub target_column_name: String,
+	pub values: PartialDependenceValues,
+	pub mean_predictions: Vec<f32>,
+	pub example_predictions: Vec<Vec<f32>>,
+}
+
+#[derive(Clone)]
+pub enum PartialDependenceValues {
+	Number(Vec<f32>),
+	Enum(Vec<String>),
 }
 
 #[derive(Clone)]
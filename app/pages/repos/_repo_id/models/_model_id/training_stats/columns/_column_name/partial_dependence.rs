@@ -0,0 +1,121 @@
+use super::page::{PartialDependenceProps, PartialDependenceValues};
+use tangram_app_common::class_select_field::ClassSelectField;
+use tangram_charts::{
+	common::GridLineInterval,
+	components::LineChart,
+	line_chart::{LineChartPoint, LineChartSeries, LineStyle, PointStyle},
+};
+use tangram_deps::html::{self, component, html};
+use tangram_deps::num_traits::ToPrimitive;
+use tangram_ui as ui;
+
+#[component]
+pub fn PartialDependence(column_name: String, props: PartialDependenceProps) {
+	let (x_values, labels): (Vec<f64>, Option<Vec<String>>) = match props.values {
+		PartialDependenceValues::Number(values) => (
+			values.iter().map(|value| value.to_f64().unwrap()).collect(),
+			None,
+		),
+		PartialDependenceValues::Enum(options) => (
+			(0..options.len())
+				.map(|index| index.to_f64().unwrap())
+				.collect(),
+			Some(options),
+		),
+	};
+	let x_axis_grid_line_interval = labels.as_ref().map(|_| GridLineInterval { k: 1.0, p: 0.0 });
+	let to_points = |predictions: &[f32]| {
+		x_values
+			.iter()
+			.zip(predictions.iter())
+			.map(|(x, prediction)| LineChartPoint {
+				x: *x,
+				y: prediction.to_f64().unwrap(),
+			})
+			.collect::<Vec<_>>()
+	};
+	// Draw the individual conditional expectations first so the partial dependence is drawn on top of them. Only the first of them has a title so the legend has a single entry for all of them.
+	let mut series = props
+		.example_predictions
+		.iter()
+		.enumerate()
+		.map(|(index, predictions)| LineChartSeries {
+			color: ui::colors::GRAY.to_owned(),
+			data: to_points(predictions),
+			line_style: Some(LineStyle::Solid),
+			point_style: Some(PointStyle::Hidden),
+			title: if index == 0 {
+				Some("Individual Conditional Expectation".to_owned())
+			} else {
+				None
+			},
+		})
+		.collect::<Vec<_>>();
+	series.push(LineChartSeries {
+		color: ui::colors::BLUE.to_owned(),
+		data: to_points(&props.mean_predictions),
+		line_style: Some(LineStyle::Solid),
+		point_style: Some(PointStyle::Circle),
+		title: Some("Partial Dependence".to_owned()),
+	});
+	let y_axis_title = match &props.class {
+		None => format!("Predicted {}", props.target_column_name),
+		Some(class) => format!("Probability of {}", class),
+	};
+	let (y_min, y_max) = match &props.class {
+		None => (None, None),
+		Some(_) => (Some(0.0), Some(1.0)),
+	};
+	let class = props.class;
+	html! {
+		<ui::S2>
+			<ui::H2 center={None}>{"Partial Dependence"}</ui::H2>
+			<ui::P>
+				{format!("The partial dependence is the model's average prediction for a sample of examples from the test dataset when {} is set to each value and every other column keeps its value. Each individual conditional expectation is the model's prediction for one of the examples.", column_name)}
+			</ui::P>
+			{props.classes.map(|classes| html! {
+				<ui::Form
+					enc_type={None}
+					post={None}
+					id={None}
+					action={None}
+					autocomplete={None}
+				>
+					<ClassSelectField class={class.unwrap()} classes={classes} />
+					<noscript>
+						<ui::Button
+							color={None}
+							disabled={None}
+							button_type={ui::ButtonType::Submit}
+							download={None}
+							href={None}
+							id={None}
+						>
+							{"Submit"}
+						</ui::Button>
+					</noscript>
+				</ui::Form>
+			})}
+			<ui::Card>
+				<LineChart
+					class={None}
+					hide_legend={None}
+					id={"partial_dependence".to_owned()}
+					labels={labels}
+					series={series}
+					should_draw_x_axis_labels={None}
+					should_draw_y_axis_labels={None}
+					title={format!("Partial Dependence on {}", column_name)}
+					x_axis_grid_line_interval={x_axis_grid_line_interval}
+					x_axis_title={column_name}
+					x_max={None}
+					x_min={None}
+					y_axis_grid_line_interval={None}
+					y_axis_title={y_axis_title}
+					y_max={y_max}
+					y_min={y_min}
+				/>
+			</ui::Card>
+		</ui::S2>
+	}
+}
@@ -36,6 +36,7 @@ pub fn app(args: AppArgs) -> Result<()> {
 		database_max_connections: args.database_max_connections,
 		host: args.host,
 		port: args.port,
+		production_feature_contributions_enabled: args.production_feature_contributions_enabled,
 		sendgrid_api_token: args.sendgrid_api_token,
 		stripe_publishable_key: args.stripe_publishable_key,
 		stripe_secret_key: args.stripe_secret_key,
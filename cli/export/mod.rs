@@ -0,0 +1,21 @@
+use crate::{ExportArgs, ExportFormat};
+use tangram_core::{export, model};
+use tangram_util::error::Result;
+
+pub fn export(args: ExportArgs) -> Result<()> {
+	let model = model::Model::from_path(&args.model)?;
+	let (data, extension) = match args.format {
+		ExportFormat::Onnx => (export::onnx::export(&model)?, "onnx"),
+		ExportFormat::Lightgbm => (export::lightgbm::export(&model)?.into_bytes(), "txt"),
+		ExportFormat::Xgboost => (export::xgboost::export(&model)?.into_bytes(), "json"),
+		ExportFormat::Pmml => (export::pmml::export(&model)?.into_bytes(), "pmml"),
+	};
+	// Retrieve the output path from the command line arguments or write the exported model next to the .tangram file.
+	let output_path = match args.output {
+		Some(output) => output,
+		None => args.model.with_extension(extension),
+	};
+	std::fs::write(&output_path, data)?;
+	eprintln!("Your model was exported to {}.", output_path.display());
+	Ok(())
+}
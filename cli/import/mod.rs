@@ -0,0 +1,20 @@
+use crate::{ImportArgs, ImportFormat};
+use tangram_core::import::{self, ImportMetadata};
+use tangram_util::error::Result;
+
+pub fn import(args: ImportArgs) -> Result<()> {
+	let text = std::fs::read_to_string(&args.model)?;
+	let metadata: ImportMetadata = serde_json::from_slice(&std::fs::read(&args.metadata)?)?;
+	let model = match args.format {
+		ImportFormat::Lightgbm => import::lightgbm::import(&text, &metadata)?,
+		ImportFormat::Xgboost => import::xgboost::import(&text, &metadata)?,
+	};
+	// Retrieve the output path from the command line arguments or write the .tangram file next to the imported model.
+	let output_path = match args.output {
+		Some(output) => output,
+		None => args.model.with_extension("tangram"),
+	};
+	model.to_file(&output_path)?;
+	eprintln!("Your model was imported to {}.", output_path.display());
+	Ok(())
+}
@@ -0,0 +1,278 @@
+use crate::InspectArgs;
+use std::cmp::Ordering;
+use tangram_core::model;
+use tangram_util::{error::Result, zip};
+
+/// The maximum number of feature importances to print in the summary.
+const MAX_FEATURE_IMPORTANCES: usize = 20;
+
+pub fn inspect(args: InspectArgs) -> Result<()> {
+	let model = model::Model::from_path(&args.model)?;
+	if args.json {
+		// Write the full model as json to stdout.
+		let stdout = std::io::stdout();
+		serde_json::to_writer_pretty(stdout.lock(), &model)?;
+		println!();
+	} else {
+		print_summary(&model);
+	}
+	Ok(())
+}
+
+fn print_summary(model: &model::Model) {
+	match model {
+		model::Model::Regressor(model) => {
+			println!("id: {}", model.id);
+			println!("task: regression");
+			println!("target: {}", model.target_column_name);
+			println!("train rows: {}", model.train_row_count);
+			println!("test rows: {}", model.test_row_count);
+			print_columns(&model.overall_column_stats);
+			let (feature_groups, feature_importances) = match &model.model {
+				model::RegressionModel::Linear(model) => {
+					(&model.feature_groups, &model.feature_importances)
+				}
+				model::RegressionModel::Tree(model) => {
+					(&model.feature_groups, &model.feature_importances)
+				}
+			};
+			print_feature_groups(feature_groups);
+			print_grid_item(&model.grid[model.best_grid_item_index]);
+			println!();
+			println!("test metrics:");
+			println!("  mse: {}", model.test_metrics.mse);
+			println!("  rmse: {}", model.test_metrics.rmse);
+			println!("  mae: {}", model.test_metrics.mae);
+			println!("  r2: {}", model.test_metrics.r2);
+			print_feature_importances(feature_groups, feature_importances);
+		}
+		model::Model::BinaryClassifier(model) => {
+			println!("id: {}", model.id);
+			println!("task: binary classification");
+			println!("target: {}", model.target_column_name);
+			println!("negative class: {}", model.negative_class);
+			println!("positive class: {}", model.positive_class);
+			println!("train rows: {}", model.train_row_count);
+			println!("test rows: {}", model.test_row_count);
+			print_columns(&model.overall_column_stats);
+			let (feature_groups, feature_importances) = match &model.model {
+				model::BinaryClassificationModel::Linear(model) => {
+					(&model.feature_groups, &model.feature_importances)
+				}
+				model::BinaryClassificationModel::Tree(model) => {
+					(&model.feature_groups, &model.feature_importances)
+				}
+			};
+			print_feature_groups(feature_groups);
+			print_grid_item(&model.grid[model.best_grid_item_index]);
+			println!();
+			println!("test metrics:");
+			println!("  auc roc: {}", model.test_metrics.auc_roc);
+			// Print the metrics at the default threshold of 0.5, which is the center threshold.
+			let thresholds = &model.test_metrics.thresholds;
+			if let Some(metrics) = thresholds.get(thresholds.len() / 2) {
+				println!("  threshold: {}", metrics.threshold);
+				println!("  accuracy: {}", metrics.accuracy);
+				println!("  precision: {}", metrics.precision);
+				println!("  recall: {}", metrics.recall);
+				println!("  f1 score: {}", metrics.f1_score);
+			}
+			print_feature_importances(feature_groups, feature_importances);
+		}
+		model::Model::MulticlassClassifier(model) => {
+			println!("id: {}", model.id);
+			println!("task: multiclass classification");
+			println!("target: {}", model.target_column_name);
+			println!("classes: {}", model.classes.join(", "));
+			println!("train rows: {}", model.train_row_count);
+			println!("test rows: {}", model.test_row_count);
+			print_columns(&model.overall_column_stats);
+			let (feature_groups, feature_importances) = match &model.model {
+				model::MulticlassClassificationModel::Linear(model) => {
+					(&model.feature_groups, &model.feature_importances)
+				}
+				model::MulticlassClassificationModel::Tree(model) => {
+					(&model.feature_groups, &model.feature_importances)
+				}
+			};
+			print_feature_groups(feature_groups);
+			print_grid_item(&model.grid[model.best_grid_item_index]);
+			println!();
+			println!("test metrics:");
+			println!("  accuracy: {}", model.test_metrics.accuracy);
+			println!(
+				"  precision (unweighted): {}",
+				model.test_metrics.precision_unweighted
+			);
+			println!(
+				"  precision (weighted): {}",
+				model.test_metrics.precision_weighted
+			);
+			println!(
+				"  recall (unweighted): {}",
+				model.test_metrics.recall_unweighted
+			);
+			println!("  recall (weighted): {}", model.test_metrics.recall_weighted);
+			for (class, class_metrics) in
+				zip!(model.classes.iter(), model.test_metrics.class_metrics.iter())
+			{
+				println!(
+					"  {}: precision {}, recall {}, f1 score {}",
+					class, class_metrics.precision, class_metrics.recall, class_metrics.f1_score,
+				);
+			}
+			print_feature_importances(feature_groups, feature_importances);
+		}
+	}
+}
+
+fn print_columns(column_stats: &[model::ColumnStats]) {
+	println!();
+	println!("columns:");
+	for column_stats in column_stats.iter() {
+		match column_stats {
+			model::ColumnStats::Unknown(column_stats) => {
+				println!("  {}: unknown", column_stats.column_name);
+			}
+			model::ColumnStats::Number(column_stats) => {
+				println!(
+					"  {}: number (min {}, max {}, mean {})",
+					column_stats.column_name, column_stats.min, column_stats.max, column_stats.mean,
+				);
+			}
+			model::ColumnStats::Enum(column_stats) => {
+				println!(
+					"  {}: enum ({} options)",
+					column_stats.column_name,
+					column_stats.histogram.len(),
+				);
+			}
+			model::ColumnStats::Text(column_stats) => {
+				println!("  {}: text", column_stats.column_name);
+			}
+		}
+	}
+}
+
+fn print_feature_groups(feature_groups: &[model::FeatureGroup]) {
+	println!();
+	println!("feature groups:");
+	for feature_group in feature_groups.iter() {
+		match feature_group {
+			model::FeatureGroup::Identity(feature_group) => {
+				println!("  {}: identity", feature_group.source_column_name);
+			}
+			model::FeatureGroup::Normalized(feature_group) => {
+				println!(
+					"  {}: normalized (mean {}, variance {})",
+					feature_group.source_column_name, feature_group.mean, feature_group.variance,
+				);
+			}
+			model::FeatureGroup::OneHotEncoded(feature_group) => {
+				println!(
+					"  {}: one hot encoded ({} options)",
+					feature_group.source_column_name,
+					feature_group.options.len(),
+				);
+			}
+			model::FeatureGroup::BagOfWords(feature_group) => {
+				println!(
+					"  {}: bag of words ({} tokens)",
+					feature_group.source_column_name,
+					feature_group.tokens.len(),
+				);
+			}
+		}
+	}
+}
+
+fn print_grid_item(grid_item: &model::GridItem) {
+	println!();
+	match grid_item {
+		model::GridItem::Linear(grid_item) => {
+			let hyperparameters = &grid_item.hyperparameters;
+			println!("model: linear");
+			println!("  l2 regularization: {}", hyperparameters.l2_regularization);
+			println!("  learning rate: {}", hyperparameters.learning_rate);
+			println!("  max epochs: {}", hyperparameters.max_epochs);
+			println!(
+				"  examples per batch: {}",
+				hyperparameters.n_examples_per_batch
+			);
+			println!(
+				"  comparison metric value: {}",
+				grid_item.model_comparison_metric_value
+			);
+			println!("  training duration: {}s", grid_item.duration);
+		}
+		model::GridItem::Tree(grid_item) => {
+			let hyperparameters = &grid_item.hyperparameters;
+			println!("model: tree");
+			println!("  l2 regularization: {}", hyperparameters.l2_regularization);
+			println!("  learning rate: {}", hyperparameters.learning_rate);
+			match hyperparameters.max_depth {
+				Some(max_depth) => println!("  max depth: {}", max_depth),
+				None => println!("  max depth: none"),
+			}
+			println!("  max leaf nodes: {}", hyperparameters.max_leaf_nodes);
+			println!("  max rounds: {}", hyperparameters.max_rounds);
+			println!(
+				"  comparison metric value: {}",
+				grid_item.model_comparison_metric_value
+			);
+			println!("  training duration: {}s", grid_item.duration);
+		}
+	}
+}
+
+fn print_feature_importances(feature_groups: &[model::FeatureGroup], feature_importances: &[f32]) {
+	let feature_names = compute_feature_names(feature_groups);
+	let mut feature_importances = zip!(feature_names, feature_importances.iter())
+		.map(|(feature_name, feature_importance)| (feature_name, *feature_importance))
+		.collect::<Vec<_>>();
+	feature_importances.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+	let n_features = feature_importances.len();
+	println!();
+	if n_features > MAX_FEATURE_IMPORTANCES {
+		println!(
+			"feature importances (top {} of {}):",
+			MAX_FEATURE_IMPORTANCES, n_features
+		);
+	} else {
+		println!("feature importances:");
+	}
+	for (feature_name, feature_importance) in
+		feature_importances.iter().take(MAX_FEATURE_IMPORTANCES)
+	{
+		println!("  {}: {}", feature_name, feature_importance);
+	}
+}
+
+fn compute_feature_names(feature_groups: &[model::FeatureGroup]) -> Vec<String> {
+	feature_groups
+		.iter()
+		.flat_map(|feature_group| match feature_group {
+			model::FeatureGroup::Identity(feature_group) => {
+				vec![feature_group.source_column_name.clone()]
+			}
+			model::FeatureGroup::Normalized(feature_group) => {
+				vec![feature_group.source_column_name.clone()]
+			}
+			model::FeatureGroup::OneHotEncoded(feature_group) => vec!["OOV".to_owned()]
+				.iter()
+				.chain(feature_group.options.iter())
+				.map(|option| format!("{} = {}", feature_group.source_column_name, option))
+				.collect(),
+			model::FeatureGroup::BagOfWords(feature_group) => feature_group
+				.tokens
+				.iter()
+				.map(|token| {
+					format!(
+						"{} contains {}",
+						feature_group.source_column_name, token.token
+					)
+				})
+				.collect(),
+		})
+		.collect()
+}
@@ -7,6 +7,12 @@ use url::Url;
 
 #[cfg(feature = "app")]
 mod app;
+#[cfg(feature = "export")]
+mod export;
+#[cfg(feature = "import")]
+mod import;
+#[cfg(feature = "inspect")]
+mod inspect;
 #[cfg(feature = "train")]
 mod train;
 
@@ -19,6 +25,15 @@ enum Args {
 	#[cfg(feature = "train")]
 	#[clap(name = "train")]
 	Train(Box<TrainArgs>),
+	#[cfg(feature = "inspect")]
+	#[clap(name = "inspect")]
+	Inspect(Box<InspectArgs>),
+	#[cfg(feature = "export")]
+	#[clap(name = "export")]
+	Export(Box<ExportArgs>),
+	#[cfg(feature = "import")]
+	#[clap(name = "import")]
+	Import(Box<ImportArgs>),
 	#[cfg(feature = "app")]
 	#[clap(name = "app")]
 	App(Box<AppArgs>),
@@ -53,6 +68,72 @@ pub struct TrainArgs {
 	progress: bool,
 }
 
+#[cfg(feature = "inspect")]
+#[derive(Clap)]
+#[clap(about = "inspect a model")]
+#[clap(long_about = "print a summary of a .tangram file, or dump the full model as json")]
+pub struct InspectArgs {
+	#[clap(about = "the path to your .tangram file")]
+	model: PathBuf,
+	#[clap(long, about = "print the full model as json")]
+	json: bool,
+}
+
+#[cfg(feature = "export")]
+#[derive(Clap)]
+#[clap(about = "export a model")]
+#[clap(long_about = "convert a .tangram file to a format other machine learning tools can load")]
+pub struct ExportArgs {
+	#[clap(about = "the path to your .tangram file")]
+	model: PathBuf,
+	#[clap(long, arg_enum, about = "the format to export to")]
+	format: ExportFormat,
+	#[clap(short, long, about = "the path to write the exported model to")]
+	output: Option<PathBuf>,
+}
+
+#[cfg(feature = "export")]
+#[derive(Clap, Clone, Copy)]
+pub enum ExportFormat {
+	#[clap(name = "onnx")]
+	Onnx,
+	#[clap(name = "lightgbm")]
+	Lightgbm,
+	#[clap(name = "xgboost")]
+	Xgboost,
+	#[clap(name = "pmml")]
+	Pmml,
+}
+
+#[cfg(feature = "import")]
+#[derive(Clap)]
+#[clap(about = "import a model")]
+#[clap(
+	long_about = "convert a model trained with another machine learning tool to a .tangram file"
+)]
+pub struct ImportArgs {
+	#[clap(about = "the path to the model to import")]
+	model: PathBuf,
+	#[clap(long, arg_enum, about = "the format of the model to import")]
+	format: ImportFormat,
+	#[clap(
+		long,
+		about = "the path to a .json file describing the model's target and columns"
+	)]
+	metadata: PathBuf,
+	#[clap(short, long, about = "the path to write the .tangram file to")]
+	output: Option<PathBuf>,
+}
+
+#[cfg(feature = "import")]
+#[derive(Clap, Clone, Copy)]
+pub enum ImportFormat {
+	#[clap(name = "lightgbm")]
+	Lightgbm,
+	#[clap(name = "xgboost")]
+	Xgboost,
+}
+
 #[cfg(feature = "app")]
 #[derive(Clap)]
 #[clap(about = "run the app")]
@@ -70,6 +151,12 @@ pub struct AppArgs {
 	host: std::net::IpAddr,
 	#[clap(long, env = "PORT", default_value = "8080")]
 	port: u16,
+	#[clap(
+		long = "production-feature-contributions",
+		env = "PRODUCTION_FEATURE_CONTRIBUTIONS",
+		takes_value = false
+	)]
+	production_feature_contributions_enabled: bool,
 	#[clap(long, env = "SENDGRID_API_TOKEN")]
 	sendgrid_api_token: Option<String>,
 	#[clap(long, env = "LICENSE")]
@@ -87,6 +174,12 @@ fn main() {
 	let result = match args {
 		#[cfg(feature = "train")]
 		Args::Train(args) => self::train::train(*args),
+		#[cfg(feature = "inspect")]
+		Args::Inspect(args) => self::inspect::inspect(*args),
+		#[cfg(feature = "export")]
+		Args::Export(args) => self::export::export(*args),
+		#[cfg(feature = "import")]
+		Args::Import(args) => self::import::import(*args),
 		#[cfg(feature = "app")]
 		Args::App(args) => self::app::app(*args),
 	};
@@ -0,0 +1,146 @@
+# This script checks that models exported to ONNX make the same predictions as tangram. For each task it generates a dataset with a number column that has missing values, an enum column that has invalid values in the test data, and a text column with mixed case and punctuation, so the exported normalization, one hot encoding, and bag of words preprocessing are all exercised. It trains a linear and a tree model on each dataset with the tangram cli, exports them with `tangram export --format onnx`, and compares the predictions onnxruntime makes on the test data with the predictions tangram makes natively with the libtangram predict example.
+import argparse
+import csv
+import json
+import numpy as np
+import onnxruntime
+import random
+import subprocess
+import tempfile
+
+tasks = {
+	'regression': None,
+	'binary_classification': ['no', 'yes'],
+	'multiclass_classification': ['low', 'medium', 'high'],
+}
+
+parser = argparse.ArgumentParser()
+parser.add_argument('--tasks', nargs='+', choices=list(tasks.keys()), default=list(tasks.keys()))
+parser.add_argument('--models', nargs='+', choices=['linear', 'tree'], default=['linear', 'tree'])
+parser.add_argument('--tangram', default='tangram', help='the path to the tangram cli')
+parser.add_argument('--predict', default='cargo run --release --quiet -p libtangram --example predict --', help='the command that prints tangram\'s predictions for a model and an input file')
+args = parser.parse_args()
+
+tolerance = 1e-4
+n_train = 1000
+n_test = 200
+words = ['red', 'green', 'blue', 'fast', 'slow', 'big', 'small']
+
+def generate(task, n, invalid_enum_fraction, rng):
+	rows = []
+	for _ in range(n):
+		number = rng.uniform(-10, 10)
+		if rng.random() < invalid_enum_fraction:
+			enum = 'invalid'
+		else:
+			enum = rng.choice(['a', 'b', 'c'])
+		tokens = [rng.choice(words) for _ in range(rng.randint(1, 5))]
+		text = ' '.join(token.upper() if rng.random() < 0.2 else token for token in tokens)
+		if rng.random() < 0.3:
+			text += rng.choice(['!', '.', ', ok'])
+		score = 0.3 * number + {'a': 0.0, 'b': 2.0, 'c': -2.0}.get(enum, 0.0)
+		score += 1.5 * ('red' in tokens) - 1.5 * any(a == 'slow' and b == 'big' for a, b in zip(tokens, tokens[1:]))
+		score += rng.gauss(0, 0.5)
+		if task == 'regression':
+			target = '{:.4f}'.format(score)
+		elif task == 'binary_classification':
+			target = 'yes' if score > 0 else 'no'
+		else:
+			target = 'low' if score < -1 else 'medium' if score < 1 else 'high'
+		rows.append({
+			'number': '' if rng.random() < 0.1 else '{:.4f}'.format(number),
+			'enum': enum,
+			'text': text,
+			'target': target,
+		})
+	return rows
+
+def write_csv(path, rows):
+	with open(path, 'w', newline='') as file:
+		writer = csv.DictWriter(file, fieldnames=['number', 'enum', 'text', 'target'])
+		writer.writeheader()
+		writer.writerows(rows)
+
+def number(value):
+	try:
+		return float(value)
+	except ValueError:
+		return None
+
+def check(task, model_type):
+	classes = tasks[task]
+	rng = random.Random(0)
+	data_train = generate(task, n_train, 0.0, rng)
+	data_test = generate(task, n_test, 0.05, rng)
+	with tempfile.TemporaryDirectory() as directory:
+		# Train and export the model.
+		path_train = '{}/train.csv'.format(directory)
+		path_test = '{}/test.csv'.format(directory)
+		config_path = '{}/config.json'.format(directory)
+		model_path = '{}/model.tangram'.format(directory)
+		onnx_path = '{}/model.onnx'.format(directory)
+		input_path = '{}/input.json'.format(directory)
+		write_csv(path_train, data_train)
+		write_csv(path_test, data_test)
+		column_types = {
+			'number': {'type': 'number'},
+			'enum': {'type': 'enum', 'options': ['a', 'b', 'c']},
+			'text': {'type': 'text'},
+			'target': {'type': 'number'} if classes is None else {'type': 'enum', 'options': classes},
+		}
+		with open(config_path, 'w') as config_file:
+			json.dump({'column_types': column_types, 'grid': [{'model': model_type}]}, config_file)
+		subprocess.run([args.tangram, 'train', '--file-train', path_train, '--file-test', path_test, '--target', 'target', '--config', config_path, '--output', model_path, '--no-progress'], check=True)
+		subprocess.run([args.tangram, 'export', model_path, '--format', 'onnx', '--output', onnx_path], check=True)
+		session = onnxruntime.InferenceSession(onnx_path)
+
+		# Make predictions with tangram.
+		with open(input_path, 'w') as input_file:
+			json.dump([
+				{'number': number(row['number']), 'enum': row['enum'], 'text': row['text']}
+				for row in data_test
+			], input_file)
+		predict_output = subprocess.run(args.predict.split() + [model_path, input_path], check=True, stdout=subprocess.PIPE)
+		outputs = json.loads(predict_output.stdout)
+
+		# Make predictions with onnxruntime.
+		inputs = {}
+		for input in session.get_inputs():
+			values = [row[input.name] for row in data_test]
+			if input.type == 'tensor(float)':
+				values = np.array([number(value) if number(value) is not None else np.nan for value in values], dtype=np.float32)
+			else:
+				values = np.array(values, dtype=object)
+			inputs[input.name] = values.reshape(-1, 1)
+		output_names = [output.name for output in session.get_outputs()]
+		onnx_outputs = dict(zip(output_names, session.run(output_names, inputs)))
+
+		# Compare the predictions.
+		if task == 'regression':
+			expected = np.array([output['value'] for output in outputs], dtype=np.float32)
+			actual = onnx_outputs['value'][:, 0]
+			max_difference = float(np.max(np.abs(expected - actual)))
+			label_mismatches = 0
+		else:
+			if task == 'binary_classification':
+				expected = np.array([
+					[1.0 - output['probability'], output['probability']] if output['className'] == classes[1] else [output['probability'], 1.0 - output['probability']]
+					for output in outputs
+				], dtype=np.float32)
+			else:
+				expected = np.array([[output['probabilities'][class_name] for class_name in classes] for output in outputs], dtype=np.float32)
+			actual = onnx_outputs['probabilities']
+			max_difference = float(np.max(np.abs(expected - actual)))
+			label_mismatches = sum(1 for output, label in zip(outputs, onnx_outputs['label']) if output['className'] != label)
+		return {
+			'task': task,
+			'model': model_type,
+			'max_difference': max_difference,
+			'label_mismatches': label_mismatches,
+			'ok': max_difference <= tolerance and label_mismatches == 0,
+		}
+
+results = [check(task, model_type) for task in args.tasks for model_type in args.models]
+print(json.dumps(results, indent=2))
+if not all(result['ok'] for result in results):
+	exit(1)
@@ -12,6 +12,9 @@ pub struct Config {
 	pub shuffle: Option<Shuffle>,
 	pub comparison_metric: Option<ComparisonMetric>,
 	pub text_features_max_tokens_count: Option<usize>,
+	pub fairness: Option<FairnessConfig>,
+	pub confidence_intervals: Option<ConfidenceIntervalsConfig>,
+	pub prediction_intervals: Option<PredictionIntervalsConfig>,
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -27,6 +30,40 @@ pub enum ColumnType {
 	Text,
 }
 
+/// Set the fairness config to slice a binary classifier's test metrics by the options of a sensitive enum column.
+#[derive(Debug, serde::Deserialize)]
+pub struct FairnessConfig {
+	pub sensitive_column_name: String,
+	/// If true, the sensitive column is not used to compute features. The default is false.
+	pub exclude_from_features: Option<bool>,
+}
+
+/// Set the confidence intervals config to compute 95% confidence intervals for the test metrics by bootstrapping the test dataset.
+#[derive(Debug, serde::Deserialize)]
+pub struct ConfidenceIntervalsConfig {
+	/// This is the number of bootstrap resamples of the test dataset. The default is 1000.
+	pub n_bootstrap_samples: Option<usize>,
+}
+
+/// Set the prediction intervals config to have a regressor return a lower and upper bound with each prediction. The intervals are calibrated on the model comparison dataset, which is held out from training.
+#[derive(Debug, serde::Deserialize)]
+pub struct PredictionIntervalsConfig {
+	/// This is the method used to compute the intervals. The default is conformal.
+	pub method: Option<PredictionIntervalsMethod>,
+	/// This is the fraction of true values the intervals should contain. It must be between 0 and 1. The default is 0.9.
+	pub coverage: Option<f32>,
+}
+
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+pub enum PredictionIntervalsMethod {
+	/// Split conformal intervals have the same width for every prediction.
+	#[serde(rename = "conformal")]
+	Conformal,
+	/// Quantile intervals come from quantile regressions of the residuals on the prediction, so their width can vary with the prediction.
+	#[serde(rename = "quantile")]
+	Quantile,
+}
+
 #[derive(Debug, serde::Deserialize)]
 #[serde(untagged)]
 pub enum Shuffle {
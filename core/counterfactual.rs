@@ -0,0 +1,635 @@
+/*!
+This module searches for counterfactual explanations, which are small changes to an example's number and enum columns that change the model's prediction to a desired outcome.
+*/
+
+use crate::predict::{predict_dataframe_into, Column, Model};
+use num_traits::ToPrimitive;
+use std::{cmp::Ordering, collections::BTreeSet, num::NonZeroUsize};
+use tangram_util::{err, error::Result};
+
+/// Number columns are tried at this many values evenly spaced between the min and max of the column's values in the training dataset.
+const N_NUMBER_COLUMN_VALUES: usize = 20;
+
+/// After each step of the search, only this many of the combinations of changes that came closest to the desired outcome are extended with another change.
+const BEAM_WIDTH: usize = 10;
+
+#[derive(serde::Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum DesiredOutcome {
+	/// The model should predict `class`. This is only valid for classifiers.
+	#[serde(rename = "class")]
+	Class { class: String },
+	/// The model's prediction should reach `value`, from below if the current prediction is less than `value` and from above otherwise. This is only valid for regressors.
+	#[serde(rename = "value")]
+	Value { value: f32 },
+}
+
+#[derive(serde::Deserialize, Debug)]
+#[serde(default)]
+pub struct CounterfactualOptions {
+	/// These are the names of the columns the search may change. If it is `None`, the search may change any number or enum column.
+	pub mutable_columns: Option<Vec<String>>,
+	/// The search changes at most this many columns.
+	pub max_changed_columns: usize,
+	/// This is the maximum number of counterfactuals returned.
+	pub max_counterfactuals: usize,
+	/// This is the threshold a binary classifier's probability must reach to predict the positive class.
+	pub threshold: f32,
+}
+
+impl Default for CounterfactualOptions {
+	fn default() -> CounterfactualOptions {
+		CounterfactualOptions {
+			mutable_columns: None,
+			max_changed_columns: 3,
+			max_counterfactuals: 5,
+			threshold: 0.5,
+		}
+	}
+}
+
+#[derive(serde::Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Counterfactual {
+	/// These are the changes to the example's columns, which is empty if the model already predicts the desired outcome.
+	pub changes: Vec<CounterfactualChange>,
+	/// The cost is the sum of the costs of the changes. Changing a number column costs the size of the change divided by the range of the column's values in the training dataset, and changing an enum column costs 1.
+	pub cost: f32,
+	/// For a class outcome, this is the probability of the desired class with the changes. For a value outcome, this is the predicted value with the changes.
+	pub output_value: f32,
+}
+
+#[derive(serde::Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CounterfactualChange {
+	pub column_name: String,
+	pub value: serde_json::Value,
+	pub counterfactual_value: serde_json::Value,
+}
+
+/// This is the value of one of the model's columns for the example, parsed into the type of the column.
+#[derive(Clone)]
+enum Value {
+	Unknown,
+	Number(f32),
+	Enum(Option<NonZeroUsize>),
+	Text(String),
+}
+
+/// This is a column the search may change, along with the values it may change it to.
+struct MutableColumn {
+	column_index: usize,
+	candidates: Vec<Candidate>,
+}
+
+struct Candidate {
+	value: Value,
+	json_value: serde_json::Value,
+	cost: f32,
+}
+
+/// A state is a combination of changes, each of which is the index of a mutable column and the index of one of its candidates, sorted by the index of the mutable column.
+#[derive(Clone)]
+struct State {
+	changes: Vec<(usize, usize)>,
+	cost: f32,
+}
+
+struct Evaluation {
+	output_value: f32,
+	/// This is larger the closer the prediction is to the desired outcome.
+	score: f32,
+	achieved: bool,
+}
+
+/// Search for the counterfactuals for `example` that change as few columns as possible, and among those, have the lowest cost. An empty `Vec` is returned if no combination of at most `options.max_changed_columns` changes reaches the desired outcome.
+pub fn compute_counterfactuals(
+	model: &Model,
+	example: &serde_json::Map<String, serde_json::Value>,
+	desired_outcome: DesiredOutcome,
+	options: Option<CounterfactualOptions>,
+) -> Result<Vec<Counterfactual>> {
+	let options = options.unwrap_or_default();
+	let class_index = match (&desired_outcome, model.classes()) {
+		(DesiredOutcome::Class { class }, Some(classes)) => {
+			match classes.iter().position(|c| c == class) {
+				Some(class_index) => Some(class_index),
+				None => return Err(err!("the model has no class named \"{}\"", class)),
+			}
+		}
+		(DesiredOutcome::Value { .. }, None) => None,
+		(DesiredOutcome::Class { .. }, None) => {
+			return Err(err!("a class outcome requires a classifier"))
+		}
+		(DesiredOutcome::Value { .. }, Some(_)) => {
+			return Err(err!("a value outcome requires a regressor"))
+		}
+	};
+	let columns = model.columns();
+	if let Some(mutable_columns) = &options.mutable_columns {
+		for column_name in mutable_columns {
+			match columns.iter().find(|column| column.name() == column_name) {
+				Some(Column::Number(_)) | Some(Column::Enum(_)) => {}
+				Some(_) => {
+					return Err(err!(
+						"the column \"{}\" is not a number or enum column",
+						column_name
+					))
+				}
+				None => return Err(err!("the model has no column named \"{}\"", column_name)),
+			}
+		}
+	}
+	let values = parse_example(columns, example);
+	let mutable_columns = compute_mutable_columns(columns, &values, &options);
+	let initial_state = State {
+		changes: Vec::new(),
+		cost: 0.0,
+	};
+	// For a value outcome, the direction the prediction must move in depends on the current prediction.
+	let target = match desired_outcome {
+		DesiredOutcome::Value { value } => {
+			let prediction =
+				predict_states(model, &values, &mutable_columns, &[initial_state.clone()])?[0];
+			Some((value, prediction < value))
+		}
+		DesiredOutcome::Class { .. } => None,
+	};
+	let evaluate = |states: &[State]| -> Result<Vec<Evaluation>> {
+		let outputs = predict_states(model, &values, &mutable_columns, states)?;
+		let evaluations = outputs
+			.chunks(model.n_outputs())
+			.map(|output| match (target, class_index) {
+				(Some((value, increase)), _) => {
+					let prediction = output[0];
+					if increase {
+						Evaluation {
+							output_value: prediction,
+							score: prediction,
+							achieved: prediction >= value,
+						}
+					} else {
+						Evaluation {
+							output_value: prediction,
+							score: -prediction,
+							achieved: prediction <= value,
+						}
+					}
+				}
+				(None, Some(class_index)) => {
+					let (probability, predicted_class_index) = if output.len() == 1 {
+						// This is a binary classifier, whose single output is the probability of the positive class.
+						let predicted_class_index =
+							if output[0] >= options.threshold { 1 } else { 0 };
+						let probability = if class_index == 1 {
+							output[0]
+						} else {
+							1.0 - output[0]
+						};
+						(probability, predicted_class_index)
+					} else {
+						let predicted_class_index = output
+							.iter()
+							.enumerate()
+							.max_by(|(_, a), (_, b)| compare_nan_lowest(**a, **b))
+							.unwrap()
+							.0;
+						(output[class_index], predicted_class_index)
+					};
+					Evaluation {
+						output_value: probability,
+						score: probability,
+						achieved: predicted_class_index == class_index,
+					}
+				}
+				(None, None) => unreachable!(),
+			})
+			.collect();
+		Ok(evaluations)
+	};
+	let initial_evaluation = evaluate(&[initial_state.clone()])?.remove(0);
+	if initial_evaluation.achieved {
+		return Ok(vec![Counterfactual {
+			changes: Vec::new(),
+			cost: 0.0,
+			output_value: initial_evaluation.output_value,
+		}]);
+	}
+	let mut beam = vec![initial_state];
+	for _ in 0..options.max_changed_columns {
+		// Extend each state in the beam with a change to each column it does not change yet.
+		let mut seen = BTreeSet::new();
+		let mut states = Vec::new();
+		for state in beam.iter() {
+			for (mutable_column_index, mutable_column) in mutable_columns.iter().enumerate() {
+				if state
+					.changes
+					.iter()
+					.any(|(index, _)| *index == mutable_column_index)
+				{
+					continue;
+				}
+				for (candidate_index, candidate) in mutable_column.candidates.iter().enumerate() {
+					let mut changes = state.changes.clone();
+					changes.push((mutable_column_index, candidate_index));
+					changes.sort_unstable();
+					if !seen.insert(changes.clone()) {
+						continue;
+					}
+					states.push(State {
+						changes,
+						cost: state.cost + candidate.cost,
+					});
+				}
+			}
+		}
+		if states.is_empty() {
+			break;
+		}
+		let evaluations = evaluate(&states)?;
+		let (achieved, mut not_achieved): (Vec<_>, Vec<_>) = states
+			.into_iter()
+			.zip(evaluations)
+			.partition(|(_, evaluation)| evaluation.achieved);
+		if !achieved.is_empty() {
+			return Ok(compute_counterfactual_outputs(
+				achieved,
+				&mutable_columns,
+				columns,
+				example,
+				options.max_counterfactuals,
+			));
+		}
+		not_achieved.sort_by(|(_, a), (_, b)| compare_nan_lowest(a.score, b.score).reverse());
+		beam = not_achieved
+			.into_iter()
+			.take(BEAM_WIDTH)
+			.map(|(state, _)| state)
+			.collect();
+	}
+	Ok(Vec::new())
+}
+
+/// Compare two floats, treating `NaN` as less than every other value, so a model that outputs `NaN` never has its output chosen as the best.
+fn compare_nan_lowest(a: f32, b: f32) -> Ordering {
+	match (a.is_nan(), b.is_nan()) {
+		(true, true) => Ordering::Equal,
+		(true, false) => Ordering::Less,
+		(false, true) => Ordering::Greater,
+		(false, false) => a.partial_cmp(&b).unwrap(),
+	}
+}
+
+/// Parse the example's value for each of the model's columns. Values that are missing or invalid are treated as missing, like in `predict`.
+fn parse_example(
+	columns: &[Column],
+	example: &serde_json::Map<String, serde_json::Value>,
+) -> Vec<Value> {
+	columns
+		.iter()
+		.map(|column| match column {
+			Column::Unknown(_) => Value::Unknown,
+			Column::Number(_) => match example.get(column.name()) {
+				Some(serde_json::Value::Number(value)) => {
+					Value::Number(value.as_f64().unwrap().to_f32().unwrap())
+				}
+				_ => Value::Number(std::f32::NAN),
+			},
+			Column::Enum(enum_column) => match example.get(column.name()) {
+				Some(serde_json::Value::String(value)) => Value::Enum(
+					enum_column
+						.options()
+						.iter()
+						.position(|option| option == value)
+						.map(|position| NonZeroUsize::new(position + 1).unwrap()),
+				),
+				_ => Value::Enum(None),
+			},
+			Column::Text(_) => match example.get(column.name()) {
+				Some(serde_json::Value::String(value)) => Value::Text(value.clone()),
+				_ => Value::Text(String::new()),
+			},
+		})
+		.collect()
+}
+
+/// Determine the columns the search may change and the values it may change them to. Number columns whose values in the training dataset were all the same are left out.
+fn compute_mutable_columns(
+	columns: &[Column],
+	values: &[Value],
+	options: &CounterfactualOptions,
+) -> Vec<MutableColumn> {
+	let mut mutable_columns = Vec::new();
+	for (column_index, (column, value)) in columns.iter().zip(values.iter()).enumerate() {
+		if let Some(mutable_columns) = &options.mutable_columns {
+			if !mutable_columns
+				.iter()
+				.any(|column_name| column_name == column.name())
+			{
+				continue;
+			}
+		}
+		let candidates = match (column, value) {
+			(Column::Number(column), Value::Number(value)) => {
+				let (min, max) = (column.min(), column.max());
+				let range = max - min;
+				if !(range > 0.0) {
+					continue;
+				}
+				(0..N_NUMBER_COLUMN_VALUES)
+					.map(|index| {
+						min + range * index.to_f32().unwrap()
+							/ (N_NUMBER_COLUMN_VALUES - 1).to_f32().unwrap()
+					})
+					.filter(|candidate| candidate != value)
+					.map(|candidate| {
+						// Setting a missing value costs as much as changing it across the column's whole range.
+						let cost = if value.is_finite() {
+							(candidate - value).abs() / range
+						} else {
+							1.0
+						};
+						Candidate {
+							value: Value::Number(candidate),
+							json_value: serde_json::json!(candidate),
+							cost,
+						}
+					})
+					.collect()
+			}
+			(Column::Enum(column), Value::Enum(value)) => column
+				.options()
+				.iter()
+				.enumerate()
+				.map(|(index, option)| (NonZeroUsize::new(index + 1).unwrap(), option))
+				.filter(|(candidate, _)| Some(*candidate) != *value)
+				.map(|(candidate, option)| Candidate {
+					value: Value::Enum(Some(candidate)),
+					json_value: serde_json::Value::String(option.clone()),
+					cost: 1.0,
+				})
+				.collect(),
+			_ => continue,
+		};
+		mutable_columns.push(MutableColumn {
+			column_index,
+			candidates,
+		});
+	}
+	mutable_columns
+}
+
+/// Make predictions for the example with each state's changes applied, and return the outputs for each state one after another.
+fn predict_states(
+	model: &Model,
+	values: &[Value],
+	mutable_columns: &[MutableColumn],
+	states: &[State],
+) -> Result<Vec<f32>> {
+	let mut dataframe = model.dataframe();
+	for state in states {
+		let mut values = values.to_owned();
+		for (mutable_column_index, candidate_index) in state.changes.iter() {
+			let mutable_column = &mutable_columns[*mutable_column_index];
+			values[mutable_column.column_index] =
+				mutable_column.candidates[*candidate_index].value.clone();
+		}
+		for (column, value) in dataframe.columns_mut().iter_mut().zip(values) {
+			match (column, value) {
+				(tangram_dataframe::DataFrameColumn::Unknown(column), Value::Unknown) => {
+					*column.len_mut() += 1
+				}
+				(tangram_dataframe::DataFrameColumn::Number(column), Value::Number(value)) => {
+					column.data_mut().push(value)
+				}
+				(tangram_dataframe::DataFrameColumn::Enum(column), Value::Enum(value)) => {
+					column.data_mut().push(value)
+				}
+				(tangram_dataframe::DataFrameColumn::Text(column), Value::Text(value)) => {
+					column.data_mut().push(value)
+				}
+				_ => unreachable!(),
+			}
+		}
+	}
+	let mut outputs = vec![0.0; states.len() * model.n_outputs()];
+	predict_dataframe_into(model, &dataframe.view(), &mut outputs)?;
+	Ok(outputs)
+}
+
+/// Keep the cheapest state for each combination of changed columns, sort them by cost, and describe the changes in each.
+fn compute_counterfactual_outputs(
+	mut achieved: Vec<(State, Evaluation)>,
+	mutable_columns: &[MutableColumn],
+	columns: &[Column],
+	example: &serde_json::Map<String, serde_json::Value>,
+	max_counterfactuals: usize,
+) -> Vec<Counterfactual> {
+	achieved.sort_by(|(a, _), (b, _)| a.cost.partial_cmp(&b.cost).unwrap());
+	let mut seen = BTreeSet::new();
+	achieved
+		.into_iter()
+		.filter(|(state, _)| {
+			let changed_columns = state
+				.changes
+				.iter()
+				.map(|(mutable_column_index, _)| *mutable_column_index)
+				.collect::<Vec<_>>();
+			seen.insert(changed_columns)
+		})
+		.take(max_counterfactuals)
+		.map(|(state, evaluation)| {
+			let changes = state
+				.changes
+				.iter()
+				.map(|(mutable_column_index, candidate_index)| {
+					let mutable_column = &mutable_columns[*mutable_column_index];
+					let column_name = columns[mutable_column.column_index].name().to_owned();
+					let value = example
+						.get(&column_name)
+						.cloned()
+						.unwrap_or(serde_json::Value::Null);
+					CounterfactualChange {
+						column_name,
+						value,
+						counterfactual_value: mutable_column.candidates[*candidate_index]
+							.json_value
+							.clone(),
+					}
+				})
+				.collect();
+			Counterfactual {
+				changes,
+				cost: state.cost,
+				output_value: evaluation.output_value,
+			}
+		})
+		.collect()
+}
+
+/// Train a tree model on a dataset with a number column `x` from 0 to 99 and an enum column `color`, whose target is computed from them by `target`.
+#[cfg(all(test, feature = "train"))]
+fn train_test_model(name: &str, target: impl Fn(usize, &str) -> String) -> Model {
+	use std::{convert::TryFrom, fmt::Write};
+	let directory = std::env::temp_dir().join(format!(
+		"tangram_test_counterfactual_{}_{}",
+		name,
+		std::process::id()
+	));
+	std::fs::create_dir_all(&directory).unwrap();
+	let mut csv = "x,color,target\n".to_owned();
+	for i in 0..400 {
+		let x = i % 100;
+		let color = if i % 3 == 0 { "blue" } else { "red" };
+		writeln!(csv, "{},{},{}", x, color, target(x, color)).unwrap();
+	}
+	let data_path = directory.join("data.csv");
+	let config_path = directory.join("config.json");
+	std::fs::write(&data_path, csv).unwrap();
+	std::fs::write(
+		&config_path,
+		r#"{"column_types":{"x":{"type":"number"}},"grid":[{"model":"tree"}]}"#,
+	)
+	.unwrap();
+	let model = crate::train(
+		tangram_util::id::Id::new(),
+		Some(&data_path),
+		None,
+		None,
+		"target",
+		Some(&config_path),
+		&mut |_| {},
+	)
+	.unwrap();
+	std::fs::remove_dir_all(&directory).unwrap();
+	Model::try_from(model).unwrap()
+}
+
+#[cfg(test)]
+fn test_example(x: f32, color: &str) -> serde_json::Map<String, serde_json::Value> {
+	let mut example = serde_json::Map::new();
+	example.insert("x".to_owned(), serde_json::json!(x));
+	example.insert("color".to_owned(), serde_json::json!(color));
+	example
+}
+
+#[cfg(feature = "train")]
+#[test]
+fn test_counterfactuals_binary_classifier() {
+	let model = train_test_model("binary_classifier", |x, color| {
+		if x >= 50 || color == "blue" {
+			"yes"
+		} else {
+			"no"
+		}
+		.to_owned()
+	});
+	let desired_outcome = || DesiredOutcome::Class {
+		class: "yes".to_owned(),
+	};
+	// Increasing x is cheaper than changing the color, which costs 1.
+	let counterfactuals =
+		compute_counterfactuals(&model, &test_example(10.0, "red"), desired_outcome(), None)
+			.unwrap();
+	assert!(!counterfactuals.is_empty());
+	let counterfactual = &counterfactuals[0];
+	assert_eq!(counterfactual.changes.len(), 1);
+	assert_eq!(counterfactual.changes[0].column_name, "x");
+	assert!(
+		counterfactual.changes[0]
+			.counterfactual_value
+			.as_f64()
+			.unwrap() >= 49.0
+	);
+	assert!(counterfactual.output_value >= 0.5);
+	// If only the color may change, it is changed to blue.
+	let options = CounterfactualOptions {
+		mutable_columns: Some(vec!["color".to_owned()]),
+		..Default::default()
+	};
+	let counterfactuals = compute_counterfactuals(
+		&model,
+		&test_example(10.0, "red"),
+		desired_outcome(),
+		Some(options),
+	)
+	.unwrap();
+	assert!(!counterfactuals.is_empty());
+	for counterfactual in counterfactuals.iter() {
+		for change in counterfactual.changes.iter() {
+			assert_eq!(change.column_name, "color");
+		}
+	}
+	assert_eq!(
+		counterfactuals[0].changes[0].counterfactual_value,
+		serde_json::json!("blue")
+	);
+}
+
+#[cfg(feature = "train")]
+#[test]
+fn test_counterfactuals_already_achieved() {
+	let model = train_test_model("already_achieved", |x, color| {
+		if x >= 50 || color == "blue" {
+			"yes"
+		} else {
+			"no"
+		}
+		.to_owned()
+	});
+	let counterfactuals = compute_counterfactuals(
+		&model,
+		&test_example(90.0, "red"),
+		DesiredOutcome::Class {
+			class: "yes".to_owned(),
+		},
+		None,
+	)
+	.unwrap();
+	assert_eq!(counterfactuals.len(), 1);
+	assert!(counterfactuals[0].changes.is_empty());
+	assert!(f32::abs(counterfactuals[0].cost) < f32::EPSILON);
+	assert!(counterfactuals[0].output_value >= 0.5);
+}
+
+#[cfg(feature = "train")]
+#[test]
+fn test_counterfactuals_regressor() {
+	let model = train_test_model("regressor", |x, _| (x * 2).to_string());
+	let counterfactuals = compute_counterfactuals(
+		&model,
+		&test_example(10.0, "red"),
+		DesiredOutcome::Value { value: 150.0 },
+		None,
+	)
+	.unwrap();
+	assert!(!counterfactuals.is_empty());
+	let counterfactual = &counterfactuals[0];
+	assert_eq!(counterfactual.changes.len(), 1);
+	assert_eq!(counterfactual.changes[0].column_name, "x");
+	assert!(counterfactual.output_value >= 150.0);
+	// The prediction must move down to reach a value below it.
+	let counterfactuals = compute_counterfactuals(
+		&model,
+		&test_example(90.0, "red"),
+		DesiredOutcome::Value { value: 40.0 },
+		None,
+	)
+	.unwrap();
+	assert!(!counterfactuals.is_empty());
+	assert!(counterfactuals[0].output_value <= 40.0);
+}
+
+#[test]
+fn test_compare_nan_lowest() {
+	let mut values = vec![1.0, std::f32::NAN, 3.0, -2.0];
+	values.sort_by(|a, b| compare_nan_lowest(*a, *b));
+	assert!(values[0].is_nan());
+	assert_eq!(&values[1..], &[-2.0, 1.0, 3.0]);
+	let max = values
+		.iter()
+		.cloned()
+		.max_by(|a, b| compare_nan_lowest(*a, *b))
+		.unwrap();
+	assert!(f32::abs(max - 3.0) < f32::EPSILON);
+}
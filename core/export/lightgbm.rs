@@ -0,0 +1,176 @@
+/*!
+This module exports tree models to LightGBM's text model format, the format written by `Booster.save_model`.
+
+Number features are exported as is. Enum features are exported as categorical features whose value is the zero based index of the option in the model's column stats, with `NaN` for invalid values. Text features are exported as one feature per token that is one if the token is present and zero otherwise.
+*/
+
+use super::{left_options, preorder, TreeFeatureType, TreeModel, TreeTask};
+use crate::model;
+use std::fmt::Write;
+use tangram_util::error::Result;
+
+/// This decision type compares the feature with the threshold, sends values less than or equal to the threshold left, and sends `NaN` right.
+const NUMERICAL_DECISION_TYPE: u8 = 8;
+/// This decision type sends values in the category set left and everything else, including `NaN`, right.
+const CATEGORICAL_DECISION_TYPE: u8 = 9;
+
+pub fn export(model: &model::Model) -> Result<String> {
+	let model = TreeModel::new(model)?;
+	let mut output = String::new();
+	let (num_class, objective) = match model.task {
+		TreeTask::Regression => (1, "regression".to_owned()),
+		TreeTask::BinaryClassification => (1, "binary sigmoid:1".to_owned()),
+		TreeTask::MulticlassClassification { n_classes } => {
+			(n_classes, format!("multiclass num_class:{}", n_classes))
+		}
+	};
+	let feature_names = model
+		.features
+		.iter()
+		.map(|feature| feature.name.as_str())
+		.collect::<Vec<_>>();
+	let feature_infos = model
+		.features
+		.iter()
+		.map(|feature| match feature.feature_type {
+			TreeFeatureType::Number { min, max } if min.is_finite() && max.is_finite() => {
+				format!("[{}:{}]", min, max)
+			}
+			TreeFeatureType::Number { .. } => "none".to_owned(),
+			TreeFeatureType::Enum { n_options } => {
+				join((0..n_options).map(|option| option.to_string()), ":")
+			}
+		})
+		.collect::<Vec<_>>();
+	writeln!(output, "tree").unwrap();
+	writeln!(output, "version=v3").unwrap();
+	writeln!(output, "num_class={}", num_class).unwrap();
+	writeln!(output, "num_tree_per_iteration={}", model.n_outputs()).unwrap();
+	writeln!(output, "label_index=0").unwrap();
+	writeln!(
+		output,
+		"max_feature_idx={}",
+		model.features.len() as i64 - 1
+	)
+	.unwrap();
+	writeln!(output, "objective={}", objective).unwrap();
+	writeln!(output, "feature_names={}", feature_names.join(" ")).unwrap();
+	writeln!(output, "feature_infos={}", feature_infos.join(" ")).unwrap();
+	writeln!(output).unwrap();
+	for tree_index in 0..model.n_trees() {
+		writeln!(output, "Tree={}", tree_index).unwrap();
+		let leaf_offset = model.leaf_offset(tree_index);
+		match model.trees.get(tree_index) {
+			Some(tree) => write_tree(&mut output, tree, leaf_offset),
+			None => write_leaf_tree(&mut output, leaf_offset),
+		}
+		writeln!(output).unwrap();
+	}
+	writeln!(output, "end of trees").unwrap();
+	Ok(output)
+}
+
+/// LightGBM numbers a tree's branches and leaves separately. A child index that is not negative refers to a branch, and a negative child index `!i` refers to leaf `i`.
+fn write_tree(output: &mut String, tree: &model::Tree, leaf_offset: f64) {
+	let (nodes, positions) = preorder(tree);
+	let child_index = |node_index: usize| {
+		let node = &nodes[positions[node_index]];
+		match node.node {
+			model::Node::Branch(_) => node.index as i64,
+			model::Node::Leaf(_) => !(node.index as i64),
+		}
+	};
+	let mut split_feature = Vec::new();
+	let mut threshold = Vec::new();
+	let mut decision_type = Vec::new();
+	let mut left_child = Vec::new();
+	let mut right_child = Vec::new();
+	let mut leaf_value = Vec::new();
+	let mut cat_boundaries = vec![0];
+	let mut cat_threshold = Vec::new();
+	for node in nodes.iter() {
+		match node.node {
+			model::Node::Branch(branch) => {
+				let mut left = child_index(branch.left_child_index);
+				let mut right = child_index(branch.right_child_index);
+				match &branch.split {
+					model::BranchSplit::Continuous(split) => {
+						split_feature.push(split.feature_index);
+						// LightGBM reads thresholds as doubles, so write the exact value of the threshold rather than the shortest decimal that rounds to it as a float.
+						threshold.push((split.split_value as f64).to_string());
+						decision_type.push(NUMERICAL_DECISION_TYPE);
+					}
+					model::BranchSplit::Discrete(split) => {
+						let (mut options, invalid_values_go_left) = left_options(&split.directions);
+						// LightGBM always sends invalid values right, so when they go left in tangram, swap the children and send the complement of the options left instead.
+						if invalid_values_go_left {
+							std::mem::swap(&mut left, &mut right);
+							let n_options = split.directions.len().saturating_sub(1);
+							options = (0..n_options)
+								.filter(|option| !options.contains(option))
+								.collect();
+						}
+						let n_words = options.iter().max().map(|max| max / 32 + 1).unwrap_or(1);
+						let mut words = vec![0u32; n_words];
+						for option in options {
+							words[option / 32] |= 1 << (option % 32);
+						}
+						split_feature.push(split.feature_index);
+						threshold.push((cat_boundaries.len() - 1).to_string());
+						decision_type.push(CATEGORICAL_DECISION_TYPE);
+						cat_threshold.extend(words);
+						cat_boundaries.push(cat_threshold.len());
+					}
+				}
+				left_child.push(left);
+				right_child.push(right);
+			}
+			model::Node::Leaf(leaf) => {
+				leaf_value.push(leaf.value + leaf_offset);
+			}
+		}
+	}
+	let num_cat = cat_boundaries.len() - 1;
+	writeln!(output, "num_leaves={}", leaf_value.len()).unwrap();
+	writeln!(output, "num_cat={}", num_cat).unwrap();
+	writeln!(output, "split_feature={}", join(&split_feature, " ")).unwrap();
+	writeln!(
+		output,
+		"split_gain={}",
+		join(split_feature.iter().map(|_| 0), " ")
+	)
+	.unwrap();
+	writeln!(output, "threshold={}", threshold.join(" ")).unwrap();
+	writeln!(output, "decision_type={}", join(decision_type, " ")).unwrap();
+	writeln!(output, "left_child={}", join(left_child, " ")).unwrap();
+	writeln!(output, "right_child={}", join(right_child, " ")).unwrap();
+	writeln!(output, "leaf_value={}", join(leaf_value, " ")).unwrap();
+	if num_cat > 0 {
+		writeln!(output, "cat_boundaries={}", join(cat_boundaries, " ")).unwrap();
+		writeln!(output, "cat_threshold={}", join(cat_threshold, " ")).unwrap();
+	}
+	writeln!(output, "is_linear=0").unwrap();
+	writeln!(output, "shrinkage=1").unwrap();
+}
+
+fn write_leaf_tree(output: &mut String, value: f64) {
+	writeln!(output, "num_leaves=1").unwrap();
+	writeln!(output, "num_cat=0").unwrap();
+	writeln!(output, "split_feature=").unwrap();
+	writeln!(output, "split_gain=").unwrap();
+	writeln!(output, "threshold=").unwrap();
+	writeln!(output, "decision_type=").unwrap();
+	writeln!(output, "left_child=").unwrap();
+	writeln!(output, "right_child=").unwrap();
+	writeln!(output, "leaf_value={}", value).unwrap();
+	writeln!(output, "is_linear=0").unwrap();
+	writeln!(output, "shrinkage=1").unwrap();
+}
+
+fn join<T: std::fmt::Display>(values: impl IntoIterator<Item = T>, separator: &str) -> String {
+	values
+		.into_iter()
+		.map(|value| value.to_string())
+		.collect::<Vec<_>>()
+		.join(separator)
+}
@@ -0,0 +1,218 @@
+/*!
+This module converts models to formats that other machine learning tools can load.
+*/
+
+use crate::model;
+use tangram_util::{err, error::Result};
+
+pub mod lightgbm;
+pub mod onnx;
+pub mod pmml;
+pub mod xgboost;
+
+/// The LightGBM and XGBoost formats only store trees, so `TreeModel` collects what they need from any of the tree models.
+struct TreeModel<'a> {
+	task: TreeTask,
+	features: Vec<TreeFeature>,
+	trees: &'a [model::Tree],
+	biases: Vec<f32>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum TreeTask {
+	Regression,
+	BinaryClassification,
+	MulticlassClassification { n_classes: usize },
+}
+
+/// Each feature a tree model was trained on becomes one feature in the exported model. Enum features take the zero based index of the option, with `NaN` for invalid values.
+struct TreeFeature {
+	name: String,
+	feature_type: TreeFeatureType,
+}
+
+enum TreeFeatureType {
+	Number { min: f32, max: f32 },
+	Enum { n_options: usize },
+}
+
+impl<'a> TreeModel<'a> {
+	fn new(model: &'a model::Model) -> Result<TreeModel<'a>> {
+		let (task, column_stats, feature_groups, trees, biases) = match model {
+			model::Model::Regressor(model) => match &model.model {
+				model::RegressionModel::Tree(inner_model) => (
+					TreeTask::Regression,
+					&model.overall_column_stats,
+					&inner_model.feature_groups,
+					&inner_model.trees,
+					vec![inner_model.bias],
+				),
+				model::RegressionModel::Linear(_) => {
+					return Err(err!("only tree models can be exported to this format"))
+				}
+			},
+			model::Model::BinaryClassifier(model) => match &model.model {
+				model::BinaryClassificationModel::Tree(inner_model) => (
+					TreeTask::BinaryClassification,
+					&model.overall_column_stats,
+					&inner_model.feature_groups,
+					&inner_model.trees,
+					vec![inner_model.bias],
+				),
+				model::BinaryClassificationModel::Linear(_) => {
+					return Err(err!("only tree models can be exported to this format"))
+				}
+			},
+			model::Model::MulticlassClassifier(model) => match &model.model {
+				model::MulticlassClassificationModel::Tree(inner_model) => (
+					TreeTask::MulticlassClassification {
+						n_classes: inner_model.n_classes,
+					},
+					&model.overall_column_stats,
+					&inner_model.feature_groups,
+					&inner_model.trees,
+					inner_model.biases.clone(),
+				),
+				model::MulticlassClassificationModel::Linear(_) => {
+					return Err(err!("only tree models can be exported to this format"))
+				}
+			},
+		};
+		let features = tree_features(column_stats, feature_groups)?;
+		Ok(TreeModel {
+			task,
+			features,
+			trees,
+			biases,
+		})
+	}
+
+	/// This is the number of trees in each round.
+	fn n_outputs(&self) -> usize {
+		self.biases.len()
+	}
+
+	/// A model with no rounds still exports one tree for each output to hold the biases.
+	fn n_trees(&self) -> usize {
+		self.trees.len().max(self.n_outputs())
+	}
+
+	/// Neither format has a bias for each output that matches tangram's, so the biases are added to the leaves of the trees in the first round.
+	fn leaf_offset(&self, tree_index: usize) -> f64 {
+		self.biases.get(tree_index).cloned().unwrap_or(0.0) as f64
+	}
+}
+
+fn tree_features(
+	column_stats: &[model::ColumnStats],
+	feature_groups: &[model::FeatureGroup],
+) -> Result<Vec<TreeFeature>> {
+	let mut features = Vec::new();
+	for feature_group in feature_groups {
+		match feature_group {
+			model::FeatureGroup::Identity(feature_group) => {
+				let column_stats = column_stats
+					.iter()
+					.find(|column_stats| {
+						column_stats.column_name() == feature_group.source_column_name
+					})
+					.ok_or_else(|| {
+						err!(
+							"the model has no column named {}",
+							feature_group.source_column_name
+						)
+					})?;
+				let feature_type = match column_stats {
+					model::ColumnStats::Number(column_stats) => TreeFeatureType::Number {
+						min: column_stats.min,
+						max: column_stats.max,
+					},
+					model::ColumnStats::Enum(column_stats) => TreeFeatureType::Enum {
+						n_options: column_stats.histogram.len(),
+					},
+					_ => {
+						return Err(err!(
+							"column {} has an unsupported type",
+							feature_group.source_column_name
+						))
+					}
+				};
+				features.push(TreeFeature {
+					name: feature_name(&feature_group.source_column_name),
+					feature_type,
+				});
+			}
+			model::FeatureGroup::BagOfWords(feature_group) => {
+				for entry in feature_group.tokens.iter() {
+					features.push(TreeFeature {
+						name: feature_name(&format!(
+							"{}_{}",
+							feature_group.source_column_name, entry.token
+						)),
+						feature_type: TreeFeatureType::Number { min: 0.0, max: 1.0 },
+					});
+				}
+			}
+			model::FeatureGroup::Normalized(_) | model::FeatureGroup::OneHotEncoded(_) => {
+				return Err(err!("tree models do not use this feature group"))
+			}
+		}
+	}
+	Ok(features)
+}
+
+/// LightGBM separates feature names with spaces and XGBoost does not allow some punctuation in them, so replace everything but letters, numbers, and underscores.
+fn feature_name(name: &str) -> String {
+	name.chars()
+		.map(|c| if c.is_alphanumeric() { c } else { '_' })
+		.collect()
+}
+
+/// This is a tree's nodes in preorder, with leaves and branches numbered separately, the way LightGBM numbers them.
+struct PreorderNode<'a> {
+	node: &'a model::Node,
+	/// For branches, this is the branch index. For leaves, this is the leaf index.
+	index: usize,
+}
+
+/// Retrieve the nodes of `tree` in preorder, and the preorder position of each node, indexed by the node's index in `tree.nodes`.
+fn preorder(tree: &model::Tree) -> (Vec<PreorderNode<'_>>, Vec<usize>) {
+	let mut nodes = Vec::with_capacity(tree.nodes.len());
+	let mut positions = vec![0; tree.nodes.len()];
+	let mut n_branches = 0;
+	let mut n_leaves = 0;
+	let mut stack = vec![0];
+	while let Some(node_index) = stack.pop() {
+		let node = &tree.nodes[node_index];
+		positions[node_index] = nodes.len();
+		let index = match node {
+			model::Node::Branch(branch) => {
+				stack.push(branch.right_child_index);
+				stack.push(branch.left_child_index);
+				n_branches += 1;
+				n_branches - 1
+			}
+			model::Node::Leaf(_) => {
+				n_leaves += 1;
+				n_leaves - 1
+			}
+		};
+		nodes.push(PreorderNode { node, index });
+	}
+	(nodes, positions)
+}
+
+/// Retrieve the zero based indexes of the options that go left at a discrete split, and whether invalid values go left.
+fn left_options(directions: &[model::SplitDirection]) -> (Vec<usize>, bool) {
+	let is_left =
+		|direction: &model::SplitDirection| matches!(direction, model::SplitDirection::Left);
+	let invalid_values_go_left = directions.first().map(is_left).unwrap_or(false);
+	let left_options = directions
+		.iter()
+		.skip(1)
+		.enumerate()
+		.filter(|(_, direction)| is_left(direction))
+		.map(|(option_index, _)| option_index)
+		.collect();
+	(left_options, invalid_values_go_left)
+}
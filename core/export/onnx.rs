@@ -0,0 +1,998 @@
+/*!
+This module exports models to [ONNX](https://onnx.ai), so they can be run by any ONNX runtime.
+
+The exported graph has one input for each column the model's feature groups read, named after the column, with shape `[N, 1]`. Number columns are `float` tensors where invalid values are `NaN`, and enum and text columns are `string` tensors. Regressors have a `value` output with shape `[N, 1]`. Classifiers have a `label` output with shape `[N]` and a `probabilities` output with shape `[N, n_classes]`.
+
+Feature groups are computed by preprocessing nodes in the graph, so the exported model makes the same predictions as [`predict`](crate::predict). Tree models use the `TreeEnsembleRegressor` and `TreeEnsembleClassifier` operators from the `ai.onnx.ml` domain, and linear models use `MatMul` and `Add` followed by `Sigmoid` or `Softmax`. The standard operators cannot split text into tokens, so bag of words feature groups use the `Tokenizer` operator from the `com.microsoft` domain, which onnxruntime implements.
+*/
+
+use crate::model;
+use std::collections::HashMap;
+use tangram_util::{err, error::Result};
+
+const IR_VERSION: i64 = 7;
+const OPSET_VERSION: i64 = 13;
+const ML_DOMAIN: &str = "ai.onnx.ml";
+const ML_OPSET_VERSION: i64 = 2;
+const MICROSOFT_DOMAIN: &str = "com.microsoft";
+const MICROSOFT_OPSET_VERSION: i64 = 1;
+
+// These are the values of `TensorProto.DataType` used by the exported graph.
+const FLOAT: i64 = 1;
+const INT64: i64 = 7;
+const STRING: i64 = 8;
+
+/// Export `model` to ONNX and return the serialized `ModelProto`.
+pub fn export(model: &model::Model) -> Result<Vec<u8>> {
+	let graph = match model {
+		model::Model::Regressor(model) => {
+			let mut graph = Graph::new(&model.overall_column_stats);
+			match &model.model {
+				model::RegressionModel::Linear(model) => {
+					let features = graph.features(&model.feature_groups)?;
+					let logits = graph.linear(&features, &model.weights, &[model.bias]);
+					graph.node("Identity", &[&logits], &["value"], Vec::new());
+				}
+				model::RegressionModel::Tree(model) => {
+					let mut features = graph.features(&model.feature_groups)?;
+					let ensemble = graph.tree_ensemble(
+						&mut features,
+						&model.feature_groups,
+						&model.trees,
+						|_| 0,
+					)?;
+					let mut attributes = ensemble.attributes("target");
+					attributes.push(attribute("n_targets", Attribute::Int(1)));
+					attributes.push(attribute(
+						"base_values",
+						Attribute::Floats(vec![model.bias]),
+					));
+					attributes.push(attribute("aggregate_function", Attribute::String("SUM")));
+					attributes.push(attribute("post_transform", Attribute::String("NONE")));
+					graph.ml_node(
+						"TreeEnsembleRegressor",
+						&[&features],
+						&["value"],
+						attributes,
+					);
+				}
+			}
+			graph.output("value", FLOAT, &[Dimension::Examples, Dimension::Value(1)]);
+			graph
+		}
+		model::Model::BinaryClassifier(model) => {
+			let mut graph = Graph::new(&model.overall_column_stats);
+			let classes = vec![model.negative_class.clone(), model.positive_class.clone()];
+			let probability = match &model.model {
+				model::BinaryClassificationModel::Linear(model) => {
+					let features = graph.features(&model.feature_groups)?;
+					let logits = graph.linear(&features, &model.weights, &[model.bias]);
+					let probability = graph.value("Sigmoid", &[&logits], Vec::new());
+					let one = graph.float_constant(&[], &[1.0]);
+					let negative_probability =
+						graph.value("Sub", &[&one, &probability], Vec::new());
+					graph.node(
+						"Concat",
+						&[&negative_probability, &probability],
+						&["probabilities"],
+						vec![attribute("axis", Attribute::Int(1))],
+					);
+					probability
+				}
+				model::BinaryClassificationModel::Tree(model) => {
+					let mut features = graph.features(&model.feature_groups)?;
+					let ensemble = graph.tree_ensemble(
+						&mut features,
+						&model.feature_groups,
+						&model.trees,
+						|_| 1,
+					)?;
+					// Every leaf also gets a zero weight for the negative class. Otherwise onnxruntime treats the ensemble as a binary classifier with a single score, which does not apply the post transform the way tangram does. With two scores, the softmax of `[0, logit]` is `[1 - sigmoid(logit), sigmoid(logit)]`.
+					let ensemble = ensemble.with_zero_weights_for_class(0);
+					let mut attributes = ensemble.attributes("class");
+					attributes.push(attribute(
+						"classlabels_strings",
+						Attribute::Strings(classes.clone()),
+					));
+					attributes.push(attribute(
+						"base_values",
+						Attribute::Floats(vec![0.0, model.bias]),
+					));
+					attributes.push(attribute("post_transform", Attribute::String("SOFTMAX")));
+					let label = graph.name("label");
+					graph.ml_node(
+						"TreeEnsembleClassifier",
+						&[&features],
+						&[&label, "probabilities"],
+						attributes,
+					);
+					let index = graph.int64_constant(&[1], &[1]);
+					graph.value(
+						"Gather",
+						&["probabilities", &index],
+						vec![attribute("axis", Attribute::Int(1))],
+					)
+				}
+			};
+			// Compute the label the way tangram does, choosing the positive class when its probability is at least 0.5.
+			let threshold = graph.float_constant(&[], &[0.5]);
+			let is_positive =
+				graph.value("GreaterOrEqual", &[&probability, &threshold], Vec::new());
+			let class_index = graph.value(
+				"Cast",
+				&[&is_positive],
+				vec![attribute("to", Attribute::Int(INT64))],
+			);
+			let shape = graph.int64_constant(&[1], &[-1]);
+			let class_index = graph.value("Reshape", &[&class_index, &shape], Vec::new());
+			let classes = graph.string_constant(&[2], &classes);
+			graph.node("Gather", &[&classes, &class_index], &["label"], Vec::new());
+			graph.output("label", STRING, &[Dimension::Examples]);
+			graph.output(
+				"probabilities",
+				FLOAT,
+				&[Dimension::Examples, Dimension::Value(2)],
+			);
+			graph
+		}
+		model::Model::MulticlassClassifier(model) => {
+			let mut graph = Graph::new(&model.overall_column_stats);
+			let classes = &model.classes;
+			let n_classes = classes.len();
+			match &model.model {
+				model::MulticlassClassificationModel::Linear(model) => {
+					let features = graph.features(&model.feature_groups)?;
+					let logits = graph.linear(&features, &model.weights, &model.biases);
+					graph.node(
+						"Softmax",
+						&[&logits],
+						&["probabilities"],
+						vec![attribute("axis", Attribute::Int(1))],
+					);
+				}
+				model::MulticlassClassificationModel::Tree(model) => {
+					let mut features = graph.features(&model.feature_groups)?;
+					let ensemble = graph.tree_ensemble(
+						&mut features,
+						&model.feature_groups,
+						&model.trees,
+						|tree_index| tree_index % n_classes,
+					)?;
+					let mut attributes = ensemble.attributes("class");
+					attributes.push(attribute(
+						"classlabels_strings",
+						Attribute::Strings(classes.clone()),
+					));
+					attributes.push(attribute(
+						"base_values",
+						Attribute::Floats(model.biases.clone()),
+					));
+					attributes.push(attribute("post_transform", Attribute::String("SOFTMAX")));
+					let label = graph.name("label");
+					graph.ml_node(
+						"TreeEnsembleClassifier",
+						&[&features],
+						&[&label, "probabilities"],
+						attributes,
+					);
+				}
+			}
+			let class_index = graph.value(
+				"ArgMax",
+				&["probabilities"],
+				vec![
+					attribute("axis", Attribute::Int(1)),
+					attribute("keepdims", Attribute::Int(0)),
+				],
+			);
+			let classes = graph.string_constant(&[n_classes as i64], classes);
+			graph.node("Gather", &[&classes, &class_index], &["label"], Vec::new());
+			graph.output("label", STRING, &[Dimension::Examples]);
+			graph.output(
+				"probabilities",
+				FLOAT,
+				&[Dimension::Examples, Dimension::Value(n_classes as i64)],
+			);
+			graph
+		}
+	};
+	Ok(graph.into_model(&model.id().to_string()).0)
+}
+
+struct Graph<'a> {
+	column_stats: &'a [model::ColumnStats],
+	input_names: Vec<String>,
+	inputs: Vec<Message>,
+	outputs: Vec<Message>,
+	nodes: Vec<Message>,
+	initializers: Vec<Message>,
+	n_names: usize,
+	uses_microsoft_domain: bool,
+}
+
+enum Dimension {
+	Examples,
+	Value(i64),
+}
+
+impl<'a> Graph<'a> {
+	fn new(column_stats: &'a [model::ColumnStats]) -> Graph<'a> {
+		Graph {
+			column_stats,
+			input_names: Vec::new(),
+			inputs: Vec::new(),
+			outputs: Vec::new(),
+			nodes: Vec::new(),
+			initializers: Vec::new(),
+			n_names: 0,
+			uses_microsoft_domain: false,
+		}
+	}
+
+	/// Make a name for an intermediate value that will not collide with the name of any other value in the graph.
+	fn name(&mut self, prefix: &str) -> String {
+		self.n_names += 1;
+		format!("{}:{}", prefix, self.n_names)
+	}
+
+	fn node(&mut self, op_type: &str, inputs: &[&str], outputs: &[&str], attributes: Vec<Message>) {
+		self.node_in_domain("", op_type, inputs, outputs, attributes)
+	}
+
+	fn ml_node(
+		&mut self,
+		op_type: &str,
+		inputs: &[&str],
+		outputs: &[&str],
+		attributes: Vec<Message>,
+	) {
+		self.node_in_domain(ML_DOMAIN, op_type, inputs, outputs, attributes)
+	}
+
+	fn node_in_domain(
+		&mut self,
+		domain: &str,
+		op_type: &str,
+		inputs: &[&str],
+		outputs: &[&str],
+		attributes: Vec<Message>,
+	) {
+		let name = self.name(op_type);
+		let mut node = Message::default();
+		for input in inputs {
+			node = node.string(1, input);
+		}
+		for output in outputs {
+			node = node.string(2, output);
+		}
+		node = node.string(3, &name).string(4, op_type);
+		for attribute in attributes.iter() {
+			node = node.message(5, attribute);
+		}
+		if !domain.is_empty() {
+			node = node.string(7, domain);
+		}
+		self.nodes.push(node);
+	}
+
+	/// Add a node from the default domain with a single output and return the output's name.
+	fn value(&mut self, op_type: &str, inputs: &[&str], attributes: Vec<Message>) -> String {
+		let output = self.name(op_type);
+		self.node(op_type, inputs, &[&output], attributes);
+		output
+	}
+
+	fn float_constant(&mut self, dims: &[i64], values: &[f32]) -> String {
+		let name = self.name("constant");
+		let tensor = tensor(&name, dims, FLOAT).packed_floats(4, values);
+		self.initializers.push(tensor);
+		name
+	}
+
+	fn int64_constant(&mut self, dims: &[i64], values: &[i64]) -> String {
+		let name = self.name("constant");
+		let tensor = tensor(&name, dims, INT64).packed_varints(7, values);
+		self.initializers.push(tensor);
+		name
+	}
+
+	fn string_constant(&mut self, dims: &[i64], values: &[String]) -> String {
+		let name = self.name("constant");
+		let mut tensor = tensor(&name, dims, STRING);
+		for value in values {
+			tensor = tensor.string(6, value);
+		}
+		self.initializers.push(tensor);
+		name
+	}
+
+	/// Get the graph input for the column named `column_name`, adding it if this is the first time it is used.
+	fn column(&mut self, column_name: &str) -> Result<(String, &'a model::ColumnStats)> {
+		let column_stats = self
+			.column_stats
+			.iter()
+			.find(|column_stats| column_stats.column_name() == column_name)
+			.ok_or_else(|| err!("the model has no column named {}", column_name))?;
+		if !self.input_names.iter().any(|name| name == column_name) {
+			let elem_type = match column_stats {
+				model::ColumnStats::Number(_) => FLOAT,
+				model::ColumnStats::Enum(_) | model::ColumnStats::Text(_) => STRING,
+				model::ColumnStats::Unknown(_) => {
+					return Err(err!("column {} has unknown type", column_name))
+				}
+			};
+			let input = value_info(
+				column_name,
+				elem_type,
+				&[Dimension::Examples, Dimension::Value(1)],
+			);
+			self.inputs.push(input);
+			self.input_names.push(column_name.to_owned());
+		}
+		Ok((column_name.to_owned(), column_stats))
+	}
+
+	fn output(&mut self, name: &str, elem_type: i64, dims: &[Dimension]) {
+		self.outputs.push(value_info(name, elem_type, dims));
+	}
+
+	/// Add the nodes that compute the features for `feature_groups` and return the name of the `[N, n_features]` features tensor.
+	fn features(&mut self, feature_groups: &[model::FeatureGroup]) -> Result<String> {
+		let mut features = Vec::new();
+		for feature_group in feature_groups {
+			let group_features = match feature_group {
+				model::FeatureGroup::Identity(feature_group) => {
+					Some(self.identity_features(feature_group)?)
+				}
+				model::FeatureGroup::Normalized(feature_group) => {
+					Some(self.normalized_features(feature_group)?)
+				}
+				model::FeatureGroup::OneHotEncoded(feature_group) => {
+					Some(self.one_hot_encoded_features(feature_group)?)
+				}
+				model::FeatureGroup::BagOfWords(feature_group) => {
+					self.bag_of_words_features(feature_group)?
+				}
+			};
+			features.extend(group_features);
+		}
+		if features.is_empty() {
+			return Err(err!("the model has no features"));
+		}
+		let features = features
+			.iter()
+			.map(|name| name.as_str())
+			.collect::<Vec<_>>();
+		Ok(self.value(
+			"Concat",
+			&features,
+			vec![attribute("axis", Attribute::Int(1))],
+		))
+	}
+
+	fn identity_features(&mut self, feature_group: &model::IdentityFeatureGroup) -> Result<String> {
+		let (column, column_stats) = self.column(&feature_group.source_column_name)?;
+		match column_stats {
+			model::ColumnStats::Number(_) => Ok(column),
+			model::ColumnStats::Enum(column_stats) => {
+				Ok(self.enum_option_numbers(&column, &enum_options(column_stats)))
+			}
+			_ => Err(err!(
+				"identity feature group for column {} has an unsupported column type",
+				feature_group.source_column_name
+			)),
+		}
+	}
+
+	fn normalized_features(
+		&mut self,
+		feature_group: &model::NormalizedFeatureGroup,
+	) -> Result<String> {
+		let (column, column_stats) = self.column(&feature_group.source_column_name)?;
+		let (values, values_may_be_nan) = match column_stats {
+			model::ColumnStats::Number(_) => (column, true),
+			model::ColumnStats::Enum(column_stats) => (
+				self.enum_option_numbers(&column, &enum_options(column_stats)),
+				false,
+			),
+			_ => {
+				return Err(err!(
+					"normalized feature group for column {} has an unsupported column type",
+					feature_group.source_column_name
+				))
+			}
+		};
+		let mean = self.float_constant(&[], &[feature_group.mean]);
+		let centered = self.value("Sub", &[&values, &mean], Vec::new());
+		let normalized = if feature_group.variance == 0.0 {
+			let zero = self.float_constant(&[], &[0.0]);
+			self.value("Mul", &[&centered, &zero], Vec::new())
+		} else {
+			let std = self.float_constant(&[], &[f32::sqrt(feature_group.variance)]);
+			self.value("Div", &[&centered, &std], Vec::new())
+		};
+		if !values_may_be_nan {
+			return Ok(normalized);
+		}
+		// Invalid values have the feature value zero.
+		let is_nan = self.value("IsNaN", &[&values], Vec::new());
+		let zero = self.float_constant(&[], &[0.0]);
+		Ok(self.value("Where", &[&is_nan, &zero, &normalized], Vec::new()))
+	}
+
+	fn one_hot_encoded_features(
+		&mut self,
+		feature_group: &model::OneHotEncodedFeatureGroup,
+	) -> Result<String> {
+		let (column, _) = self.column(&feature_group.source_column_name)?;
+		// Map each option to its index in the feature group, where zero is the index of the feature for invalid values, and compare the index with the index of each feature.
+		let n_features = feature_group.options.len() + 1;
+		let option_index = self.ml_value(
+			"LabelEncoder",
+			&[&column],
+			vec![
+				attribute(
+					"keys_strings",
+					Attribute::Strings(feature_group.options.clone()),
+				),
+				attribute(
+					"values_int64s",
+					Attribute::Ints((1..n_features as i64).collect()),
+				),
+				attribute("default_int64", Attribute::Int(0)),
+			],
+		);
+		let feature_indexes = self.int64_constant(
+			&[n_features as i64],
+			&(0..n_features as i64).collect::<Vec<_>>(),
+		);
+		let is_feature = self.value("Equal", &[&option_index, &feature_indexes], Vec::new());
+		Ok(self.value(
+			"Cast",
+			&[&is_feature],
+			vec![attribute("to", Attribute::Int(FLOAT))],
+		))
+	}
+
+	fn bag_of_words_features(
+		&mut self,
+		feature_group: &model::BagOfWordsFeatureGroup,
+	) -> Result<Option<String>> {
+		if feature_group.tokens.is_empty() {
+			return Ok(None);
+		}
+		let (column, _) = self.column(&feature_group.source_column_name)?;
+		match feature_group.tokenizer {
+			model::Tokenizer::Alphanumeric => {}
+		}
+		// Tokenize the text the way the alphanumeric tokenizer does, by lowercasing it and finding runs of at least two alphanumeric characters.
+		let shape = self.int64_constant(&[1], &[-1]);
+		let text = self.value("Reshape", &[&column, &shape], Vec::new());
+		let text = self.value(
+			"StringNormalizer",
+			&[&text],
+			vec![attribute("case_change_action", Attribute::String("LOWER"))],
+		);
+		let tokens = self.name("Tokenizer");
+		self.uses_microsoft_domain = true;
+		self.node_in_domain(
+			MICROSOFT_DOMAIN,
+			"Tokenizer",
+			&[&text],
+			&[&tokens],
+			vec![
+				attribute("mark", Attribute::Int(0)),
+				attribute("mincharnum", Attribute::Int(2)),
+				attribute("pad_value", Attribute::String("#")),
+				attribute("tokenexp", Attribute::String("[\\p{L}\\p{N}]+")),
+			],
+		);
+		// The pool lists the unigrams followed by the words of each bigram. Each entry's output index is the index of its feature in the feature group.
+		let mut pool = Vec::new();
+		let mut ngram_indexes = Vec::new();
+		for (feature_index, entry) in feature_group.tokens.iter().enumerate() {
+			if let model::Token::Unigram(token) = &entry.token {
+				pool.push(token.clone());
+				ngram_indexes.push(feature_index as i64);
+			}
+		}
+		let n_unigrams = pool.len() as i64;
+		for (feature_index, entry) in feature_group.tokens.iter().enumerate() {
+			if let model::Token::Bigram(token_a, token_b) = &entry.token {
+				pool.push(token_a.clone());
+				pool.push(token_b.clone());
+				ngram_indexes.push(feature_index as i64);
+			}
+		}
+		let has_bigrams = ngram_indexes.len() as i64 > n_unigrams;
+		let ngram_counts = if has_bigrams {
+			vec![0, n_unigrams]
+		} else {
+			vec![0]
+		};
+		// Tangram sets the feature for each token to one if the token occurs in the text, which is what the `IDF` mode computes when every weight is one.
+		let weights = vec![1.0; ngram_indexes.len()];
+		Ok(Some(self.value(
+			"TfIdfVectorizer",
+			&[&tokens],
+			vec![
+				attribute("mode", Attribute::String("IDF")),
+				attribute("min_gram_length", Attribute::Int(1)),
+				attribute(
+					"max_gram_length",
+					Attribute::Int(if has_bigrams { 2 } else { 1 }),
+				),
+				attribute("max_skip_count", Attribute::Int(0)),
+				attribute("ngram_counts", Attribute::Ints(ngram_counts)),
+				attribute("ngram_indexes", Attribute::Ints(ngram_indexes)),
+				attribute("pool_strings", Attribute::Strings(pool)),
+				attribute("weights", Attribute::Floats(weights)),
+			],
+		)))
+	}
+
+	fn ml_value(&mut self, op_type: &str, inputs: &[&str], attributes: Vec<Message>) -> String {
+		let output = self.name(op_type);
+		self.ml_node(op_type, inputs, &[&output], attributes);
+		output
+	}
+
+	/// Map each value of an enum column to its one based option index as a float, with zero for invalid values.
+	fn enum_option_numbers(&mut self, column: &str, options: &[String]) -> String {
+		let values = (1..=options.len()).map(|value| value as f32).collect();
+		self.ml_value(
+			"LabelEncoder",
+			&[column],
+			vec![
+				attribute("keys_strings", Attribute::Strings(options.to_owned())),
+				attribute("values_floats", Attribute::Floats(values)),
+				attribute("default_float", Attribute::Float(0.0)),
+			],
+		)
+	}
+
+	/// Compute `features * weights + biases`, where `weights` has shape `[n_features, biases.len()]`.
+	fn linear(&mut self, features: &str, weights: &[f32], biases: &[f32]) -> String {
+		let n_outputs = biases.len();
+		let n_features = weights.len() / n_outputs;
+		let weights = self.float_constant(&[n_features as i64, n_outputs as i64], weights);
+		let biases = self.float_constant(&[n_outputs as i64], biases);
+		let product = self.value("MatMul", &[features, &weights], Vec::new());
+		self.value("Add", &[&product, &biases], Vec::new())
+	}
+
+	/// Build the node attributes for `trees`. Continuous splits compare a feature directly. Discrete splits cannot be expressed by the tree ensemble operators, so each distinct discrete split gets its own feature that is one if the example goes right and zero if it goes left, and the features are appended to `features`.
+	fn tree_ensemble(
+		&mut self,
+		features: &mut String,
+		feature_groups: &[model::FeatureGroup],
+		trees: &[model::Tree],
+		target_for_tree: impl Fn(usize) -> usize,
+	) -> Result<TreeEnsemble> {
+		let n_features = feature_groups.iter().map(n_features).sum::<usize>();
+		let mut split_features: HashMap<(usize, Vec<bool>), usize> = HashMap::new();
+		let mut split_feature_values = Vec::new();
+		let mut ensemble = TreeEnsemble::default();
+		for (tree_index, tree) in trees.iter().enumerate() {
+			for (node_index, node) in tree.nodes.iter().enumerate() {
+				ensemble.tree_ids.push(tree_index as i64);
+				ensemble.node_ids.push(node_index as i64);
+				match node {
+					model::Node::Branch(node) => {
+						let (feature_id, value) = match &node.split {
+							model::BranchSplit::Continuous(split) => {
+								(split.feature_index, split.split_value)
+							}
+							model::BranchSplit::Discrete(split) => {
+								let goes_right = split
+									.directions
+									.iter()
+									.map(|direction| {
+										matches!(direction, model::SplitDirection::Right)
+									})
+									.collect::<Vec<_>>();
+								let key = (split.feature_index, goes_right);
+								let feature_id = match split_features.get(&key) {
+									Some(feature_id) => *feature_id,
+									None => {
+										let feature_id = n_features + split_features.len();
+										let values = self.discrete_split_feature(
+											feature_groups,
+											split.feature_index,
+											&key.1,
+										)?;
+										split_feature_values.push(values);
+										split_features.insert(key, feature_id);
+										feature_id
+									}
+								};
+								(feature_id, 0.5)
+							}
+						};
+						ensemble.feature_ids.push(feature_id as i64);
+						ensemble.modes.push("BRANCH_LEQ".to_owned());
+						ensemble.values.push(value);
+						ensemble.true_node_ids.push(node.left_child_index as i64);
+						ensemble.false_node_ids.push(node.right_child_index as i64);
+					}
+					model::Node::Leaf(node) => {
+						ensemble.feature_ids.push(0);
+						ensemble.modes.push("LEAF".to_owned());
+						ensemble.values.push(0.0);
+						ensemble.true_node_ids.push(0);
+						ensemble.false_node_ids.push(0);
+						ensemble.weight_tree_ids.push(tree_index as i64);
+						ensemble.weight_node_ids.push(node_index as i64);
+						ensemble.weight_ids.push(target_for_tree(tree_index) as i64);
+						ensemble.weights.push(node.value as f32);
+					}
+				}
+			}
+		}
+		if !split_feature_values.is_empty() {
+			let mut inputs = vec![features.as_str()];
+			inputs.extend(split_feature_values.iter().map(|name| name.as_str()));
+			*features = self.value(
+				"Concat",
+				&inputs,
+				vec![attribute("axis", Attribute::Int(1))],
+			);
+		}
+		Ok(ensemble)
+	}
+
+	/// Add a feature that is one for examples that go right at a discrete split on the enum feature at `feature_index` and zero for examples that go left.
+	fn discrete_split_feature(
+		&mut self,
+		feature_groups: &[model::FeatureGroup],
+		feature_index: usize,
+		goes_right: &[bool],
+	) -> Result<String> {
+		let mut offset = 0;
+		let feature_group = feature_groups
+			.iter()
+			.find(|feature_group| {
+				offset += n_features(feature_group);
+				feature_index < offset
+			})
+			.ok_or_else(|| err!("invalid feature index {}", feature_index))?;
+		let source_column_name = match feature_group {
+			model::FeatureGroup::Identity(feature_group) => &feature_group.source_column_name,
+			_ => {
+				return Err(err!(
+					"discrete split on feature {} is not an enum feature",
+					feature_index
+				))
+			}
+		};
+		let (column, column_stats) = self.column(source_column_name)?;
+		let options = match column_stats {
+			model::ColumnStats::Enum(column_stats) => enum_options(column_stats),
+			_ => {
+				return Err(err!(
+					"discrete split on column {} is not an enum column",
+					source_column_name
+				))
+			}
+		};
+		let direction = |bin_index: usize| {
+			if goes_right.get(bin_index).cloned().unwrap_or(false) {
+				1.0
+			} else {
+				0.0
+			}
+		};
+		let values = (1..=options.len()).map(direction).collect();
+		Ok(self.ml_value(
+			"LabelEncoder",
+			&[&column],
+			vec![
+				attribute("keys_strings", Attribute::Strings(options)),
+				attribute("values_floats", Attribute::Floats(values)),
+				attribute("default_float", Attribute::Float(direction(0))),
+			],
+		))
+	}
+
+	fn into_model(self, model_id: &str) -> Message {
+		let mut graph = Message::default();
+		for node in self.nodes.iter() {
+			graph = graph.message(1, node);
+		}
+		graph = graph.string(2, "tangram");
+		for initializer in self.initializers.iter() {
+			graph = graph.message(5, initializer);
+		}
+		for input in self.inputs.iter() {
+			graph = graph.message(11, input);
+		}
+		for output in self.outputs.iter() {
+			graph = graph.message(12, output);
+		}
+		let mut opset_imports = vec![
+			Message::default().varint(2, OPSET_VERSION),
+			Message::default()
+				.string(1, ML_DOMAIN)
+				.varint(2, ML_OPSET_VERSION),
+		];
+		if self.uses_microsoft_domain {
+			opset_imports.push(
+				Message::default()
+					.string(1, MICROSOFT_DOMAIN)
+					.varint(2, MICROSOFT_OPSET_VERSION),
+			);
+		}
+		let mut model = Message::default()
+			.varint(1, IR_VERSION)
+			.string(2, "tangram")
+			.string(3, env!("CARGO_PKG_VERSION"))
+			.message(7, &graph);
+		for opset_import in opset_imports.iter() {
+			model = model.message(8, opset_import);
+		}
+		let metadata = Message::default()
+			.string(1, "tangram_model_id")
+			.string(2, model_id);
+		model.message(14, &metadata)
+	}
+}
+
+fn n_features(feature_group: &model::FeatureGroup) -> usize {
+	match feature_group {
+		model::FeatureGroup::Identity(_) => 1,
+		model::FeatureGroup::Normalized(_) => 1,
+		model::FeatureGroup::OneHotEncoded(feature_group) => feature_group.options.len() + 1,
+		model::FeatureGroup::BagOfWords(feature_group) => feature_group.tokens.len(),
+	}
+}
+
+fn enum_options(column_stats: &model::EnumColumnStats) -> Vec<String> {
+	column_stats
+		.histogram
+		.iter()
+		.map(|(option, _)| option.clone())
+		.collect()
+}
+
+/// These are the attributes shared by `TreeEnsembleRegressor` and `TreeEnsembleClassifier`. The weight attributes are prefixed with `target` for the regressor and `class` for the classifier.
+#[derive(Default)]
+struct TreeEnsemble {
+	tree_ids: Vec<i64>,
+	node_ids: Vec<i64>,
+	feature_ids: Vec<i64>,
+	modes: Vec<String>,
+	values: Vec<f32>,
+	true_node_ids: Vec<i64>,
+	false_node_ids: Vec<i64>,
+	weight_tree_ids: Vec<i64>,
+	weight_node_ids: Vec<i64>,
+	weight_ids: Vec<i64>,
+	weights: Vec<f32>,
+}
+
+impl TreeEnsemble {
+	fn with_zero_weights_for_class(mut self, class: i64) -> TreeEnsemble {
+		for leaf_index in 0..self.weights.len() {
+			self.weight_tree_ids.push(self.weight_tree_ids[leaf_index]);
+			self.weight_node_ids.push(self.weight_node_ids[leaf_index]);
+			self.weight_ids.push(class);
+			self.weights.push(0.0);
+		}
+		self
+	}
+
+	fn attributes(self, weights_prefix: &str) -> Vec<Message> {
+		// Invalid number values are NaN, which tangram sends right because `NaN <= split_value` is false.
+		let missing_value_tracks_true = vec![0; self.tree_ids.len()];
+		vec![
+			attribute("nodes_treeids", Attribute::Ints(self.tree_ids)),
+			attribute("nodes_nodeids", Attribute::Ints(self.node_ids)),
+			attribute("nodes_featureids", Attribute::Ints(self.feature_ids)),
+			attribute("nodes_modes", Attribute::Strings(self.modes)),
+			attribute("nodes_values", Attribute::Floats(self.values)),
+			attribute("nodes_truenodeids", Attribute::Ints(self.true_node_ids)),
+			attribute("nodes_falsenodeids", Attribute::Ints(self.false_node_ids)),
+			attribute(
+				"nodes_missing_value_tracks_true",
+				Attribute::Ints(missing_value_tracks_true),
+			),
+			attribute(
+				&format!("{}_treeids", weights_prefix),
+				Attribute::Ints(self.weight_tree_ids),
+			),
+			attribute(
+				&format!("{}_nodeids", weights_prefix),
+				Attribute::Ints(self.weight_node_ids),
+			),
+			attribute(
+				&format!("{}_ids", weights_prefix),
+				Attribute::Ints(self.weight_ids),
+			),
+			attribute(
+				&format!("{}_weights", weights_prefix),
+				Attribute::Floats(self.weights),
+			),
+		]
+	}
+}
+
+enum Attribute<'a> {
+	Int(i64),
+	Float(f32),
+	String(&'a str),
+	Ints(Vec<i64>),
+	Floats(Vec<f32>),
+	Strings(Vec<String>),
+}
+
+/// Build an `AttributeProto`.
+fn attribute(name: &str, value: Attribute) -> Message {
+	let attribute = Message::default().string(1, name);
+	match value {
+		Attribute::Float(value) => attribute.float(2, value).varint(20, 1),
+		Attribute::Int(value) => attribute.varint(3, value).varint(20, 2),
+		Attribute::String(value) => attribute.string(4, value).varint(20, 3),
+		Attribute::Floats(values) => attribute.packed_floats(7, &values).varint(20, 6),
+		Attribute::Ints(values) => attribute.packed_varints(8, &values).varint(20, 7),
+		Attribute::Strings(values) => values
+			.iter()
+			.fold(attribute, |attribute, value| attribute.string(9, value))
+			.varint(20, 8),
+	}
+}
+
+/// Build a `TensorProto` with no data.
+fn tensor(name: &str, dims: &[i64], data_type: i64) -> Message {
+	Message::default()
+		.packed_varints(1, dims)
+		.varint(2, data_type)
+		.string(8, name)
+}
+
+/// Build a `ValueInfoProto` for a tensor.
+fn value_info(name: &str, elem_type: i64, dims: &[Dimension]) -> Message {
+	let mut shape = Message::default();
+	for dim in dims {
+		let dim = match dim {
+			Dimension::Examples => Message::default().string(2, "N"),
+			Dimension::Value(value) => Message::default().varint(1, *value),
+		};
+		shape = shape.message(1, &dim);
+	}
+	let tensor_type = Message::default().varint(1, elem_type).message(2, &shape);
+	let type_proto = Message::default().message(1, &tensor_type);
+	Message::default().string(1, name).message(2, &type_proto)
+}
+
+/// A `Message` is an encoded protocol buffer message. ONNX only needs a handful of field types, so they are written by hand instead of with generated code.
+#[derive(Default)]
+struct Message(Vec<u8>);
+
+impl Message {
+	fn key(&mut self, field: u32, wire_type: u32) {
+		self.write_varint(u64::from((field << 3) | wire_type));
+	}
+
+	fn write_varint(&mut self, mut value: u64) {
+		while value >= 0x80 {
+			self.0.push((value as u8) | 0x80);
+			value >>= 7;
+		}
+		self.0.push(value as u8);
+	}
+
+	fn varint(mut self, field: u32, value: i64) -> Message {
+		self.key(field, 0);
+		// Negative values are encoded as their ten byte two's complement representation.
+		self.write_varint(value as u64);
+		self
+	}
+
+	fn float(mut self, field: u32, value: f32) -> Message {
+		self.key(field, 5);
+		self.0.extend_from_slice(&value.to_le_bytes());
+		self
+	}
+
+	fn bytes(mut self, field: u32, value: &[u8]) -> Message {
+		self.key(field, 2);
+		self.write_varint(value.len() as u64);
+		self.0.extend_from_slice(value);
+		self
+	}
+
+	fn string(self, field: u32, value: &str) -> Message {
+		self.bytes(field, value.as_bytes())
+	}
+
+	fn message(self, field: u32, value: &Message) -> Message {
+		self.bytes(field, &value.0)
+	}
+
+	fn packed_floats(self, field: u32, values: &[f32]) -> Message {
+		let bytes = values
+			.iter()
+			.flat_map(|value| value.to_le_bytes().to_vec())
+			.collect::<Vec<_>>();
+		self.bytes(field, &bytes)
+	}
+
+	fn packed_varints(self, field: u32, values: &[i64]) -> Message {
+		let mut packed = Message::default();
+		for value in values {
+			packed.write_varint(*value as u64);
+		}
+		self.bytes(field, &packed.0)
+	}
+}
+
+#[test]
+fn test_message_varint() {
+	let message = Message::default().varint(1, 150);
+	assert_eq!(message.0, vec![0x08, 0x96, 0x01]);
+	let message = Message::default().varint(1, -1);
+	assert_eq!(
+		message.0,
+		vec![0x08, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]
+	);
+	// Field numbers of 16 and above take two bytes for the key.
+	let message = Message::default().varint(20, 2);
+	assert_eq!(message.0, vec![0xa0, 0x01, 0x02]);
+}
+
+#[test]
+fn test_message_string() {
+	let message = Message::default().string(2, "testing");
+	assert_eq!(
+		message.0,
+		vec![0x12, 0x07, 0x74, 0x65, 0x73, 0x74, 0x69, 0x6e, 0x67]
+	);
+}
+
+#[test]
+fn test_message_message() {
+	let inner = Message::default().varint(1, 150);
+	let message = Message::default().message(3, &inner);
+	assert_eq!(message.0, vec![0x1a, 0x03, 0x08, 0x96, 0x01]);
+}
+
+#[test]
+fn test_message_floats() {
+	let message = Message::default().float(2, 1.0);
+	assert_eq!(message.0, vec![0x15, 0x00, 0x00, 0x80, 0x3f]);
+	let message = Message::default().packed_floats(7, &[1.0, -2.0]);
+	assert_eq!(
+		message.0,
+		vec![0x3a, 0x08, 0x00, 0x00, 0x80, 0x3f, 0x00, 0x00, 0x00, 0xc0]
+	);
+}
+
+#[test]
+fn test_message_packed_varints() {
+	let message = Message::default().packed_varints(4, &[3, 270, 86942]);
+	assert_eq!(
+		message.0,
+		vec![0x22, 0x06, 0x03, 0x8e, 0x02, 0x9e, 0xa7, 0x05]
+	);
+}
+
+#[test]
+fn test_attribute() {
+	let message = attribute("alpha", Attribute::Int(2));
+	assert_eq!(
+		message.0,
+		vec![0x0a, 0x05, 0x61, 0x6c, 0x70, 0x68, 0x61, 0x18, 0x02, 0xa0, 0x01, 0x02]
+	);
+}
+
+#[test]
+fn test_value_info() {
+	let message = value_info("x", FLOAT, &[Dimension::Examples, Dimension::Value(1)]);
+	assert_eq!(
+		message.0,
+		vec![
+			0x0a, 0x01, 0x78, 0x12, 0x0f, 0x0a, 0x0d, 0x08, 0x01, 0x12, 0x09, 0x0a, 0x03, 0x12,
+			0x01, 0x4e, 0x0a, 0x02, 0x08, 0x01
+		]
+	);
+}
@@ -0,0 +1,1269 @@
+/*!
+This module exports models to PMML 4.4.
+
+Linear models become a `RegressionModel`. Tree models become a `MiningModel` whose segments are `TreeModel`s, and classifiers chain the sum of each class's trees into a `RegressionModel` that computes the probabilities. Feature groups become derived fields in the model's `LocalTransformations`.
+
+Number columns are declared as floats so evaluators compare them with split values the way tangram does. Values of enum columns that are not one of the column's options are treated as missing, which is how tangram treats invalid values.
+*/
+
+use crate::model;
+use std::{borrow::Cow, fmt::Write};
+use tangram_util::{err, error::Result};
+
+pub fn export(model: &model::Model) -> Result<String> {
+	let (features, target, model_element) = match model {
+		model::Model::Regressor(model) => {
+			let target = Target {
+				name: &model.target_column_name,
+				classes: None,
+			};
+			match &model.model {
+				model::RegressionModel::Linear(inner_model) => {
+					let features =
+						Features::new(&model.overall_column_stats, &inner_model.feature_groups)?;
+					let table = regression_table(&features, &inner_model.weights, 1, 0)?
+						.attribute("intercept", inner_model.bias);
+					let element = Element::new("RegressionModel")
+						.attribute("functionName", "regression")
+						.attribute("normalizationMethod", "none")
+						.child(features.mining_schema(Some(&target)))
+						.child(target.output())
+						.child(features.local_transformations())
+						.child(table);
+					(features, target, element)
+				}
+				model::RegressionModel::Tree(inner_model) => {
+					let features =
+						Features::new(&model.overall_column_stats, &inner_model.feature_groups)?;
+					let trees = inner_model.trees.iter().collect::<Vec<_>>();
+					// The bias is added to the sum of the trees by rescaling the target.
+					let targets = Element::new("Targets").child(
+						Element::new("Target")
+							.attribute("field", target.name)
+							.attribute("rescaleConstant", inner_model.bias),
+					);
+					let element = Element::new("MiningModel")
+						.attribute("functionName", "regression")
+						.child(features.mining_schema(Some(&target)))
+						.child(target.output())
+						.child(targets)
+						.child(features.local_transformations())
+						.child(trees_segmentation(&features, &trees)?);
+					(features, target, element)
+				}
+			}
+		}
+		model::Model::BinaryClassifier(model) => {
+			let classes = vec![model.negative_class.as_str(), model.positive_class.as_str()];
+			let target = Target {
+				name: &model.target_column_name,
+				classes: Some(classes),
+			};
+			match &model.model {
+				model::BinaryClassificationModel::Linear(inner_model) => {
+					let features =
+						Features::new(&model.overall_column_stats, &inner_model.feature_groups)?;
+					// With the logit normalization, the first table computes the logit of the probability of its class, and the second table must be empty.
+					let positive_table = regression_table(&features, &inner_model.weights, 1, 0)?
+						.attribute("intercept", inner_model.bias)
+						.attribute("targetCategory", &model.positive_class);
+					let negative_table = Element::new("RegressionTable")
+						.attribute("intercept", 0)
+						.attribute("targetCategory", &model.negative_class);
+					let element = Element::new("RegressionModel")
+						.attribute("functionName", "classification")
+						.attribute("normalizationMethod", "logit")
+						.child(features.mining_schema(Some(&target)))
+						.child(target.output())
+						.child(features.local_transformations())
+						.child(positive_table)
+						.child(negative_table);
+					(features, target, element)
+				}
+				model::BinaryClassificationModel::Tree(inner_model) => {
+					let features =
+						Features::new(&model.overall_column_stats, &inner_model.feature_groups)?;
+					let trees = inner_model.trees.iter().collect::<Vec<_>>();
+					let logit = "logit".to_owned();
+					let sum = trees_model(&features, &trees, &logit)?;
+					let positive_table = logits_table(&logit, inner_model.bias)
+						.attribute("targetCategory", &model.positive_class);
+					let negative_table = Element::new("RegressionTable")
+						.attribute("intercept", 0)
+						.attribute("targetCategory", &model.negative_class);
+					let probabilities = Element::new("RegressionModel")
+						.attribute("functionName", "classification")
+						.attribute("normalizationMethod", "logit")
+						.child(mining_schema(&[&logit], Some(&target)))
+						.child(positive_table)
+						.child(negative_table);
+					let element = chain_model(&features, &target, vec![sum, probabilities]);
+					(features, target, element)
+				}
+			}
+		}
+		model::Model::MulticlassClassifier(model) => {
+			let classes = model.classes.iter().map(|class| class.as_str()).collect();
+			let target = Target {
+				name: &model.target_column_name,
+				classes: Some(classes),
+			};
+			let n_classes = model.classes.len();
+			match &model.model {
+				model::MulticlassClassificationModel::Linear(inner_model) => {
+					let features =
+						Features::new(&model.overall_column_stats, &inner_model.feature_groups)?;
+					let tables = model
+						.classes
+						.iter()
+						.enumerate()
+						.map(|(class_index, class)| {
+							Ok(regression_table(
+								&features,
+								&inner_model.weights,
+								n_classes,
+								class_index,
+							)?
+							.attribute("intercept", inner_model.biases[class_index])
+							.attribute("targetCategory", class))
+						})
+						.collect::<Result<Vec<_>>>()?;
+					let element = Element::new("RegressionModel")
+						.attribute("functionName", "classification")
+						.attribute("normalizationMethod", "softmax")
+						.child(features.mining_schema(Some(&target)))
+						.child(target.output())
+						.child(features.local_transformations())
+						.children(tables);
+					(features, target, element)
+				}
+				model::MulticlassClassificationModel::Tree(inner_model) => {
+					let features =
+						Features::new(&model.overall_column_stats, &inner_model.feature_groups)?;
+					let logits = model
+						.classes
+						.iter()
+						.map(|class| format!("logit({})", class))
+						.collect::<Vec<_>>();
+					// The trees are ordered by round and then by class.
+					let mut segments = (0..n_classes)
+						.map(|class_index| {
+							let trees = inner_model
+								.trees
+								.iter()
+								.skip(class_index)
+								.step_by(n_classes)
+								.collect::<Vec<_>>();
+							trees_model(&features, &trees, &logits[class_index])
+						})
+						.collect::<Result<Vec<_>>>()?;
+					let tables = model
+						.classes
+						.iter()
+						.enumerate()
+						.map(|(class_index, class)| {
+							logits_table(&logits[class_index], inner_model.biases[class_index])
+								.attribute("targetCategory", class)
+						});
+					let logits = logits
+						.iter()
+						.map(|logit| logit.as_str())
+						.collect::<Vec<_>>();
+					segments.push(
+						Element::new("RegressionModel")
+							.attribute("functionName", "classification")
+							.attribute("normalizationMethod", "softmax")
+							.child(mining_schema(&logits, Some(&target)))
+							.children(tables),
+					);
+					let element = chain_model(&features, &target, segments);
+					(features, target, element)
+				}
+			}
+		}
+	};
+	let header = Element::new("Header")
+		.attribute("description", "This model was exported from tangram.")
+		.child(
+			Element::new("Extension")
+				.attribute("name", "tangram_model_id")
+				.attribute("value", model.id()),
+		)
+		.child(
+			Element::new("Application")
+				.attribute("name", "tangram")
+				.attribute("version", env!("CARGO_PKG_VERSION")),
+		);
+	let data_fields = features
+		.columns
+		.iter()
+		.map(|column_stats| data_field(column_stats))
+		.chain(std::iter::once(target.data_field()))
+		.collect::<Vec<_>>();
+	let data_dictionary = Element::new("DataDictionary")
+		.attribute("numberOfFields", data_fields.len())
+		.children(data_fields);
+	let document = Element::new("PMML")
+		.attribute("xmlns", "http://www.dmg.org/PMML-4_4")
+		.attribute("version", "4.4")
+		.child(header)
+		.child(data_dictionary)
+		.child(model_element);
+	let mut output = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n".to_owned();
+	document.write(&mut output, 0);
+	Ok(output)
+}
+
+struct Target<'a> {
+	name: &'a str,
+	/// This is `None` for regressors.
+	classes: Option<Vec<&'a str>>,
+}
+
+impl<'a> Target<'a> {
+	fn data_field(&self) -> Element {
+		match &self.classes {
+			None => Element::new("DataField")
+				.attribute("name", self.name)
+				.attribute("optype", "continuous")
+				.attribute("dataType", "double"),
+			Some(classes) => Element::new("DataField")
+				.attribute("name", self.name)
+				.attribute("optype", "categorical")
+				.attribute("dataType", "string")
+				.children(
+					classes
+						.iter()
+						.map(|class| Element::new("Value").attribute("value", class)),
+				),
+		}
+	}
+
+	fn output(&self) -> Element {
+		match &self.classes {
+			None => Element::new("Output").child(
+				Element::new("OutputField")
+					.attribute("name", format!("predicted({})", self.name))
+					.attribute("optype", "continuous")
+					.attribute("dataType", "double")
+					.attribute("feature", "predictedValue"),
+			),
+			Some(classes) => Element::new("Output")
+				.child(
+					Element::new("OutputField")
+						.attribute("name", format!("predicted({})", self.name))
+						.attribute("optype", "categorical")
+						.attribute("dataType", "string")
+						.attribute("feature", "predictedValue"),
+				)
+				.children(classes.iter().map(|class| {
+					Element::new("OutputField")
+						.attribute("name", format!("probability({})", class))
+						.attribute("optype", "continuous")
+						.attribute("dataType", "double")
+						.attribute("feature", "probability")
+						.attribute("value", class)
+				})),
+		}
+	}
+}
+
+/// `Features` maps each of a model's features to the field that holds its value.
+struct Features<'a> {
+	/// These are the stats of the columns the features are computed from.
+	columns: Vec<&'a model::ColumnStats>,
+	features: Vec<Feature>,
+	derived_fields: Vec<Element>,
+}
+
+enum Feature {
+	Number {
+		field: String,
+	},
+	/// Tree models split on enum columns directly.
+	Enum {
+		field: String,
+		options: Vec<String>,
+	},
+}
+
+impl Feature {
+	fn field(&self) -> &str {
+		match self {
+			Feature::Number { field } => field,
+			Feature::Enum { field, .. } => field,
+		}
+	}
+}
+
+impl<'a> Features<'a> {
+	fn new(
+		column_stats: &'a [model::ColumnStats],
+		feature_groups: &[model::FeatureGroup],
+	) -> Result<Features<'a>> {
+		let mut features = Features {
+			columns: Vec::new(),
+			features: Vec::new(),
+			derived_fields: Vec::new(),
+		};
+		for feature_group in feature_groups {
+			match feature_group {
+				model::FeatureGroup::Identity(feature_group) => {
+					let column =
+						features.column(column_stats, &feature_group.source_column_name)?;
+					let feature = match column {
+						model::ColumnStats::Number(_) => Feature::Number {
+							field: feature_group.source_column_name.clone(),
+						},
+						model::ColumnStats::Enum(column) => Feature::Enum {
+							field: feature_group.source_column_name.clone(),
+							options: enum_options(column),
+						},
+						_ => {
+							return Err(err!(
+								"identity feature group for column {} has an unsupported column type",
+								feature_group.source_column_name
+							))
+						}
+					};
+					features.features.push(feature);
+				}
+				model::FeatureGroup::Normalized(feature_group) => {
+					features.normalized_feature(column_stats, feature_group)?;
+				}
+				model::FeatureGroup::OneHotEncoded(feature_group) => {
+					features.column(column_stats, &feature_group.source_column_name)?;
+					features.one_hot_encoded_features(feature_group);
+				}
+				model::FeatureGroup::BagOfWords(feature_group) => {
+					features.column(column_stats, &feature_group.source_column_name)?;
+					features.bag_of_words_features(feature_group);
+				}
+			}
+		}
+		Ok(features)
+	}
+
+	/// Find the stats for the column named `column_name` and add the column to the columns the features use.
+	fn column(
+		&mut self,
+		column_stats: &'a [model::ColumnStats],
+		column_name: &str,
+	) -> Result<&'a model::ColumnStats> {
+		let column = column_stats
+			.iter()
+			.find(|column_stats| column_stats.column_name() == column_name)
+			.ok_or_else(|| err!("the model has no column named {}", column_name))?;
+		if !self
+			.columns
+			.iter()
+			.any(|column| column.column_name() == column_name)
+		{
+			self.columns.push(column);
+		}
+		Ok(column)
+	}
+
+	fn normalized_feature(
+		&mut self,
+		column_stats: &'a [model::ColumnStats],
+		feature_group: &model::NormalizedFeatureGroup,
+	) -> Result<()> {
+		let column_name = &feature_group.source_column_name;
+		let column = self.column(column_stats, column_name)?;
+		// Enum columns are normalized by the number of their option, where invalid values are zero.
+		let (value, is_number) = match column {
+			model::ColumnStats::Number(_) => (field_ref(column_name), true),
+			model::ColumnStats::Enum(column) => {
+				let field = format!("option_number({})", column_name);
+				self.derived_fields
+					.push(derived_field(&field).child(enum_option_numbers(column_name, column)));
+				(field_ref(&field), false)
+			}
+			_ => {
+				return Err(err!(
+					"normalized feature group for column {} has an unsupported column type",
+					column_name
+				))
+			}
+		};
+		let mut value = if feature_group.variance == 0.0 {
+			constant(0.0)
+		} else {
+			apply("/")
+				.child(apply("-").child(value).child(constant(feature_group.mean)))
+				.child(constant(feature_group.variance.sqrt()))
+		};
+		// Invalid values of number columns have the feature value zero.
+		if is_number {
+			value = apply("if")
+				.child(apply("isMissing").child(field_ref(column_name)))
+				.child(constant(0.0))
+				.child(value);
+		}
+		let field = format!("normalized({})", column_name);
+		self.derived_fields.push(derived_field(&field).child(value));
+		self.features.push(Feature::Number { field });
+		Ok(())
+	}
+
+	/// The first feature is one for invalid values, and each of the others is one for its option.
+	fn one_hot_encoded_features(&mut self, feature_group: &model::OneHotEncodedFeatureGroup) {
+		let column_name = &feature_group.source_column_name;
+		let field = format!("one_hot_encoded({}, invalid)", column_name);
+		self.derived_fields.push(
+			derived_field(&field).child(
+				apply("if")
+					.child(apply("isMissing").child(field_ref(column_name)))
+					.child(constant(1.0))
+					.child(constant(0.0)),
+			),
+		);
+		self.features.push(Feature::Number { field });
+		for option in feature_group.options.iter() {
+			let field = format!("one_hot_encoded({}, {})", column_name, option);
+			self.derived_fields.push(
+				derived_field(&field).child(
+					Element::new("NormDiscrete")
+						.attribute("field", column_name)
+						.attribute("value", option)
+						.attribute("mapMissingTo", 0),
+				),
+			);
+			self.features.push(Feature::Number { field });
+		}
+	}
+
+	/// Each feature is one if its token occurs in the text and zero if the text is missing. To match the alphanumeric tokenizer, words of a single character are removed from the text, and then the text is split into words at characters that are not letters or numbers.
+	fn bag_of_words_features(&mut self, feature_group: &model::BagOfWordsFeatureGroup) {
+		match feature_group.tokenizer {
+			model::Tokenizer::Alphanumeric => {}
+		}
+		let column_name = &feature_group.source_column_name;
+		for entry in feature_group.tokens.iter() {
+			let field = format!("bag_of_words({}, {})", column_name, entry.token);
+			let text_index = Element::new("TextIndex")
+				.attribute("textField", column_name)
+				.attribute("localTermWeights", "binary")
+				.attribute("isCaseSensitive", "false")
+				.attribute("maxLevenshteinDistance", 0)
+				.attribute("wordSeparatorCharacterRE", "[^\\p{L}\\p{N}]+")
+				.child(
+					Element::new("TextIndexNormalization")
+						.attribute("inField", "string")
+						.attribute("outField", "stem")
+						.attribute("regexField", "regex")
+						.child(
+							Element::new("InlineTable").child(
+								Element::new("row")
+									.child(Element::new("string").text(
+										"(?<![\\p{L}\\p{N}])[\\p{L}\\p{N}](?![\\p{L}\\p{N}])",
+									))
+									.child(Element::new("stem").text(" "))
+									.child(Element::new("regex").text("true")),
+							),
+						),
+				)
+				.child(Element::new("Constant").text(entry.token.to_string()));
+			self.derived_fields.push(
+				derived_field(&field).child(
+					apply("if")
+						.child(apply("isMissing").child(field_ref(column_name)))
+						.child(constant(0.0))
+						.child(text_index),
+				),
+			);
+			self.features.push(Feature::Number { field });
+		}
+	}
+
+	fn mining_schema(&self, target: Option<&Target>) -> Element {
+		let fields = self
+			.columns
+			.iter()
+			.map(|column| column.column_name())
+			.collect::<Vec<_>>();
+		mining_schema(&fields, target)
+	}
+
+	fn local_transformations(&self) -> Element {
+		Element::new("LocalTransformations").children(self.derived_fields.iter().cloned())
+	}
+
+	/// This is the names of the fields the features use, without duplicates, for the mining schemas of nested models.
+	fn fields(&self) -> Vec<&str> {
+		let mut fields: Vec<&str> = Vec::new();
+		for feature in self.features.iter() {
+			if !fields.contains(&feature.field()) {
+				fields.push(feature.field());
+			}
+		}
+		fields
+	}
+}
+
+fn data_field(column_stats: &model::ColumnStats) -> Element {
+	let field = Element::new("DataField").attribute("name", column_stats.column_name());
+	match column_stats {
+		model::ColumnStats::Enum(column_stats) => field
+			.attribute("optype", "categorical")
+			.attribute("dataType", "string")
+			.children(
+				enum_options(column_stats)
+					.into_iter()
+					.map(|option| Element::new("Value").attribute("value", option)),
+			),
+		model::ColumnStats::Text(_) | model::ColumnStats::Unknown(_) => field
+			.attribute("optype", "categorical")
+			.attribute("dataType", "string"),
+		model::ColumnStats::Number(_) => field
+			.attribute("optype", "continuous")
+			.attribute("dataType", "float"),
+	}
+}
+
+fn mining_schema(fields: &[&str], target: Option<&Target>) -> Element {
+	let mut mining_schema = Element::new("MiningSchema");
+	if let Some(target) = target {
+		mining_schema = mining_schema.child(
+			Element::new("MiningField")
+				.attribute("name", target.name)
+				.attribute("usageType", "target"),
+		);
+	}
+	mining_schema.children(fields.iter().map(|field| {
+		Element::new("MiningField")
+			.attribute("name", field)
+			.attribute("invalidValueTreatment", "asMissing")
+	}))
+}
+
+/// Build a regression table with a coefficient for each feature. `weights` has `n_outputs` weights for each feature, and the table uses the weights for `output_index`.
+fn regression_table(
+	features: &Features,
+	weights: &[f32],
+	n_outputs: usize,
+	output_index: usize,
+) -> Result<Element> {
+	if weights.len() != features.features.len() * n_outputs {
+		return Err(err!(
+			"expected {} weights but found {}",
+			features.features.len() * n_outputs,
+			weights.len()
+		));
+	}
+	let predictors = features
+		.features
+		.iter()
+		.enumerate()
+		.map(|(feature_index, feature)| match feature {
+			Feature::Number { field } => Ok(Element::new("NumericPredictor")
+				.attribute("name", field)
+				.attribute(
+					"coefficient",
+					weights[feature_index * n_outputs + output_index],
+				)),
+			Feature::Enum { field, .. } => Err(err!(
+				"linear model uses enum column {} without encoding it",
+				field
+			)),
+		})
+		.collect::<Result<Vec<_>>>()?;
+	Ok(Element::new("RegressionTable").children(predictors))
+}
+
+/// Build a regression table that adds `bias` to the output of a previous segment.
+fn logits_table(logit: &str, bias: f32) -> Element {
+	Element::new("RegressionTable")
+		.attribute("intercept", bias)
+		.child(
+			Element::new("NumericPredictor")
+				.attribute("name", logit)
+				.attribute("coefficient", 1),
+		)
+}
+
+/// Build a model whose segments are evaluated in order, with each segment able to use the outputs of the previous ones, and whose result is the result of the last segment. The outer model declares the outputs, so the last segment does not.
+fn chain_model(features: &Features, target: &Target, models: Vec<Element>) -> Element {
+	let segments = models.into_iter().enumerate().map(|(index, model)| {
+		Element::new("Segment")
+			.attribute("id", index + 1)
+			.child(Element::new("True"))
+			.child(model)
+	});
+	Element::new("MiningModel")
+		.attribute("functionName", "classification")
+		.child(features.mining_schema(Some(target)))
+		.child(target.output())
+		.child(features.local_transformations())
+		.child(
+			Element::new("Segmentation")
+				.attribute("multipleModelMethod", "modelChain")
+				.children(segments),
+		)
+}
+
+/// Build a model that sums `trees` and outputs the sum in a field named `output`.
+fn trees_model(features: &Features, trees: &[&model::Tree], output: &str) -> Result<Element> {
+	Ok(Element::new("MiningModel")
+		.attribute("functionName", "regression")
+		.child(mining_schema(&features.fields(), None))
+		.child(
+			Element::new("Output").child(
+				Element::new("OutputField")
+					.attribute("name", output)
+					.attribute("optype", "continuous")
+					.attribute("dataType", "double")
+					.attribute("feature", "predictedValue")
+					.attribute("isFinalResult", "false"),
+			),
+		)
+		.child(trees_segmentation(features, trees)?))
+}
+
+fn trees_segmentation(features: &Features, trees: &[&model::Tree]) -> Result<Element> {
+	// A segmentation must have at least one segment, so a model with no trees gets a tree that always predicts zero.
+	let empty_tree = model::Tree {
+		nodes: vec![model::Node::Leaf(model::LeafNode {
+			value: 0.0,
+			examples_fraction: 1.0,
+		})],
+	};
+	let trees = if trees.is_empty() {
+		vec![&empty_tree]
+	} else {
+		trees.to_owned()
+	};
+	let segments = trees
+		.iter()
+		.enumerate()
+		.map(|(tree_index, tree)| {
+			let tree_model = Element::new("TreeModel")
+				.attribute("functionName", "regression")
+				.attribute("missingValueStrategy", "defaultChild")
+				.attribute("noTrueChildStrategy", "returnLastPrediction")
+				.attribute("splitCharacteristic", "binarySplit")
+				.child(mining_schema(&features.fields(), None))
+				.child(tree_node(features, tree, 0, Element::new("True"))?);
+			Ok(Element::new("Segment")
+				.attribute("id", tree_index + 1)
+				.child(Element::new("True"))
+				.child(tree_model))
+		})
+		.collect::<Result<Vec<_>>>()?;
+	Ok(Element::new("Segmentation")
+		.attribute("multipleModelMethod", "sum")
+		.children(segments))
+}
+
+/// Build the node at `node_index` and its descendants. The left child of a branch has the branch's split as its predicate, and the right child is chosen otherwise. When a feature is missing, the predicate is unknown and the branch's default child is chosen, which is the direction tangram sends invalid values.
+fn tree_node(
+	features: &Features,
+	tree: &model::Tree,
+	node_index: usize,
+	predicate: Element,
+) -> Result<Element> {
+	let node = tree
+		.nodes
+		.get(node_index)
+		.ok_or_else(|| err!("invalid node index {}", node_index))?;
+	let branch = match node {
+		model::Node::Leaf(leaf) => {
+			return Ok(Element::new("Node")
+				.attribute("id", node_index)
+				.attribute("score", leaf.value)
+				.child(predicate))
+		}
+		model::Node::Branch(branch) => branch,
+	};
+	let (left_predicate, invalid_values_go_left) = match &branch.split {
+		// Tangram sends invalid values right at continuous splits regardless of `invalid_values_direction`.
+		model::BranchSplit::Continuous(split) => {
+			let feature = features
+				.features
+				.get(split.feature_index)
+				.ok_or_else(|| err!("invalid feature index {}", split.feature_index))?;
+			let predicate = Element::new("SimplePredicate")
+				.attribute("field", feature.field())
+				.attribute("operator", "lessOrEqual")
+				.attribute("value", split.split_value);
+			(predicate, false)
+		}
+		model::BranchSplit::Discrete(split) => {
+			let (field, options) = match features.features.get(split.feature_index) {
+				Some(Feature::Enum { field, options }) => (field, options),
+				_ => {
+					return Err(err!(
+						"discrete split on feature {} is not an enum feature",
+						split.feature_index
+					))
+				}
+			};
+			let is_left = |direction: Option<&model::SplitDirection>| {
+				matches!(direction, Some(model::SplitDirection::Left))
+			};
+			let left_options = options
+				.iter()
+				.enumerate()
+				.filter(|(option_index, _)| is_left(split.directions.get(option_index + 1)))
+				.map(|(_, option)| option)
+				.collect::<Vec<_>>();
+			let right_options = options
+				.iter()
+				.enumerate()
+				.filter(|(option_index, _)| !is_left(split.directions.get(option_index + 1)))
+				.map(|(_, option)| option)
+				.collect::<Vec<_>>();
+			// A set predicate is unknown for missing values, so when no option goes left, the predicate that no option goes right is used instead of one that is always false.
+			let predicate = if !left_options.is_empty() {
+				set_predicate(field, "isIn", &left_options)
+			} else if !right_options.is_empty() {
+				set_predicate(field, "isNotIn", &right_options)
+			} else {
+				Element::new("False")
+			};
+			(predicate, is_left(split.directions.first()))
+		}
+	};
+	let default_child = if invalid_values_go_left {
+		branch.left_child_index
+	} else {
+		branch.right_child_index
+	};
+	Ok(Element::new("Node")
+		.attribute("id", node_index)
+		.attribute("defaultChild", default_child)
+		.child(predicate)
+		.child(tree_node(
+			features,
+			tree,
+			branch.left_child_index,
+			left_predicate,
+		)?)
+		.child(tree_node(
+			features,
+			tree,
+			branch.right_child_index,
+			Element::new("True"),
+		)?))
+}
+
+fn set_predicate(field: &str, operator: &str, options: &[&String]) -> Element {
+	let values = options
+		.iter()
+		.map(|option| format!("\"{}\"", option.replace('\\', "\\\\").replace('"', "\\\"")))
+		.collect::<Vec<_>>()
+		.join(" ");
+	Element::new("SimpleSetPredicate")
+		.attribute("field", field)
+		.attribute("booleanOperator", operator)
+		.child(
+			Element::new("Array")
+				.attribute("n", options.len())
+				.attribute("type", "string")
+				.text(values),
+		)
+}
+
+/// Map each option of an enum column to its number, starting at one, with zero for invalid values.
+fn enum_option_numbers(column_name: &str, column_stats: &model::EnumColumnStats) -> Element {
+	let rows = enum_options(column_stats)
+		.into_iter()
+		.enumerate()
+		.map(|(option_index, option)| {
+			Element::new("row")
+				.child(Element::new("input").text(option))
+				.child(Element::new("output").text((option_index + 1).to_string()))
+		});
+	Element::new("MapValues")
+		.attribute("outputColumn", "output")
+		.attribute("dataType", "double")
+		.attribute("mapMissingTo", 0)
+		.attribute("defaultValue", 0)
+		.child(
+			Element::new("FieldColumnPair")
+				.attribute("field", column_name)
+				.attribute("column", "input"),
+		)
+		.child(Element::new("InlineTable").children(rows))
+}
+
+fn enum_options(column_stats: &model::EnumColumnStats) -> Vec<String> {
+	column_stats
+		.histogram
+		.iter()
+		.map(|(option, _)| option.clone())
+		.collect()
+}
+
+fn derived_field(name: &str) -> Element {
+	Element::new("DerivedField")
+		.attribute("name", name)
+		.attribute("optype", "continuous")
+		.attribute("dataType", "double")
+}
+
+fn apply(function: &str) -> Element {
+	Element::new("Apply").attribute("function", function)
+}
+
+fn field_ref(field: &str) -> Element {
+	Element::new("FieldRef").attribute("field", field)
+}
+
+fn constant(value: f32) -> Element {
+	Element::new("Constant")
+		.attribute("dataType", "double")
+		.text(value.to_string())
+}
+
+/// This is an XML element. Like the protobuf messages in the ONNX exporter, elements are built with methods that take and return `self`.
+#[derive(Clone)]
+struct Element {
+	name: &'static str,
+	attributes: Vec<(&'static str, String)>,
+	children: Vec<Element>,
+	text: Option<String>,
+}
+
+impl Element {
+	fn new(name: &'static str) -> Element {
+		Element {
+			name,
+			attributes: Vec::new(),
+			children: Vec::new(),
+			text: None,
+		}
+	}
+
+	fn attribute(mut self, name: &'static str, value: impl ToString) -> Element {
+		self.attributes.push((name, value.to_string()));
+		self
+	}
+
+	fn child(mut self, child: Element) -> Element {
+		self.children.push(child);
+		self
+	}
+
+	fn children(mut self, children: impl IntoIterator<Item = Element>) -> Element {
+		self.children.extend(children);
+		self
+	}
+
+	fn text(mut self, text: impl Into<String>) -> Element {
+		self.text = Some(text.into());
+		self
+	}
+
+	fn write(&self, output: &mut String, depth: usize) {
+		let indent = "\t".repeat(depth);
+		write!(output, "{}<{}", indent, self.name).unwrap();
+		for (name, value) in self.attributes.iter() {
+			write!(output, " {}=\"{}\"", name, escape(value)).unwrap();
+		}
+		match (&self.text, self.children.is_empty()) {
+			(None, true) => writeln!(output, "/>").unwrap(),
+			(Some(text), true) => writeln!(output, ">{}</{}>", escape(text), self.name).unwrap(),
+			(text, false) => {
+				writeln!(output, ">").unwrap();
+				if let Some(text) = text {
+					writeln!(output, "{}\t{}", indent, escape(text)).unwrap();
+				}
+				for child in self.children.iter() {
+					child.write(output, depth + 1);
+				}
+				writeln!(output, "{}</{}>", indent, self.name).unwrap();
+			}
+		}
+	}
+}
+
+fn escape(value: &str) -> Cow<'_, str> {
+	if !value.contains(|c| matches!(c, '&' | '<' | '>' | '"' | '\'')) {
+		return Cow::Borrowed(value);
+	}
+	let mut escaped = String::with_capacity(value.len());
+	for c in value.chars() {
+		match c {
+			'&' => escaped.push_str("&amp;"),
+			'<' => escaped.push_str("&lt;"),
+			'>' => escaped.push_str("&gt;"),
+			'"' => escaped.push_str("&quot;"),
+			'\'' => escaped.push_str("&apos;"),
+			c => escaped.push(c),
+		}
+	}
+	Cow::Owned(escaped)
+}
+
+/// Train a small model with the given grid item on a dataset with a number column that has missing values, an enum column, and a text column, and return it.
+#[cfg(all(test, feature = "train"))]
+fn train_test_model(name: &str, task: &str, model: &str) -> model::Model {
+	let directory =
+		std::env::temp_dir().join(format!("tangram_test_pmml_{}_{}", name, std::process::id()));
+	std::fs::create_dir_all(&directory).unwrap();
+	let texts = [
+		"slow big car",
+		"fast small car",
+		"slow big truck",
+		"small truck",
+	];
+	let mut csv = "number,enum,text,target\n".to_owned();
+	for i in 0..60 {
+		let number = if i % 10 == 0 {
+			String::new()
+		} else {
+			(i % 13).to_string()
+		};
+		let enum_value = ["a", "b", "c"][i % 3];
+		let score = (i % 13) as f32 + (i % 3) as f32 * 4.0 + (i % 4) as f32 * 2.0;
+		let target = match task {
+			"regression" => score.to_string(),
+			"binary_classification" => if score > 12.0 { "yes" } else { "no" }.to_owned(),
+			_ => if score > 16.0 {
+				"high"
+			} else if score > 8.0 {
+				"medium"
+			} else {
+				"low"
+			}
+			.to_owned(),
+		};
+		writeln!(csv, "{},{},{},{}", number, enum_value, texts[i % 4], target).unwrap();
+	}
+	let data_path = directory.join("data.csv");
+	let config_path = directory.join("config.json");
+	std::fs::write(&data_path, csv).unwrap();
+	let grid_item = match model {
+		"tree" => r#"{"model":"tree","max_rounds":10}"#,
+		_ => r#"{"model":"linear"}"#,
+	};
+	let config = format!(
+		r#"{{"column_types":{{"number":{{"type":"number"}},"text":{{"type":"text"}}}},"grid":[{}]}}"#,
+		grid_item
+	);
+	std::fs::write(&config_path, config).unwrap();
+	let model = crate::train(
+		tangram_util::id::Id::new(),
+		Some(&data_path),
+		None,
+		None,
+		"target",
+		Some(&config_path),
+		&mut |_| {},
+	)
+	.unwrap();
+	std::fs::remove_dir_all(&directory).unwrap();
+	model
+}
+
+/// Find `pattern` in `pmml` and return its position, so tests can check the order of elements.
+#[cfg(test)]
+fn find(pmml: &str, pattern: &str) -> usize {
+	pmml.find(pattern)
+		.unwrap_or_else(|| panic!("expected the pmml to contain {}", pattern))
+}
+
+#[cfg(feature = "train")]
+#[test]
+fn test_export_linear_regressor() {
+	let model = train_test_model("linear_regressor", "regression", "linear");
+	let pmml = export(&model).unwrap();
+	find(
+		&pmml,
+		"<RegressionModel functionName=\"regression\" normalizationMethod=\"none\">",
+	);
+	// The number column is normalized, and its missing values have the feature value zero.
+	let normalized = find(&pmml, "<DerivedField name=\"normalized(number)\"");
+	find(&pmml[normalized..], "<Apply function=\"isMissing\">");
+	find(&pmml, "<NumericPredictor name=\"normalized(number)\"");
+	// Invalid values of the enum column are treated as missing, so they have the invalid feature.
+	find(
+		&pmml,
+		"<MiningField name=\"enum\" invalidValueTreatment=\"asMissing\"/>",
+	);
+	find(
+		&pmml,
+		"<DerivedField name=\"one_hot_encoded(enum, invalid)\"",
+	);
+	find(
+		&pmml,
+		"<NormDiscrete field=\"enum\" value=\"b\" mapMissingTo=\"0\"/>",
+	);
+	// The text column has a feature for each unigram and bigram, which matches the phrase.
+	find(&pmml, "<DerivedField name=\"bag_of_words(text, slow)\"");
+	let bigram = find(&pmml, "<DerivedField name=\"bag_of_words(text, slow big)\"");
+	let text_index = bigram + find(&pmml[bigram..], "<TextIndex textField=\"text\"");
+	let constant = bigram + find(&pmml[bigram..], "<Constant>slow big</Constant>");
+	assert!(text_index < constant);
+	find(
+		&pmml,
+		"<NumericPredictor name=\"bag_of_words(text, slow big)\"",
+	);
+}
+
+#[cfg(feature = "train")]
+#[test]
+fn test_export_tree_regressor() {
+	let model = train_test_model("tree_regressor", "regression", "tree");
+	let bias = match &model {
+		model::Model::Regressor(model::Regressor {
+			model: model::RegressionModel::Tree(model),
+			..
+		}) => model.bias,
+		_ => unreachable!(),
+	};
+	let pmml = export(&model).unwrap();
+	find(&pmml, "<MiningModel functionName=\"regression\">");
+	// The bias is added to the sum of the trees by the target's rescale constant.
+	find(
+		&pmml,
+		&format!("<Target field=\"target\" rescaleConstant=\"{}\"/>", bias),
+	);
+	find(&pmml, "<Segmentation multipleModelMethod=\"sum\">");
+	find(&pmml, "missingValueStrategy=\"defaultChild\"");
+	// Tree models split on the number and enum columns directly.
+	find(
+		&pmml,
+		"<MiningField name=\"number\" invalidValueTreatment=\"asMissing\"/>",
+	);
+	find(&pmml, "<DerivedField name=\"bag_of_words(text, slow big)\"");
+	assert!(!pmml.contains("normalized(number)"));
+	assert!(!pmml.contains("one_hot_encoded"));
+}
+
+#[cfg(feature = "train")]
+#[test]
+fn test_export_linear_binary_classifier() {
+	let model = train_test_model(
+		"linear_binary_classifier",
+		"binary_classification",
+		"linear",
+	);
+	let bias = match &model {
+		model::Model::BinaryClassifier(model::BinaryClassifier {
+			model: model::BinaryClassificationModel::Linear(model),
+			..
+		}) => model.bias,
+		_ => unreachable!(),
+	};
+	let pmml = export(&model).unwrap();
+	find(
+		&pmml,
+		"<RegressionModel functionName=\"classification\" normalizationMethod=\"logit\">",
+	);
+	// The positive class's table computes the logit, and the negative class's table is empty.
+	let positive = find(
+		&pmml,
+		&format!(
+			"<RegressionTable intercept=\"{}\" targetCategory=\"yes\">",
+			bias
+		),
+	);
+	let negative = find(
+		&pmml,
+		"<RegressionTable intercept=\"0\" targetCategory=\"no\"/>",
+	);
+	assert!(positive < negative);
+	find(&pmml, "<OutputField name=\"probability(yes)\"");
+}
+
+#[cfg(feature = "train")]
+#[test]
+fn test_export_tree_binary_classifier() {
+	let model = train_test_model("tree_binary_classifier", "binary_classification", "tree");
+	let bias = match &model {
+		model::Model::BinaryClassifier(model::BinaryClassifier {
+			model: model::BinaryClassificationModel::Tree(model),
+			..
+		}) => model.bias,
+		_ => unreachable!(),
+	};
+	let pmml = export(&model).unwrap();
+	// The first segment sums the trees into the logit, and the second applies the bias and the logit normalization.
+	let chain = find(&pmml, "<Segmentation multipleModelMethod=\"modelChain\">");
+	let sum = find(&pmml, "<Segmentation multipleModelMethod=\"sum\">");
+	let logit = find(
+		&pmml,
+		"<OutputField name=\"logit\" optype=\"continuous\" dataType=\"double\" feature=\"predictedValue\" isFinalResult=\"false\"/>",
+	);
+	// The nested model's segments have ids too, so the last segment with this id is the outer one.
+	let probabilities = pmml.rfind("<Segment id=\"2\">").unwrap();
+	assert!(chain < logit && logit < sum && sum < probabilities);
+	let normalization = find(
+		&pmml,
+		"<RegressionModel functionName=\"classification\" normalizationMethod=\"logit\">",
+	);
+	assert!(probabilities < normalization);
+	let positive = find(
+		&pmml,
+		&format!(
+			"<RegressionTable intercept=\"{}\" targetCategory=\"yes\">",
+			bias
+		),
+	);
+	assert!(normalization < positive);
+	find(
+		&pmml,
+		"<NumericPredictor name=\"logit\" coefficient=\"1\"/>",
+	);
+	find(
+		&pmml,
+		"<RegressionTable intercept=\"0\" targetCategory=\"no\"/>",
+	);
+}
+
+#[cfg(feature = "train")]
+#[test]
+fn test_export_linear_multiclass_classifier() {
+	let model = train_test_model(
+		"linear_multiclass_classifier",
+		"multiclass_classification",
+		"linear",
+	);
+	let pmml = export(&model).unwrap();
+	find(
+		&pmml,
+		"<RegressionModel functionName=\"classification\" normalizationMethod=\"softmax\">",
+	);
+	for class in &["low", "medium", "high"] {
+		find(&pmml, &format!("targetCategory=\"{}\">", class));
+		find(
+			&pmml,
+			&format!("<OutputField name=\"probability({})\"", class),
+		);
+	}
+}
+
+#[cfg(feature = "train")]
+#[test]
+fn test_export_tree_multiclass_classifier() {
+	let model = train_test_model(
+		"tree_multiclass_classifier",
+		"multiclass_classification",
+		"tree",
+	);
+	let (classes, biases) = match &model {
+		model::Model::MulticlassClassifier(model::MulticlassClassifier {
+			classes,
+			model: model::MulticlassClassificationModel::Tree(model),
+			..
+		}) => (classes.clone(), model.biases.clone()),
+		_ => unreachable!(),
+	};
+	let pmml = export(&model).unwrap();
+	// There is a segment that sums each class's trees into its logit, followed by a segment that applies the biases and the softmax normalization.
+	find(&pmml, "<Segmentation multipleModelMethod=\"modelChain\">");
+	let logits = classes
+		.iter()
+		.map(|class| find(&pmml, &format!("<OutputField name=\"logit({})\"", class)))
+		.collect::<Vec<_>>();
+	assert!(logits.windows(2).all(|logits| logits[0] < logits[1]));
+	// The nested models' segments have ids too, so the last segment with this id is the outer one.
+	let probabilities = pmml
+		.rfind(&format!("<Segment id=\"{}\">", classes.len() + 1))
+		.unwrap();
+	let normalization = find(
+		&pmml,
+		"<RegressionModel functionName=\"classification\" normalizationMethod=\"softmax\">",
+	);
+	assert!(logits.last().unwrap() < &probabilities && probabilities < normalization);
+	for (class, bias) in classes.iter().zip(biases.iter()) {
+		let table = find(
+			&pmml,
+			&format!(
+				"<RegressionTable intercept=\"{}\" targetCategory=\"{}\">",
+				bias, class
+			),
+		);
+		assert!(normalization < table);
+		find(
+			&pmml,
+			&format!(
+				"<NumericPredictor name=\"logit({})\" coefficient=\"1\"/>",
+				class
+			),
+		);
+	}
+}
+
+#[test]
+fn test_tree_node_invalid_values() {
+	// The root splits on x at 1.5, and tangram sends invalid values right even though the split's invalid values direction is left. Its right child splits on e, and sends a's and invalid values left.
+	let features = Features {
+		columns: Vec::new(),
+		features: vec![
+			Feature::Number {
+				field: "x".to_owned(),
+			},
+			Feature::Enum {
+				field: "e".to_owned(),
+				options: vec!["a".to_owned(), "b".to_owned()],
+			},
+		],
+		derived_fields: Vec::new(),
+	};
+	let tree = model::Tree {
+		nodes: vec![
+			model::Node::Branch(model::BranchNode {
+				left_child_index: 1,
+				right_child_index: 2,
+				split: model::BranchSplit::Continuous(model::BranchSplitContinuous {
+					feature_index: 0,
+					split_value: 1.5,
+					invalid_values_direction: true,
+				}),
+				examples_fraction: 1.0,
+			}),
+			model::Node::Leaf(model::LeafNode {
+				value: 1.0,
+				examples_fraction: 0.5,
+			}),
+			model::Node::Branch(model::BranchNode {
+				left_child_index: 3,
+				right_child_index: 4,
+				split: model::BranchSplit::Discrete(model::BranchSplitDiscrete {
+					feature_index: 1,
+					directions: vec![
+						model::SplitDirection::Left,
+						model::SplitDirection::Left,
+						model::SplitDirection::Right,
+					],
+				}),
+				examples_fraction: 0.5,
+			}),
+			model::Node::Leaf(model::LeafNode {
+				value: 2.0,
+				examples_fraction: 0.25,
+			}),
+			model::Node::Leaf(model::LeafNode {
+				value: 3.0,
+				examples_fraction: 0.25,
+			}),
+		],
+	};
+	let mut output = String::new();
+	tree_node(&features, &tree, 0, Element::new("True"))
+		.unwrap()
+		.write(&mut output, 0);
+	let expected = "<Node id=\"0\" defaultChild=\"2\">
+	<True/>
+	<Node id=\"1\" score=\"1\">
+		<SimplePredicate field=\"x\" operator=\"lessOrEqual\" value=\"1.5\"/>
+	</Node>
+	<Node id=\"2\" defaultChild=\"3\">
+		<True/>
+		<Node id=\"3\" score=\"2\">
+			<SimpleSetPredicate field=\"e\" booleanOperator=\"isIn\">
+				<Array n=\"1\" type=\"string\">&quot;a&quot;</Array>
+			</SimpleSetPredicate>
+		</Node>
+		<Node id=\"4\" score=\"3\">
+			<True/>
+		</Node>
+	</Node>
+</Node>
+";
+	assert_eq!(output, expected);
+}
@@ -0,0 +1,214 @@
+/*!
+This module exports tree models to XGBoost's JSON model format, the format written by `Booster.save_model` when the file name ends in `.json`.
+
+Features are exported the same way as for LightGBM. Enum features are categorical features, so XGBoost must be told to enable categorical support when loading data for models with enum columns.
+*/
+
+use super::{left_options, preorder, TreeFeatureType, TreeModel, TreeTask};
+use crate::model;
+use serde_json::json;
+use tangram_util::error::Result;
+
+pub fn export(model: &model::Model) -> Result<String> {
+	let model = TreeModel::new(model)?;
+	let (objective, base_score, num_class) = match model.task {
+		TreeTask::Regression => (
+			json!({
+				"name": "reg:squarederror",
+				"reg_loss_param": { "scale_pos_weight": "1" },
+			}),
+			"0",
+			0,
+		),
+		// XGBoost applies the inverse of the sigmoid to the base score for binary classifiers, so a base score of one half adds zero to the logits.
+		TreeTask::BinaryClassification => (
+			json!({
+				"name": "binary:logistic",
+				"reg_loss_param": { "scale_pos_weight": "1" },
+			}),
+			"0.5",
+			0,
+		),
+		TreeTask::MulticlassClassification { n_classes } => (
+			json!({
+				"name": "multi:softprob",
+				"softmax_multiclass_param": { "num_class": n_classes.to_string() },
+			}),
+			"0",
+			n_classes,
+		),
+	};
+	let n_features = model.features.len();
+	let n_outputs = model.n_outputs();
+	let trees = (0..model.n_trees())
+		.map(|tree_index| {
+			let leaf_offset = model.leaf_offset(tree_index);
+			match model.trees.get(tree_index) {
+				Some(tree) => tree_json(tree, tree_index, n_features, leaf_offset),
+				None => leaf_tree_json(tree_index, n_features, leaf_offset),
+			}
+		})
+		.collect::<Vec<_>>();
+	let tree_info = (0..model.n_trees())
+		.map(|tree_index| tree_index % n_outputs)
+		.collect::<Vec<_>>();
+	let iteration_indptr = (0..=model.n_trees() / n_outputs)
+		.map(|round_index| round_index * n_outputs)
+		.collect::<Vec<_>>();
+	let feature_names = model
+		.features
+		.iter()
+		.map(|feature| feature.name.as_str())
+		.collect::<Vec<_>>();
+	let feature_types = model
+		.features
+		.iter()
+		.map(|feature| match feature.feature_type {
+			TreeFeatureType::Number { .. } => "float",
+			TreeFeatureType::Enum { .. } => "c",
+		})
+		.collect::<Vec<_>>();
+	let output = json!({
+		"learner": {
+			"attributes": {},
+			"feature_names": feature_names,
+			"feature_types": feature_types,
+			"gradient_booster": {
+				"name": "gbtree",
+				"model": {
+					"gbtree_model_param": {
+						"num_parallel_tree": "1",
+						"num_trees": model.n_trees().to_string(),
+					},
+					"iteration_indptr": iteration_indptr,
+					"tree_info": tree_info,
+					"trees": trees,
+				},
+			},
+			"learner_model_param": {
+				"base_score": base_score,
+				"boost_from_average": "1",
+				"num_class": num_class.to_string(),
+				"num_feature": n_features.to_string(),
+				"num_target": "1",
+			},
+			"objective": objective,
+		},
+		"version": [1, 7, 0],
+	});
+	Ok(serde_json::to_string(&output)?)
+}
+
+/// XGBoost stores each of a tree's attributes in its own array, indexed by node. The root must be node zero, so the nodes are renumbered in preorder.
+fn tree_json(
+	tree: &model::Tree,
+	tree_index: usize,
+	n_features: usize,
+	leaf_offset: f64,
+) -> serde_json::Value {
+	let (nodes, positions) = preorder(tree);
+	let n_nodes = nodes.len();
+	let mut left_children = vec![-1i64; n_nodes];
+	let mut right_children = vec![-1i64; n_nodes];
+	let mut parents = vec![i32::MAX as i64; n_nodes];
+	let mut split_indices = vec![0; n_nodes];
+	let mut split_conditions = vec![0.0f64; n_nodes];
+	let mut split_type = vec![0; n_nodes];
+	let mut default_left = vec![0; n_nodes];
+	let mut base_weights = vec![0.0f64; n_nodes];
+	// XGBoost computes SHAP values with the sum of the hessians at each node, and any quantity proportional to the number of examples works.
+	let mut sum_hessian = vec![0.0f32; n_nodes];
+	let mut categories = Vec::new();
+	let mut categories_nodes = Vec::new();
+	let mut categories_segments = Vec::new();
+	let mut categories_sizes = Vec::new();
+	for (position, node) in nodes.iter().enumerate() {
+		match node.node {
+			model::Node::Branch(branch) => {
+				sum_hessian[position] = branch.examples_fraction;
+				let left = positions[branch.left_child_index];
+				let right = positions[branch.right_child_index];
+				left_children[position] = left as i64;
+				right_children[position] = right as i64;
+				parents[left] = position as i64;
+				parents[right] = position as i64;
+				match &branch.split {
+					model::BranchSplit::Continuous(split) => {
+						split_indices[position] = split.feature_index;
+						// XGBoost sends values less than the split condition left, and tangram sends values less than or equal to the split value left.
+						split_conditions[position] = next_up(split.split_value) as f64;
+						// Tangram sends invalid values right regardless of `invalid_values_direction` when predicting.
+						default_left[position] = 0;
+					}
+					model::BranchSplit::Discrete(split) => {
+						let (options, invalid_values_go_left) = left_options(&split.directions);
+						let n_options = split.directions.len().saturating_sub(1);
+						// XGBoost sends the categories in the set right.
+						let right_options = (0..n_options)
+							.filter(|option| !options.contains(option))
+							.collect::<Vec<_>>();
+						split_indices[position] = split.feature_index;
+						split_type[position] = 1;
+						default_left[position] = invalid_values_go_left as u8;
+						categories_nodes.push(position);
+						categories_segments.push(categories.len());
+						categories_sizes.push(right_options.len());
+						categories.extend(right_options);
+					}
+				}
+			}
+			model::Node::Leaf(leaf) => {
+				sum_hessian[position] = leaf.examples_fraction;
+				let value = leaf.value + leaf_offset;
+				split_conditions[position] = value;
+				base_weights[position] = value;
+			}
+		}
+	}
+	json!({
+		"base_weights": base_weights,
+		"categories": categories,
+		"categories_nodes": categories_nodes,
+		"categories_segments": categories_segments,
+		"categories_sizes": categories_sizes,
+		"default_left": default_left,
+		"id": tree_index,
+		"left_children": left_children,
+		"loss_changes": vec![0.0; n_nodes],
+		"parents": parents,
+		"right_children": right_children,
+		"split_conditions": split_conditions,
+		"split_indices": split_indices,
+		"split_type": split_type,
+		"sum_hessian": sum_hessian,
+		"tree_param": {
+			"num_deleted": "0",
+			"num_feature": n_features.to_string(),
+			"num_nodes": n_nodes.to_string(),
+			"size_leaf_vector": "1",
+		},
+	})
+}
+
+fn leaf_tree_json(tree_index: usize, n_features: usize, value: f64) -> serde_json::Value {
+	let tree = model::Tree {
+		nodes: vec![model::Node::Leaf(model::LeafNode {
+			value,
+			examples_fraction: 1.0,
+		})],
+	};
+	tree_json(&tree, tree_index, n_features, 0.0)
+}
+
+/// Retrieve the smallest float greater than `value`.
+fn next_up(value: f32) -> f32 {
+	if value.is_nan() || value == f32::INFINITY {
+		value
+	} else if value == 0.0 {
+		f32::from_bits(1)
+	} else if value > 0.0 {
+		f32::from_bits(value.to_bits() + 1)
+	} else {
+		f32::from_bits(value.to_bits() - 1)
+	}
+}
@@ -0,0 +1,438 @@
+/*!
+This module imports models in LightGBM's text model format, the format written by `Booster.save_model`.
+*/
+
+use super::{import_model, import_tree, ImportMetadata, ImportNode, ImportSplit, ImportTask};
+use crate::model;
+use std::{collections::HashMap, str::FromStr};
+use tangram_util::{err, error::Result};
+
+const CATEGORICAL_MASK: u8 = 1;
+const DEFAULT_LEFT_MASK: u8 = 2;
+
+enum MissingType {
+	None,
+	Zero,
+	NaN,
+}
+
+pub fn import(text: &str, metadata: &ImportMetadata) -> Result<model::Model> {
+	let mut header = HashMap::new();
+	let mut blocks: Vec<HashMap<&str, &str>> = Vec::new();
+	for line in text.lines().map(|line| line.trim()) {
+		if line == "end of trees" {
+			break;
+		}
+		let (key, value) = match line.find('=') {
+			Some(index) => (&line[..index], &line[index + 1..]),
+			None => {
+				if line == "average_output" {
+					return Err(err!(
+						"models that average their trees' outputs are not supported"
+					));
+				}
+				continue;
+			}
+		};
+		if key == "Tree" {
+			blocks.push(HashMap::new());
+		}
+		match blocks.last_mut() {
+			Some(block) => block.insert(key, value),
+			None => header.insert(key, value),
+		};
+	}
+	let n_features = parse::<i64>(&header, "max_feature_idx")? + 1;
+	if n_features as usize != metadata.columns.len() {
+		return Err(err!(
+			"the model has {} features but the metadata has {} columns",
+			n_features,
+			metadata.columns.len()
+		));
+	}
+	let objective = header
+		.get("objective")
+		.ok_or_else(|| err!("the model has no objective"))?;
+	let mut objective_parts = objective.split(' ');
+	let objective_name = objective_parts.next().unwrap_or("");
+	let objective_params = objective_parts
+		.filter_map(|part| {
+			let index = part.find(':')?;
+			Some((&part[..index], &part[index + 1..]))
+		})
+		.collect::<HashMap<_, _>>();
+	// Binary classifiers compute the probability as the sigmoid of the sum of the leaf values multiplied by this factor.
+	let mut leaf_scale = 1.0;
+	let task = match objective_name {
+		"regression" | "regression_l1" | "huber" | "fair" | "quantile" | "mape" => {
+			ImportTask::Regression
+		}
+		"binary" => {
+			leaf_scale = parse::<f64>(&objective_params, "sigmoid")?;
+			ImportTask::BinaryClassification
+		}
+		"multiclass" => ImportTask::MulticlassClassification {
+			n_classes: parse(&objective_params, "num_class")?,
+		},
+		_ => return Err(err!("objective {} is not supported", objective)),
+	};
+	let n_outputs = match task {
+		ImportTask::MulticlassClassification { n_classes } => n_classes,
+		_ => 1,
+	};
+	let n_trees_per_iteration = parse::<usize>(&header, "num_tree_per_iteration")?;
+	if n_trees_per_iteration != n_outputs {
+		return Err(err!(
+			"expected {} trees per iteration but found {}",
+			n_outputs,
+			n_trees_per_iteration
+		));
+	}
+	if blocks.len() % n_outputs != 0 {
+		return Err(err!(
+			"the model has {} trees, which is not a multiple of {}",
+			blocks.len(),
+			n_outputs
+		));
+	}
+	let trees = blocks
+		.iter()
+		.map(|block| {
+			let nodes = import_nodes(block, leaf_scale)?;
+			import_tree(&nodes, &metadata.columns)
+		})
+		.collect::<Result<Vec<_>>>()?;
+	import_model(metadata, task, vec![0.0; n_outputs], trees)
+}
+
+/// LightGBM numbers a tree's branches and leaves separately, so the leaves are numbered after the branches here. A child index that is not negative refers to a branch, and a negative child index `!i` refers to leaf `i`.
+fn import_nodes(block: &HashMap<&str, &str>, leaf_scale: f64) -> Result<Vec<ImportNode>> {
+	if block
+		.get("is_linear")
+		.map(|value| *value == "1")
+		.unwrap_or(false)
+	{
+		return Err(err!("linear trees are not supported"));
+	}
+	let n_leaves = parse::<usize>(block, "num_leaves")?;
+	let leaf_values = parse_list::<f64>(block, "leaf_value")?;
+	let leaf_counts = parse_list::<f64>(block, "leaf_count").ok();
+	if leaf_values.len() != n_leaves {
+		return Err(err!(
+			"expected {} leaf values but found {}",
+			n_leaves,
+			leaf_values.len()
+		));
+	}
+	let leaf = |leaf_index: usize| ImportNode::Leaf {
+		value: leaf_values[leaf_index] * leaf_scale,
+		weight: leaf_counts
+			.as_ref()
+			.and_then(|leaf_counts| leaf_counts.get(leaf_index).cloned()),
+	};
+	if n_leaves == 1 {
+		return Ok(vec![leaf(0)]);
+	}
+	let n_branches = n_leaves - 1;
+	let split_features = parse_list::<usize>(block, "split_feature")?;
+	let thresholds = parse_list::<f64>(block, "threshold")?;
+	let decision_types = parse_list::<u8>(block, "decision_type")?;
+	let left_children = parse_list::<i64>(block, "left_child")?;
+	let right_children = parse_list::<i64>(block, "right_child")?;
+	let internal_counts = parse_list::<f64>(block, "internal_count").ok();
+	let cat_boundaries = parse_list::<usize>(block, "cat_boundaries").unwrap_or_default();
+	let cat_threshold = parse_list::<u32>(block, "cat_threshold").unwrap_or_default();
+	for list_len in &[
+		split_features.len(),
+		thresholds.len(),
+		decision_types.len(),
+		left_children.len(),
+		right_children.len(),
+	] {
+		if *list_len != n_branches {
+			return Err(err!(
+				"expected {} branches but found {}",
+				n_branches,
+				list_len
+			));
+		}
+	}
+	let child_index = |child: i64| {
+		if child >= 0 {
+			child as usize
+		} else {
+			n_branches + !child as usize
+		}
+	};
+	let mut nodes = Vec::with_capacity(n_branches + n_leaves);
+	for branch_index in 0..n_branches {
+		let feature_index = split_features[branch_index];
+		let threshold = thresholds[branch_index];
+		let decision_type = decision_types[branch_index];
+		let missing_type = match (decision_type >> 2) & 3 {
+			0 => MissingType::None,
+			1 => MissingType::Zero,
+			2 => MissingType::NaN,
+			_ => return Err(err!("invalid decision type {}", decision_type)),
+		};
+		let split = if decision_type & CATEGORICAL_MASK == 0 {
+			let invalid_values_go_left = match missing_type {
+				// LightGBM replaces missing values with zero.
+				MissingType::None => 0.0 <= threshold,
+				MissingType::Zero => {
+					return Err(err!(
+						"models that treat zeros as missing values are not supported"
+					))
+				}
+				MissingType::NaN => decision_type & DEFAULT_LEFT_MASK != 0,
+			};
+			ImportSplit::Number {
+				feature_index,
+				split_value: f32_at_most(threshold),
+				invalid_values_go_left,
+			}
+		} else {
+			let cat_index = threshold as usize;
+			let (start, end) = match (
+				cat_boundaries.get(cat_index),
+				cat_boundaries.get(cat_index + 1),
+			) {
+				(Some(start), Some(end)) if start <= end && *end <= cat_threshold.len() => {
+					(*start, *end)
+				}
+				_ => return Err(err!("invalid categorical split {}", cat_index)),
+			};
+			let words = &cat_threshold[start..end];
+			let options = (0..words.len() * 32)
+				.filter(|option| words[option / 32] & (1 << (option % 32)) != 0)
+				.collect::<Vec<_>>();
+			// LightGBM sends missing values right, unless the missing type is not NaN, in which case it replaces them with category zero.
+			let invalid_values_go_left = match missing_type {
+				MissingType::NaN => false,
+				_ => options.contains(&0),
+			};
+			ImportSplit::Options {
+				feature_index,
+				options,
+				options_go_left: true,
+				invalid_values_go_left,
+			}
+		};
+		nodes.push(ImportNode::Branch {
+			left_child_index: child_index(left_children[branch_index]),
+			right_child_index: child_index(right_children[branch_index]),
+			split,
+			weight: internal_counts
+				.as_ref()
+				.and_then(|internal_counts| internal_counts.get(branch_index).cloned()),
+		});
+	}
+	nodes.extend((0..n_leaves).map(leaf));
+	Ok(nodes)
+}
+
+/// LightGBM compares features with thresholds as doubles, and tangram compares them as floats, so the threshold becomes the largest float that is less than or equal to it.
+fn f32_at_most(value: f64) -> f32 {
+	let rounded = value as f32;
+	if rounded as f64 > value {
+		if rounded > 0.0 {
+			f32::from_bits(rounded.to_bits() - 1)
+		} else if rounded == 0.0 {
+			-f32::from_bits(1)
+		} else {
+			f32::from_bits(rounded.to_bits() + 1)
+		}
+	} else {
+		rounded
+	}
+}
+
+fn parse<T: FromStr>(values: &HashMap<&str, &str>, key: &str) -> Result<T> {
+	let value = values
+		.get(key)
+		.ok_or_else(|| err!("the model has no {}", key))?;
+	value
+		.parse()
+		.map_err(|_| err!("invalid value for {}: {}", key, value))
+}
+
+fn parse_list<T: FromStr>(values: &HashMap<&str, &str>, key: &str) -> Result<Vec<T>> {
+	let value = values
+		.get(key)
+		.ok_or_else(|| err!("the model has no {}", key))?;
+	value
+		.split_whitespace()
+		.map(|item| {
+			item.parse()
+				.map_err(|_| err!("invalid value for {}: {}", key, item))
+		})
+		.collect()
+}
+
+#[cfg(test)]
+fn test_metadata() -> ImportMetadata {
+	ImportMetadata {
+		target_column_name: "y".to_owned(),
+		classes: Some(vec!["false".to_owned(), "true".to_owned()]),
+		columns: vec![
+			super::ImportColumn::Number {
+				name: "a".to_owned(),
+			},
+			super::ImportColumn::Enum {
+				name: "b".to_owned(),
+				options: vec!["x".to_owned(), "y".to_owned(), "z".to_owned()],
+			},
+		],
+	}
+}
+
+#[test]
+fn test_import_regressor() {
+	// The root splits on a at 1.5 and sends missing values left. Its left child splits on the index of b's option at 0.5, with missing values replaced by zero.
+	let text = "
+		tree
+		version=v3
+		num_class=1
+		num_tree_per_iteration=1
+		max_feature_idx=1
+		objective=regression
+
+		Tree=0
+		num_leaves=3
+		split_feature=0 1
+		threshold=1.5 0.5
+		decision_type=10 0
+		left_child=1 -1
+		right_child=-3 -2
+		leaf_value=1 2 3
+		leaf_count=2 3 5
+		internal_count=10 5
+
+		end of trees
+	";
+	let model = import(text, &test_metadata()).unwrap();
+	let tree_regressor = match model {
+		model::Model::Regressor(model::Regressor {
+			model: model::RegressionModel::Tree(tree_regressor),
+			..
+		}) => tree_regressor,
+		_ => panic!(),
+	};
+	assert!(f32::abs(tree_regressor.bias) < f32::EPSILON);
+	assert_eq!(tree_regressor.trees.len(), 1);
+	let nodes = &tree_regressor.trees[0].nodes;
+	assert_eq!(nodes.len(), 5);
+	match &nodes[0] {
+		model::Node::Branch(model::BranchNode {
+			left_child_index: 1,
+			right_child_index: 4,
+			split:
+				model::BranchSplit::Continuous(model::BranchSplitContinuous {
+					feature_index: 0,
+					split_value,
+					invalid_values_direction: false,
+				}),
+			examples_fraction,
+		}) => {
+			assert!(f32::abs(split_value - 1.5) < f32::EPSILON);
+			assert!(f32::abs(examples_fraction - 1.0) < f32::EPSILON);
+		}
+		_ => panic!(),
+	}
+	match &nodes[1] {
+		model::Node::Branch(model::BranchNode {
+			left_child_index: 2,
+			right_child_index: 3,
+			split:
+				model::BranchSplit::Discrete(model::BranchSplitDiscrete {
+					feature_index: 1,
+					directions,
+				}),
+			examples_fraction,
+		}) => {
+			assert!(matches!(
+				directions.as_slice(),
+				[
+					model::SplitDirection::Left,
+					model::SplitDirection::Left,
+					model::SplitDirection::Right,
+					model::SplitDirection::Right,
+				]
+			));
+			assert!(f32::abs(examples_fraction - 0.5) < f32::EPSILON);
+		}
+		_ => panic!(),
+	}
+	for (node, (expected_value, expected_examples_fraction)) in
+		nodes[2..]
+			.iter()
+			.zip(vec![(1.0, 0.2), (2.0, 0.3), (3.0, 0.5)])
+	{
+		match node {
+			model::Node::Leaf(model::LeafNode {
+				value,
+				examples_fraction,
+			}) => {
+				assert!(f64::abs(value - expected_value) < f64::EPSILON);
+				assert!(f32::abs(examples_fraction - expected_examples_fraction) < f32::EPSILON);
+			}
+			_ => panic!(),
+		}
+	}
+}
+
+#[test]
+fn test_import_binary_classifier() {
+	// The leaf values are multiplied by the sigmoid parameter.
+	let text = "
+		max_feature_idx=1
+		objective=binary sigmoid:2
+		num_tree_per_iteration=1
+
+		Tree=0
+		num_leaves=1
+		leaf_value=0.25
+	";
+	let model = import(text, &test_metadata()).unwrap();
+	let tree_binary_classifier = match model {
+		model::Model::BinaryClassifier(model::BinaryClassifier {
+			model: model::BinaryClassificationModel::Tree(tree_binary_classifier),
+			..
+		}) => tree_binary_classifier,
+		_ => panic!(),
+	};
+	match tree_binary_classifier.trees[0].nodes.as_slice() {
+		[model::Node::Leaf(model::LeafNode { value, .. })] => {
+			assert!(f64::abs(value - 0.5) < f64::EPSILON);
+		}
+		_ => panic!(),
+	}
+}
+
+#[test]
+fn test_import_errors() {
+	let text = "
+		max_feature_idx=0
+		objective=regression
+		num_tree_per_iteration=1
+	";
+	assert!(import(text, &test_metadata()).is_err());
+	let text = "
+		max_feature_idx=1
+		objective=lambdarank
+		num_tree_per_iteration=1
+	";
+	assert!(import(text, &test_metadata()).is_err());
+}
+
+#[test]
+fn test_f32_at_most() {
+	assert!(f32::abs(f32_at_most(1.5) - 1.5) < f32::EPSILON);
+	// 0.1 rounds up to the nearest float, so the threshold is the float below it.
+	let value = f32_at_most(0.1);
+	assert!((value as f64) < 0.1);
+	assert!(f32::from_bits(value.to_bits() + 1) as f64 > 0.1);
+	let value = f32_at_most(-0.1);
+	assert!((value as f64) <= -0.1);
+	assert!(f32::from_bits(value.to_bits() - 1) as f64 > -0.1);
+}
@@ -0,0 +1,528 @@
+/*!
+This module imports tree models trained with other machine learning tools, so they can be served by tangram and shown in the app.
+
+Models written by other tools record their trees but not the columns their features came from, so importing a model requires an [`ImportMetadata`] describing the columns in the order of the model's features. Enum columns are expected to have been encoded the way [`crate::export`] encodes them, with the zero based index of the option, and `NaN` for invalid values.
+
+The other tools compute no statistics tangram can use, so an imported model's column stats only list its columns, its metrics are `NaN`, and it has no SHAP summaries, partial dependences, or slice metrics.
+*/
+
+use crate::model;
+use tangram_util::{err, error::Result, id::Id};
+
+pub mod lightgbm;
+pub mod xgboost;
+
+#[derive(serde::Deserialize, Debug)]
+pub struct ImportMetadata {
+	pub target_column_name: String,
+	/// These are the classes for classification models, in the order of the model's outputs. For binary classifiers, the negative class comes first.
+	pub classes: Option<Vec<String>>,
+	pub columns: Vec<ImportColumn>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum ImportColumn {
+	#[serde(rename = "number")]
+	Number { name: String },
+	#[serde(rename = "enum")]
+	Enum { name: String, options: Vec<String> },
+}
+
+impl ImportColumn {
+	fn name(&self) -> &str {
+		match self {
+			ImportColumn::Number { name } => name,
+			ImportColumn::Enum { name, .. } => name,
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ImportTask {
+	Regression,
+	BinaryClassification,
+	MulticlassClassification { n_classes: usize },
+}
+
+/// This is a node of a tree in another tool's format. `left_child_index` and `right_child_index` index into the tree's nodes, and the root is the first node.
+enum ImportNode {
+	Branch {
+		left_child_index: usize,
+		right_child_index: usize,
+		split: ImportSplit,
+		/// This is the number of training examples, or the sum of their hessians, that reached this node, if the format records it.
+		weight: Option<f64>,
+	},
+	Leaf {
+		value: f64,
+		weight: Option<f64>,
+	},
+}
+
+enum ImportSplit {
+	/// Values less than or equal to `split_value` go left.
+	Number {
+		feature_index: usize,
+		split_value: f32,
+		invalid_values_go_left: bool,
+	},
+	/// The zero based options in `options` go left if `options_go_left` is true, and the other options go the other way.
+	Options {
+		feature_index: usize,
+		options: Vec<usize>,
+		options_go_left: bool,
+		invalid_values_go_left: bool,
+	},
+}
+
+impl ImportNode {
+	fn weight(&self) -> Option<f64> {
+		match self {
+			ImportNode::Branch { weight, .. } => *weight,
+			ImportNode::Leaf { weight, .. } => *weight,
+		}
+	}
+}
+
+/// Convert a tree from another tool's format. Tangram requires a node's children to come after it, so the nodes are renumbered in preorder.
+fn import_tree(nodes: &[ImportNode], columns: &[ImportColumn]) -> Result<model::Tree> {
+	if nodes.is_empty() {
+		return Err(err!("tree has no nodes"));
+	}
+	let root_weight = nodes[0].weight().filter(|weight| *weight > 0.0);
+	let mut tree_nodes = Vec::with_capacity(nodes.len());
+	// Each entry is the index of a node to visit, the examples fraction to use if the format records no weights, and the position of the parent and whether this node is its left child.
+	let mut stack = vec![(0, 1.0, None)];
+	while let Some((node_index, fallback_examples_fraction, parent)) = stack.pop() {
+		if tree_nodes.len() == nodes.len() {
+			return Err(err!("tree has a cycle"));
+		}
+		let node = nodes
+			.get(node_index)
+			.ok_or_else(|| err!("invalid node index {}", node_index))?;
+		let position = tree_nodes.len();
+		if let Some((parent_position, is_left_child)) = parent {
+			if let model::Node::Branch(branch) = &mut tree_nodes[parent_position] {
+				if is_left_child {
+					branch.left_child_index = position;
+				} else {
+					branch.right_child_index = position;
+				}
+			}
+		}
+		let examples_fraction = match (node.weight(), root_weight) {
+			(Some(weight), Some(root_weight)) => (weight / root_weight) as f32,
+			_ => fallback_examples_fraction,
+		};
+		let tree_node = match node {
+			ImportNode::Branch {
+				left_child_index,
+				right_child_index,
+				split,
+				..
+			} => {
+				stack.push((
+					*right_child_index,
+					examples_fraction / 2.0,
+					Some((position, false)),
+				));
+				stack.push((
+					*left_child_index,
+					examples_fraction / 2.0,
+					Some((position, true)),
+				));
+				model::Node::Branch(model::BranchNode {
+					left_child_index: 0,
+					right_child_index: 0,
+					split: import_split(split, columns)?,
+					examples_fraction,
+				})
+			}
+			ImportNode::Leaf { value, .. } => model::Node::Leaf(model::LeafNode {
+				value: *value,
+				examples_fraction,
+			}),
+		};
+		tree_nodes.push(tree_node);
+	}
+	Ok(model::Tree { nodes: tree_nodes })
+}
+
+fn import_split(split: &ImportSplit, columns: &[ImportColumn]) -> Result<model::BranchSplit> {
+	let feature_index = match split {
+		ImportSplit::Number { feature_index, .. } => *feature_index,
+		ImportSplit::Options { feature_index, .. } => *feature_index,
+	};
+	let column = columns
+		.get(feature_index)
+		.ok_or_else(|| err!("the metadata has no column for feature {}", feature_index))?;
+	let direction = |is_left: bool| {
+		if is_left {
+			model::SplitDirection::Left
+		} else {
+			model::SplitDirection::Right
+		}
+	};
+	match (split, column) {
+		(
+			ImportSplit::Number {
+				split_value,
+				invalid_values_go_left,
+				..
+			},
+			ImportColumn::Number { .. },
+		) => Ok(model::BranchSplit::Continuous(
+			model::BranchSplitContinuous {
+				feature_index,
+				split_value: *split_value,
+				invalid_values_direction: !invalid_values_go_left,
+			},
+		)),
+		// A number split on an enum column compares the index of the option, so it becomes a discrete split.
+		(
+			ImportSplit::Number {
+				split_value,
+				invalid_values_go_left,
+				..
+			},
+			ImportColumn::Enum { options, .. },
+		) => {
+			let mut directions = vec![direction(*invalid_values_go_left)];
+			directions
+				.extend((0..options.len()).map(|option| direction(option as f32 <= *split_value)));
+			Ok(model::BranchSplit::Discrete(model::BranchSplitDiscrete {
+				feature_index,
+				directions,
+			}))
+		}
+		(
+			ImportSplit::Options {
+				options: split_options,
+				options_go_left,
+				invalid_values_go_left,
+				..
+			},
+			ImportColumn::Enum { options, .. },
+		) => {
+			let mut directions = vec![direction(*invalid_values_go_left)];
+			directions.extend(
+				(0..options.len())
+					.map(|option| direction(split_options.contains(&option) == *options_go_left)),
+			);
+			Ok(model::BranchSplit::Discrete(model::BranchSplitDiscrete {
+				feature_index,
+				directions,
+			}))
+		}
+		(ImportSplit::Options { .. }, ImportColumn::Number { name }) => Err(err!(
+			"the model has a categorical split on number column {}",
+			name
+		)),
+	}
+}
+
+/// Build a tangram model from the trees of an imported model. `trees` are in round major order, and `biases` has one bias for each tree in a round.
+fn import_model(
+	metadata: &ImportMetadata,
+	task: ImportTask,
+	biases: Vec<f32>,
+	trees: Vec<model::Tree>,
+) -> Result<model::Model> {
+	let n_features = metadata.columns.len();
+	let id = Id::new().to_string();
+	let column_stats = || {
+		metadata
+			.columns
+			.iter()
+			.map(import_column_stats)
+			.collect::<Vec<_>>()
+	};
+	let feature_groups = || {
+		metadata
+			.columns
+			.iter()
+			.map(|column| {
+				model::FeatureGroup::Identity(model::IdentityFeatureGroup {
+					source_column_name: column.name().to_owned(),
+				})
+			})
+			.collect::<Vec<_>>()
+	};
+	let feature_importances = compute_feature_importances(&trees, n_features);
+	let n_rounds = trees.len() / biases.len().max(1);
+	let max_leaf_nodes = trees
+		.iter()
+		.map(|tree| {
+			tree.nodes
+				.iter()
+				.filter(|node| matches!(node, model::Node::Leaf(_)))
+				.count()
+		})
+		.max()
+		.unwrap_or(1);
+	let grid = vec![model::GridItem::Tree(model::TreeGridItem {
+		hyperparameters: train_options(max_leaf_nodes, n_rounds),
+		model_comparison_metric_value: f32::NAN,
+		duration: f32::NAN,
+	})];
+	let target_column_name = metadata.target_column_name.clone();
+	let classes = match (task, &metadata.classes) {
+		(ImportTask::Regression, _) => Vec::new(),
+		(ImportTask::BinaryClassification, Some(classes)) if classes.len() == 2 => classes.clone(),
+		(ImportTask::MulticlassClassification { n_classes }, Some(classes))
+			if classes.len() == n_classes =>
+		{
+			classes.clone()
+		}
+		(ImportTask::BinaryClassification, _) => {
+			return Err(err!("the metadata must list the 2 classes"))
+		}
+		(ImportTask::MulticlassClassification { n_classes }, _) => {
+			return Err(err!("the metadata must list the {} classes", n_classes))
+		}
+	};
+	let target_column_stats = || match task {
+		ImportTask::Regression => import_column_stats(&ImportColumn::Number {
+			name: target_column_name.clone(),
+		}),
+		_ => import_column_stats(&ImportColumn::Enum {
+			name: target_column_name.clone(),
+			options: classes.clone(),
+		}),
+	};
+	let stats_settings = model::StatsSettings {
+		number_histogram_max_size: 100,
+	};
+	let model = match task {
+		ImportTask::Regression => model::Model::Regressor(model::Regressor {
+			id,
+			target_column_name: target_column_name.clone(),
+			train_row_count: 0,
+			test_row_count: 0,
+			stats_settings,
+			overall_column_stats: column_stats(),
+			overall_target_column_stats: target_column_stats(),
+			train_column_stats: column_stats(),
+			train_target_column_stats: target_column_stats(),
+			test_column_stats: column_stats(),
+			test_target_column_stats: target_column_stats(),
+			test_metrics: regression_metrics(),
+			test_metrics_confidence_intervals: None,
+			baseline_metrics: regression_metrics(),
+			model: model::RegressionModel::Tree(model::TreeRegressor {
+				bias: biases[0],
+				trees,
+				train_options: train_options(max_leaf_nodes, n_rounds),
+				feature_groups: feature_groups(),
+				losses: None,
+				feature_importances,
+			}),
+			comparison_metric: model::RegressionComparisonMetric::RootMeanSquaredError,
+			grid,
+			best_grid_item_index: 0,
+			shap_summary: None,
+			partial_dependences: None,
+			slice_metrics: None,
+			prediction_intervals: None,
+		}),
+		ImportTask::BinaryClassification => {
+			model::Model::BinaryClassifier(model::BinaryClassifier {
+				id,
+				target_column_name: target_column_name.clone(),
+				negative_class: classes[0].clone(),
+				positive_class: classes[1].clone(),
+				train_row_count: 0,
+				test_row_count: 0,
+				stats_settings,
+				overall_column_stats: column_stats(),
+				overall_target_column_stats: target_column_stats(),
+				train_column_stats: column_stats(),
+				train_target_column_stats: target_column_stats(),
+				test_column_stats: column_stats(),
+				test_target_column_stats: target_column_stats(),
+				test_metrics: binary_classification_metrics(),
+				test_metrics_confidence_intervals: None,
+				baseline_metrics: binary_classification_metrics(),
+				model: model::BinaryClassificationModel::Tree(model::TreeBinaryClassifier {
+					bias: biases[0],
+					trees,
+					train_options: train_options(max_leaf_nodes, n_rounds),
+					feature_groups: feature_groups(),
+					losses: None,
+					feature_importances,
+				}),
+				comparison_metric: model::BinaryClassificationComparisonMetric::AUCROC,
+				grid,
+				best_grid_item_index: 0,
+				shap_summary: None,
+				partial_dependences: None,
+				slice_metrics: None,
+				fairness_metrics: None,
+			})
+		}
+		ImportTask::MulticlassClassification { n_classes } => {
+			model::Model::MulticlassClassifier(model::MulticlassClassifier {
+				id,
+				target_column_name: target_column_name.clone(),
+				classes: classes.clone(),
+				train_row_count: 0,
+				test_row_count: 0,
+				stats_settings,
+				overall_column_stats: column_stats(),
+				overall_target_column_stats: target_column_stats(),
+				train_column_stats: column_stats(),
+				train_target_column_stats: target_column_stats(),
+				test_column_stats: column_stats(),
+				test_target_column_stats: target_column_stats(),
+				test_metrics: multiclass_classification_metrics(n_classes),
+				test_metrics_confidence_intervals: None,
+				baseline_metrics: multiclass_classification_metrics(n_classes),
+				model: model::MulticlassClassificationModel::Tree(
+					model::TreeMulticlassClassifier {
+						n_classes,
+						n_rounds,
+						biases,
+						trees,
+						train_options: train_options(max_leaf_nodes, n_rounds),
+						feature_groups: feature_groups(),
+						losses: None,
+						feature_importances,
+					},
+				),
+				comparison_metric: model::MulticlassClassificationComparisonMetric::Accuracy,
+				grid,
+				best_grid_item_index: 0,
+				shap_summaries: None,
+				partial_dependences: None,
+				slice_metrics: None,
+			})
+		}
+	};
+	Ok(model)
+}
+
+fn import_column_stats(column: &ImportColumn) -> model::ColumnStats {
+	match column {
+		ImportColumn::Number { name } => model::ColumnStats::Number(model::NumberColumnStats {
+			column_name: name.clone(),
+			invalid_count: 0,
+			unique_count: 0,
+			histogram: None,
+			min: f32::NAN,
+			max: f32::NAN,
+			mean: f32::NAN,
+			variance: f32::NAN,
+			std: f32::NAN,
+			p25: f32::NAN,
+			p50: f32::NAN,
+			p75: f32::NAN,
+		}),
+		ImportColumn::Enum { name, options } => model::ColumnStats::Enum(model::EnumColumnStats {
+			column_name: name.clone(),
+			invalid_count: 0,
+			histogram: options.iter().map(|option| (option.clone(), 0)).collect(),
+			unique_count: options.len() as u64,
+		}),
+	}
+}
+
+fn regression_metrics() -> model::RegressionMetrics {
+	model::RegressionMetrics {
+		mse: f32::NAN,
+		rmse: f32::NAN,
+		mae: f32::NAN,
+		r2: f32::NAN,
+	}
+}
+
+/// The app reads the metrics at each threshold, so this has the same thresholds as the metrics computed when training.
+fn binary_classification_metrics() -> model::BinaryClassificationMetrics {
+	let n_thresholds = 101;
+	let thresholds = (0..n_thresholds)
+		.map(|i| model::BinaryClassificationMetricsForThreshold {
+			threshold: (i + 1) as f32 / (n_thresholds + 1) as f32,
+			true_positives: 0,
+			false_positives: 0,
+			true_negatives: 0,
+			false_negatives: 0,
+			accuracy: f32::NAN,
+			precision: f32::NAN,
+			recall: f32::NAN,
+			f1_score: f32::NAN,
+			true_positive_rate: f32::NAN,
+			false_positive_rate: f32::NAN,
+		})
+		.collect();
+	model::BinaryClassificationMetrics {
+		auc_roc: f32::NAN,
+		thresholds,
+	}
+}
+
+fn multiclass_classification_metrics(n_classes: usize) -> model::MulticlassClassificationMetrics {
+	let class_metrics = (0..n_classes)
+		.map(|_| model::ClassMetrics {
+			true_positives: 0,
+			false_positives: 0,
+			true_negatives: 0,
+			false_negatives: 0,
+			accuracy: f32::NAN,
+			precision: f32::NAN,
+			recall: f32::NAN,
+			f1_score: f32::NAN,
+		})
+		.collect();
+	model::MulticlassClassificationMetrics {
+		class_metrics,
+		accuracy: f32::NAN,
+		precision_unweighted: f32::NAN,
+		precision_weighted: f32::NAN,
+		recall_unweighted: f32::NAN,
+		recall_weighted: f32::NAN,
+	}
+}
+
+/// The options an imported model was trained with are unknown, so only the ones that can be read from the trees are filled in.
+fn train_options(max_leaf_nodes: usize, n_rounds: usize) -> model::TreeModelTrainOptions {
+	model::TreeModelTrainOptions {
+		binned_features_layout: model::BinnedFeaturesLayout::ColumnMajor,
+		compute_loss: false,
+		early_stopping_options: None,
+		l2_regularization: f32::NAN,
+		learning_rate: f32::NAN,
+		max_depth: None,
+		max_examples_for_computing_bin_thresholds: 0,
+		max_leaf_nodes: max_leaf_nodes as u64,
+		max_rounds: n_rounds as u64,
+		max_valid_bins_for_number_features: 0,
+		min_examples_per_node: 0,
+		min_gain_to_split: f32::NAN,
+		min_sum_hessians_per_node: f32::NAN,
+		smoothing_factor_for_discrete_bin_sorting: f32::NAN,
+		supplemental_l2_regularization_for_discrete_splits: f32::NAN,
+	}
+}
+
+/// Like models trained by tangram, the importance of a feature is the fraction of branches that split on it.
+fn compute_feature_importances(trees: &[model::Tree], n_features: usize) -> Vec<f32> {
+	let mut feature_importances = vec![0.0; n_features];
+	for tree in trees.iter() {
+		for node in tree.nodes.iter() {
+			if let model::Node::Branch(branch) = node {
+				let feature_index = match &branch.split {
+					model::BranchSplit::Continuous(split) => split.feature_index,
+					model::BranchSplit::Discrete(split) => split.feature_index,
+				};
+				feature_importances[feature_index] += 1.0;
+			}
+		}
+	}
+	let total = feature_importances.iter().sum::<f32>();
+	if total > 0.0 {
+		for feature_importance in feature_importances.iter_mut() {
+			*feature_importance /= total;
+		}
+	}
+	feature_importances
+}
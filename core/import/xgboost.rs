@@ -0,0 +1,424 @@
+/*!
+This module imports models in XGBoost's JSON model format, the format written by `Booster.save_model` when the file name ends in `.json`.
+*/
+
+use super::{import_model, import_tree, ImportMetadata, ImportNode, ImportSplit, ImportTask};
+use crate::model;
+use tangram_util::{err, error::Result};
+
+#[derive(serde::Deserialize)]
+struct XGBoostModel {
+	learner: Learner,
+}
+
+#[derive(serde::Deserialize)]
+struct Learner {
+	gradient_booster: GradientBooster,
+	learner_model_param: LearnerModelParam,
+	objective: Objective,
+}
+
+#[derive(serde::Deserialize)]
+struct GradientBooster {
+	name: String,
+	model: Option<GBTreeModel>,
+}
+
+#[derive(serde::Deserialize)]
+struct GBTreeModel {
+	gbtree_model_param: GBTreeModelParam,
+	tree_info: Vec<usize>,
+	trees: Vec<Tree>,
+}
+
+#[derive(serde::Deserialize)]
+struct GBTreeModelParam {
+	num_parallel_tree: String,
+}
+
+#[derive(serde::Deserialize)]
+struct LearnerModelParam {
+	base_score: String,
+	num_class: String,
+	num_feature: String,
+}
+
+#[derive(serde::Deserialize)]
+struct Objective {
+	name: String,
+}
+
+#[derive(serde::Deserialize)]
+struct Tree {
+	left_children: Vec<i64>,
+	right_children: Vec<i64>,
+	split_indices: Vec<usize>,
+	split_conditions: Vec<f32>,
+	default_left: Vec<Flag>,
+	#[serde(default)]
+	split_type: Vec<u8>,
+	#[serde(default)]
+	categories: Vec<usize>,
+	#[serde(default)]
+	categories_nodes: Vec<usize>,
+	#[serde(default)]
+	categories_segments: Vec<usize>,
+	#[serde(default)]
+	categories_sizes: Vec<usize>,
+	#[serde(default)]
+	sum_hessian: Vec<f64>,
+}
+
+/// Older versions of XGBoost write `default_left` as booleans and newer versions write it as integers.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum Flag {
+	Bool(bool),
+	Int(u8),
+}
+
+impl Flag {
+	fn is_set(&self) -> bool {
+		match self {
+			Flag::Bool(value) => *value,
+			Flag::Int(value) => *value != 0,
+		}
+	}
+}
+
+pub fn import(text: &str, metadata: &ImportMetadata) -> Result<model::Model> {
+	let learner = serde_json::from_str::<XGBoostModel>(text)?.learner;
+	let n_features = parse_param::<usize>(&learner.learner_model_param.num_feature)?;
+	if n_features != metadata.columns.len() {
+		return Err(err!(
+			"the model has {} features but the metadata has {} columns",
+			n_features,
+			metadata.columns.len()
+		));
+	}
+	let task = match learner.objective.name.as_str() {
+		"reg:squarederror" | "reg:linear" | "reg:pseudohubererror" | "reg:absoluteerror" => {
+			ImportTask::Regression
+		}
+		"binary:logistic" => ImportTask::BinaryClassification,
+		"multi:softprob" | "multi:softmax" => ImportTask::MulticlassClassification {
+			n_classes: parse_param(&learner.learner_model_param.num_class)?,
+		},
+		objective => return Err(err!("objective {} is not supported", objective)),
+	};
+	let gbtree_model = match (
+		learner.gradient_booster.name.as_str(),
+		learner.gradient_booster.model,
+	) {
+		("gbtree", Some(gbtree_model)) => gbtree_model,
+		(name, _) => return Err(err!("booster {} is not supported", name)),
+	};
+	if parse_param::<usize>(&gbtree_model.gbtree_model_param.num_parallel_tree)? != 1 {
+		return Err(err!(
+			"models with more than one parallel tree are not supported"
+		));
+	}
+	let n_outputs = match task {
+		ImportTask::MulticlassClassification { n_classes } => n_classes,
+		_ => 1,
+	};
+	// Tangram requires the trees to be ordered by round and then by output.
+	let is_round_major = gbtree_model.trees.len() % n_outputs == 0
+		&& gbtree_model
+			.tree_info
+			.iter()
+			.enumerate()
+			.all(|(tree_index, output)| *output == tree_index % n_outputs);
+	if !is_round_major || gbtree_model.tree_info.len() != gbtree_model.trees.len() {
+		return Err(err!("the model's trees are not ordered by round"));
+	}
+	// XGBoost adds the base score to the margin after applying the inverse of the link function to it.
+	let base_score = learner
+		.learner_model_param
+		.base_score
+		.trim_matches(|c| c == '[' || c == ']')
+		.split(',')
+		.map(|value| parse_param::<f32>(value.trim()))
+		.collect::<Result<Vec<_>>>()?;
+	let base_score = |output: usize| {
+		let base_score = base_score.get(output).or_else(|| base_score.first());
+		base_score.cloned().unwrap_or(0.0)
+	};
+	let biases = (0..n_outputs)
+		.map(|output| match task {
+			ImportTask::BinaryClassification => {
+				let base_score = base_score(output);
+				(base_score / (1.0 - base_score)).ln()
+			}
+			_ => base_score(output),
+		})
+		.collect();
+	let trees = gbtree_model
+		.trees
+		.iter()
+		.map(|tree| {
+			let nodes = import_nodes(tree)?;
+			import_tree(&nodes, &metadata.columns)
+		})
+		.collect::<Result<Vec<_>>>()?;
+	import_model(metadata, task, biases, trees)
+}
+
+fn import_nodes(tree: &Tree) -> Result<Vec<ImportNode>> {
+	let n_nodes = tree.left_children.len();
+	for list_len in &[
+		tree.right_children.len(),
+		tree.split_indices.len(),
+		tree.split_conditions.len(),
+		tree.default_left.len(),
+	] {
+		if *list_len != n_nodes {
+			return Err(err!("expected {} nodes but found {}", n_nodes, list_len));
+		}
+	}
+	let mut nodes = Vec::with_capacity(n_nodes);
+	for node_index in 0..n_nodes {
+		let weight = tree.sum_hessian.get(node_index).cloned();
+		let left_child_index = tree.left_children[node_index];
+		let right_child_index = tree.right_children[node_index];
+		if left_child_index < 0 {
+			nodes.push(ImportNode::Leaf {
+				value: tree.split_conditions[node_index] as f64,
+				weight,
+			});
+			continue;
+		}
+		let feature_index = tree.split_indices[node_index];
+		let invalid_values_go_left = tree.default_left[node_index].is_set();
+		let is_categorical = tree.split_type.get(node_index).cloned().unwrap_or(0) == 1;
+		let split = if is_categorical {
+			let categories_index = tree
+				.categories_nodes
+				.iter()
+				.position(|categories_node| *categories_node == node_index)
+				.ok_or_else(|| err!("node {} has no categories", node_index))?;
+			let options = tree
+				.categories_segments
+				.get(categories_index)
+				.zip(tree.categories_sizes.get(categories_index))
+				.and_then(|(start, size)| tree.categories.get(*start..*start + *size))
+				.ok_or_else(|| err!("node {} has invalid categories", node_index))?;
+			// XGBoost sends the categories in the set right and every other category left.
+			ImportSplit::Options {
+				feature_index,
+				options: options.to_owned(),
+				options_go_left: false,
+				invalid_values_go_left,
+			}
+		} else {
+			// XGBoost sends values less than the split condition left, and tangram sends values less than or equal to the split value left.
+			ImportSplit::Number {
+				feature_index,
+				split_value: next_down(tree.split_conditions[node_index]),
+				invalid_values_go_left,
+			}
+		};
+		nodes.push(ImportNode::Branch {
+			left_child_index: left_child_index as usize,
+			right_child_index: right_child_index as usize,
+			split,
+			weight,
+		});
+	}
+	Ok(nodes)
+}
+
+/// Retrieve the largest float less than `value`.
+fn next_down(value: f32) -> f32 {
+	if value.is_nan() || value == f32::NEG_INFINITY {
+		value
+	} else if value == 0.0 {
+		-f32::from_bits(1)
+	} else if value > 0.0 {
+		f32::from_bits(value.to_bits() - 1)
+	} else {
+		f32::from_bits(value.to_bits() + 1)
+	}
+}
+
+fn parse_param<T: std::str::FromStr>(value: &str) -> Result<T> {
+	value
+		.parse()
+		.map_err(|_| err!("invalid model parameter {}", value))
+}
+
+#[cfg(test)]
+fn test_metadata() -> ImportMetadata {
+	ImportMetadata {
+		target_column_name: "y".to_owned(),
+		classes: Some(vec!["false".to_owned(), "true".to_owned()]),
+		columns: vec![
+			super::ImportColumn::Number {
+				name: "a".to_owned(),
+			},
+			super::ImportColumn::Enum {
+				name: "b".to_owned(),
+				options: vec!["x".to_owned(), "y".to_owned(), "z".to_owned()],
+			},
+		],
+	}
+}
+
+#[cfg(test)]
+fn test_model_json(objective: &str, base_score: &str, tree_info: &str, trees: &str) -> String {
+	format!(
+		r#"{{
+			"learner": {{
+				"gradient_booster": {{
+					"name": "gbtree",
+					"model": {{
+						"gbtree_model_param": {{ "num_parallel_tree": "1" }},
+						"tree_info": {},
+						"trees": {}
+					}}
+				}},
+				"learner_model_param": {{
+					"base_score": "{}",
+					"num_class": "0",
+					"num_feature": "2"
+				}},
+				"objective": {{ "name": "{}" }}
+			}}
+		}}"#,
+		tree_info, trees, base_score, objective
+	)
+}
+
+#[test]
+fn test_import_binary_classifier() {
+	// The root sends values of a less than 1.5 and missing values left.
+	let trees = r#"[{
+		"left_children": [1, -1, -1],
+		"right_children": [2, -1, -1],
+		"split_indices": [0, 0, 0],
+		"split_conditions": [1.5, -0.5, 0.5],
+		"default_left": [1, 0, 0],
+		"sum_hessian": [4.0, 1.0, 3.0]
+	}]"#;
+	let text = test_model_json("binary:logistic", "2E-1", "[0]", trees);
+	let model = import(&text, &test_metadata()).unwrap();
+	let tree_binary_classifier = match model {
+		model::Model::BinaryClassifier(model::BinaryClassifier {
+			model: model::BinaryClassificationModel::Tree(tree_binary_classifier),
+			..
+		}) => tree_binary_classifier,
+		_ => panic!(),
+	};
+	// The base score is a probability, so the bias is its log odds, ln(0.2 / 0.8).
+	let expected_bias = -f32::ln(4.0);
+	assert!(f32::abs(tree_binary_classifier.bias - expected_bias) < 1e-6);
+	let nodes = &tree_binary_classifier.trees[0].nodes;
+	match &nodes[0] {
+		model::Node::Branch(model::BranchNode {
+			left_child_index: 1,
+			right_child_index: 2,
+			split:
+				model::BranchSplit::Continuous(model::BranchSplitContinuous {
+					feature_index: 0,
+					split_value,
+					invalid_values_direction: false,
+				}),
+			..
+		}) => {
+			assert!(*split_value < 1.5);
+			assert!(f32::from_bits(split_value.to_bits() + 1) >= 1.5);
+		}
+		_ => panic!(),
+	}
+	for (node, (expected_value, expected_examples_fraction)) in
+		nodes[1..].iter().zip(vec![(-0.5, 0.25), (0.5, 0.75)])
+	{
+		match node {
+			model::Node::Leaf(model::LeafNode {
+				value,
+				examples_fraction,
+			}) => {
+				assert!(f64::abs(value - expected_value) < f64::EPSILON);
+				assert!(f32::abs(examples_fraction - expected_examples_fraction) < f32::EPSILON);
+			}
+			_ => panic!(),
+		}
+	}
+}
+
+#[test]
+fn test_import_categorical_split() {
+	// The root sends option y of b right, and the other options and missing values left.
+	let trees = r#"[{
+		"left_children": [1, -1, -1],
+		"right_children": [2, -1, -1],
+		"split_indices": [1, 0, 0],
+		"split_conditions": [0.0, 1.0, 2.0],
+		"default_left": [true, false, false],
+		"split_type": [1, 0, 0],
+		"categories": [1],
+		"categories_nodes": [0],
+		"categories_segments": [0],
+		"categories_sizes": [1]
+	}]"#;
+	let text = test_model_json("reg:squarederror", "5E-1", "[0]", trees);
+	let model = import(&text, &test_metadata()).unwrap();
+	let tree_regressor = match model {
+		model::Model::Regressor(model::Regressor {
+			model: model::RegressionModel::Tree(tree_regressor),
+			..
+		}) => tree_regressor,
+		_ => panic!(),
+	};
+	assert!(f32::abs(tree_regressor.bias - 0.5) < f32::EPSILON);
+	match &tree_regressor.trees[0].nodes[0] {
+		model::Node::Branch(model::BranchNode {
+			split:
+				model::BranchSplit::Discrete(model::BranchSplitDiscrete {
+					feature_index: 1,
+					directions,
+				}),
+			examples_fraction,
+			..
+		}) => {
+			assert!(matches!(
+				directions.as_slice(),
+				[
+					model::SplitDirection::Left,
+					model::SplitDirection::Left,
+					model::SplitDirection::Right,
+					model::SplitDirection::Left,
+				]
+			));
+			assert!(f32::abs(examples_fraction - 1.0) < f32::EPSILON);
+		}
+		_ => panic!(),
+	}
+}
+
+#[test]
+fn test_import_errors() {
+	let tree = r#"{
+		"left_children": [-1],
+		"right_children": [-1],
+		"split_indices": [0],
+		"split_conditions": [1.0],
+		"default_left": [0]
+	}"#;
+	let trees = format!("[{}]", tree);
+	let text = test_model_json("rank:pairwise", "5E-1", "[0]", &trees);
+	assert!(import(&text, &test_metadata()).is_err());
+	// The tree info must list an output for every tree.
+	let trees = format!("[{}, {}]", tree, tree);
+	let text = test_model_json("reg:squarederror", "5E-1", "[0]", &trees);
+	assert!(import(&text, &test_metadata()).is_err());
+}
+
+#[test]
+fn test_next_down() {
+	assert!(next_down(1.0) < 1.0);
+	assert_eq!(f32::from_bits(next_down(1.0).to_bits() + 1), 1.0);
+	assert!(next_down(0.0) < 0.0);
+	assert!(next_down(-1.0) < -1.0);
+	assert_eq!(next_down(f32::NEG_INFINITY), f32::NEG_INFINITY);
+}
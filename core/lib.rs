@@ -1,20 +1,34 @@
 /*!
+This crate trains models and makes predictions with them. Building it without the default `train` feature leaves only the modules needed to load models and make predictions.
 */
 
+#[cfg(feature = "train")]
 mod config;
+pub mod counterfactual;
+pub mod export;
+#[cfg(feature = "train")]
 mod features;
+#[cfg(feature = "train")]
 mod grid;
+pub mod import;
+pub mod metadata;
 pub mod model;
+#[cfg(feature = "train")]
+mod partial_dependence;
 pub mod predict;
+#[cfg(feature = "train")]
+mod prediction_intervals;
+#[cfg(feature = "train")]
+mod shap;
+#[cfg(feature = "train")]
+mod slice_metrics;
+#[cfg(feature = "train")]
 mod stats;
+#[cfg(feature = "train")]
 mod test;
+#[cfg(feature = "train")]
 mod train;
 
-pub use self::{
-	predict::predict,
-	train::{train, Progress},
-};
-
-#[cfg(target_arch = "wasm32")]
-#[global_allocator]
-static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+pub use self::predict::predict;
+#[cfg(feature = "train")]
+pub use self::train::{train, Progress};
@@ -0,0 +1,209 @@
+/*!
+This module defines `ModelMetadata`, a description of a model that is useful to programs making predictions with it, such as the columns it expects as input and how it performed on the test set.
+*/
+
+use crate::model;
+
+#[derive(serde::Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelMetadata {
+	pub id: String,
+	pub task: Task,
+	pub target_column_name: String,
+	pub columns: Vec<Column>,
+	/// These are the classes for classifiers, in the order used for probabilities. For binary classifiers, the negative class is first.
+	pub classes: Option<Vec<String>>,
+	/// This is the threshold binary classifiers use when `PredictOptions` are not provided.
+	pub default_threshold: Option<f32>,
+	pub test_metrics: TestMetrics,
+}
+
+#[derive(serde::Serialize, Debug)]
+pub enum Task {
+	#[serde(rename = "regression")]
+	Regression,
+	#[serde(rename = "binary_classification")]
+	BinaryClassification,
+	#[serde(rename = "multiclass_classification")]
+	MulticlassClassification,
+}
+
+#[derive(serde::Serialize, Debug)]
+#[serde(tag = "type")]
+pub enum Column {
+	#[serde(rename = "unknown")]
+	Unknown(UnknownColumn),
+	#[serde(rename = "number")]
+	Number(NumberColumn),
+	#[serde(rename = "enum")]
+	Enum(EnumColumn),
+	#[serde(rename = "text")]
+	Text(TextColumn),
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct UnknownColumn {
+	pub name: String,
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct NumberColumn {
+	pub name: String,
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct EnumColumn {
+	pub name: String,
+	pub options: Vec<String>,
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct TextColumn {
+	pub name: String,
+}
+
+#[derive(serde::Serialize, Debug)]
+#[serde(untagged)]
+pub enum TestMetrics {
+	Regression(RegressionTestMetrics),
+	BinaryClassification(BinaryClassificationTestMetrics),
+	MulticlassClassification(MulticlassClassificationTestMetrics),
+}
+
+#[derive(serde::Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RegressionTestMetrics {
+	pub mse: f32,
+	pub rmse: f32,
+	pub mae: f32,
+	pub r2: f32,
+}
+
+/// The threshold dependent metrics are computed at the default threshold.
+#[derive(serde::Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BinaryClassificationTestMetrics {
+	pub auc_roc: f32,
+	pub accuracy: Option<f32>,
+	pub precision: Option<f32>,
+	pub recall: Option<f32>,
+	pub f1_score: Option<f32>,
+}
+
+#[derive(serde::Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MulticlassClassificationTestMetrics {
+	pub accuracy: f32,
+	pub precision_unweighted: f32,
+	pub precision_weighted: f32,
+	pub recall_unweighted: f32,
+	pub recall_weighted: f32,
+	pub class_metrics: Vec<ClassTestMetrics>,
+}
+
+#[derive(serde::Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassTestMetrics {
+	pub class_name: String,
+	pub precision: f32,
+	pub recall: f32,
+	pub f1_score: f32,
+}
+
+impl From<&model::Model> for ModelMetadata {
+	fn from(model: &model::Model) -> ModelMetadata {
+		match model {
+			model::Model::Regressor(model) => ModelMetadata {
+				id: model.id.clone(),
+				task: Task::Regression,
+				target_column_name: model.target_column_name.clone(),
+				columns: model.overall_column_stats.iter().map(Into::into).collect(),
+				classes: None,
+				default_threshold: None,
+				test_metrics: TestMetrics::Regression(RegressionTestMetrics {
+					mse: model.test_metrics.mse,
+					rmse: model.test_metrics.rmse,
+					mae: model.test_metrics.mae,
+					r2: model.test_metrics.r2,
+				}),
+			},
+			model::Model::BinaryClassifier(model) => {
+				// The metrics for the default threshold of 0.5 are in the middle of the thresholds.
+				let thresholds = &model.test_metrics.thresholds;
+				let default_threshold_metrics = thresholds.get(thresholds.len() / 2);
+				ModelMetadata {
+					id: model.id.clone(),
+					task: Task::BinaryClassification,
+					target_column_name: model.target_column_name.clone(),
+					columns: model.overall_column_stats.iter().map(Into::into).collect(),
+					classes: Some(vec![
+						model.negative_class.clone(),
+						model.positive_class.clone(),
+					]),
+					default_threshold: Some(0.5),
+					test_metrics: TestMetrics::BinaryClassification(
+						BinaryClassificationTestMetrics {
+							auc_roc: model.test_metrics.auc_roc,
+							accuracy: default_threshold_metrics.map(|metrics| metrics.accuracy),
+							precision: default_threshold_metrics.map(|metrics| metrics.precision),
+							recall: default_threshold_metrics.map(|metrics| metrics.recall),
+							f1_score: default_threshold_metrics.map(|metrics| metrics.f1_score),
+						},
+					),
+				}
+			}
+			model::Model::MulticlassClassifier(model) => ModelMetadata {
+				id: model.id.clone(),
+				task: Task::MulticlassClassification,
+				target_column_name: model.target_column_name.clone(),
+				columns: model.overall_column_stats.iter().map(Into::into).collect(),
+				classes: Some(model.classes.clone()),
+				default_threshold: None,
+				test_metrics: TestMetrics::MulticlassClassification(
+					MulticlassClassificationTestMetrics {
+						accuracy: model.test_metrics.accuracy,
+						precision_unweighted: model.test_metrics.precision_unweighted,
+						precision_weighted: model.test_metrics.precision_weighted,
+						recall_unweighted: model.test_metrics.recall_unweighted,
+						recall_weighted: model.test_metrics.recall_weighted,
+						class_metrics: model
+							.classes
+							.iter()
+							.zip(model.test_metrics.class_metrics.iter())
+							.map(|(class_name, class_metrics)| ClassTestMetrics {
+								class_name: class_name.clone(),
+								precision: class_metrics.precision,
+								recall: class_metrics.recall,
+								f1_score: class_metrics.f1_score,
+							})
+							.collect(),
+					},
+				),
+			},
+		}
+	}
+}
+
+impl From<&model::ColumnStats> for Column {
+	fn from(column_stats: &model::ColumnStats) -> Column {
+		match column_stats {
+			model::ColumnStats::Unknown(column_stats) => Column::Unknown(UnknownColumn {
+				name: column_stats.column_name.clone(),
+			}),
+			model::ColumnStats::Number(column_stats) => Column::Number(NumberColumn {
+				name: column_stats.column_name.clone(),
+			}),
+			model::ColumnStats::Enum(column_stats) => Column::Enum(EnumColumn {
+				name: column_stats.column_name.clone(),
+				options: column_stats
+					.histogram
+					.iter()
+					.map(|(option, _)| option.clone())
+					.collect(),
+			}),
+			model::ColumnStats::Text(column_stats) => Column::Text(TextColumn {
+				name: column_stats.column_name.clone(),
+			}),
+		}
+	}
+}
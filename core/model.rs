@@ -18,11 +18,12 @@ pub enum Model {
 impl Model {
 	/// Deserialize a `Model` from a slice.
 	pub fn from_slice(slice: &[u8]) -> Result<Model> {
-		let major_version = slice[0];
-		if major_version != 0 {
+		let (major_version, slice) = slice
+			.split_first()
+			.ok_or_else(|| err!("model data is empty"))?;
+		if *major_version != 0 {
 			return Err(err!("unknown major version {}", major_version));
 		}
-		let slice = &slice[1..];
 		let model = rmp_serde::from_slice(slice)?;
 		Ok(model)
 	}
@@ -74,11 +75,21 @@ pub struct Regressor {
 	pub test_column_stats: Vec<ColumnStats>,
 	pub test_target_column_stats: ColumnStats,
 	pub test_metrics: RegressionMetrics,
+	/// These are computed only if the confidence intervals config was set.
+	pub test_metrics_confidence_intervals: Option<RegressionMetricsConfidenceIntervals>,
 	pub baseline_metrics: RegressionMetrics,
 	pub model: RegressionModel,
 	pub comparison_metric: RegressionComparisonMetric,
 	pub grid: Vec<GridItem>,
 	pub best_grid_item_index: usize,
+	/// This is `None` for imported models and models trained before SHAP summaries were computed.
+	pub shap_summary: Option<ShapSummary>,
+	/// These are the partial dependences of the model's predictions on each number and enum column. They are `None` for imported models and models trained before they were computed.
+	pub partial_dependences: Option<Vec<PartialDependence>>,
+	/// These are the test mean absolute errors of the slices of the test dataset. They are `None` for imported models and models trained before they were computed.
+	pub slice_metrics: Option<SliceMetrics>,
+	/// This is computed only if the prediction intervals config was set.
+	pub prediction_intervals: Option<PredictionIntervals>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
@@ -89,6 +100,58 @@ pub struct RegressionMetrics {
 	pub r2: f32,
 }
 
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct RegressionMetricsConfidenceIntervals {
+	pub n_bootstrap_samples: u64,
+	pub mse: ConfidenceInterval,
+	pub rmse: ConfidenceInterval,
+	pub mae: ConfidenceInterval,
+	pub r2: ConfidenceInterval,
+}
+
+/// A confidence interval is the range between the 2.5th and 97.5th percentiles of a metric computed on bootstrap resamples of the test dataset. The bounds are NaN if the metric was not defined on any resample.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy)]
+pub struct ConfidenceInterval {
+	pub lower: f32,
+	pub upper: f32,
+}
+
+/// Prediction intervals are calibrated on the residuals of the model's predictions on the model comparison dataset, which is held out from training. Because the best model is chosen on the same dataset, the intervals can be slightly too narrow when the grid has many models.
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct PredictionIntervals {
+	/// This is the fraction of true values the intervals are calibrated to contain.
+	pub coverage: f32,
+	pub method: PredictionIntervalsMethod,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub enum PredictionIntervalsMethod {
+	#[serde(rename = "conformal")]
+	Conformal(ConformalPredictionIntervals),
+	#[serde(rename = "quantile")]
+	Quantile(QuantilePredictionIntervals),
+}
+
+/// A split conformal interval is the prediction plus or minus `margin`, which is the `ceil((n + 1) * coverage)`th smallest absolute residual of the `n` calibration examples.
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct ConformalPredictionIntervals {
+	pub margin: f32,
+}
+
+/// A quantile interval's bounds are linear quantile regressions of the residuals on the prediction, at the quantiles `(1 - coverage) / 2` and `(1 + coverage) / 2`.
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct QuantilePredictionIntervals {
+	pub lower: QuantileLine,
+	pub upper: QuantileLine,
+}
+
+/// The bound for a prediction is `prediction + intercept + slope * prediction`.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy)]
+pub struct QuantileLine {
+	pub intercept: f32,
+	pub slope: f32,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 pub enum RegressionModel {
 	#[serde(rename = "linear")]
@@ -197,11 +260,54 @@ pub struct BinaryClassifier {
 	pub test_column_stats: Vec<ColumnStats>,
 	pub test_target_column_stats: ColumnStats,
 	pub test_metrics: BinaryClassificationMetrics,
+	/// These are computed only if the confidence intervals config was set.
+	pub test_metrics_confidence_intervals: Option<BinaryClassificationMetricsConfidenceIntervals>,
 	pub baseline_metrics: BinaryClassificationMetrics,
 	pub model: BinaryClassificationModel,
 	pub comparison_metric: BinaryClassificationComparisonMetric,
 	pub grid: Vec<GridItem>,
 	pub best_grid_item_index: usize,
+	/// This is `None` for imported models and models trained before SHAP summaries were computed.
+	pub shap_summary: Option<ShapSummary>,
+	/// These are the partial dependences of the model's predictions on each number and enum column. They are `None` for imported models and models trained before they were computed.
+	pub partial_dependences: Option<Vec<PartialDependence>>,
+	/// These are the test accuracies of the slices of the test dataset, at the default threshold of 0.5. They are `None` for imported models and models trained before they were computed.
+	pub slice_metrics: Option<SliceMetrics>,
+	/// These are the test metrics sliced by the sensitive column set in the fairness config, if one was set.
+	pub fairness_metrics: Option<FairnessMetrics>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct FairnessMetrics {
+	pub sensitive_column_name: String,
+	/// This is true if the sensitive column was not used to compute features.
+	pub excluded_from_features: bool,
+	/// These are the metrics for each option of the sensitive column that occurs in the test dataset.
+	pub groups: Vec<FairnessGroupMetrics>,
+	/// This is the difference between the largest and smallest fraction of examples predicted as the positive class among the groups, at the default threshold of 0.5.
+	pub demographic_parity_difference: f32,
+	/// This is the difference between the largest and smallest true positive rate among the groups, at the default threshold of 0.5.
+	pub equal_opportunity_difference: f32,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct FairnessGroupMetrics {
+	pub group: String,
+	pub n_examples: u64,
+	/// This is the fraction of the group's examples predicted as the positive class at the default threshold of 0.5.
+	pub positive_rate: f32,
+	pub metrics: BinaryClassificationMetrics,
+}
+
+/// The metrics other than the area under the receiver operating characteristic curve are computed at the default threshold of 0.5.
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct BinaryClassificationMetricsConfidenceIntervals {
+	pub n_bootstrap_samples: u64,
+	pub auc_roc: ConfidenceInterval,
+	pub accuracy: ConfidenceInterval,
+	pub precision: ConfidenceInterval,
+	pub recall: ConfidenceInterval,
+	pub f1_score: ConfidenceInterval,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
@@ -275,11 +381,30 @@ pub struct MulticlassClassifier {
 	pub test_column_stats: Vec<ColumnStats>,
 	pub test_target_column_stats: ColumnStats,
 	pub test_metrics: MulticlassClassificationMetrics,
+	/// These are computed only if the confidence intervals config was set.
+	pub test_metrics_confidence_intervals:
+		Option<MulticlassClassificationMetricsConfidenceIntervals>,
 	pub baseline_metrics: MulticlassClassificationMetrics,
 	pub model: MulticlassClassificationModel,
 	pub comparison_metric: MulticlassClassificationComparisonMetric,
 	pub grid: Vec<GridItem>,
 	pub best_grid_item_index: usize,
+	/// There is one summary for each class. This is `None` for imported models and models trained before SHAP summaries were computed.
+	pub shap_summaries: Option<Vec<ShapSummary>>,
+	/// There is one list of partial dependences for each class, of the predicted probability of that class on each number and enum column. They are `None` for imported models and models trained before they were computed.
+	pub partial_dependences: Option<Vec<Vec<PartialDependence>>>,
+	/// These are the test accuracies of the slices of the test dataset. They are `None` for imported models and models trained before they were computed.
+	pub slice_metrics: Option<SliceMetrics>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct MulticlassClassificationMetricsConfidenceIntervals {
+	pub n_bootstrap_samples: u64,
+	pub accuracy: ConfidenceInterval,
+	pub precision_unweighted: ConfidenceInterval,
+	pub precision_weighted: ConfidenceInterval,
+	pub recall_unweighted: ConfidenceInterval,
+	pub recall_weighted: ConfidenceInterval,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
@@ -348,6 +473,91 @@ pub struct StatsSettings {
 	pub number_histogram_max_size: usize,
 }
 
+/// This is a summary of the SHAP values of a model's features, computed for a sample of the examples in the test dataset.
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct ShapSummary {
+	pub n_examples: u64,
+	/// This is the mean of the absolute SHAP value of each of the model's features.
+	pub mean_abs_shap_values: Vec<f32>,
+	/// These are the values of the features with the largest mean absolute SHAP values, in order of decreasing mean absolute SHAP value.
+	pub features: Vec<FeatureShapValues>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct FeatureShapValues {
+	pub feature_index: usize,
+	/// This is the feature's value for each example. Enum features have the number of their option, which is zero for invalid values.
+	pub feature_values: Vec<f32>,
+	/// This is the feature's SHAP value for each example.
+	pub shap_values: Vec<f32>,
+}
+
+/// A partial dependence shows how a model's prediction changes as the value of one column is varied while the values of the other columns are held fixed, computed for a sample of the examples in the test dataset.
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct PartialDependence {
+	pub column_name: String,
+	/// These are the values the column was set to.
+	pub values: PartialDependenceValues,
+	/// This is the mean prediction over the sampled examples at each value.
+	pub mean_predictions: Vec<f32>,
+	/// These are the individual conditional expectation curves, which are the predictions for some of the sampled examples at each value.
+	pub example_predictions: Vec<Vec<f32>>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub enum PartialDependenceValues {
+	#[serde(rename = "number")]
+	Number(Vec<f32>),
+	#[serde(rename = "enum")]
+	Enum(Vec<String>),
+}
+
+/// Slice metrics are a model's test metric computed on each slice of the test dataset, where a slice is the examples with one option of an enum column, the examples in one quantile bin of a number column, or the examples with an invalid value for either.
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct SliceMetrics {
+	pub metric: SliceMetric,
+	pub slices: Vec<Slice>,
+	/// These are the indexes into `slices` of the slices with the worst values of the metric, worst first. Slices that are too small to be meaningful are left out.
+	pub worst_slice_indexes: Vec<usize>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy)]
+pub enum SliceMetric {
+	#[serde(rename = "accuracy")]
+	Accuracy,
+	#[serde(rename = "mean_absolute_error")]
+	MeanAbsoluteError,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct Slice {
+	pub column_name: String,
+	pub filter: SliceFilter,
+	pub n_examples: u64,
+	pub value: f32,
+	/// This is the lower bound of the 95% confidence interval for the value.
+	pub confidence_interval_lower: f32,
+	/// This is the upper bound of the 95% confidence interval for the value.
+	pub confidence_interval_upper: f32,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub enum SliceFilter {
+	#[serde(rename = "invalid")]
+	Invalid,
+	#[serde(rename = "enum")]
+	Enum(String),
+	#[serde(rename = "number")]
+	Number(NumberSliceFilter),
+}
+
+/// A number slice contains the values greater than `start` and less than or equal to `end`. A bound of `None` means the slice is unbounded on that side.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct NumberSliceFilter {
+	pub start: Option<f32>,
+	pub end: Option<f32>,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 pub enum ColumnStats {
 	#[serde(rename = "unknown")]
@@ -578,3 +788,21 @@ impl std::fmt::Display for Token {
 		}
 	}
 }
+
+#[test]
+fn test_load_model_saved_before_optional_fields() {
+	use std::convert::TryFrom;
+	let bytes = include_bytes!("../languages/go/examples/heart_disease.tangram");
+	let model = Model::from_slice(bytes).unwrap();
+	assert!(crate::predict::Model::try_from(model).is_ok());
+	let model = Model::from_slice(bytes).unwrap();
+	let model = match model {
+		Model::BinaryClassifier(model) => model,
+		_ => panic!(),
+	};
+	assert!(model.test_metrics_confidence_intervals.is_none());
+	assert!(model.shap_summary.is_none());
+	assert!(model.partial_dependences.is_none());
+	assert!(model.slice_metrics.is_none());
+	assert!(model.fairness_metrics.is_none());
+}
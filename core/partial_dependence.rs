@@ -0,0 +1,276 @@
+/*!
+This module computes the partial dependence of a model's predictions on each number and enum column, which the app shows on the training stats page for each column.
+*/
+
+use crate::model;
+use ndarray::prelude::*;
+use std::num::NonZeroUsize;
+use tangram_dataframe::prelude::*;
+use tangram_tree::BinningInstruction;
+
+/// Partial dependences are computed for at most this many examples from the test dataset.
+const MAX_EXAMPLES: usize = 100;
+
+/// The predictions at each value are kept for at most this many of the sampled examples.
+const MAX_EXAMPLES_WITH_PREDICTIONS: usize = 20;
+
+/// Number columns are set to their min, their max, and the thresholds a tree model with this many bins for valid values would use to bin them.
+const MAX_VALID_BINS_FOR_NUMBER_COLUMNS: u8 = 20;
+
+pub fn compute_partial_dependences_linear_regressor(
+	dataframe_train: &DataFrameView,
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_linear::Regressor,
+) -> Vec<model::PartialDependence> {
+	let mut partial_dependences = compute_partial_dependences(
+		dataframe_train,
+		dataframe_test,
+		target_column_index,
+		1,
+		&|dataframe| {
+			let features =
+				tangram_features::compute_features_array_f32(dataframe, feature_groups, &|| {});
+			let mut predictions = Array::zeros(dataframe.nrows());
+			model.predict(features.view(), predictions.view_mut());
+			predictions.insert_axis(Axis(1))
+		},
+	);
+	partial_dependences.remove(0)
+}
+
+pub fn compute_partial_dependences_tree_regressor(
+	dataframe_train: &DataFrameView,
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_tree::Regressor,
+) -> Vec<model::PartialDependence> {
+	let mut partial_dependences = compute_partial_dependences(
+		dataframe_train,
+		dataframe_test,
+		target_column_index,
+		1,
+		&|dataframe| {
+			let features =
+				tangram_features::compute_features_array_value(dataframe, feature_groups, &|| {});
+			let mut predictions = Array::zeros(dataframe.nrows());
+			model.predict(features.view(), predictions.view_mut());
+			predictions.insert_axis(Axis(1))
+		},
+	);
+	partial_dependences.remove(0)
+}
+
+pub fn compute_partial_dependences_linear_binary_classifier(
+	dataframe_train: &DataFrameView,
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_linear::BinaryClassifier,
+) -> Vec<model::PartialDependence> {
+	let mut partial_dependences = compute_partial_dependences(
+		dataframe_train,
+		dataframe_test,
+		target_column_index,
+		1,
+		&|dataframe| {
+			let features =
+				tangram_features::compute_features_array_f32(dataframe, feature_groups, &|| {});
+			let mut probabilities = Array::zeros(dataframe.nrows());
+			model.predict(features.view(), probabilities.view_mut());
+			probabilities.insert_axis(Axis(1))
+		},
+	);
+	partial_dependences.remove(0)
+}
+
+pub fn compute_partial_dependences_tree_binary_classifier(
+	dataframe_train: &DataFrameView,
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_tree::BinaryClassifier,
+) -> Vec<model::PartialDependence> {
+	let mut partial_dependences = compute_partial_dependences(
+		dataframe_train,
+		dataframe_test,
+		target_column_index,
+		1,
+		&|dataframe| {
+			let features =
+				tangram_features::compute_features_array_value(dataframe, feature_groups, &|| {});
+			let mut probabilities = Array::zeros(dataframe.nrows());
+			model.predict(features.view(), probabilities.view_mut());
+			probabilities.insert_axis(Axis(1))
+		},
+	);
+	partial_dependences.remove(0)
+}
+
+pub fn compute_partial_dependences_linear_multiclass_classifier(
+	dataframe_train: &DataFrameView,
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_linear::MulticlassClassifier,
+) -> Vec<Vec<model::PartialDependence>> {
+	let n_classes = model.biases.len();
+	compute_partial_dependences(
+		dataframe_train,
+		dataframe_test,
+		target_column_index,
+		n_classes,
+		&|dataframe| {
+			let features =
+				tangram_features::compute_features_array_f32(dataframe, feature_groups, &|| {});
+			let mut probabilities = Array::zeros((dataframe.nrows(), n_classes));
+			model.predict(features.view(), probabilities.view_mut());
+			probabilities
+		},
+	)
+}
+
+pub fn compute_partial_dependences_tree_multiclass_classifier(
+	dataframe_train: &DataFrameView,
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_tree::MulticlassClassifier,
+) -> Vec<Vec<model::PartialDependence>> {
+	let n_classes = model.n_classes;
+	compute_partial_dependences(
+		dataframe_train,
+		dataframe_test,
+		target_column_index,
+		n_classes,
+		&|dataframe| {
+			let features =
+				tangram_features::compute_features_array_value(dataframe, feature_groups, &|| {});
+			let mut probabilities = Array::zeros((dataframe.nrows(), n_classes));
+			model.predict(features.view(), probabilities.view_mut());
+			probabilities
+		},
+	)
+}
+
+/// Compute the partial dependences of each of a model's `n_outputs` outputs. `predict` returns the outputs for each row of the dataframe it is passed.
+fn compute_partial_dependences(
+	dataframe_train: &DataFrameView,
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	n_outputs: usize,
+	predict: &dyn Fn(&DataFrameView) -> Array2<f32>,
+) -> Vec<Vec<model::PartialDependence>> {
+	let mut partial_dependences = (0..n_outputs).map(|_| Vec::new()).collect::<Vec<_>>();
+	// The test dataset was shuffled along with the train dataset, so its first rows are a random sample.
+	let n_examples = dataframe_test.nrows().min(MAX_EXAMPLES);
+	if n_examples == 0 {
+		return partial_dependences;
+	}
+	let (dataframe_sample, _) = dataframe_test.split_at_row(n_examples);
+	let column_indexes = dataframe_train
+		.columns()
+		.iter()
+		.enumerate()
+		.filter(|(column_index, column)| {
+			*column_index != target_column_index
+				&& matches!(
+					column,
+					DataFrameColumnView::Number(_) | DataFrameColumnView::Enum(_)
+				)
+		})
+		.map(|(column_index, _)| column_index)
+		.collect::<Vec<_>>();
+	let train_options = tangram_tree::TrainOptions {
+		max_valid_bins_for_number_features: MAX_VALID_BINS_FOR_NUMBER_COLUMNS,
+		..Default::default()
+	};
+	let binning_instructions = tangram_tree::compute_binning_instructions(
+		&dataframe_train.view_columns(&column_indexes),
+		&train_options,
+	);
+	for (column_index, binning_instruction) in column_indexes.iter().zip(binning_instructions) {
+		let column = &dataframe_train.columns()[*column_index];
+		let values = match (column, binning_instruction) {
+			(DataFrameColumnView::Number(column), BinningInstruction::Number { thresholds }) => {
+				let mut finite_values = column.iter().cloned().filter(|value| value.is_finite());
+				let first_value = match finite_values.next() {
+					Some(first_value) => first_value,
+					None => continue,
+				};
+				let (min, max) = finite_values
+					.fold((first_value, first_value), |(min, max), value| {
+						(min.min(value), max.max(value))
+					});
+				let mut values = Vec::with_capacity(thresholds.len() + 2);
+				values.push(min);
+				values.extend(thresholds);
+				values.push(max);
+				values.dedup();
+				model::PartialDependenceValues::Number(values)
+			}
+			(DataFrameColumnView::Enum(column), BinningInstruction::Enum { .. }) => {
+				model::PartialDependenceValues::Enum(column.options().to_owned())
+			}
+			_ => unreachable!(),
+		};
+		let n_values = match &values {
+			model::PartialDependenceValues::Number(values) => values.len(),
+			model::PartialDependenceValues::Enum(options) => options.len(),
+		};
+		// There is nothing to show for a column with only one value.
+		if n_values < 2 {
+			continue;
+		}
+		// Predict with the column set to each value for every example in the sample.
+		let mut predictions = Array3::zeros((n_outputs, n_values, n_examples));
+		for value_index in 0..n_values {
+			let number_data: Vec<f32>;
+			let enum_data: Vec<Option<NonZeroUsize>>;
+			let value_column = match &values {
+				model::PartialDependenceValues::Number(values) => {
+					number_data = vec![values[value_index]; n_examples];
+					DataFrameColumnView::Number(NumberDataFrameColumnView::new(
+						column.name(),
+						&number_data,
+					))
+				}
+				model::PartialDependenceValues::Enum(options) => {
+					enum_data = vec![NonZeroUsize::new(value_index + 1); n_examples];
+					DataFrameColumnView::Enum(EnumDataFrameColumnView::new(
+						column.name(),
+						options,
+						&enum_data,
+					))
+				}
+			};
+			let mut columns = dataframe_sample.columns().clone();
+			columns[*column_index] = value_column;
+			let outputs = predict(&DataFrameView::from_columns(columns));
+			predictions
+				.slice_mut(s![.., value_index, ..])
+				.assign(&outputs.t());
+		}
+		let column_name = column.name().unwrap().to_owned();
+		for (partial_dependences, predictions) in partial_dependences
+			.iter_mut()
+			.zip(predictions.axis_iter(Axis(0)))
+		{
+			let mean_predictions = predictions.mean_axis(Axis(1)).unwrap().to_vec();
+			let example_predictions = predictions
+				.axis_iter(Axis(1))
+				.take(MAX_EXAMPLES_WITH_PREDICTIONS)
+				.map(|predictions| predictions.to_vec())
+				.collect();
+			partial_dependences.push(model::PartialDependence {
+				column_name: column_name.clone(),
+				values: values.clone(),
+				mean_predictions,
+				example_predictions,
+			});
+		}
+	}
+	partial_dependences
+}
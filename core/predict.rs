@@ -6,16 +6,25 @@ use std::{
 	convert::{TryFrom, TryInto},
 };
 use tangram_dataframe::prelude::*;
-use tangram_util::{error::Result, zip};
+use tangram_util::{err, error::Result, zip};
 
 #[derive(serde::Deserialize, Debug)]
+#[serde(default)]
 pub struct PredictOptions {
 	pub threshold: f32,
+	/// If `strict` is true, making predictions returns an error instead of a warning when the input is missing columns, has unknown enum options, has values of the wrong type, or has columns the model does not know about.
+	pub strict: bool,
+	/// If `compute_feature_interactions` is true, each output includes the SHAP interaction values for each pair of features. Computing them takes much longer than computing feature contributions. Linear models have no interactions, so their outputs have none.
+	pub compute_feature_interactions: bool,
 }
 
 impl Default for PredictOptions {
 	fn default() -> PredictOptions {
-		PredictOptions { threshold: 0.5 }
+		PredictOptions {
+			threshold: 0.5,
+			strict: false,
+			compute_feature_interactions: false,
+		}
 	}
 }
 
@@ -34,7 +43,12 @@ pub enum PredictOutput {
 #[serde(rename_all = "camelCase")]
 pub struct RegressionPredictOutput {
 	pub value: f32,
+	/// The lower and upper bounds of the prediction interval are `Some` if the model was trained with the prediction intervals config. The interval contains the true value with the probability the model's intervals were calibrated for.
+	pub lower: Option<f32>,
+	pub upper: Option<f32>,
 	pub feature_contributions: Option<FeatureContributions>,
+	pub feature_interactions: Option<Vec<FeatureInteraction>>,
+	pub warnings: Vec<PredictWarning>,
 }
 
 #[derive(serde::Serialize, Debug)]
@@ -43,6 +57,8 @@ pub struct BinaryClassificationPredictOutput {
 	pub class_name: String,
 	pub probability: f32,
 	pub feature_contributions: Option<FeatureContributions>,
+	pub feature_interactions: Option<Vec<FeatureInteraction>>,
+	pub warnings: Vec<PredictWarning>,
 }
 
 #[derive(serde::Serialize, Debug)]
@@ -52,6 +68,47 @@ pub struct MulticlassClassificationPredictOutput {
 	pub probability: f32,
 	pub probabilities: BTreeMap<String, f32>,
 	pub feature_contributions: Option<BTreeMap<String, FeatureContributions>>,
+	pub feature_interactions: Option<BTreeMap<String, Vec<FeatureInteraction>>>,
+	pub warnings: Vec<PredictWarning>,
+}
+
+/// A `PredictWarning` describes a problem with a row of input that caused one of its values to be treated as missing.
+#[derive(serde::Serialize, Clone, Debug)]
+#[serde(tag = "type")]
+pub enum PredictWarning {
+	/// The input did not have a value for one of the model's columns.
+	#[serde(rename = "missing_column", rename_all = "camelCase")]
+	MissingColumn { column_name: String },
+	/// The value for an enum column was not one of the options seen during training.
+	#[serde(rename = "unknown_enum_option", rename_all = "camelCase")]
+	UnknownEnumOption { column_name: String, value: String },
+	/// The value for a column had the wrong type, such as a string for a number column.
+	#[serde(rename = "invalid_value", rename_all = "camelCase")]
+	InvalidValue { column_name: String },
+	/// The input had a value for a column the model does not know about.
+	#[serde(rename = "unknown_column", rename_all = "camelCase")]
+	UnknownColumn { column_name: String },
+}
+
+impl std::fmt::Display for PredictWarning {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			PredictWarning::MissingColumn { column_name } => {
+				write!(f, "missing column \"{}\"", column_name)
+			}
+			PredictWarning::UnknownEnumOption { column_name, value } => write!(
+				f,
+				"unknown option \"{}\" for enum column \"{}\"",
+				value, column_name
+			),
+			PredictWarning::InvalidValue { column_name } => {
+				write!(f, "invalid value for column \"{}\"", column_name)
+			}
+			PredictWarning::UnknownColumn { column_name } => {
+				write!(f, "unknown column \"{}\"", column_name)
+			}
+		}
+	}
 }
 
 #[derive(serde::Serialize, Debug)]
@@ -95,6 +152,36 @@ pub enum FeatureContribution {
 }
 
 #[derive(serde::Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureInteraction {
+	pub feature_a: Feature,
+	pub feature_b: Feature,
+	/// This is the sum of the SHAP interaction values for the pair of features, which is how much their combined contribution differs from the sum of the contributions each would have without the other.
+	pub feature_interaction_value: f32,
+}
+
+#[derive(serde::Serialize, Clone, Debug)]
+#[serde(tag = "feature_type")]
+pub enum Feature {
+	#[serde(rename = "identity")]
+	Identity { column_name: String },
+	#[serde(rename = "normalized")]
+	Normalized { column_name: String },
+	#[serde(rename = "one_hot_encoded")]
+	OneHotEncoded {
+		column_name: String,
+		option: Option<String>,
+		feature_value: bool,
+	},
+	#[serde(rename = "bag_of_words")]
+	BagOfWords {
+		column_name: String,
+		token: Token,
+		feature_value: bool,
+	},
+}
+
+#[derive(serde::Serialize, Clone, Debug)]
 #[serde(untagged)]
 pub enum Token {
 	Unigram(String),
@@ -134,6 +221,49 @@ pub struct Regressor {
 	pub columns: Vec<Column>,
 	pub feature_groups: Vec<tangram_features::FeatureGroup>,
 	pub model: RegressionModel,
+	pub prediction_intervals: Option<PredictionIntervals>,
+}
+
+#[derive(Debug)]
+pub struct PredictionIntervals {
+	/// This is the fraction of true values the intervals were calibrated to contain.
+	pub coverage: f32,
+	pub method: PredictionIntervalsMethod,
+}
+
+#[derive(Debug)]
+pub enum PredictionIntervalsMethod {
+	Conformal {
+		margin: f32,
+	},
+	Quantile {
+		lower: QuantileLine,
+		upper: QuantileLine,
+	},
+}
+
+/// The bound for a prediction is `prediction + intercept + slope * prediction`.
+#[derive(Debug, Clone, Copy)]
+pub struct QuantileLine {
+	pub intercept: f32,
+	pub slope: f32,
+}
+
+impl PredictionIntervals {
+	/// Compute the lower and upper bounds of the prediction interval for `prediction`.
+	pub fn compute(&self, prediction: f32) -> (f32, f32) {
+		match &self.method {
+			PredictionIntervalsMethod::Conformal { margin } => {
+				(prediction - margin, prediction + margin)
+			}
+			PredictionIntervalsMethod::Quantile { lower, upper } => {
+				let lower = prediction + lower.intercept + lower.slope * prediction;
+				let upper = prediction + upper.intercept + upper.slope * prediction;
+				// The quantile lines can cross far from the calibration data.
+				(lower.min(upper), lower.max(upper))
+			}
+		}
+	}
 }
 
 #[derive(Debug)]
@@ -155,22 +285,32 @@ pub struct MulticlassClassifier {
 	pub model: MulticlassClassificationModel,
 }
 
+/// Tree models hold their compiled form once [`Model::compile`] is called. Predictions are made with the compiled model if there is one, because it is faster, and feature contributions are always computed with the trees.
 #[derive(Debug)]
 pub enum RegressionModel {
 	Linear(tangram_linear::Regressor),
-	Tree(tangram_tree::Regressor),
+	Tree(
+		tangram_tree::Regressor,
+		Option<tangram_tree::CompiledRegressor>,
+	),
 }
 
 #[derive(Debug)]
 pub enum BinaryClassificationModel {
 	Linear(tangram_linear::BinaryClassifier),
-	Tree(tangram_tree::BinaryClassifier),
+	Tree(
+		tangram_tree::BinaryClassifier,
+		Option<tangram_tree::CompiledBinaryClassifier>,
+	),
 }
 
 #[derive(Debug)]
 pub enum MulticlassClassificationModel {
 	Linear(tangram_linear::MulticlassClassifier),
-	Tree(tangram_tree::MulticlassClassifier),
+	Tree(
+		tangram_tree::MulticlassClassifier,
+		Option<tangram_tree::CompiledMulticlassClassifier>,
+	),
 }
 
 #[derive(Debug)]
@@ -189,6 +329,8 @@ pub struct UnknownColumn {
 #[derive(Debug)]
 pub struct NumberColumn {
 	name: String,
+	min: f32,
+	max: f32,
 }
 
 #[derive(Debug)]
@@ -202,70 +344,553 @@ pub struct TextColumn {
 	name: String,
 }
 
+/// `PredictInputValue` is a typed value for a single column in a [`PredictInputRow`].
+#[derive(Clone, Copy, Debug)]
+pub enum PredictInputValue<'a> {
+	Number(f32),
+	Enum(&'a str),
+	Text(&'a str),
+}
+
+/**
+`PredictInputRow` is a single row of typed input to [`predict_rows`]. Use it instead of [`PredictInput`] to avoid building and parsing json values.
+
+```
+use tangram_core::predict::PredictInputRow;
+
+let row = PredictInputRow::new()
+    .number("age", 63.0)
+    .enum_option("gender", "male")
+    .text("notes", "chest pain");
+```
+*/
+#[derive(Clone, Debug, Default)]
+pub struct PredictInputRow<'a>(pub BTreeMap<&'a str, PredictInputValue<'a>>);
+
+impl<'a> PredictInputRow<'a> {
+	pub fn new() -> PredictInputRow<'a> {
+		PredictInputRow(BTreeMap::new())
+	}
+
+	/// Set the value for the number column `column_name`.
+	pub fn number(mut self, column_name: &'a str, value: f32) -> PredictInputRow<'a> {
+		self.0.insert(column_name, PredictInputValue::Number(value));
+		self
+	}
+
+	/// Set the value for the enum column `column_name`.
+	pub fn enum_option(mut self, column_name: &'a str, value: &'a str) -> PredictInputRow<'a> {
+		self.0.insert(column_name, PredictInputValue::Enum(value));
+		self
+	}
+
+	/// Set the value for the text column `column_name`.
+	pub fn text(mut self, column_name: &'a str, value: &'a str) -> PredictInputRow<'a> {
+		self.0.insert(column_name, PredictInputValue::Text(value));
+		self
+	}
+}
+
+impl Model {
+	/// Retrieve the columns this model expects as input.
+	pub fn columns(&self) -> &[Column] {
+		match self {
+			Model::Regressor(model) => model.columns.as_slice(),
+			Model::BinaryClassifier(model) => model.columns.as_slice(),
+			Model::MulticlassClassifier(model) => model.columns.as_slice(),
+		}
+	}
+
+	/// Retrieve the classes for a classifier, or `None` for a regressor.
+	pub fn classes(&self) -> Option<Vec<&str>> {
+		match self {
+			Model::Regressor(_) => None,
+			Model::BinaryClassifier(model) => Some(vec![
+				model.negative_class.as_str(),
+				model.positive_class.as_str(),
+			]),
+			Model::MulticlassClassifier(model) => {
+				Some(model.classes.iter().map(|class| class.as_str()).collect())
+			}
+		}
+	}
+
+	/// Retrieve the number of values [`predict_dataframe_into`] writes for each row.
+	pub fn n_outputs(&self) -> usize {
+		match self {
+			Model::Regressor(_) => 1,
+			Model::BinaryClassifier(_) => 1,
+			Model::MulticlassClassifier(model) => model.classes.len(),
+		}
+	}
+
+	/// Compile this model's trees into the flattened layout, so predictions are faster at the cost of holding a second copy of the trees. Call this if you make many predictions with a tree model. It has no effect on linear models or models that are already compiled.
+	pub fn compile(&mut self) {
+		match self {
+			Model::Regressor(model) => {
+				if let RegressionModel::Tree(inner_model, compiled_model @ None) = &mut model.model
+				{
+					*compiled_model = Some(inner_model.compile());
+				}
+			}
+			Model::BinaryClassifier(model) => {
+				if let BinaryClassificationModel::Tree(inner_model, compiled_model @ None) =
+					&mut model.model
+				{
+					*compiled_model = Some(inner_model.compile());
+				}
+			}
+			Model::MulticlassClassifier(model) => {
+				if let MulticlassClassificationModel::Tree(inner_model, compiled_model @ None) =
+					&mut model.model
+				{
+					*compiled_model = Some(inner_model.compile());
+				}
+			}
+		}
+	}
+
+	/// Create an empty `DataFrame` with one column for each of this model's input columns. Fill it with your input and pass it to [`predict_dataframe`] to make predictions in batches.
+	pub fn dataframe(&self) -> DataFrame {
+		let columns = self.columns();
+		let column_names = columns
+			.iter()
+			.map(|column| Some(column.name().to_owned()))
+			.collect();
+		let column_types = columns
+			.iter()
+			.map(|column| match column {
+				Column::Unknown(_) => tangram_dataframe::DataFrameColumnType::Unknown,
+				Column::Number(_) => tangram_dataframe::DataFrameColumnType::Number,
+				Column::Enum(column) => tangram_dataframe::DataFrameColumnType::Enum {
+					options: column.options.clone(),
+				},
+				Column::Text(_) => tangram_dataframe::DataFrameColumnType::Text,
+			})
+			.collect();
+		tangram_dataframe::DataFrame::new(column_names, column_types)
+	}
+}
+
+impl Column {
+	pub fn name(&self) -> &str {
+		match self {
+			Column::Unknown(column) => &column.name,
+			Column::Number(column) => &column.name,
+			Column::Enum(column) => &column.name,
+			Column::Text(column) => &column.name,
+		}
+	}
+}
+
+impl NumberColumn {
+	/// Retrieve the minimum of the column's values in the training dataset.
+	pub fn min(&self) -> f32 {
+		self.min
+	}
+
+	/// Retrieve the maximum of the column's values in the training dataset.
+	pub fn max(&self) -> f32 {
+		self.max
+	}
+}
+
+impl EnumColumn {
+	pub fn options(&self) -> &[String] {
+		&self.options
+	}
+}
+
+/// Make predictions for rows of json input. Values that are missing, have the wrong type, or are not one of an enum column's options are treated as missing and reported in each output's `warnings`, unless `options.strict` is true, in which case an error is returned.
 pub fn predict(
 	model: &Model,
 	input: PredictInput,
 	options: Option<PredictOptions>,
-) -> PredictOutput {
+) -> Result<PredictOutput> {
 	// Initialize the dataframe.
-	let columns = match model {
-		Model::Regressor(model) => model.columns.as_slice(),
-		Model::BinaryClassifier(model) => model.columns.as_slice(),
-		Model::MulticlassClassifier(model) => model.columns.as_slice(),
-	};
-	let column_names = columns
-		.iter()
-		.map(|column| match column {
-			Column::Unknown(column) => Some(column.name.clone()),
-			Column::Number(column) => Some(column.name.clone()),
-			Column::Enum(column) => Some(column.name.clone()),
-			Column::Text(column) => Some(column.name.clone()),
-		})
-		.collect();
-	let column_types = columns
-		.iter()
-		.map(|column| match column {
-			Column::Unknown(_) => tangram_dataframe::DataFrameColumnType::Unknown,
-			Column::Number(_) => tangram_dataframe::DataFrameColumnType::Number,
-			Column::Enum(column) => tangram_dataframe::DataFrameColumnType::Enum {
-				options: column.options.clone(),
-			},
-			Column::Text(_) => tangram_dataframe::DataFrameColumnType::Text,
-		})
-		.collect();
-	let mut dataframe = tangram_dataframe::DataFrame::new(column_names, column_types);
+	let mut dataframe = model.dataframe();
+	let mut warnings = Vec::with_capacity(input.0.len());
 	// Fill the dataframe with the input.
 	for input in input.0 {
+		let mut row_warnings = Vec::new();
 		for column in dataframe.columns_mut().iter_mut() {
 			match column {
 				tangram_dataframe::DataFrameColumn::Unknown(column) => *column.len_mut() += 1,
 				tangram_dataframe::DataFrameColumn::Number(column) => {
-					let value = match input.get(column.name().as_ref().unwrap()) {
+					let column_name = column.name().as_ref().unwrap();
+					let value = match input.get(column_name) {
 						Some(serde_json::Value::Number(value)) => {
 							value.as_f64().unwrap().to_f32().unwrap()
 						}
-						_ => std::f32::NAN,
+						Some(serde_json::Value::Null) => std::f32::NAN,
+						Some(_) => {
+							row_warnings.push(PredictWarning::InvalidValue {
+								column_name: column_name.clone(),
+							});
+							std::f32::NAN
+						}
+						None => {
+							row_warnings.push(PredictWarning::MissingColumn {
+								column_name: column_name.clone(),
+							});
+							std::f32::NAN
+						}
+					};
+					column.data_mut().push(value);
+				}
+				tangram_dataframe::DataFrameColumn::Enum(column) => {
+					let column_name = column.name().as_ref().unwrap();
+					let value = match input.get(column_name) {
+						Some(serde_json::Value::String(value)) => {
+							let option = column.value_for_option(value);
+							if option.is_none() {
+								row_warnings.push(PredictWarning::UnknownEnumOption {
+									column_name: column_name.clone(),
+									value: value.clone(),
+								});
+							}
+							option
+						}
+						Some(serde_json::Value::Null) => None,
+						Some(_) => {
+							row_warnings.push(PredictWarning::InvalidValue {
+								column_name: column_name.clone(),
+							});
+							None
+						}
+						None => {
+							row_warnings.push(PredictWarning::MissingColumn {
+								column_name: column_name.clone(),
+							});
+							None
+						}
+					};
+					column.data_mut().push(value);
+				}
+				tangram_dataframe::DataFrameColumn::Text(column) => {
+					let column_name = column.name().as_ref().unwrap();
+					let value = match input.get(column_name) {
+						Some(serde_json::Value::String(value)) => value.clone(),
+						Some(serde_json::Value::Null) => String::new(),
+						Some(_) => {
+							row_warnings.push(PredictWarning::InvalidValue {
+								column_name: column_name.clone(),
+							});
+							String::new()
+						}
+						None => {
+							row_warnings.push(PredictWarning::MissingColumn {
+								column_name: column_name.clone(),
+							});
+							String::new()
+						}
+					};
+					column.data_mut().push(value);
+				}
+			}
+		}
+		push_unknown_column_warnings(model, input.keys(), &mut row_warnings);
+		warnings.push(row_warnings);
+	}
+	predict_with_warnings(model, &dataframe.view(), options, warnings)
+}
+
+/// Make predictions for rows of typed input. Values that are missing, have a type that does not match the type of the model's column, or are not one of an enum column's options are treated as missing and reported in each output's `warnings`, unless `options.strict` is true, in which case an error is returned.
+pub fn predict_rows(
+	model: &Model,
+	input: &[PredictInputRow],
+	options: Option<PredictOptions>,
+) -> Result<PredictOutput> {
+	// Initialize the dataframe.
+	let mut dataframe = model.dataframe();
+	let mut warnings = Vec::with_capacity(input.len());
+	// Fill the dataframe with the input.
+	for input in input.iter() {
+		let mut row_warnings = Vec::new();
+		for column in dataframe.columns_mut().iter_mut() {
+			match column {
+				tangram_dataframe::DataFrameColumn::Unknown(column) => *column.len_mut() += 1,
+				tangram_dataframe::DataFrameColumn::Number(column) => {
+					let column_name = column.name().as_deref().unwrap();
+					let value = match input.0.get(column_name) {
+						Some(PredictInputValue::Number(value)) => *value,
+						Some(_) => {
+							row_warnings.push(PredictWarning::InvalidValue {
+								column_name: column_name.to_owned(),
+							});
+							std::f32::NAN
+						}
+						None => {
+							row_warnings.push(PredictWarning::MissingColumn {
+								column_name: column_name.to_owned(),
+							});
+							std::f32::NAN
+						}
 					};
 					column.data_mut().push(value);
 				}
 				tangram_dataframe::DataFrameColumn::Enum(column) => {
-					let value = input
-						.get(column.name().as_ref().unwrap())
-						.and_then(|value| value.as_str());
-					let value = value.and_then(|value| column.value_for_option(value));
+					let column_name = column.name().as_deref().unwrap();
+					let value = match input.0.get(column_name) {
+						Some(PredictInputValue::Enum(value)) => {
+							let option = column.value_for_option(value);
+							if option.is_none() {
+								row_warnings.push(PredictWarning::UnknownEnumOption {
+									column_name: column_name.to_owned(),
+									value: (*value).to_owned(),
+								});
+							}
+							option
+						}
+						Some(_) => {
+							row_warnings.push(PredictWarning::InvalidValue {
+								column_name: column_name.to_owned(),
+							});
+							None
+						}
+						None => {
+							row_warnings.push(PredictWarning::MissingColumn {
+								column_name: column_name.to_owned(),
+							});
+							None
+						}
+					};
 					column.data_mut().push(value);
 				}
 				tangram_dataframe::DataFrameColumn::Text(column) => {
-					let value = input
-						.get(column.name().as_ref().unwrap())
-						.and_then(|value| value.as_str())
-						.unwrap_or("")
-						.to_owned();
+					let column_name = column.name().as_deref().unwrap();
+					let value = match input.0.get(column_name) {
+						Some(PredictInputValue::Text(value)) => (*value).to_owned(),
+						Some(_) => {
+							row_warnings.push(PredictWarning::InvalidValue {
+								column_name: column_name.to_owned(),
+							});
+							String::new()
+						}
+						None => {
+							row_warnings.push(PredictWarning::MissingColumn {
+								column_name: column_name.to_owned(),
+							});
+							String::new()
+						}
+					};
 					column.data_mut().push(value);
 				}
 			}
 		}
+		push_unknown_column_warnings(model, input.0.keys().copied(), &mut row_warnings);
+		warnings.push(row_warnings);
+	}
+	predict_with_warnings(model, &dataframe.view(), options, warnings)
+}
+
+/// Add a warning to `warnings` for each of `input_column_names` that is not one of the model's columns.
+fn push_unknown_column_warnings(
+	model: &Model,
+	input_column_names: impl Iterator<Item = impl AsRef<str>>,
+	warnings: &mut Vec<PredictWarning>,
+) {
+	for input_column_name in input_column_names {
+		let input_column_name = input_column_name.as_ref();
+		let is_known = model
+			.columns()
+			.iter()
+			.any(|column| column.name() == input_column_name);
+		if !is_known {
+			warnings.push(PredictWarning::UnknownColumn {
+				column_name: input_column_name.to_owned(),
+			});
+		}
+	}
+}
+
+/// Make predictions and attach each row's warnings to its output. If `options.strict` is true, return an error for the first row with warnings instead.
+fn predict_with_warnings(
+	model: &Model,
+	dataframe: &DataFrameView,
+	options: Option<PredictOptions>,
+	warnings: Vec<Vec<PredictWarning>>,
+) -> Result<PredictOutput> {
+	let strict = options
+		.as_ref()
+		.map(|options| options.strict)
+		.unwrap_or(false);
+	if strict {
+		if let Some((row_index, row_warnings)) = warnings
+			.iter()
+			.enumerate()
+			.find(|(_, row_warnings)| !row_warnings.is_empty())
+		{
+			let row_warnings = row_warnings
+				.iter()
+				.map(|warning| warning.to_string())
+				.collect::<Vec<_>>()
+				.join(", ");
+			return Err(err!("invalid input in row {}: {}", row_index, row_warnings));
+		}
 	}
+	let mut output = predict_with_dataframe(model, dataframe, options);
+	match &mut output {
+		PredictOutput::Regression(output) => {
+			for (output, warnings) in zip!(output.iter_mut(), warnings) {
+				output.warnings = warnings;
+			}
+		}
+		PredictOutput::BinaryClassification(output) => {
+			for (output, warnings) in zip!(output.iter_mut(), warnings) {
+				output.warnings = warnings;
+			}
+		}
+		PredictOutput::MulticlassClassification(output) => {
+			for (output, warnings) in zip!(output.iter_mut(), warnings) {
+				output.warnings = warnings;
+			}
+		}
+	}
+	Ok(output)
+}
+
+/// Make predictions for a batch of columnar input. Each of the model's columns must be present in `dataframe` with the same type, and enum columns must have the same options in the same order. The easiest way to get a compatible `DataFrame` is to call [`Model::dataframe`] and fill it with your input.
+pub fn predict_dataframe(
+	model: &Model,
+	dataframe: &DataFrameView,
+	options: Option<PredictOptions>,
+) -> Result<PredictOutput> {
+	validate_dataframe(model, dataframe)?;
+	Ok(predict_with_dataframe(model, dataframe, options))
+}
+
+/// Make predictions for a batch of columnar input like [`predict_dataframe`], but write only the model's output values to `output` and skip computing feature contributions. `output` must have length `dataframe.nrows() * model.n_outputs()`. For regressors, each row's output is the predicted value. For binary classifiers, it is the probability of the positive class. For multiclass classifiers, it is the probability of each class, in the order of [`Model::classes`].
+pub fn predict_dataframe_into(
+	model: &Model,
+	dataframe: &DataFrameView,
+	output: &mut [f32],
+) -> Result<()> {
+	validate_dataframe(model, dataframe)?;
+	let n_examples = dataframe.nrows();
+	let expected_output_len = n_examples * model.n_outputs();
+	if output.len() != expected_output_len {
+		return Err(err!(
+			"expected an output of length {} but got {}",
+			expected_output_len,
+			output.len()
+		));
+	}
+	match model {
+		Model::Regressor(model) => {
+			let predictions = ArrayViewMut1::from(output);
+			match &model.model {
+				RegressionModel::Linear(inner_model) => {
+					let features = tangram_features::compute_features_array_f32(
+						dataframe,
+						&model.feature_groups,
+						&|| {},
+					);
+					inner_model.predict(features.view(), predictions);
+				}
+				RegressionModel::Tree(inner_model, compiled_model) => {
+					let features = tangram_features::compute_features_array_value(
+						dataframe,
+						&model.feature_groups,
+						&|| {},
+					);
+					match compiled_model {
+						Some(compiled_model) => {
+							compiled_model.predict(features.view(), predictions)
+						}
+						None => inner_model.predict(features.view(), predictions),
+					}
+				}
+			}
+		}
+		Model::BinaryClassifier(model) => {
+			let probabilities = ArrayViewMut1::from(output);
+			match &model.model {
+				BinaryClassificationModel::Linear(inner_model) => {
+					let features = tangram_features::compute_features_array_f32(
+						dataframe,
+						&model.feature_groups,
+						&|| {},
+					);
+					inner_model.predict(features.view(), probabilities);
+				}
+				BinaryClassificationModel::Tree(inner_model, compiled_model) => {
+					let features = tangram_features::compute_features_array_value(
+						dataframe,
+						&model.feature_groups,
+						&|| {},
+					);
+					match compiled_model {
+						Some(compiled_model) => {
+							compiled_model.predict(features.view(), probabilities)
+						}
+						None => inner_model.predict(features.view(), probabilities),
+					}
+				}
+			}
+		}
+		Model::MulticlassClassifier(model) => {
+			let probabilities =
+				ArrayViewMut2::from_shape((n_examples, model.classes.len()), output)?;
+			match &model.model {
+				MulticlassClassificationModel::Linear(inner_model) => {
+					let features = tangram_features::compute_features_array_f32(
+						dataframe,
+						&model.feature_groups,
+						&|| {},
+					);
+					inner_model.predict(features.view(), probabilities);
+				}
+				MulticlassClassificationModel::Tree(inner_model, compiled_model) => {
+					let features = tangram_features::compute_features_array_value(
+						dataframe,
+						&model.feature_groups,
+						&|| {},
+					);
+					match compiled_model {
+						Some(compiled_model) => {
+							compiled_model.predict(features.view(), probabilities)
+						}
+						None => inner_model.predict(features.view(), probabilities),
+					}
+				}
+			}
+		}
+	}
+	Ok(())
+}
+
+fn validate_dataframe(model: &Model, dataframe: &DataFrameView) -> Result<()> {
+	for column in model.columns().iter() {
+		let dataframe_column = dataframe
+			.columns()
+			.iter()
+			.find(|dataframe_column| dataframe_column.name() == Some(column.name()))
+			.ok_or_else(|| err!("missing column \"{}\"", column.name()))?;
+		let column_types_match = match (column, dataframe_column) {
+			(Column::Unknown(_), _) => true,
+			(Column::Number(_), DataFrameColumnView::Number(_)) => true,
+			(Column::Enum(column), DataFrameColumnView::Enum(dataframe_column)) => {
+				column.options.as_slice() == dataframe_column.options()
+			}
+			(Column::Text(_), DataFrameColumnView::Text(_)) => true,
+			_ => false,
+		};
+		if !column_types_match {
+			return Err(err!(
+				"column \"{}\" does not match the type of the model's column",
+				column.name()
+			));
+		}
+	}
+	Ok(())
+}
+
+fn predict_with_dataframe(
+	model: &Model,
+	dataframe: &DataFrameView,
+	options: Option<PredictOptions>,
+) -> PredictOutput {
 	// Make the predictions by matching on the model type.
 	match model {
 		Model::Regressor(model) => {
@@ -282,15 +907,19 @@ pub fn predict(
 
 fn predict_regressor(
 	model: &Regressor,
-	dataframe: DataFrame,
-	_options: Option<PredictOptions>,
+	dataframe: &DataFrameView,
+	options: Option<PredictOptions>,
 ) -> Vec<RegressionPredictOutput> {
 	let n_examples = dataframe.nrows();
+	let should_compute_feature_interactions = options
+		.as_ref()
+		.map(|options| options.compute_feature_interactions)
+		.unwrap_or(false);
 	match &model.model {
 		RegressionModel::Linear(inner_model) => {
 			let mut predictions = Array::zeros(n_examples);
 			let features = tangram_features::compute_features_array_f32(
-				&dataframe.view(),
+				dataframe,
 				&model.feature_groups,
 				&|| {},
 			);
@@ -316,21 +945,42 @@ fn predict_regressor(
 					output_value,
 					feature_contributions,
 				};
+				let feature_interactions = if should_compute_feature_interactions {
+					Some(Vec::new())
+				} else {
+					None
+				};
+				let (lower, upper) = match &model.prediction_intervals {
+					Some(prediction_intervals) => {
+						let (lower, upper) = prediction_intervals.compute(*prediction);
+						(Some(lower), Some(upper))
+					}
+					None => (None, None),
+				};
 				RegressionPredictOutput {
 					value: *prediction,
+					lower,
+					upper,
 					feature_contributions: Some(feature_contributions),
+					feature_interactions,
+					warnings: Vec::new(),
 				}
 			})
 			.collect()
 		}
-		RegressionModel::Tree(inner_model) => {
+		RegressionModel::Tree(inner_model, compiled_model) => {
 			let features = tangram_features::compute_features_array_value(
-				&dataframe.view(),
+				dataframe,
 				&model.feature_groups,
 				&|| {},
 			);
 			let mut predictions = Array::zeros(n_examples);
-			inner_model.predict(features.view(), predictions.view_mut());
+			match compiled_model {
+				Some(compiled_model) => {
+					compiled_model.predict(features.view(), predictions.view_mut())
+				}
+				None => inner_model.predict(features.view(), predictions.view_mut()),
+			}
 			let feature_contributions = inner_model.compute_feature_contributions(features.view());
 			zip!(
 				features.axis_iter(Axis(0)),
@@ -358,9 +1008,33 @@ fn predict_regressor(
 					output_value,
 					feature_contributions,
 				};
+				let feature_interactions = if should_compute_feature_interactions {
+					let feature_interactions = inner_model
+						.compute_feature_interactions(features.insert_axis(Axis(0)))
+						.remove(0);
+					Some(compute_feature_interactions(
+						model.feature_groups.iter(),
+						features.iter().map(tree_feature_value),
+						&feature_interactions.feature_indexes,
+						&feature_interactions.shap_interaction_values,
+					))
+				} else {
+					None
+				};
+				let (lower, upper) = match &model.prediction_intervals {
+					Some(prediction_intervals) => {
+						let (lower, upper) = prediction_intervals.compute(*prediction);
+						(Some(lower), Some(upper))
+					}
+					None => (None, None),
+				};
 				RegressionPredictOutput {
 					value: *prediction,
+					lower,
+					upper,
 					feature_contributions: Some(feature_contributions),
+					feature_interactions,
+					warnings: Vec::new(),
 				}
 			})
 			.collect()
@@ -370,15 +1044,19 @@ fn predict_regressor(
 
 fn predict_binary_classifier(
 	model: &BinaryClassifier,
-	dataframe: DataFrame,
+	dataframe: &DataFrameView,
 	options: Option<PredictOptions>,
 ) -> Vec<BinaryClassificationPredictOutput> {
 	let n_examples = dataframe.nrows();
+	let should_compute_feature_interactions = options
+		.as_ref()
+		.map(|options| options.compute_feature_interactions)
+		.unwrap_or(false);
 	match &model.model {
 		BinaryClassificationModel::Linear(inner_model) => {
 			let mut probabilities = Array::zeros(n_examples);
 			let features = tangram_features::compute_features_array_f32(
-				&dataframe.view(),
+				dataframe,
 				&model.feature_groups,
 				&|| {},
 			);
@@ -409,77 +1087,111 @@ fn predict_binary_classifier(
 						output_value,
 						feature_contributions,
 					};
+					let feature_interactions = if should_compute_feature_interactions {
+						Some(Vec::new())
+					} else {
+						None
+					};
 					BinaryClassificationPredictOutput {
 						class_name,
 						probability,
 						feature_contributions: Some(feature_contributions),
+						feature_interactions,
+						warnings: Vec::new(),
 					}
 				})
 				.collect()
 		}
-		BinaryClassificationModel::Tree(inner_model) => {
+		BinaryClassificationModel::Tree(inner_model, compiled_model) => {
 			let features = tangram_features::compute_features_array_value(
-				&dataframe.view(),
+				dataframe,
 				&model.feature_groups,
 				&|| {},
 			);
 			let mut probabilities = Array::zeros(n_examples);
-			inner_model.predict(features.view(), probabilities.view_mut());
+			match compiled_model {
+				Some(compiled_model) => {
+					compiled_model.predict(features.view(), probabilities.view_mut())
+				}
+				None => inner_model.predict(features.view(), probabilities.view_mut()),
+			}
 			let feature_contributions = inner_model.compute_feature_contributions(features.view());
 			let threshold = match options {
 				Some(options) => options.threshold,
 				None => 0.5,
 			};
-			zip!(probabilities.iter(), feature_contributions)
-				.map(|(probability, feature_contributions)| {
-					let (probability, class_name) = if *probability >= threshold {
-						(*probability, model.positive_class.clone())
-					} else {
-						(1.0 - probability, model.negative_class.clone())
-					};
-					let baseline_value = feature_contributions.baseline_value;
-					let output_value = feature_contributions.output_value;
-					let feature_contributions = compute_feature_contributions(
+			zip!(
+				features.axis_iter(Axis(0)),
+				probabilities.iter(),
+				feature_contributions
+			)
+			.map(|(example, probability, feature_contributions)| {
+				let (probability, class_name) = if *probability >= threshold {
+					(*probability, model.positive_class.clone())
+				} else {
+					(1.0 - probability, model.negative_class.clone())
+				};
+				let baseline_value = feature_contributions.baseline_value;
+				let output_value = feature_contributions.output_value;
+				let feature_contributions = compute_feature_contributions(
+					model.feature_groups.iter(),
+					features.iter().map(|v| match v {
+						tangram_dataframe::DataFrameValue::Number(value) => *value,
+						tangram_dataframe::DataFrameValue::Enum(value) => {
+							value.map(|v| v.get()).unwrap_or(0).to_f32().unwrap()
+						}
+						_ => unreachable!(),
+					}),
+					feature_contributions
+						.feature_contribution_values
+						.into_iter(),
+				);
+				let feature_contributions = FeatureContributions {
+					baseline_value,
+					output_value,
+					feature_contributions,
+				};
+				let feature_interactions = if should_compute_feature_interactions {
+					let feature_interactions = inner_model
+						.compute_feature_interactions(example.insert_axis(Axis(0)))
+						.remove(0);
+					Some(compute_feature_interactions(
 						model.feature_groups.iter(),
-						features.iter().map(|v| match v {
-							tangram_dataframe::DataFrameValue::Number(value) => *value,
-							tangram_dataframe::DataFrameValue::Enum(value) => {
-								value.map(|v| v.get()).unwrap_or(0).to_f32().unwrap()
-							}
-							_ => unreachable!(),
-						}),
-						feature_contributions
-							.feature_contribution_values
-							.into_iter(),
-					);
-					let feature_contributions = FeatureContributions {
-						baseline_value,
-						output_value,
-						feature_contributions,
-					};
-					BinaryClassificationPredictOutput {
-						class_name,
-						probability,
-						feature_contributions: Some(feature_contributions),
-					}
-				})
-				.collect()
+						example.iter().map(tree_feature_value),
+						&feature_interactions.feature_indexes,
+						&feature_interactions.shap_interaction_values,
+					))
+				} else {
+					None
+				};
+				BinaryClassificationPredictOutput {
+					class_name,
+					probability,
+					feature_contributions: Some(feature_contributions),
+					feature_interactions,
+					warnings: Vec::new(),
+				}
+			})
+			.collect()
 		}
 	}
 }
 
 fn predict_multiclass_classifier(
 	model: &MulticlassClassifier,
-	dataframe: DataFrame,
-	_options: Option<PredictOptions>,
+	dataframe: &DataFrameView,
+	options: Option<PredictOptions>,
 ) -> Vec<MulticlassClassificationPredictOutput> {
 	let n_examples = dataframe.nrows();
+	let should_compute_feature_interactions = options
+		.map(|options| options.compute_feature_interactions)
+		.unwrap_or(false);
 	let n_classes = model.classes.len();
 	match &model.model {
 		MulticlassClassificationModel::Linear(inner_model) => {
 			let mut probabilities = Array::zeros((n_examples, n_classes));
 			let features = tangram_features::compute_features_array_f32(
-				&dataframe.view(),
+				dataframe,
 				&model.feature_groups,
 				&|| {},
 			);
@@ -513,66 +1225,109 @@ fn predict_multiclass_classifier(
 							(class.clone(), feature_contributions)
 						})
 						.collect();
+					let feature_interactions = if should_compute_feature_interactions {
+						Some(
+							model
+								.classes
+								.iter()
+								.map(|class| (class.clone(), Vec::new()))
+								.collect(),
+						)
+					} else {
+						None
+					};
 					MulticlassClassificationPredictOutput {
 						class_name: class_name.clone(),
 						probability: *probability,
 						probabilities,
 						feature_contributions: Some(feature_contributions),
+						feature_interactions,
+						warnings: Vec::new(),
 					}
 				})
 				.collect()
 		}
-		MulticlassClassificationModel::Tree(inner_model) => {
+		MulticlassClassificationModel::Tree(inner_model, compiled_model) => {
 			let features = tangram_features::compute_features_array_value(
-				&dataframe.view(),
+				dataframe,
 				&model.feature_groups,
 				&|| {},
 			);
 			let mut probabilities = Array::zeros((n_examples, n_classes));
-			inner_model.predict(features.view(), probabilities.view_mut());
+			match compiled_model {
+				Some(compiled_model) => {
+					compiled_model.predict(features.view(), probabilities.view_mut())
+				}
+				None => inner_model.predict(features.view(), probabilities.view_mut()),
+			}
 			let feature_contributions = inner_model.compute_feature_contributions(features.view());
-			zip!(probabilities.axis_iter(Axis(0)), feature_contributions)
-				.map(|(probabilities, feature_contributions)| {
-					let (probability, class_name) =
-						zip!(probabilities.iter(), model.classes.iter())
-							.max_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap())
-							.unwrap();
-					let probabilities = zip!(probabilities.iter(), model.classes.iter())
-						.map(|(probability, class)| (class.clone(), *probability))
-						.collect();
-					let feature_contributions = zip!(model.classes.iter(), feature_contributions)
-						.map(|(class, feature_contributions)| {
-							let baseline_value = feature_contributions.baseline_value;
-							let output_value = feature_contributions.output_value;
-							let feature_contributions = compute_feature_contributions(
-								model.feature_groups.iter(),
-								features.iter().map(|v| match v {
-									tangram_dataframe::DataFrameValue::Number(value) => *value,
-									tangram_dataframe::DataFrameValue::Enum(value) => {
-										value.map(|v| v.get()).unwrap_or(0).to_f32().unwrap()
-									}
-									_ => unreachable!(),
-								}),
-								feature_contributions
-									.feature_contribution_values
-									.into_iter(),
-							);
-							let feature_contributions = FeatureContributions {
-								baseline_value,
-								output_value,
-								feature_contributions,
-							};
-							(class.clone(), feature_contributions)
-						})
-						.collect();
-					MulticlassClassificationPredictOutput {
-						class_name: class_name.clone(),
-						probability: *probability,
-						probabilities,
-						feature_contributions: Some(feature_contributions),
-					}
-				})
-				.collect()
+			zip!(
+				features.axis_iter(Axis(0)),
+				probabilities.axis_iter(Axis(0)),
+				feature_contributions
+			)
+			.map(|(example, probabilities, feature_contributions)| {
+				let (probability, class_name) = zip!(probabilities.iter(), model.classes.iter())
+					.max_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap())
+					.unwrap();
+				let probabilities = zip!(probabilities.iter(), model.classes.iter())
+					.map(|(probability, class)| (class.clone(), *probability))
+					.collect();
+				let feature_contributions = zip!(model.classes.iter(), feature_contributions)
+					.map(|(class, feature_contributions)| {
+						let baseline_value = feature_contributions.baseline_value;
+						let output_value = feature_contributions.output_value;
+						let feature_contributions = compute_feature_contributions(
+							model.feature_groups.iter(),
+							features.iter().map(|v| match v {
+								tangram_dataframe::DataFrameValue::Number(value) => *value,
+								tangram_dataframe::DataFrameValue::Enum(value) => {
+									value.map(|v| v.get()).unwrap_or(0).to_f32().unwrap()
+								}
+								_ => unreachable!(),
+							}),
+							feature_contributions
+								.feature_contribution_values
+								.into_iter(),
+						);
+						let feature_contributions = FeatureContributions {
+							baseline_value,
+							output_value,
+							feature_contributions,
+						};
+						(class.clone(), feature_contributions)
+					})
+					.collect();
+				let feature_interactions = if should_compute_feature_interactions {
+					let feature_interactions = inner_model
+						.compute_feature_interactions(example.insert_axis(Axis(0)))
+						.remove(0);
+					Some(
+						zip!(model.classes.iter(), feature_interactions)
+							.map(|(class, feature_interactions)| {
+								let feature_interactions = compute_feature_interactions(
+									model.feature_groups.iter(),
+									example.iter().map(tree_feature_value),
+									&feature_interactions.feature_indexes,
+									&feature_interactions.shap_interaction_values,
+								);
+								(class.clone(), feature_interactions)
+							})
+							.collect(),
+					)
+				} else {
+					None
+				};
+				MulticlassClassificationPredictOutput {
+					class_name: class_name.clone(),
+					probability: *probability,
+					probabilities,
+					feature_contributions: Some(feature_contributions),
+					feature_interactions,
+					warnings: Vec::new(),
+				}
+			})
+			.collect()
 		}
 	}
 }
@@ -638,6 +1393,102 @@ fn compute_feature_contributions<'a>(
 	feature_contributions
 }
 
+/// Pair up the SHAP interaction values for the features at `feature_indexes`, which are stored in row major order, and sort the pairs by the absolute value of their interaction, largest first. Pairs that do not interact are left out.
+fn compute_feature_interactions<'a>(
+	feature_groups: impl Iterator<Item = &'a tangram_features::FeatureGroup>,
+	features: impl Iterator<Item = f32>,
+	feature_indexes: &[usize],
+	shap_interaction_values: &[f32],
+) -> Vec<FeatureInteraction> {
+	let features = compute_feature_descriptions(feature_groups, features);
+	let n_features = feature_indexes.len();
+	let mut feature_interactions = Vec::new();
+	for (i, feature_index_a) in feature_indexes.iter().enumerate() {
+		for (j, feature_index_b) in feature_indexes.iter().enumerate().skip(i + 1) {
+			let feature_interaction_value = shap_interaction_values[i * n_features + j]
+				+ shap_interaction_values[j * n_features + i];
+			if feature_interaction_value == 0.0 {
+				continue;
+			}
+			feature_interactions.push(FeatureInteraction {
+				feature_a: features[*feature_index_a].clone(),
+				feature_b: features[*feature_index_b].clone(),
+				feature_interaction_value,
+			});
+		}
+	}
+	feature_interactions.sort_by(|a, b| {
+		a.feature_interaction_value
+			.abs()
+			.partial_cmp(&b.feature_interaction_value.abs())
+			.unwrap()
+			.reverse()
+	});
+	feature_interactions
+}
+
+/// Describe each of the features produced by `feature_groups`, in the same order as the features.
+fn compute_feature_descriptions<'a>(
+	feature_groups: impl Iterator<Item = &'a tangram_features::FeatureGroup>,
+	mut features: impl Iterator<Item = f32>,
+) -> Vec<Feature> {
+	let mut descriptions = Vec::new();
+	for feature_group in feature_groups {
+		match feature_group {
+			tangram_features::FeatureGroup::Identity(feature_group) => {
+				features.next().unwrap();
+				descriptions.push(Feature::Identity {
+					column_name: feature_group.source_column_name.clone(),
+				});
+			}
+			tangram_features::FeatureGroup::Normalized(feature_group) => {
+				features.next().unwrap();
+				descriptions.push(Feature::Normalized {
+					column_name: feature_group.source_column_name.clone(),
+				});
+			}
+			tangram_features::FeatureGroup::OneHotEncoded(feature_group) => {
+				let feature_value = features.next().unwrap();
+				descriptions.push(Feature::OneHotEncoded {
+					column_name: feature_group.source_column_name.clone(),
+					option: None,
+					feature_value: feature_value > 0.0,
+				});
+				for option in feature_group.options.iter() {
+					let feature_value = features.next().unwrap();
+					descriptions.push(Feature::OneHotEncoded {
+						column_name: feature_group.source_column_name.clone(),
+						option: Some(option.clone()),
+						feature_value: feature_value > 0.0,
+					});
+				}
+			}
+			tangram_features::FeatureGroup::BagOfWords(feature_group) => {
+				for token in feature_group.tokens.iter() {
+					let feature_value = features.next().unwrap();
+					descriptions.push(Feature::BagOfWords {
+						column_name: feature_group.source_column_name.clone(),
+						token: token.token.clone().into(),
+						feature_value: feature_value > 0.0,
+					});
+				}
+			}
+		}
+	}
+	descriptions
+}
+
+/// Tree models take enum features as the index of the option, with 0 for invalid values.
+fn tree_feature_value(value: &tangram_dataframe::DataFrameValue) -> f32 {
+	match value {
+		tangram_dataframe::DataFrameValue::Number(value) => *value,
+		tangram_dataframe::DataFrameValue::Enum(value) => {
+			value.map(|v| v.get()).unwrap_or(0).to_f32().unwrap()
+		}
+		_ => unreachable!(),
+	}
+}
+
 impl TryFrom<model::Model> for Model {
 	type Error = tangram_util::error::Error;
 	fn try_from(value: model::Model) -> Result<Model> {
@@ -660,6 +1511,31 @@ impl TryFrom<model::Regressor> for Regressor {
 			.into_iter()
 			.map(TryFrom::try_from)
 			.collect::<Result<Vec<_>>>()?;
+		let prediction_intervals =
+			value
+				.prediction_intervals
+				.map(|prediction_intervals| PredictionIntervals {
+					coverage: prediction_intervals.coverage,
+					method: match prediction_intervals.method {
+						model::PredictionIntervalsMethod::Conformal(conformal) => {
+							PredictionIntervalsMethod::Conformal {
+								margin: conformal.margin,
+							}
+						}
+						model::PredictionIntervalsMethod::Quantile(quantile) => {
+							PredictionIntervalsMethod::Quantile {
+								lower: QuantileLine {
+									intercept: quantile.lower.intercept,
+									slope: quantile.lower.slope,
+								},
+								upper: QuantileLine {
+									intercept: quantile.upper.intercept,
+									slope: quantile.upper.slope,
+								},
+							}
+						}
+					},
+				});
 		match value.model {
 			model::RegressionModel::Linear(inner_model) => {
 				let feature_groups = inner_model
@@ -667,6 +1543,8 @@ impl TryFrom<model::Regressor> for Regressor {
 					.into_iter()
 					.map(TryFrom::try_from)
 					.collect::<Result<Vec<_>>>()?;
+				let n_features = validate_feature_groups(&columns, &feature_groups)?;
+				validate_linear_weights(&inner_model.weights, &inner_model.means, n_features)?;
 				Ok(Regressor {
 					id,
 					columns,
@@ -676,6 +1554,7 @@ impl TryFrom<model::Regressor> for Regressor {
 						weights: inner_model.weights.into(),
 						means: inner_model.means,
 					}),
+					prediction_intervals,
 				})
 			}
 			model::RegressionModel::Tree(inner_model) => {
@@ -684,18 +1563,23 @@ impl TryFrom<model::Regressor> for Regressor {
 					.into_iter()
 					.map(TryFrom::try_from)
 					.collect::<Result<Vec<_>>>()?;
+				let n_features = validate_feature_groups(&columns, &feature_groups)?;
+				let trees = inner_model
+					.trees
+					.into_iter()
+					.map(TryInto::try_into)
+					.collect::<Result<Vec<_>>>()?;
+				validate_trees(&trees, n_features)?;
+				let tree_model = tangram_tree::Regressor {
+					bias: inner_model.bias,
+					trees,
+				};
 				Ok(Regressor {
 					id,
 					columns,
 					feature_groups,
-					model: RegressionModel::Tree(tangram_tree::Regressor {
-						bias: inner_model.bias,
-						trees: inner_model
-							.trees
-							.into_iter()
-							.map(TryInto::try_into)
-							.collect::<Result<Vec<_>>>()?,
-					}),
+					model: RegressionModel::Tree(tree_model, None),
+					prediction_intervals,
 				})
 			}
 		}
@@ -720,6 +1604,8 @@ impl TryFrom<model::BinaryClassifier> for BinaryClassifier {
 					.into_iter()
 					.map(TryFrom::try_from)
 					.collect::<Result<Vec<_>>>()?;
+				let n_features = validate_feature_groups(&columns, &feature_groups)?;
+				validate_linear_weights(&inner_model.weights, &inner_model.means, n_features)?;
 				Ok(BinaryClassifier {
 					id,
 					columns,
@@ -739,20 +1625,24 @@ impl TryFrom<model::BinaryClassifier> for BinaryClassifier {
 					.into_iter()
 					.map(TryFrom::try_from)
 					.collect::<Result<Vec<_>>>()?;
+				let n_features = validate_feature_groups(&columns, &feature_groups)?;
+				let trees = inner_model
+					.trees
+					.into_iter()
+					.map(TryInto::try_into)
+					.collect::<Result<Vec<_>>>()?;
+				validate_trees(&trees, n_features)?;
+				let tree_model = tangram_tree::BinaryClassifier {
+					bias: inner_model.bias,
+					trees,
+				};
 				Ok(BinaryClassifier {
 					id,
 					columns,
 					negative_class,
 					positive_class,
 					feature_groups,
-					model: BinaryClassificationModel::Tree(tangram_tree::BinaryClassifier {
-						bias: inner_model.bias,
-						trees: inner_model
-							.trees
-							.into_iter()
-							.map(TryInto::try_into)
-							.collect::<Result<Vec<_>>>()?,
-					}),
+					model: BinaryClassificationModel::Tree(tree_model, None),
 				})
 			}
 		}
@@ -772,14 +1662,34 @@ impl TryFrom<model::MulticlassClassifier> for MulticlassClassifier {
 		match model.model {
 			model::MulticlassClassificationModel::Linear(inner_model) => {
 				let n_classes = inner_model.n_classes.to_usize().unwrap();
-				let n_features = inner_model.n_features.to_usize().unwrap();
-				let weights =
-					Array::from_shape_vec((n_features, n_classes), inner_model.weights).unwrap();
 				let feature_groups = inner_model
 					.feature_groups
 					.into_iter()
 					.map(TryFrom::try_from)
 					.collect::<Result<Vec<_>>>()?;
+				let n_features = validate_feature_groups(&columns, &feature_groups)?;
+				if inner_model.n_features != n_features {
+					return Err(err!(
+						"expected {} features but the feature groups produce {}",
+						inner_model.n_features,
+						n_features
+					));
+				}
+				if inner_model.biases.len() != n_classes {
+					return Err(err!(
+						"expected {} biases but found {}",
+						n_classes,
+						inner_model.biases.len()
+					));
+				}
+				if inner_model.means.len() != n_features {
+					return Err(err!(
+						"expected {} means but found {}",
+						n_features,
+						inner_model.means.len()
+					));
+				}
+				let weights = Array::from_shape_vec((n_features, n_classes), inner_model.weights)?;
 				Ok(MulticlassClassifier {
 					id,
 					columns,
@@ -800,29 +1710,148 @@ impl TryFrom<model::MulticlassClassifier> for MulticlassClassifier {
 					.into_iter()
 					.map(TryFrom::try_from)
 					.collect::<Result<Vec<_>>>()?;
+				let n_features = validate_feature_groups(&columns, &feature_groups)?;
+				let n_classes = inner_model.n_classes.to_usize().unwrap();
+				let n_rounds = inner_model.n_rounds.to_usize().unwrap();
+				let trees = inner_model
+					.trees
+					.into_iter()
+					.map(TryInto::try_into)
+					.collect::<Result<Vec<_>>>()?;
+				if trees.len() != n_rounds * n_classes {
+					return Err(err!(
+						"expected {} trees but found {}",
+						n_rounds * n_classes,
+						trees.len()
+					));
+				}
+				if inner_model.biases.len() != n_classes {
+					return Err(err!(
+						"expected {} biases but found {}",
+						n_classes,
+						inner_model.biases.len()
+					));
+				}
+				validate_trees(&trees, n_features)?;
+				let tree_model = tangram_tree::MulticlassClassifier {
+					biases: inner_model.biases,
+					trees,
+					n_classes,
+					n_rounds,
+				};
 				Ok(MulticlassClassifier {
 					id,
 					columns,
 					classes,
 					feature_groups,
-					model: MulticlassClassificationModel::Tree(
-						tangram_tree::MulticlassClassifier {
-							biases: inner_model.biases,
-							trees: inner_model
-								.trees
-								.into_iter()
-								.map(TryInto::try_into)
-								.collect::<Result<Vec<_>>>()?,
-							n_classes: inner_model.n_classes.to_usize().unwrap(),
-							n_rounds: inner_model.n_rounds.to_usize().unwrap(),
-						},
-					),
+					model: MulticlassClassificationModel::Tree(tree_model, None),
 				})
 			}
 		}
 	}
 }
 
+/// Check that each feature group's source column is one of the model's columns with a compatible type, and return the total number of features.
+fn validate_feature_groups(
+	columns: &[Column],
+	feature_groups: &[tangram_features::FeatureGroup],
+) -> Result<usize> {
+	for feature_group in feature_groups.iter() {
+		let source_column_name = match feature_group {
+			tangram_features::FeatureGroup::Identity(feature_group) => {
+				&feature_group.source_column_name
+			}
+			tangram_features::FeatureGroup::Normalized(feature_group) => {
+				&feature_group.source_column_name
+			}
+			tangram_features::FeatureGroup::OneHotEncoded(feature_group) => {
+				&feature_group.source_column_name
+			}
+			tangram_features::FeatureGroup::BagOfWords(feature_group) => {
+				&feature_group.source_column_name
+			}
+		};
+		let column = columns
+			.iter()
+			.find(|column| column.name() == source_column_name)
+			.ok_or_else(|| {
+				err!(
+					"feature group refers to unknown column \"{}\"",
+					source_column_name
+				)
+			})?;
+		let column_type_matches = match (feature_group, column) {
+			(tangram_features::FeatureGroup::Identity(_), Column::Number(_)) => true,
+			(tangram_features::FeatureGroup::Identity(_), Column::Enum(_)) => true,
+			(tangram_features::FeatureGroup::Normalized(_), Column::Number(_)) => true,
+			(tangram_features::FeatureGroup::OneHotEncoded(_), Column::Enum(_)) => true,
+			(tangram_features::FeatureGroup::BagOfWords(_), Column::Text(_)) => true,
+			_ => false,
+		};
+		if !column_type_matches {
+			return Err(err!(
+				"feature group does not match the type of column \"{}\"",
+				source_column_name
+			));
+		}
+	}
+	Ok(feature_groups
+		.iter()
+		.map(|feature_group| feature_group.n_features())
+		.sum())
+}
+
+/// Check that a linear model has one weight and one mean per feature.
+fn validate_linear_weights(weights: &[f32], means: &[f32], n_features: usize) -> Result<()> {
+	if weights.len() != n_features {
+		return Err(err!(
+			"expected {} weights but found {}",
+			n_features,
+			weights.len()
+		));
+	}
+	if means.len() != n_features {
+		return Err(err!(
+			"expected {} means but found {}",
+			n_features,
+			means.len()
+		));
+	}
+	Ok(())
+}
+
+/// Check that the trees can be evaluated without reading out of bounds. Children are always stored after their parents, so requiring each child index to be greater than its parent's index also guarantees that every path through a tree ends at a leaf.
+fn validate_trees(trees: &[tangram_tree::Tree], n_features: usize) -> Result<()> {
+	for tree in trees.iter() {
+		if tree.nodes.is_empty() {
+			return Err(err!("tree has no nodes"));
+		}
+		for (node_index, node) in tree.nodes.iter().enumerate() {
+			let node = match node {
+				tangram_tree::Node::Branch(node) => node,
+				tangram_tree::Node::Leaf(_) => continue,
+			};
+			for child_index in &[node.left_child_index, node.right_child_index] {
+				if *child_index <= node_index || *child_index >= tree.nodes.len() {
+					return Err(err!(
+						"node {} has invalid child index {}",
+						node_index,
+						child_index
+					));
+				}
+			}
+			if node.split.feature_index() >= n_features {
+				return Err(err!(
+					"node {} has invalid feature index {}",
+					node_index,
+					node.split.feature_index()
+				));
+			}
+		}
+	}
+	Ok(())
+}
+
 impl TryFrom<model::ColumnStats> for Column {
 	type Error = tangram_util::error::Error;
 	fn try_from(value: model::ColumnStats) -> Result<Column> {
@@ -832,6 +1861,8 @@ impl TryFrom<model::ColumnStats> for Column {
 			})),
 			model::ColumnStats::Number(value) => Ok(Column::Number(NumberColumn {
 				name: value.column_name,
+				min: value.min,
+				max: value.max,
 			})),
 			model::ColumnStats::Enum(value) => Ok(Column::Enum(EnumColumn {
 				name: value.column_name,
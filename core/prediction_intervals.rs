@@ -0,0 +1,207 @@
+/*!
+This module calibrates prediction intervals for regressors on the residuals of their predictions on a dataset held out from training. Split conformal intervals add the same margin to every prediction, and have the calibrated coverage on average as long as production data looks like the calibration data. Quantile intervals fit a linear quantile regression of the residuals on the prediction for each bound, so they can widen where the model is less accurate, at the cost of the conformal guarantee.
+*/
+
+use crate::{config::PredictionIntervalsMethod, model};
+use ndarray::prelude::*;
+use num_traits::ToPrimitive;
+use std::cmp::Ordering;
+use tangram_dataframe::prelude::*;
+use tangram_util::zip;
+
+/// This is the number of iterations of iteratively reweighted least squares used to fit each quantile regression.
+const QUANTILE_REGRESSION_N_ITERATIONS: usize = 100;
+
+pub fn compute_prediction_intervals_linear_regressor(
+	dataframe_calibration: &DataFrameView,
+	target_column_index: usize,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_linear::Regressor,
+	method: PredictionIntervalsMethod,
+	coverage: f32,
+) -> Option<model::PredictionIntervals> {
+	let features =
+		tangram_features::compute_features_array_f32(dataframe_calibration, feature_groups, &|| {});
+	let mut predictions = Array::zeros(features.nrows());
+	model.predict(features.view(), predictions.view_mut());
+	compute_prediction_intervals(
+		dataframe_calibration,
+		target_column_index,
+		predictions.view(),
+		method,
+		coverage,
+	)
+}
+
+pub fn compute_prediction_intervals_tree_regressor(
+	dataframe_calibration: &DataFrameView,
+	target_column_index: usize,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_tree::Regressor,
+	method: PredictionIntervalsMethod,
+	coverage: f32,
+) -> Option<model::PredictionIntervals> {
+	let features = tangram_features::compute_features_array_value(
+		dataframe_calibration,
+		feature_groups,
+		&|| {},
+	);
+	let mut predictions = Array::zeros(features.nrows());
+	model.predict(features.view(), predictions.view_mut());
+	compute_prediction_intervals(
+		dataframe_calibration,
+		target_column_index,
+		predictions.view(),
+		method,
+		coverage,
+	)
+}
+
+/// Compute the prediction intervals from the predictions on the calibration dataset, or return `None` if it has no examples with a valid label.
+fn compute_prediction_intervals(
+	dataframe_calibration: &DataFrameView,
+	target_column_index: usize,
+	predictions: ArrayView1<f32>,
+	method: PredictionIntervalsMethod,
+	coverage: f32,
+) -> Option<model::PredictionIntervals> {
+	let labels = dataframe_calibration
+		.columns()
+		.get(target_column_index)
+		.unwrap()
+		.as_number()
+		.unwrap();
+	let (predictions, residuals): (Vec<f64>, Vec<f64>) = zip!(predictions.iter(), labels.iter())
+		.filter(|(prediction, label)| prediction.is_finite() && label.is_finite())
+		.map(|(prediction, label)| {
+			(
+				prediction.to_f64().unwrap(),
+				(label - prediction).to_f64().unwrap(),
+			)
+		})
+		.unzip();
+	if residuals.is_empty() {
+		return None;
+	}
+	let method = match method {
+		PredictionIntervalsMethod::Conformal => {
+			model::PredictionIntervalsMethod::Conformal(model::ConformalPredictionIntervals {
+				margin: compute_conformal_margin(&residuals, coverage),
+			})
+		}
+		PredictionIntervalsMethod::Quantile => {
+			let alpha = 1.0 - coverage.to_f64().unwrap();
+			model::PredictionIntervalsMethod::Quantile(model::QuantilePredictionIntervals {
+				lower: fit_quantile_line(&predictions, &residuals, alpha / 2.0),
+				upper: fit_quantile_line(&predictions, &residuals, 1.0 - alpha / 2.0),
+			})
+		}
+	};
+	Some(model::PredictionIntervals { coverage, method })
+}
+
+/// The conformal margin is the `ceil((n + 1) * coverage)`th smallest absolute residual. If that is more than `n`, there are too few calibration examples to guarantee the coverage, and the largest absolute residual is used.
+fn compute_conformal_margin(residuals: &[f64], coverage: f32) -> f32 {
+	let mut absolute_residuals = residuals
+		.iter()
+		.map(|residual| residual.abs())
+		.collect::<Vec<_>>();
+	absolute_residuals.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+	let n = absolute_residuals.len();
+	let rank = ((n + 1).to_f64().unwrap() * coverage.to_f64().unwrap())
+		.ceil()
+		.to_usize()
+		.unwrap()
+		.max(1)
+		.min(n);
+	absolute_residuals[rank - 1].to_f32().unwrap()
+}
+
+/// Fit the line `intercept + slope * prediction` that minimizes the pinball loss of the residuals at quantile `tau` with iteratively reweighted least squares.
+fn fit_quantile_line(predictions: &[f64], residuals: &[f64], tau: f64) -> model::QuantileLine {
+	// Start from the empirical quantile of the residuals, which is the best line with zero slope.
+	let mut sorted_residuals = residuals.to_owned();
+	sorted_residuals.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+	let quantile_index = (tau * (sorted_residuals.len() - 1).to_f64().unwrap())
+		.round()
+		.to_usize()
+		.unwrap();
+	let mut intercept = sorted_residuals[quantile_index];
+	let mut slope = 0.0;
+	// Standardize the predictions so the weighted least squares systems are well conditioned.
+	let n = predictions.len().to_f64().unwrap();
+	let mean = predictions.iter().sum::<f64>() / n;
+	let std = (predictions
+		.iter()
+		.map(|prediction| (prediction - mean).powi(2))
+		.sum::<f64>()
+		/ n)
+		.sqrt();
+	if std > 0.0 {
+		let xs = predictions
+			.iter()
+			.map(|prediction| (prediction - mean) / std)
+			.collect::<Vec<_>>();
+		// Residuals smaller than this are treated as this size when computing the weights, so the weights stay finite.
+		let residual_scale = residuals.iter().map(|r| r.abs()).sum::<f64>() / n;
+		let epsilon = (residual_scale * 1e-6).max(std::f64::MIN_POSITIVE);
+		let mut a = intercept;
+		let mut b = 0.0;
+		for _ in 0..QUANTILE_REGRESSION_N_ITERATIONS {
+			let (mut sw, mut swx, mut swxx, mut swy, mut swxy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+			for (x, y) in xs.iter().zip(residuals.iter()) {
+				let error = y - (a + b * x);
+				let tau_weight = if error >= 0.0 { tau } else { 1.0 - tau };
+				let w = tau_weight / error.abs().max(epsilon);
+				sw += w;
+				swx += w * x;
+				swxx += w * x * x;
+				swy += w * y;
+				swxy += w * x * y;
+			}
+			let determinant = sw * swxx - swx * swx;
+			if determinant.abs() <= std::f64::EPSILON * sw * swxx {
+				break;
+			}
+			a = (swxx * swy - swx * swxy) / determinant;
+			b = (sw * swxy - swx * swy) / determinant;
+		}
+		// Undo the standardization.
+		slope = b / std;
+		intercept = a - slope * mean;
+	}
+	model::QuantileLine {
+		intercept: intercept.to_f32().unwrap(),
+		slope: slope.to_f32().unwrap(),
+	}
+}
+
+#[test]
+fn test_compute_conformal_margin() {
+	// The absolute residuals sorted are [0.5, 1, 2, 3, 4].
+	let residuals = vec![-1.0, 2.0, -3.0, 4.0, 0.5];
+	// ceil(6 * 0.5) = 3, so the margin is the 3rd smallest absolute residual.
+	assert!(f32::abs(compute_conformal_margin(&residuals, 0.5) - 2.0) < f32::EPSILON);
+	// ceil(6 * 0.9) = 6, which is more than the 5 residuals, so the margin is the largest.
+	assert!(f32::abs(compute_conformal_margin(&residuals, 0.9) - 4.0) < f32::EPSILON);
+	// ceil(6 * 0) = 0, so the margin is the smallest.
+	assert!(f32::abs(compute_conformal_margin(&residuals, 0.0) - 0.5) < f32::EPSILON);
+}
+
+#[test]
+fn test_fit_quantile_line() {
+	// The residuals lie exactly on the line 1 + 2 * prediction, so every quantile line is that line.
+	let predictions = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+	let residuals = vec![1.0, 3.0, 5.0, 7.0, 9.0];
+	for tau in &[0.05, 0.5, 0.95] {
+		let line = fit_quantile_line(&predictions, &residuals, *tau);
+		assert!(f32::abs(line.intercept - 1.0) < 1e-3);
+		assert!(f32::abs(line.slope - 2.0) < 1e-3);
+	}
+	// If every prediction is the same, the line is flat at the empirical quantile. The sorted residuals are [-2, -1, 0, 1, 2], and the 0.25 quantile is at index 0.25 * 4 = 1.
+	let predictions = vec![1.0; 5];
+	let residuals = vec![2.0, -1.0, 0.0, -2.0, 1.0];
+	let line = fit_quantile_line(&predictions, &residuals, 0.25);
+	assert!(f32::abs(line.intercept - -1.0) < f32::EPSILON);
+	assert!(f32::abs(line.slope) < f32::EPSILON);
+}
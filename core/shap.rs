@@ -0,0 +1,173 @@
+/*!
+This module computes summaries of the SHAP values of a model's features on a sample of the test dataset, which the app shows on the training importances page.
+*/
+
+use crate::model;
+use ndarray::prelude::*;
+use num_traits::ToPrimitive;
+use tangram_dataframe::prelude::*;
+
+/// SHAP values are computed for at most this many examples from the test dataset.
+const MAX_EXAMPLES: usize = 1_000;
+
+/// The summary keeps the value of each example only for this many features with the largest mean absolute SHAP values.
+const MAX_FEATURES_WITH_VALUES: usize = 20;
+
+pub fn compute_shap_summary_linear_regressor(
+	dataframe_test: &DataFrameView,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_linear::Regressor,
+) -> model::ShapSummary {
+	let dataframe_sample = sample(dataframe_test);
+	let features =
+		tangram_features::compute_features_array_f32(&dataframe_sample, feature_groups, &|| {});
+	let shap_values = model
+		.compute_feature_contributions(features.view())
+		.into_iter()
+		.map(|output| output.feature_contribution_values);
+	compute_shap_summary(features.view(), shap_values)
+}
+
+pub fn compute_shap_summary_tree_regressor(
+	dataframe_test: &DataFrameView,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_tree::Regressor,
+) -> model::ShapSummary {
+	let dataframe_sample = sample(dataframe_test);
+	let features =
+		tangram_features::compute_features_array_value(&dataframe_sample, feature_groups, &|| {});
+	let shap_values = model
+		.compute_feature_contributions(features.view())
+		.into_iter()
+		.map(|output| output.feature_contribution_values);
+	compute_shap_summary(features.map(feature_value).view(), shap_values)
+}
+
+pub fn compute_shap_summary_linear_binary_classifier(
+	dataframe_test: &DataFrameView,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_linear::BinaryClassifier,
+) -> model::ShapSummary {
+	let dataframe_sample = sample(dataframe_test);
+	let features =
+		tangram_features::compute_features_array_f32(&dataframe_sample, feature_groups, &|| {});
+	let shap_values = model
+		.compute_feature_contributions(features.view())
+		.into_iter()
+		.map(|output| output.feature_contribution_values);
+	compute_shap_summary(features.view(), shap_values)
+}
+
+pub fn compute_shap_summary_tree_binary_classifier(
+	dataframe_test: &DataFrameView,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_tree::BinaryClassifier,
+) -> model::ShapSummary {
+	let dataframe_sample = sample(dataframe_test);
+	let features =
+		tangram_features::compute_features_array_value(&dataframe_sample, feature_groups, &|| {});
+	let shap_values = model
+		.compute_feature_contributions(features.view())
+		.into_iter()
+		.map(|output| output.feature_contribution_values);
+	compute_shap_summary(features.map(feature_value).view(), shap_values)
+}
+
+pub fn compute_shap_summaries_linear_multiclass_classifier(
+	dataframe_test: &DataFrameView,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_linear::MulticlassClassifier,
+) -> Vec<model::ShapSummary> {
+	let dataframe_sample = sample(dataframe_test);
+	let features =
+		tangram_features::compute_features_array_f32(&dataframe_sample, feature_groups, &|| {});
+	let mut outputs = model.compute_feature_contributions(features.view());
+	let n_classes = model.biases.len();
+	(0..n_classes)
+		.map(|class_index| {
+			let shap_values = outputs.iter_mut().map(|outputs| {
+				std::mem::take(&mut outputs[class_index].feature_contribution_values)
+			});
+			compute_shap_summary(features.view(), shap_values)
+		})
+		.collect()
+}
+
+pub fn compute_shap_summaries_tree_multiclass_classifier(
+	dataframe_test: &DataFrameView,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_tree::MulticlassClassifier,
+) -> Vec<model::ShapSummary> {
+	let dataframe_sample = sample(dataframe_test);
+	let features =
+		tangram_features::compute_features_array_value(&dataframe_sample, feature_groups, &|| {});
+	let feature_values = features.map(feature_value);
+	let mut outputs = model.compute_feature_contributions(features.view());
+	(0..model.n_classes)
+		.map(|class_index| {
+			let shap_values = outputs.iter_mut().map(|outputs| {
+				std::mem::take(&mut outputs[class_index].feature_contribution_values)
+			});
+			compute_shap_summary(feature_values.view(), shap_values)
+		})
+		.collect()
+}
+
+/// The test dataset was shuffled along with the train dataset, so its first rows are a random sample.
+fn sample<'a>(dataframe_test: &DataFrameView<'a>) -> DataFrameView<'a> {
+	let n_examples = dataframe_test.nrows().min(MAX_EXAMPLES);
+	let (dataframe_sample, _) = dataframe_test.split_at_row(n_examples);
+	dataframe_sample
+}
+
+fn feature_value(value: &DataFrameValue) -> f32 {
+	match value {
+		DataFrameValue::Number(value) => *value,
+		DataFrameValue::Enum(value) => value
+			.map(|value| value.get())
+			.unwrap_or(0)
+			.to_f32()
+			.unwrap(),
+		_ => unreachable!(),
+	}
+}
+
+/// Summarize the SHAP values for one output of a model. `features` has one row for each example, and `shap_values` yields the SHAP values of each example's features.
+fn compute_shap_summary(
+	features: ArrayView2<f32>,
+	shap_values: impl Iterator<Item = Vec<f32>>,
+) -> model::ShapSummary {
+	let (n_examples, n_features) = features.dim();
+	let mut shap_values_array = Array::zeros((n_examples, n_features));
+	for (mut row, shap_values) in shap_values_array.axis_iter_mut(Axis(0)).zip(shap_values) {
+		row.assign(&ArrayView1::from(shap_values.as_slice()));
+	}
+	let mean_abs_shap_values = shap_values_array
+		.axis_iter(Axis(1))
+		.map(|shap_values| {
+			let sum = shap_values.iter().map(|value| value.abs()).sum::<f32>();
+			sum / n_examples.max(1).to_f32().unwrap()
+		})
+		.collect::<Vec<_>>();
+	let mut feature_indexes = (0..n_features).collect::<Vec<_>>();
+	feature_indexes.sort_by(|a, b| {
+		mean_abs_shap_values[*a]
+			.partial_cmp(&mean_abs_shap_values[*b])
+			.unwrap()
+			.reverse()
+	});
+	feature_indexes.truncate(MAX_FEATURES_WITH_VALUES);
+	let features = feature_indexes
+		.into_iter()
+		.map(|feature_index| model::FeatureShapValues {
+			feature_index,
+			feature_values: features.column(feature_index).to_vec(),
+			shap_values: shap_values_array.column(feature_index).to_vec(),
+		})
+		.collect();
+	model::ShapSummary {
+		n_examples: n_examples.to_u64().unwrap(),
+		mean_abs_shap_values,
+		features,
+	}
+}
@@ -0,0 +1,373 @@
+/*!
+This module computes a model's test metric on slices of the test dataset, so the app can show the slices of examples the model performs worst on. Classifiers are evaluated by their accuracy and regressors by their mean absolute error.
+*/
+
+use crate::{model, stats};
+use ndarray::prelude::*;
+use num_traits::ToPrimitive;
+use std::cmp::Ordering;
+use tangram_dataframe::prelude::*;
+use tangram_metrics::{MeanVariance, StreamingMetric};
+use tangram_util::zip;
+
+/// Slices with fewer test examples than this are not considered for the worst slices, because their metrics are too noisy.
+const MIN_EXAMPLES_FOR_WORST_SLICES: u64 = 10;
+
+/// At most this many of the worst slices are kept.
+const MAX_WORST_SLICES: usize = 10;
+
+/// This is the z-score for a two sided 95% confidence interval.
+const Z: f64 = 1.96;
+
+pub fn compute_slice_metrics_linear_regressor(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	train_column_stats: &[stats::ColumnStatsOutput],
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_linear::Regressor,
+) -> model::SliceMetrics {
+	let features =
+		tangram_features::compute_features_array_f32(dataframe_test, feature_groups, &|| {});
+	let mut predictions = Array::zeros(features.nrows());
+	model.predict(features.view(), predictions.view_mut());
+	compute_slice_metrics_regressor(
+		dataframe_test,
+		target_column_index,
+		train_column_stats,
+		predictions.view(),
+	)
+}
+
+pub fn compute_slice_metrics_tree_regressor(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	train_column_stats: &[stats::ColumnStatsOutput],
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_tree::Regressor,
+) -> model::SliceMetrics {
+	let features =
+		tangram_features::compute_features_array_value(dataframe_test, feature_groups, &|| {});
+	let mut predictions = Array::zeros(features.nrows());
+	model.predict(features.view(), predictions.view_mut());
+	compute_slice_metrics_regressor(
+		dataframe_test,
+		target_column_index,
+		train_column_stats,
+		predictions.view(),
+	)
+}
+
+pub fn compute_slice_metrics_linear_binary_classifier(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	train_column_stats: &[stats::ColumnStatsOutput],
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_linear::BinaryClassifier,
+) -> model::SliceMetrics {
+	let features =
+		tangram_features::compute_features_array_f32(dataframe_test, feature_groups, &|| {});
+	let mut probabilities = Array::zeros(features.nrows());
+	model.predict(features.view(), probabilities.view_mut());
+	compute_slice_metrics_binary_classifier(
+		dataframe_test,
+		target_column_index,
+		train_column_stats,
+		probabilities.view(),
+	)
+}
+
+pub fn compute_slice_metrics_tree_binary_classifier(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	train_column_stats: &[stats::ColumnStatsOutput],
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_tree::BinaryClassifier,
+) -> model::SliceMetrics {
+	let features =
+		tangram_features::compute_features_array_value(dataframe_test, feature_groups, &|| {});
+	let mut probabilities = Array::zeros(features.nrows());
+	model.predict(features.view(), probabilities.view_mut());
+	compute_slice_metrics_binary_classifier(
+		dataframe_test,
+		target_column_index,
+		train_column_stats,
+		probabilities.view(),
+	)
+}
+
+pub fn compute_slice_metrics_linear_multiclass_classifier(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	train_column_stats: &[stats::ColumnStatsOutput],
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_linear::MulticlassClassifier,
+) -> model::SliceMetrics {
+	let n_classes = model.biases.len();
+	let features =
+		tangram_features::compute_features_array_f32(dataframe_test, feature_groups, &|| {});
+	let mut probabilities = Array::zeros((features.nrows(), n_classes));
+	model.predict(features.view(), probabilities.view_mut());
+	compute_slice_metrics_multiclass_classifier(
+		dataframe_test,
+		target_column_index,
+		train_column_stats,
+		probabilities.view(),
+	)
+}
+
+pub fn compute_slice_metrics_tree_multiclass_classifier(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	train_column_stats: &[stats::ColumnStatsOutput],
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_tree::MulticlassClassifier,
+) -> model::SliceMetrics {
+	let n_classes = model.n_classes;
+	let features =
+		tangram_features::compute_features_array_value(dataframe_test, feature_groups, &|| {});
+	let mut probabilities = Array::zeros((features.nrows(), n_classes));
+	model.predict(features.view(), probabilities.view_mut());
+	compute_slice_metrics_multiclass_classifier(
+		dataframe_test,
+		target_column_index,
+		train_column_stats,
+		probabilities.view(),
+	)
+}
+
+fn compute_slice_metrics_regressor(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	train_column_stats: &[stats::ColumnStatsOutput],
+	predictions: ArrayView1<f32>,
+) -> model::SliceMetrics {
+	let labels = dataframe_test.columns()[target_column_index]
+		.as_number()
+		.unwrap();
+	let absolute_errors = zip!(predictions.iter(), labels.iter())
+		.map(|(prediction, label)| (prediction - label).abs())
+		.collect::<Vec<_>>();
+	compute_slice_metrics(
+		dataframe_test,
+		target_column_index,
+		train_column_stats,
+		model::SliceMetric::MeanAbsoluteError,
+		&absolute_errors,
+	)
+}
+
+fn compute_slice_metrics_binary_classifier(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	train_column_stats: &[stats::ColumnStatsOutput],
+	probabilities: ArrayView1<f32>,
+) -> model::SliceMetrics {
+	let labels = dataframe_test.columns()[target_column_index]
+		.as_enum()
+		.unwrap();
+	// The positive class is the second option of the target column, and examples are predicted as the positive class at the default threshold of 0.5.
+	let correct = zip!(probabilities.iter(), labels.iter())
+		.map(|(probability, label)| {
+			let predicted = *probability >= 0.5;
+			let actual = label.unwrap().get() == 2;
+			if predicted == actual {
+				1.0
+			} else {
+				0.0
+			}
+		})
+		.collect::<Vec<_>>();
+	compute_slice_metrics(
+		dataframe_test,
+		target_column_index,
+		train_column_stats,
+		model::SliceMetric::Accuracy,
+		&correct,
+	)
+}
+
+fn compute_slice_metrics_multiclass_classifier(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	train_column_stats: &[stats::ColumnStatsOutput],
+	probabilities: ArrayView2<f32>,
+) -> model::SliceMetrics {
+	let labels = dataframe_test.columns()[target_column_index]
+		.as_enum()
+		.unwrap();
+	let correct = zip!(probabilities.axis_iter(Axis(0)), labels.iter())
+		.map(|(probabilities, label)| {
+			let prediction = probabilities
+				.iter()
+				.enumerate()
+				.max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+				.unwrap()
+				.0;
+			if prediction == label.unwrap().get() - 1 {
+				1.0
+			} else {
+				0.0
+			}
+		})
+		.collect::<Vec<_>>();
+	compute_slice_metrics(
+		dataframe_test,
+		target_column_index,
+		train_column_stats,
+		model::SliceMetric::Accuracy,
+		&correct,
+	)
+}
+
+/// Compute the metric on every slice of the test dataset. `values` has one entry for each test example, and the metric for a slice is the mean of its entries: 1 or 0 for whether a classifier's prediction was correct, or a regressor's absolute error.
+fn compute_slice_metrics(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	train_column_stats: &[stats::ColumnStatsOutput],
+	metric: model::SliceMetric,
+	values: &[f32],
+) -> model::SliceMetrics {
+	let mut slices = Vec::new();
+	for (column_index, column) in dataframe_test.columns().iter().enumerate() {
+		if column_index == target_column_index {
+			continue;
+		}
+		let column_name = column.name().unwrap();
+		let column_stats = train_column_stats
+			.iter()
+			.find(|column_stats| column_stats.column_name() == column_name);
+		// Compute the filter for each slice of the column and the index of the slice each example belongs to.
+		let (filters, example_slice_indexes): (Vec<model::SliceFilter>, Vec<usize>) =
+			match (column, column_stats) {
+				(DataFrameColumnView::Enum(column), _) => {
+					let mut filters = column
+						.options()
+						.iter()
+						.map(|option| model::SliceFilter::Enum(option.clone()))
+						.collect::<Vec<_>>();
+					let invalid_slice_index = filters.len();
+					filters.push(model::SliceFilter::Invalid);
+					let example_slice_indexes = column
+						.iter()
+						.map(|value| match value {
+							Some(value) => value.get() - 1,
+							None => invalid_slice_index,
+						})
+						.collect();
+					(filters, example_slice_indexes)
+				}
+				(
+					DataFrameColumnView::Number(column),
+					Some(stats::ColumnStatsOutput::Number(column_stats)),
+				) => {
+					// Bin the values by the quantiles of the column in the train dataset.
+					let mut thresholds = vec![column_stats.p25, column_stats.p50, column_stats.p75];
+					thresholds.dedup();
+					let mut filters = (0..=thresholds.len())
+						.map(|bin_index| {
+							model::SliceFilter::Number(model::NumberSliceFilter {
+								start: bin_index
+									.checked_sub(1)
+									.map(|threshold_index| thresholds[threshold_index]),
+								end: thresholds.get(bin_index).cloned(),
+							})
+						})
+						.collect::<Vec<_>>();
+					let invalid_slice_index = filters.len();
+					filters.push(model::SliceFilter::Invalid);
+					let example_slice_indexes = column
+						.iter()
+						.map(|value| {
+							if value.is_finite() {
+								thresholds
+									.iter()
+									.position(|threshold| value <= threshold)
+									.unwrap_or_else(|| thresholds.len())
+							} else {
+								invalid_slice_index
+							}
+						})
+						.collect();
+					(filters, example_slice_indexes)
+				}
+				_ => continue,
+			};
+		let mut slice_values = filters
+			.iter()
+			.map(|_| MeanVariance::default())
+			.collect::<Vec<_>>();
+		for (slice_index, value) in zip!(example_slice_indexes.iter(), values.iter()) {
+			slice_values[*slice_index].update(*value);
+		}
+		for (filter, slice_values) in zip!(filters.into_iter(), slice_values.into_iter()) {
+			let slice_values = slice_values.finalize();
+			if slice_values.n == 0 {
+				continue;
+			}
+			let (confidence_interval_lower, confidence_interval_upper) = match metric {
+				model::SliceMetric::Accuracy => {
+					compute_wilson_score_interval(slice_values.mean, slice_values.n)
+				}
+				model::SliceMetric::MeanAbsoluteError => compute_normal_interval(
+					slice_values.mean,
+					slice_values.variance,
+					slice_values.n,
+				),
+			};
+			slices.push(model::Slice {
+				column_name: column_name.to_owned(),
+				filter,
+				n_examples: slice_values.n,
+				value: slice_values.mean,
+				confidence_interval_lower,
+				confidence_interval_upper,
+			});
+		}
+	}
+	// The worst slices have the lowest accuracy or the highest mean absolute error.
+	let mut worst_slice_indexes = slices
+		.iter()
+		.enumerate()
+		.filter(|(_, slice)| slice.n_examples >= MIN_EXAMPLES_FOR_WORST_SLICES)
+		.map(|(slice_index, _)| slice_index)
+		.collect::<Vec<_>>();
+	worst_slice_indexes.sort_by(|a, b| {
+		let ordering = slices[*a]
+			.value
+			.partial_cmp(&slices[*b].value)
+			.unwrap_or(Ordering::Equal);
+		match metric {
+			model::SliceMetric::Accuracy => ordering,
+			model::SliceMetric::MeanAbsoluteError => ordering.reverse(),
+		}
+	});
+	worst_slice_indexes.truncate(MAX_WORST_SLICES);
+	model::SliceMetrics {
+		metric,
+		slices,
+		worst_slice_indexes,
+	}
+}
+
+/// Compute the Wilson score interval for a proportion, which unlike the normal approximation stays within [0, 1] and is accurate for small slices.
+fn compute_wilson_score_interval(proportion: f32, n: u64) -> (f32, f32) {
+	let p = proportion.to_f64().unwrap();
+	let n = n.to_f64().unwrap();
+	let z2 = Z * Z;
+	let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+	let half_width = Z / (1.0 + z2 / n) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+	(
+		(center - half_width).max(0.0).to_f32().unwrap(),
+		(center + half_width).min(1.0).to_f32().unwrap(),
+	)
+}
+
+/// Compute the normal approximation of the confidence interval for a mean of nonnegative values.
+fn compute_normal_interval(mean: f32, variance: f32, n: u64) -> (f32, f32) {
+	let mean = mean.to_f64().unwrap();
+	let standard_error = (variance.to_f64().unwrap() / n.to_f64().unwrap()).sqrt();
+	(
+		(mean - Z * standard_error).max(0.0).to_f32().unwrap(),
+		(mean + Z * standard_error).to_f32().unwrap(),
+	)
+}
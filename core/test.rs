@@ -1,6 +1,9 @@
-use crate::train::ModelTestProgress;
+use crate::{model, train::ModelTestProgress};
 use ndarray::prelude::*;
 use num_traits::ToPrimitive;
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256Plus;
+use rayon::prelude::*;
 use tangram_dataframe::prelude::*;
 use tangram_metrics::{self as metrics, StreamingMetric};
 use tangram_util::{progress_counter::ProgressCounter, zip};
@@ -263,3 +266,482 @@ pub fn test_tree_multiclass_classifier(
 	});
 	test_metrics.finalize()
 }
+
+/// This struct contains a binary classifier's test metrics sliced by the options of a sensitive enum column.
+pub struct FairnessMetricsOutput {
+	pub groups: Vec<FairnessGroupMetricsOutput>,
+	pub demographic_parity_difference: f32,
+	pub equal_opportunity_difference: f32,
+}
+
+pub struct FairnessGroupMetricsOutput {
+	pub group: String,
+	pub n_examples: u64,
+	pub positive_rate: f32,
+	pub metrics: metrics::BinaryClassificationMetricsOutput,
+}
+
+pub fn test_fairness_linear_binary_classifier(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	sensitive_column_index: usize,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_linear::BinaryClassifier,
+) -> FairnessMetricsOutput {
+	let features =
+		tangram_features::compute_features_array_f32(dataframe_test, &feature_groups, &|| {});
+	let mut probabilities = Array::zeros(features.nrows());
+	model.predict(features.view(), probabilities.view_mut());
+	compute_fairness_metrics(
+		dataframe_test,
+		target_column_index,
+		sensitive_column_index,
+		probabilities.as_slice().unwrap(),
+	)
+}
+
+pub fn test_fairness_tree_binary_classifier(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	sensitive_column_index: usize,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_tree::BinaryClassifier,
+) -> FairnessMetricsOutput {
+	let features =
+		tangram_features::compute_features_array_value(dataframe_test, feature_groups, &|| {});
+	let mut probabilities = Array::zeros(features.nrows());
+	model.predict(features.view(), probabilities.view_mut());
+	compute_fairness_metrics(
+		dataframe_test,
+		target_column_index,
+		sensitive_column_index,
+		probabilities.as_slice().unwrap(),
+	)
+}
+
+fn compute_fairness_metrics(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	sensitive_column_index: usize,
+	probabilities: &[f32],
+) -> FairnessMetricsOutput {
+	let labels = dataframe_test
+		.columns()
+		.get(target_column_index)
+		.unwrap()
+		.as_enum()
+		.unwrap();
+	let sensitive_column = dataframe_test
+		.columns()
+		.get(sensitive_column_index)
+		.unwrap()
+		.as_enum()
+		.unwrap();
+	// Compute the metrics for each option of the sensitive column. Examples with invalid values for the sensitive column do not belong to any group.
+	let mut group_metrics = sensitive_column
+		.options()
+		.iter()
+		.map(|_| (0u64, metrics::BinaryClassificationMetrics::new(101)))
+		.collect::<Vec<_>>();
+	for (probability, label, group) in
+		zip!(probabilities.iter(), labels.iter(), sensitive_column.iter())
+	{
+		if let Some(group) = group {
+			let (n_examples, test_metrics) = &mut group_metrics[group.get() - 1];
+			*n_examples += 1;
+			test_metrics.update(metrics::BinaryClassificationMetricsInput {
+				probabilities: &[*probability],
+				labels: &[*label],
+			});
+		}
+	}
+	let groups = zip!(sensitive_column.options().iter(), group_metrics.into_iter())
+		.filter(|(_, (n_examples, _))| *n_examples > 0)
+		.map(|(group, (n_examples, test_metrics))| {
+			let metrics = test_metrics.finalize();
+			let default_threshold_metrics = &metrics.thresholds[metrics.thresholds.len() / 2];
+			let positive_rate = (default_threshold_metrics.true_positives
+				+ default_threshold_metrics.false_positives)
+				.to_f32()
+				.unwrap() / n_examples.to_f32().unwrap();
+			FairnessGroupMetricsOutput {
+				group: group.clone(),
+				n_examples,
+				positive_rate,
+				metrics,
+			}
+		})
+		.collect::<Vec<_>>();
+	let demographic_parity_difference =
+		compute_max_difference(groups.iter().map(|group| group.positive_rate));
+	// Groups with no examples of the positive class have no true positive rate, so they are left out of the equal opportunity difference.
+	let equal_opportunity_difference = compute_max_difference(groups.iter().map(|group| {
+		group.metrics.thresholds[group.metrics.thresholds.len() / 2].true_positive_rate
+	}));
+	FairnessMetricsOutput {
+		groups,
+		demographic_parity_difference,
+		equal_opportunity_difference,
+	}
+}
+
+/// Compute the difference between the largest and smallest finite values, or zero if there are none.
+fn compute_max_difference(values: impl Iterator<Item = f32>) -> f32 {
+	let (min, max) = values.filter(|value| value.is_finite()).fold(
+		(std::f32::INFINITY, std::f32::NEG_INFINITY),
+		|(min, max), value| (min.min(value), max.max(value)),
+	);
+	if min <= max {
+		max - min
+	} else {
+		0.0
+	}
+}
+
+pub fn test_confidence_intervals_linear_regressor(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_linear::Regressor,
+	n_bootstrap_samples: usize,
+) -> model::RegressionMetricsConfidenceIntervals {
+	let features =
+		tangram_features::compute_features_array_f32(dataframe_test, &feature_groups, &|| {});
+	let mut predictions = Array::zeros(features.nrows());
+	model.predict(features.view(), predictions.view_mut());
+	compute_regression_confidence_intervals(
+		dataframe_test,
+		target_column_index,
+		predictions.as_slice().unwrap(),
+		n_bootstrap_samples,
+	)
+}
+
+pub fn test_confidence_intervals_tree_regressor(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_tree::Regressor,
+	n_bootstrap_samples: usize,
+) -> model::RegressionMetricsConfidenceIntervals {
+	let features =
+		tangram_features::compute_features_array_value(dataframe_test, feature_groups, &|| {});
+	let mut predictions = Array::zeros(features.nrows());
+	model.predict(features.view(), predictions.view_mut());
+	compute_regression_confidence_intervals(
+		dataframe_test,
+		target_column_index,
+		predictions.as_slice().unwrap(),
+		n_bootstrap_samples,
+	)
+}
+
+pub fn test_confidence_intervals_linear_binary_classifier(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_linear::BinaryClassifier,
+	n_bootstrap_samples: usize,
+) -> model::BinaryClassificationMetricsConfidenceIntervals {
+	let features =
+		tangram_features::compute_features_array_f32(dataframe_test, &feature_groups, &|| {});
+	let mut probabilities = Array::zeros(features.nrows());
+	model.predict(features.view(), probabilities.view_mut());
+	compute_binary_classification_confidence_intervals(
+		dataframe_test,
+		target_column_index,
+		probabilities.as_slice().unwrap(),
+		n_bootstrap_samples,
+	)
+}
+
+pub fn test_confidence_intervals_tree_binary_classifier(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_tree::BinaryClassifier,
+	n_bootstrap_samples: usize,
+) -> model::BinaryClassificationMetricsConfidenceIntervals {
+	let features =
+		tangram_features::compute_features_array_value(dataframe_test, feature_groups, &|| {});
+	let mut probabilities = Array::zeros(features.nrows());
+	model.predict(features.view(), probabilities.view_mut());
+	compute_binary_classification_confidence_intervals(
+		dataframe_test,
+		target_column_index,
+		probabilities.as_slice().unwrap(),
+		n_bootstrap_samples,
+	)
+}
+
+pub fn test_confidence_intervals_linear_multiclass_classifier(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_linear::MulticlassClassifier,
+	n_bootstrap_samples: usize,
+) -> model::MulticlassClassificationMetricsConfidenceIntervals {
+	let n_classes = model.biases.len();
+	let features =
+		tangram_features::compute_features_array_f32(dataframe_test, &feature_groups, &|| {});
+	let mut probabilities = Array::zeros((features.nrows(), n_classes));
+	model.predict(features.view(), probabilities.view_mut());
+	compute_multiclass_classification_confidence_intervals(
+		dataframe_test,
+		target_column_index,
+		probabilities.view(),
+		n_bootstrap_samples,
+	)
+}
+
+pub fn test_confidence_intervals_tree_multiclass_classifier(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	feature_groups: &[tangram_features::FeatureGroup],
+	model: &tangram_tree::MulticlassClassifier,
+	n_bootstrap_samples: usize,
+) -> model::MulticlassClassificationMetricsConfidenceIntervals {
+	let n_classes = model.n_classes;
+	let features =
+		tangram_features::compute_features_array_value(dataframe_test, feature_groups, &|| {});
+	let mut probabilities = Array::zeros((features.nrows(), n_classes));
+	model.predict(features.view(), probabilities.view_mut());
+	compute_multiclass_classification_confidence_intervals(
+		dataframe_test,
+		target_column_index,
+		probabilities.view(),
+		n_bootstrap_samples,
+	)
+}
+
+fn compute_regression_confidence_intervals(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	predictions: &[f32],
+	n_bootstrap_samples: usize,
+) -> model::RegressionMetricsConfidenceIntervals {
+	let labels = dataframe_test
+		.columns()
+		.get(target_column_index)
+		.unwrap()
+		.as_number()
+		.unwrap();
+	let labels = labels.as_slice();
+	let bootstrap_metrics = bootstrap(labels.len(), n_bootstrap_samples, &|indexes| {
+		let predictions = indexes
+			.iter()
+			.map(|index| predictions[*index])
+			.collect::<Vec<_>>();
+		let labels = indexes
+			.iter()
+			.map(|index| labels[*index])
+			.collect::<Vec<_>>();
+		let mut test_metrics = metrics::RegressionMetrics::default();
+		test_metrics.update(metrics::RegressionMetricsInput {
+			predictions: &predictions,
+			labels: &labels,
+		});
+		test_metrics.finalize()
+	});
+	model::RegressionMetricsConfidenceIntervals {
+		n_bootstrap_samples: n_bootstrap_samples.to_u64().unwrap(),
+		mse: compute_confidence_interval(bootstrap_metrics.iter().map(|metrics| metrics.mse)),
+		rmse: compute_confidence_interval(bootstrap_metrics.iter().map(|metrics| metrics.rmse)),
+		mae: compute_confidence_interval(bootstrap_metrics.iter().map(|metrics| metrics.mae)),
+		r2: compute_confidence_interval(bootstrap_metrics.iter().map(|metrics| metrics.r2)),
+	}
+}
+
+fn compute_binary_classification_confidence_intervals(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	probabilities: &[f32],
+	n_bootstrap_samples: usize,
+) -> model::BinaryClassificationMetricsConfidenceIntervals {
+	let labels = dataframe_test
+		.columns()
+		.get(target_column_index)
+		.unwrap()
+		.as_enum()
+		.unwrap();
+	let labels = labels.as_slice();
+	let bootstrap_metrics = bootstrap(labels.len(), n_bootstrap_samples, &|indexes| {
+		let probabilities = indexes
+			.iter()
+			.map(|index| probabilities[*index])
+			.collect::<Vec<_>>();
+		let labels = indexes
+			.iter()
+			.map(|index| labels[*index])
+			.collect::<Vec<_>>();
+		// Use the same thresholds as the test metrics so the intervals are for the same approximation of the area under the receiver operating characteristic curve.
+		let mut test_metrics = metrics::BinaryClassificationMetrics::new(101);
+		test_metrics.update(metrics::BinaryClassificationMetricsInput {
+			probabilities: &probabilities,
+			labels: &labels,
+		});
+		test_metrics.finalize()
+	});
+	let default_threshold_metrics = || {
+		bootstrap_metrics
+			.iter()
+			.map(|metrics| &metrics.thresholds[metrics.thresholds.len() / 2])
+	};
+	model::BinaryClassificationMetricsConfidenceIntervals {
+		n_bootstrap_samples: n_bootstrap_samples.to_u64().unwrap(),
+		auc_roc: compute_confidence_interval(
+			bootstrap_metrics
+				.iter()
+				.map(|metrics| metrics.auc_roc_approx),
+		),
+		accuracy: compute_confidence_interval(
+			default_threshold_metrics().map(|metrics| metrics.accuracy),
+		),
+		precision: compute_confidence_interval(
+			default_threshold_metrics().map(|metrics| metrics.precision),
+		),
+		recall: compute_confidence_interval(
+			default_threshold_metrics().map(|metrics| metrics.recall),
+		),
+		f1_score: compute_confidence_interval(
+			default_threshold_metrics().map(|metrics| metrics.f1_score),
+		),
+	}
+}
+
+fn compute_multiclass_classification_confidence_intervals(
+	dataframe_test: &DataFrameView,
+	target_column_index: usize,
+	probabilities: ArrayView2<f32>,
+	n_bootstrap_samples: usize,
+) -> model::MulticlassClassificationMetricsConfidenceIntervals {
+	let labels = dataframe_test
+		.columns()
+		.get(target_column_index)
+		.unwrap()
+		.as_enum()
+		.unwrap();
+	let n_classes = labels.options().len();
+	let labels = labels.as_slice();
+	let bootstrap_metrics = bootstrap(labels.len(), n_bootstrap_samples, &|indexes| {
+		let probabilities = probabilities.select(Axis(0), indexes);
+		let labels = indexes
+			.iter()
+			.map(|index| labels[*index])
+			.collect::<Vec<_>>();
+		let mut test_metrics = metrics::MulticlassClassificationMetrics::new(n_classes);
+		test_metrics.update(metrics::MulticlassClassificationMetricsInput {
+			probabilities: probabilities.view(),
+			labels: labels.as_slice().into(),
+		});
+		test_metrics.finalize()
+	});
+	model::MulticlassClassificationMetricsConfidenceIntervals {
+		n_bootstrap_samples: n_bootstrap_samples.to_u64().unwrap(),
+		accuracy: compute_confidence_interval(
+			bootstrap_metrics.iter().map(|metrics| metrics.accuracy),
+		),
+		precision_unweighted: compute_confidence_interval(
+			bootstrap_metrics
+				.iter()
+				.map(|metrics| metrics.precision_unweighted),
+		),
+		precision_weighted: compute_confidence_interval(
+			bootstrap_metrics
+				.iter()
+				.map(|metrics| metrics.precision_weighted),
+		),
+		recall_unweighted: compute_confidence_interval(
+			bootstrap_metrics
+				.iter()
+				.map(|metrics| metrics.recall_unweighted),
+		),
+		recall_weighted: compute_confidence_interval(
+			bootstrap_metrics
+				.iter()
+				.map(|metrics| metrics.recall_weighted),
+		),
+	}
+}
+
+/// Call `compute` in parallel on `n_bootstrap_samples` resamples of the `n_examples` test examples. Each resample is drawn with replacement and has as many examples as the test dataset, and `compute` is passed the indexes of the examples in it.
+fn bootstrap<T: Send>(
+	n_examples: usize,
+	n_bootstrap_samples: usize,
+	compute: &(dyn Fn(&[usize]) -> T + Sync),
+) -> Vec<T> {
+	(0..n_bootstrap_samples)
+		.into_par_iter()
+		.map(|sample_index| {
+			// Seed each resample with its index so the intervals do not change when the same model is trained on the same data again.
+			let mut rng = Xoshiro256Plus::seed_from_u64(sample_index.to_u64().unwrap());
+			let indexes = (0..n_examples)
+				.map(|_| rng.gen_range(0, n_examples))
+				.collect::<Vec<_>>();
+			compute(&indexes)
+		})
+		.collect()
+}
+
+/// Compute the 95% confidence interval from the values of a metric on the bootstrap resamples. Resamples where the metric is not defined, such as the precision when there are no positive predictions, are left out.
+fn compute_confidence_interval(values: impl Iterator<Item = f32>) -> model::ConfidenceInterval {
+	let mut values = values.filter(|value| value.is_finite()).collect::<Vec<_>>();
+	if values.is_empty() {
+		return model::ConfidenceInterval {
+			lower: f32::NAN,
+			upper: f32::NAN,
+		};
+	}
+	values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+	let percentile = |percentile: f32| {
+		let index = (percentile * (values.len() - 1).to_f32().unwrap())
+			.round()
+			.to_usize()
+			.unwrap();
+		values[index]
+	};
+	model::ConfidenceInterval {
+		lower: percentile(0.025),
+		upper: percentile(0.975),
+	}
+}
+
+#[test]
+fn test_compute_confidence_interval() {
+	// With 101 values, the 2.5th percentile is at index 2.5, which rounds to 3, and the 97.5th percentile is at index 97.5, which rounds to 98.
+	let values = (0..=100).rev().map(|value| value.to_f32().unwrap());
+	let confidence_interval = compute_confidence_interval(values);
+	assert!(f32::abs(confidence_interval.lower - 3.0) < f32::EPSILON);
+	assert!(f32::abs(confidence_interval.upper - 98.0) < f32::EPSILON);
+	// Values that are not finite are left out, leaving [1, 2, 3], whose percentiles are at indexes 0.05 and 1.95.
+	let values = vec![f32::NAN, 3.0, f32::INFINITY, 1.0, 2.0];
+	let confidence_interval = compute_confidence_interval(values.into_iter());
+	assert!(f32::abs(confidence_interval.lower - 1.0) < f32::EPSILON);
+	assert!(f32::abs(confidence_interval.upper - 3.0) < f32::EPSILON);
+	// If the metric is not defined for any resample, neither is the interval.
+	let values = vec![f32::NAN, f32::NAN];
+	let confidence_interval = compute_confidence_interval(values.into_iter());
+	assert!(confidence_interval.lower.is_nan());
+	assert!(confidence_interval.upper.is_nan());
+}
+
+#[test]
+fn test_bootstrap() {
+	// Each resample draws n_examples indexes with replacement.
+	let resamples = bootstrap(10, 5, &|indexes| indexes.to_owned());
+	assert_eq!(resamples.len(), 5);
+	for indexes in resamples.iter() {
+		assert_eq!(indexes.len(), 10);
+		assert!(indexes.iter().all(|index| *index < 10));
+	}
+	// The resamples are seeded, so they are the same every time.
+	assert_eq!(resamples, bootstrap(10, 5, &|indexes| indexes.to_owned()));
+	// The mean of a constant column is the same on every resample, so its interval has no width.
+	let labels = [2.5f32; 20];
+	let means = bootstrap(labels.len(), 100, &|indexes| {
+		indexes.iter().map(|index| labels[*index]).sum::<f32>() / indexes.len().to_f32().unwrap()
+	});
+	let confidence_interval = compute_confidence_interval(means.into_iter());
+	assert!(f32::abs(confidence_interval.lower - 2.5) < f32::EPSILON);
+	assert!(f32::abs(confidence_interval.upper - 2.5) < f32::EPSILON);
+}
@@ -1,6 +1,6 @@
 use crate::{
-	config::{self, Config},
-	grid, model, stats, test,
+	config::{self, Config, PredictionIntervalsMethod},
+	grid, model, partial_dependence, prediction_intervals, shap, slice_metrics, stats, test,
 };
 use ndarray::prelude::*;
 use num_traits::ToPrimitive;
@@ -176,6 +176,88 @@ pub fn train(
 		_ => return Err(err!("invalid target column type")),
 	};
 
+	// Find the sensitive column, if the fairness config is set.
+	let fairness_config = config.as_ref().and_then(|config| config.fairness.as_ref());
+	let sensitive_column_index = match fairness_config {
+		Some(fairness_config) => {
+			let sensitive_column_name = &fairness_config.sensitive_column_name;
+			let sensitive_column_index = column_names
+				.iter()
+				.position(|column_name| column_name == sensitive_column_name)
+				.ok_or_else(|| {
+					err!(
+						"did not find sensitive column \"{}\" among column names \"{}\"",
+						sensitive_column_name,
+						column_names.join(", ")
+					)
+				})?;
+			if sensitive_column_index == target_column_index {
+				return Err(err!("the sensitive column must not be the target column"));
+			}
+			if dataframe_train.columns()[sensitive_column_index]
+				.as_enum()
+				.is_none()
+			{
+				return Err(err!(
+					"the sensitive column \"{}\" must be an enum column",
+					sensitive_column_name
+				));
+			}
+			match task {
+				Task::BinaryClassification => {}
+				_ => {
+					return Err(err!(
+						"fairness metrics are only supported for binary classifiers"
+					))
+				}
+			}
+			Some(sensitive_column_index)
+		}
+		None => None,
+	};
+	let exclude_sensitive_column_from_features = fairness_config
+		.and_then(|fairness_config| fairness_config.exclude_from_features)
+		.unwrap_or(false);
+
+	// Confidence intervals for the test metrics are computed only if the confidence intervals config is set.
+	let default_n_bootstrap_samples = 1000;
+	let n_bootstrap_samples = config
+		.as_ref()
+		.and_then(|config| config.confidence_intervals.as_ref())
+		.map(|confidence_intervals_config| {
+			confidence_intervals_config
+				.n_bootstrap_samples
+				.unwrap_or(default_n_bootstrap_samples)
+		});
+
+	// Prediction intervals are computed only for regressors, and only if the prediction intervals config is set.
+	let prediction_intervals_config = config
+		.as_ref()
+		.and_then(|config| config.prediction_intervals.as_ref());
+	let prediction_intervals_options = match prediction_intervals_config {
+		Some(prediction_intervals_config) => {
+			match task {
+				Task::Regression => {}
+				_ => {
+					return Err(err!(
+						"prediction intervals are only supported for regressors"
+					))
+				}
+			}
+			let method = prediction_intervals_config
+				.method
+				.unwrap_or(PredictionIntervalsMethod::Conformal);
+			let coverage = prediction_intervals_config.coverage.unwrap_or(0.9);
+			if !(coverage > 0.0 && coverage < 1.0) {
+				return Err(err!(
+					"the prediction intervals coverage must be between 0 and 1"
+				));
+			}
+			Some((method, coverage))
+		}
+		None => None,
+	};
+
 	// Compute the baseline metrics.
 	let baseline_metrics = match task {
 		Task::Regression => {
@@ -263,9 +345,22 @@ pub fn train(
 	// Choose the comparison metric.
 	let comparison_metric = choose_comparison_metric(&config, &task)?;
 
-	// Create the hyperparameter grid.
+	// Create the hyperparameter grid. If the sensitive column is excluded from the features, its stats are set aside while the feature groups are chosen.
+	let sensitive_column_stats = if exclude_sensitive_column_from_features {
+		let sensitive_column_name = &fairness_config.unwrap().sensitive_column_name;
+		let index = train_column_stats
+			.iter()
+			.position(|column_stats| column_stats.column_name() == sensitive_column_name)
+			.unwrap();
+		Some((index, train_column_stats.remove(index)))
+	} else {
+		None
+	};
 	let grid =
 		compute_hyperparameter_grid(&config, &task, target_column_index, &train_column_stats);
+	if let Some((index, sensitive_column_stats)) = sensitive_column_stats {
+		train_column_stats.insert(index, sensitive_column_stats);
+	}
 
 	// Train each model in the grid and compute model comparison metrics.
 	let num_models = grid.len();
@@ -309,6 +404,41 @@ pub fn train(
 	update_progress(Progress::Testing);
 	let test_metrics = test_model(&train_model_output, &dataframe_test, &mut |_| {});
 
+	// Bootstrap the test dataset to compute confidence intervals for the test metrics.
+	let test_metrics_confidence_intervals = n_bootstrap_samples.map(|n_bootstrap_samples| {
+		compute_test_metrics_confidence_intervals(
+			&train_model_output,
+			&dataframe_test,
+			n_bootstrap_samples,
+		)
+	});
+
+	// Summarize the SHAP values of the best model's features on the test dataset.
+	let mut shap_summaries = compute_shap_summaries(&train_model_output, &dataframe_test);
+
+	// Compute the partial dependence of the best model's predictions on each number and enum column.
+	let mut partial_dependences =
+		compute_partial_dependences(&train_model_output, &dataframe_train, &dataframe_test);
+
+	// Calibrate the best model's prediction intervals on the model comparison dataset, which was held out from training.
+	let prediction_intervals = prediction_intervals_options.and_then(|(method, coverage)| {
+		compute_prediction_intervals(&train_model_output, &dataframe_comparison, method, coverage)
+	});
+
+	// Compute the best model's test metric on slices of the test dataset.
+	let slice_metrics =
+		compute_slice_metrics(&train_model_output, &dataframe_test, &train_column_stats);
+
+	// Slice the test metrics of the best model by the sensitive column.
+	let fairness_metrics = sensitive_column_index.map(|sensitive_column_index| {
+		compute_fairness_metrics(
+			&train_model_output,
+			&dataframe_test,
+			sensitive_column_index,
+			exclude_sensitive_column_from_features,
+		)
+	});
+
 	// Assemble the model.
 	let model = match task {
 		Task::Regression => {
@@ -324,6 +454,15 @@ pub fn train(
 				Metrics::Regression(test_metrics) => test_metrics,
 				_ => unreachable!(),
 			};
+			let test_metrics_confidence_intervals =
+				test_metrics_confidence_intervals.map(|confidence_intervals| {
+					match confidence_intervals {
+						MetricsConfidenceIntervals::Regression(confidence_intervals) => {
+							confidence_intervals
+						}
+						_ => unreachable!(),
+					}
+				});
 			let model = match train_model_output {
 				TrainModelOutput::LinearRegressor(LinearRegressorTrainModelOutput {
 					model,
@@ -368,11 +507,16 @@ pub fn train(
 				test_column_stats: test_column_stats.into_iter().map(Into::into).collect(),
 				test_target_column_stats: test_target_column_stats.into(),
 				test_metrics: test_metrics.into(),
+				test_metrics_confidence_intervals,
 				baseline_metrics: baseline_metrics.into(),
 				model: model.into(),
 				comparison_metric: comparison_metric.into(),
 				grid,
 				best_grid_item_index: best_model_index,
+				shap_summary: Some(shap_summaries.remove(0)),
+				partial_dependences: Some(partial_dependences.remove(0)),
+				slice_metrics: Some(slice_metrics),
+				prediction_intervals,
 			})
 		}
 		Task::BinaryClassification => {
@@ -388,6 +532,15 @@ pub fn train(
 				Metrics::BinaryClassification(test_metrics) => test_metrics,
 				_ => unreachable!(),
 			};
+			let test_metrics_confidence_intervals =
+				test_metrics_confidence_intervals.map(|confidence_intervals| {
+					match confidence_intervals {
+						MetricsConfidenceIntervals::BinaryClassification(confidence_intervals) => {
+							confidence_intervals
+						}
+						_ => unreachable!(),
+					}
+				});
 			let model = match train_model_output {
 				TrainModelOutput::LinearBinaryClassifier(
 					LinearBinaryClassifierTrainModelOutput {
@@ -443,11 +596,16 @@ pub fn train(
 				test_column_stats: test_column_stats.into_iter().map(Into::into).collect(),
 				test_target_column_stats: test_target_column_stats.into(),
 				test_metrics: test_metrics.into(),
+				test_metrics_confidence_intervals,
 				baseline_metrics: baseline_metrics.into(),
 				model: model.into(),
 				comparison_metric: comparison_metric.into(),
 				grid,
 				best_grid_item_index: best_model_index,
+				shap_summary: Some(shap_summaries.remove(0)),
+				partial_dependences: Some(partial_dependences.remove(0)),
+				slice_metrics: Some(slice_metrics),
+				fairness_metrics,
 			})
 		}
 		Task::MulticlassClassification { .. } => {
@@ -463,6 +621,15 @@ pub fn train(
 				Metrics::MulticlassClassification(test_metrics) => test_metrics,
 				_ => unreachable!(),
 			};
+			let test_metrics_confidence_intervals =
+				test_metrics_confidence_intervals.map(|confidence_intervals| {
+					match confidence_intervals {
+						MetricsConfidenceIntervals::MulticlassClassification(
+							confidence_intervals,
+						) => confidence_intervals,
+						_ => unreachable!(),
+					}
+				});
 			let model = match train_model_output {
 				TrainModelOutput::LinearMulticlassClassifier(
 					LinearMulticlassClassifierTrainModelOutput {
@@ -522,11 +689,15 @@ pub fn train(
 				test_column_stats: test_column_stats.into_iter().map(Into::into).collect(),
 				test_target_column_stats: test_target_column_stats.into(),
 				test_metrics: test_metrics.into(),
+				test_metrics_confidence_intervals,
 				baseline_metrics: baseline_metrics.into(),
 				model: model.into(),
 				comparison_metric: comparison_metric.into(),
 				grid,
 				best_grid_item_index: best_model_index,
+				shap_summaries: Some(shap_summaries),
+				partial_dependences: Some(partial_dependences),
+				slice_metrics: Some(slice_metrics),
 			})
 		}
 	};
@@ -629,6 +800,12 @@ enum Metrics {
 	MulticlassClassification(tangram_metrics::MulticlassClassificationMetricsOutput),
 }
 
+enum MetricsConfidenceIntervals {
+	Regression(model::RegressionMetricsConfidenceIntervals),
+	BinaryClassification(model::BinaryClassificationMetricsConfidenceIntervals),
+	MulticlassClassification(model::MulticlassClassificationMetricsConfidenceIntervals),
+}
+
 #[derive(Debug)]
 pub enum Progress {
 	Loading(ProgressCounter),
@@ -1716,6 +1893,429 @@ fn test_model(
 	}
 }
 
+fn compute_fairness_metrics(
+	train_model_output: &TrainModelOutput,
+	dataframe_test: &DataFrameView,
+	sensitive_column_index: usize,
+	excluded_from_features: bool,
+) -> model::FairnessMetrics {
+	let fairness_metrics = match train_model_output {
+		TrainModelOutput::LinearBinaryClassifier(LinearBinaryClassifierTrainModelOutput {
+			target_column_index,
+			feature_groups,
+			model,
+			..
+		}) => test::test_fairness_linear_binary_classifier(
+			dataframe_test,
+			*target_column_index,
+			sensitive_column_index,
+			feature_groups,
+			model,
+		),
+		TrainModelOutput::TreeBinaryClassifier(TreeBinaryClassifierTrainModelOutput {
+			target_column_index,
+			feature_groups,
+			model,
+			..
+		}) => test::test_fairness_tree_binary_classifier(
+			dataframe_test,
+			*target_column_index,
+			sensitive_column_index,
+			feature_groups,
+			model,
+		),
+		_ => unreachable!(),
+	};
+	model::FairnessMetrics {
+		sensitive_column_name: dataframe_test.columns()[sensitive_column_index]
+			.name()
+			.unwrap()
+			.to_owned(),
+		excluded_from_features,
+		groups: fairness_metrics
+			.groups
+			.into_iter()
+			.map(|group| model::FairnessGroupMetrics {
+				group: group.group,
+				n_examples: group.n_examples,
+				positive_rate: group.positive_rate,
+				metrics: group.metrics.into(),
+			})
+			.collect(),
+		demographic_parity_difference: fairness_metrics.demographic_parity_difference,
+		equal_opportunity_difference: fairness_metrics.equal_opportunity_difference,
+	}
+}
+
+/// Compute a SHAP summary for each of the model's outputs, which is one for regressors and binary classifiers and one for each class for multiclass classifiers.
+fn compute_shap_summaries(
+	train_model_output: &TrainModelOutput,
+	dataframe_test: &DataFrameView,
+) -> Vec<model::ShapSummary> {
+	match train_model_output {
+		TrainModelOutput::LinearRegressor(LinearRegressorTrainModelOutput {
+			feature_groups,
+			model,
+			..
+		}) => vec![shap::compute_shap_summary_linear_regressor(
+			dataframe_test,
+			feature_groups,
+			model,
+		)],
+		TrainModelOutput::TreeRegressor(TreeRegressorTrainModelOutput {
+			feature_groups,
+			model,
+			..
+		}) => vec![shap::compute_shap_summary_tree_regressor(
+			dataframe_test,
+			feature_groups,
+			model,
+		)],
+		TrainModelOutput::LinearBinaryClassifier(LinearBinaryClassifierTrainModelOutput {
+			feature_groups,
+			model,
+			..
+		}) => vec![shap::compute_shap_summary_linear_binary_classifier(
+			dataframe_test,
+			feature_groups,
+			model,
+		)],
+		TrainModelOutput::TreeBinaryClassifier(TreeBinaryClassifierTrainModelOutput {
+			feature_groups,
+			model,
+			..
+		}) => vec![shap::compute_shap_summary_tree_binary_classifier(
+			dataframe_test,
+			feature_groups,
+			model,
+		)],
+		TrainModelOutput::LinearMulticlassClassifier(
+			LinearMulticlassClassifierTrainModelOutput {
+				feature_groups,
+				model,
+				..
+			},
+		) => shap::compute_shap_summaries_linear_multiclass_classifier(
+			dataframe_test,
+			feature_groups,
+			model,
+		),
+		TrainModelOutput::TreeMulticlassClassifier(TreeMulticlassClassifierTrainModelOutput {
+			feature_groups,
+			model,
+			..
+		}) => shap::compute_shap_summaries_tree_multiclass_classifier(
+			dataframe_test,
+			feature_groups,
+			model,
+		),
+	}
+}
+
+fn compute_partial_dependences(
+	train_model_output: &TrainModelOutput,
+	dataframe_train: &DataFrameView,
+	dataframe_test: &DataFrameView,
+) -> Vec<Vec<model::PartialDependence>> {
+	match train_model_output {
+		TrainModelOutput::LinearRegressor(LinearRegressorTrainModelOutput {
+			feature_groups,
+			model,
+			target_column_index,
+			..
+		}) => vec![
+			partial_dependence::compute_partial_dependences_linear_regressor(
+				dataframe_train,
+				dataframe_test,
+				*target_column_index,
+				feature_groups,
+				model,
+			),
+		],
+		TrainModelOutput::TreeRegressor(TreeRegressorTrainModelOutput {
+			feature_groups,
+			model,
+			target_column_index,
+			..
+		}) => vec![
+			partial_dependence::compute_partial_dependences_tree_regressor(
+				dataframe_train,
+				dataframe_test,
+				*target_column_index,
+				feature_groups,
+				model,
+			),
+		],
+		TrainModelOutput::LinearBinaryClassifier(LinearBinaryClassifierTrainModelOutput {
+			feature_groups,
+			model,
+			target_column_index,
+			..
+		}) => vec![
+			partial_dependence::compute_partial_dependences_linear_binary_classifier(
+				dataframe_train,
+				dataframe_test,
+				*target_column_index,
+				feature_groups,
+				model,
+			),
+		],
+		TrainModelOutput::TreeBinaryClassifier(TreeBinaryClassifierTrainModelOutput {
+			feature_groups,
+			model,
+			target_column_index,
+			..
+		}) => vec![
+			partial_dependence::compute_partial_dependences_tree_binary_classifier(
+				dataframe_train,
+				dataframe_test,
+				*target_column_index,
+				feature_groups,
+				model,
+			),
+		],
+		TrainModelOutput::LinearMulticlassClassifier(
+			LinearMulticlassClassifierTrainModelOutput {
+				feature_groups,
+				model,
+				target_column_index,
+				..
+			},
+		) => partial_dependence::compute_partial_dependences_linear_multiclass_classifier(
+			dataframe_train,
+			dataframe_test,
+			*target_column_index,
+			feature_groups,
+			model,
+		),
+		TrainModelOutput::TreeMulticlassClassifier(TreeMulticlassClassifierTrainModelOutput {
+			feature_groups,
+			model,
+			target_column_index,
+			..
+		}) => partial_dependence::compute_partial_dependences_tree_multiclass_classifier(
+			dataframe_train,
+			dataframe_test,
+			*target_column_index,
+			feature_groups,
+			model,
+		),
+	}
+}
+
+fn compute_test_metrics_confidence_intervals(
+	train_model_output: &TrainModelOutput,
+	dataframe_test: &DataFrameView,
+	n_bootstrap_samples: usize,
+) -> MetricsConfidenceIntervals {
+	match train_model_output {
+		TrainModelOutput::LinearRegressor(LinearRegressorTrainModelOutput {
+			feature_groups,
+			model,
+			target_column_index,
+			..
+		}) => MetricsConfidenceIntervals::Regression(
+			test::test_confidence_intervals_linear_regressor(
+				dataframe_test,
+				*target_column_index,
+				feature_groups,
+				model,
+				n_bootstrap_samples,
+			),
+		),
+		TrainModelOutput::TreeRegressor(TreeRegressorTrainModelOutput {
+			feature_groups,
+			model,
+			target_column_index,
+			..
+		}) => MetricsConfidenceIntervals::Regression(test::test_confidence_intervals_tree_regressor(
+			dataframe_test,
+			*target_column_index,
+			feature_groups,
+			model,
+			n_bootstrap_samples,
+		)),
+		TrainModelOutput::LinearBinaryClassifier(LinearBinaryClassifierTrainModelOutput {
+			feature_groups,
+			model,
+			target_column_index,
+			..
+		}) => MetricsConfidenceIntervals::BinaryClassification(
+			test::test_confidence_intervals_linear_binary_classifier(
+				dataframe_test,
+				*target_column_index,
+				feature_groups,
+				model,
+				n_bootstrap_samples,
+			),
+		),
+		TrainModelOutput::TreeBinaryClassifier(TreeBinaryClassifierTrainModelOutput {
+			feature_groups,
+			model,
+			target_column_index,
+			..
+		}) => MetricsConfidenceIntervals::BinaryClassification(
+			test::test_confidence_intervals_tree_binary_classifier(
+				dataframe_test,
+				*target_column_index,
+				feature_groups,
+				model,
+				n_bootstrap_samples,
+			),
+		),
+		TrainModelOutput::LinearMulticlassClassifier(
+			LinearMulticlassClassifierTrainModelOutput {
+				feature_groups,
+				model,
+				target_column_index,
+				..
+			},
+		) => MetricsConfidenceIntervals::MulticlassClassification(
+			test::test_confidence_intervals_linear_multiclass_classifier(
+				dataframe_test,
+				*target_column_index,
+				feature_groups,
+				model,
+				n_bootstrap_samples,
+			),
+		),
+		TrainModelOutput::TreeMulticlassClassifier(TreeMulticlassClassifierTrainModelOutput {
+			feature_groups,
+			model,
+			target_column_index,
+			..
+		}) => MetricsConfidenceIntervals::MulticlassClassification(
+			test::test_confidence_intervals_tree_multiclass_classifier(
+				dataframe_test,
+				*target_column_index,
+				feature_groups,
+				model,
+				n_bootstrap_samples,
+			),
+		),
+	}
+}
+
+fn compute_prediction_intervals(
+	train_model_output: &TrainModelOutput,
+	dataframe_calibration: &DataFrameView,
+	method: PredictionIntervalsMethod,
+	coverage: f32,
+) -> Option<model::PredictionIntervals> {
+	match train_model_output {
+		TrainModelOutput::LinearRegressor(LinearRegressorTrainModelOutput {
+			feature_groups,
+			model,
+			target_column_index,
+			..
+		}) => prediction_intervals::compute_prediction_intervals_linear_regressor(
+			dataframe_calibration,
+			*target_column_index,
+			feature_groups,
+			model,
+			method,
+			coverage,
+		),
+		TrainModelOutput::TreeRegressor(TreeRegressorTrainModelOutput {
+			feature_groups,
+			model,
+			target_column_index,
+			..
+		}) => prediction_intervals::compute_prediction_intervals_tree_regressor(
+			dataframe_calibration,
+			*target_column_index,
+			feature_groups,
+			model,
+			method,
+			coverage,
+		),
+		_ => unreachable!(),
+	}
+}
+
+fn compute_slice_metrics(
+	train_model_output: &TrainModelOutput,
+	dataframe_test: &DataFrameView,
+	train_column_stats: &[stats::ColumnStatsOutput],
+) -> model::SliceMetrics {
+	match train_model_output {
+		TrainModelOutput::LinearRegressor(LinearRegressorTrainModelOutput {
+			feature_groups,
+			model,
+			target_column_index,
+			..
+		}) => slice_metrics::compute_slice_metrics_linear_regressor(
+			dataframe_test,
+			*target_column_index,
+			train_column_stats,
+			feature_groups,
+			model,
+		),
+		TrainModelOutput::TreeRegressor(TreeRegressorTrainModelOutput {
+			feature_groups,
+			model,
+			target_column_index,
+			..
+		}) => slice_metrics::compute_slice_metrics_tree_regressor(
+			dataframe_test,
+			*target_column_index,
+			train_column_stats,
+			feature_groups,
+			model,
+		),
+		TrainModelOutput::LinearBinaryClassifier(LinearBinaryClassifierTrainModelOutput {
+			feature_groups,
+			model,
+			target_column_index,
+			..
+		}) => slice_metrics::compute_slice_metrics_linear_binary_classifier(
+			dataframe_test,
+			*target_column_index,
+			train_column_stats,
+			feature_groups,
+			model,
+		),
+		TrainModelOutput::TreeBinaryClassifier(TreeBinaryClassifierTrainModelOutput {
+			feature_groups,
+			model,
+			target_column_index,
+			..
+		}) => slice_metrics::compute_slice_metrics_tree_binary_classifier(
+			dataframe_test,
+			*target_column_index,
+			train_column_stats,
+			feature_groups,
+			model,
+		),
+		TrainModelOutput::LinearMulticlassClassifier(
+			LinearMulticlassClassifierTrainModelOutput {
+				feature_groups,
+				model,
+				target_column_index,
+				..
+			},
+		) => slice_metrics::compute_slice_metrics_linear_multiclass_classifier(
+			dataframe_test,
+			*target_column_index,
+			train_column_stats,
+			feature_groups,
+			model,
+		),
+		TrainModelOutput::TreeMulticlassClassifier(TreeMulticlassClassifierTrainModelOutput {
+			feature_groups,
+			model,
+			target_column_index,
+			..
+		}) => slice_metrics::compute_slice_metrics_tree_multiclass_classifier(
+			dataframe_test,
+			*target_column_index,
+			train_column_stats,
+			feature_groups,
+			model,
+		),
+	}
+}
+
 impl Into<model::StatsSettings> for stats::StatsSettings {
 	fn into(self) -> model::StatsSettings {
 		model::StatsSettings {
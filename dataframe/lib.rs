@@ -505,6 +505,10 @@ impl TextDataFrameColumn {
 }
 
 impl<'a> DataFrameView<'a> {
+	pub fn from_columns(columns: Vec<DataFrameColumnView<'a>>) -> DataFrameView<'a> {
+		DataFrameView { columns }
+	}
+
 	pub fn columns(&self) -> &Vec<DataFrameColumnView<'a>> {
 		&self.columns
 	}
@@ -731,6 +735,10 @@ impl<'a> DataFrameColumnView<'a> {
 }
 
 impl<'a> UnknownDataFrameColumnView<'a> {
+	pub fn new(name: Option<&'a str>, len: usize) -> UnknownDataFrameColumnView<'a> {
+		UnknownDataFrameColumnView { name, len }
+	}
+
 	pub fn name(&self) -> Option<&str> {
 		self.name
 	}
@@ -749,6 +757,10 @@ impl<'a> UnknownDataFrameColumnView<'a> {
 }
 
 impl<'a> NumberDataFrameColumnView<'a> {
+	pub fn new(name: Option<&'a str>, data: &'a [f32]) -> NumberDataFrameColumnView<'a> {
+		NumberDataFrameColumnView { name, data }
+	}
+
 	pub fn name(&self) -> Option<&str> {
 		self.name
 	}
@@ -779,6 +791,18 @@ impl<'a> NumberDataFrameColumnView<'a> {
 }
 
 impl<'a> EnumDataFrameColumnView<'a> {
+	pub fn new(
+		name: Option<&'a str>,
+		options: &'a [String],
+		data: &'a [Option<NonZeroUsize>],
+	) -> EnumDataFrameColumnView<'a> {
+		EnumDataFrameColumnView {
+			name,
+			options,
+			data,
+		}
+	}
+
 	pub fn name(&self) -> Option<&str> {
 		self.name
 	}
@@ -813,6 +837,10 @@ impl<'a> EnumDataFrameColumnView<'a> {
 }
 
 impl<'a> TextDataFrameColumnView<'a> {
+	pub fn new(name: Option<&'a str>, data: &'a [String]) -> TextDataFrameColumnView<'a> {
+		TextDataFrameColumnView { name, data }
+	}
+
 	pub fn name(&self) -> Option<&str> {
 		self.name
 	}
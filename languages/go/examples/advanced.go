@@ -45,7 +45,10 @@ func main() {
 	predictOptions := tangram.PredictOptions{
 		Threshold: 0.25,
 	}
-	output := model.PredictOne(input, &predictOptions)
+	output, err := model.PredictOne(input, &predictOptions)
+	if err != nil {
+		log.Fatal(err)
+	}
 
 	// Print out the input and output.
 	fmt.Println("Input:", input)
@@ -33,7 +33,10 @@ func main() {
 	}
 
 	// Make the prediction!
-	output := model.PredictOne(input, nil)
+	output, err := model.PredictOne(input, nil)
+	if err != nil {
+		log.Fatal(err)
+	}
 
 	// Print out the input and output.
 	fmt.Println("Input:", input)
@@ -11,8 +11,8 @@ import (
 	"encoding/json"
 	"errors"
 	"io/ioutil"
-	"log"
 	"net/http"
+	"runtime"
 	"unsafe"
 )
 
@@ -28,9 +28,11 @@ type ModelOptions struct {
 	TangramURL string
 }
 
-// PredictOptions is the second argument to the predict function. It is used to configure the threshold for classification tasks.
+// PredictOptions is the second argument to the predict function. It is used to configure the threshold for classification tasks. If Strict is true, inputs with missing columns, unknown enum options, values of the wrong type, or unknown columns cause an error instead of a warning. If ComputeFeatureInteractions is true, outputs include the SHAP interaction values for each pair of features.
 type PredictOptions struct {
-	Threshold float32 `json:"threshold"`
+	Threshold                  float32 `json:"threshold"`
+	Strict                     bool    `json:"strict"`
+	ComputeFeatureInteractions bool    `json:"compute_feature_interactions"`
 }
 
 // Input is the input to a model for making predictions.
@@ -39,8 +41,38 @@ type Input map[string]interface{}
 // Output is the output of calling Predict
 type Output struct {
 	Value         float32            `json:"value"`
+	Lower         *float32           `json:"lower"`
+	Upper         *float32           `json:"upper"`
 	ClassName     string             `json:"className"`
 	Probabilities map[string]float32 `json:"probabilities"`
+	Warnings      []PredictWarning   `json:"warnings"`
+}
+
+// PredictWarning describes a problem with an input that caused one of its values to be treated as missing. Type is one of "missing_column", "unknown_enum_option", "invalid_value", or "unknown_column". Value is only set for "unknown_enum_option" warnings.
+type PredictWarning struct {
+	Type       string `json:"type"`
+	ColumnName string `json:"columnName"`
+	Value      string `json:"value"`
+}
+
+// ModelMetadata describes a model. It is returned by the Metadata function.
+type ModelMetadata struct {
+	ID               string          `json:"id"`
+	Task             string          `json:"task"`
+	TargetColumnName string          `json:"targetColumnName"`
+	Columns          []ColumnMetadata `json:"columns"`
+	// Classes holds the classes for classifiers, in the order used for probabilities. For binary classifiers, the negative class is first.
+	Classes          []string `json:"classes"`
+	DefaultThreshold *float32 `json:"defaultThreshold"`
+	// TestMetrics holds the model's metrics on the test set. Its keys depend on the task.
+	TestMetrics map[string]interface{} `json:"testMetrics"`
+}
+
+// ColumnMetadata describes one of the columns a model expects as input. Type is one of "unknown", "number", "enum", or "text". Options is only set for enum columns.
+type ColumnMetadata struct {
+	Name    string   `json:"name"`
+	Type    string   `json:"type"`
+	Options []string `json:"options"`
 }
 
 // LogPredictionOptions should be passed to the LogPrediction function.
@@ -59,6 +91,25 @@ type LogTrueValueOptions struct {
 
 type event map[string]interface{}
 
+// Error is returned when a call to libtangram fails. Code is one of the status codes below.
+type Error struct {
+	Code    int
+	Message string
+}
+
+func (e *Error) Error() string {
+	return e.Message
+}
+
+// These are the status codes libtangram returns when a call fails.
+const (
+	StatusError           = C.TANGRAM_ERROR
+	StatusInvalidArgument = C.TANGRAM_INVALID_ARGUMENT
+	StatusInvalidModel    = C.TANGRAM_INVALID_MODEL
+	StatusInvalidInput    = C.TANGRAM_INVALID_INPUT
+	StatusPanic           = C.TANGRAM_PANIC
+)
+
 // LoadModelFromFile loads a model from the specified path.
 func LoadModelFromFile(path string, options *ModelOptions) (*Model, error) {
 	modelData, err := ioutil.ReadFile(path)
@@ -72,10 +123,13 @@ func LoadModelFromFile(path string, options *ModelOptions) (*Model, error) {
 func LoadModel(modelData []byte, options *ModelOptions) (*Model, error) {
 	cModelData := C.CBytes(modelData)
 	cModelDataLen := C.size_t(len(modelData))
+	// libtangram stores the last error message per thread, so stay on this thread until it has been read.
+	runtime.LockOSThread()
+	defer runtime.UnlockOSThread()
 	var modelPtr *C.tangram_model
 	result := C.tangram_model_load(cModelData, cModelDataLen, (*unsafe.Pointer)(unsafe.Pointer(&modelPtr)))
-	if result != 0 {
-		return nil, errors.New("tangram error")
+	if result != C.TANGRAM_OK {
+		return nil, lastError(result)
 	}
 	logQueue := []event{}
 	model := Model{
@@ -86,79 +140,142 @@ func LoadModel(modelData []byte, options *ModelOptions) (*Model, error) {
 	return &model, nil
 }
 
+// lastError returns an *Error with the status code `result` and the message for the most recent libtangram error. The caller must have locked the OS thread it made the failing call on.
+func lastError(result C.int) error {
+	message := "tangram error"
+	var cMessage *C.char
+	C.tangram_last_error_message(&cMessage)
+	if cMessage != nil {
+		message = C.GoString(cMessage)
+		C.tangram_string_free(cMessage)
+	}
+	return &Error{
+		Code:    int(result),
+		Message: message,
+	}
+}
+
 // Destroy frees up the memory used by the model. You should call this with defer after loading your model.
-func (m Model) Destroy() {
+func (m Model) Destroy() error {
+	runtime.LockOSThread()
+	defer runtime.UnlockOSThread()
 	result := C.tangram_model_free(unsafe.Pointer(m.modelPtr))
-	if result != 0 {
-		log.Fatal("tangram error")
+	if result != C.TANGRAM_OK {
+		return lastError(result)
 	}
+	return nil
 }
 
 // ID is the unique identifier for this model.
-func (m Model) ID() string {
+func (m Model) ID() (string, error) {
+	runtime.LockOSThread()
+	defer runtime.UnlockOSThread()
 	var cID *C.char
 	result := C.tangram_model_id(unsafe.Pointer(m.modelPtr), &cID)
-	if result != 0 {
-		log.Fatal("tangram error")
+	if result != C.TANGRAM_OK {
+		return "", lastError(result)
 	}
 	id := C.GoString(cID)
 	C.tangram_string_free(cID)
-	return id
+	return id, nil
+}
+
+// Metadata describes the model, including the columns it expects as input, its classes, and its metrics on the test set.
+func (m Model) Metadata() (ModelMetadata, error) {
+	runtime.LockOSThread()
+	defer runtime.UnlockOSThread()
+	var metadata ModelMetadata
+	var cMetadata *C.char
+	result := C.tangram_model_metadata(unsafe.Pointer(m.modelPtr), &cMetadata)
+	if result != C.TANGRAM_OK {
+		return metadata, lastError(result)
+	}
+	metadataStr := C.GoString(cMetadata)
+	C.tangram_string_free(cMetadata)
+	err := json.Unmarshal([]byte(metadataStr), &metadata)
+	return metadata, err
 }
 
 // PredictOne makes a prediction a single input.
-func (m Model) PredictOne(input Input, options *PredictOptions) Output {
-	return m.Predict([]Input{input}, options)[0]
+func (m Model) PredictOne(input Input, options *PredictOptions) (Output, error) {
+	output, err := m.Predict([]Input{input}, options)
+	if err != nil {
+		return Output{}, err
+	}
+	return output[0], nil
 }
 
 // Predict makes predictions with a slice of inputs.
-func (m Model) Predict(input []Input, options *PredictOptions) []Output {
+func (m Model) Predict(input []Input, options *PredictOptions) ([]Output, error) {
 	inputStr, err := json.Marshal(input)
 	if err != nil {
-		log.Fatal(err)
+		return nil, err
 	}
 	cInputStr := C.CString(string(inputStr))
 	var cOptionsStr *C.char
 	if options != nil {
 		optionsStr, err := json.Marshal(options)
 		if err != nil {
-			log.Fatal(err)
+			return nil, err
 		}
 		cOptionsStr = C.CString(string(optionsStr))
 	}
+	runtime.LockOSThread()
+	defer runtime.UnlockOSThread()
 	var cOutputStr *C.char
 	result := C.tangram_model_predict(unsafe.Pointer(m.modelPtr), cInputStr, cOptionsStr, &cOutputStr)
-	if result != 0 {
-		log.Fatal("tangram error")
+	if result != C.TANGRAM_OK {
+		return nil, lastError(result)
 	}
 	outputStr := C.GoString(cOutputStr)
 	result = C.tangram_string_free(cOutputStr)
-	if result != 0 {
-		log.Fatal("tangram error")
+	if result != C.TANGRAM_OK {
+		return nil, lastError(result)
 	}
 	var output []Output
-	json.Unmarshal([]byte(outputStr), &output)
-	return output
+	err = json.Unmarshal([]byte(outputStr), &output)
+	if err != nil {
+		return nil, err
+	}
+	return output, nil
 }
 
 // LogPrediction immediately sends a prediction monitoring event.
 func (m Model) LogPrediction(options LogPredictionOptions) error {
-	return m.logEvent(m.predictionEvent(options))
+	e, err := m.predictionEvent(options)
+	if err != nil {
+		return err
+	}
+	return m.logEvent(e)
 }
 
 // LogTrueValue immediately sends a true value monitoring event.
 func (m Model) LogTrueValue(options LogTrueValueOptions) error {
-	return m.logEvent(m.trueValueEvent(options))
+	e, err := m.trueValueEvent(options)
+	if err != nil {
+		return err
+	}
+	return m.logEvent(e)
 }
 
 // EnqueueLogPrediction queues a prediction monitoring event to be sent later.
-func (m Model) EnqueueLogPrediction(options LogPredictionOptions) {
-	m.logQueue = append(m.logQueue, m.predictionEvent(options))
+func (m Model) EnqueueLogPrediction(options LogPredictionOptions) error {
+	e, err := m.predictionEvent(options)
+	if err != nil {
+		return err
+	}
+	m.logQueue = append(m.logQueue, e)
+	return nil
 }
 
 // EnqueueLogTrueValue queues a true value monitoring event to be sent later.
-func (m Model) EnqueueLogTrueValue(options LogTrueValueOptions) {
-	m.logQueue = append(m.logQueue, m.trueValueEvent(options))
+func (m Model) EnqueueLogTrueValue(options LogTrueValueOptions) error {
+	e, err := m.trueValueEvent(options)
+	if err != nil {
+		return err
+	}
+	m.logQueue = append(m.logQueue, e)
+	return nil
 }
 
 // FlushLogQueue sends a network request to the Tangram API with any queued monitoring events.
@@ -204,7 +321,11 @@ func (m Model) logEvents(events []event) error {
 	return nil
 }
 
-func (m Model) predictionEvent(options LogPredictionOptions) event {
+func (m Model) predictionEvent(options LogPredictionOptions) (event, error) {
+	id, err := m.ID()
+	if err != nil {
+		return nil, err
+	}
 	var output map[string]interface{}
 	if options.Output.ClassName != "" {
 		output = map[string]interface{}{
@@ -213,23 +334,29 @@ func (m Model) predictionEvent(options LogPredictionOptions) event {
 	} else {
 		output = map[string]interface{}{
 			"value": options.Output.Value,
+			"lower": options.Output.Lower,
+			"upper": options.Output.Upper,
 		}
 	}
 	return event{
 		"type":       "prediction",
-		"modelId":    m.ID(),
+		"modelId":    id,
 		"identifier": options.Identifier,
 		"options":    options.Options,
 		"input":      options.Input,
 		"output":     output,
-	}
+	}, nil
 }
 
-func (m Model) trueValueEvent(options LogTrueValueOptions) event {
+func (m Model) trueValueEvent(options LogTrueValueOptions) (event, error) {
+	id, err := m.ID()
+	if err != nil {
+		return nil, err
+	}
 	return event{
 		"type":       "true_value",
-		"modelId":    m.ID(),
+		"modelId":    id,
 		"identifier": options.Identifier,
 		"trueValue":  options.TrueValue,
-	}
+	}, nil
 }
@@ -1,15 +1,42 @@
 /*
-This header file defines the C API for libtangram, the Tangram C library, which is used by the libraries for each programming language to make predictions using a model trained with the Tangram CLI. While the APIs for the language libraries are stable, this API is unstable and subject to change. All functions return a status code which will be zero on success or non-zero on error.
+This header file defines the C API for libtangram, the Tangram C library, which is used by the libraries for each programming language to make predictions using a model trained with the Tangram CLI. While the APIs for the language libraries are stable, this API is unstable and subject to change. All functions return a status code which will be `TANGRAM_OK` on success or one of the other status codes below on error. When a function returns an error, call `tangram_last_error_message` on the same thread to retrieve a description of it.
 */
 
+#define TANGRAM_OK 0
+#define TANGRAM_ERROR 1
+#define TANGRAM_INVALID_ARGUMENT 2
+#define TANGRAM_INVALID_MODEL 3
+#define TANGRAM_INVALID_INPUT 4
+#define TANGRAM_PANIC 5
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define TANGRAM_COLUMN_TYPE_NUMBER 0
+#define TANGRAM_COLUMN_TYPE_STRING 1
+
 // `tangram_model` is an opaque handle to a model loaded by libtangram.
 typedef void tangram_model;
 
+// `tangram_column` describes one column of input to `tangram_model_predict_columns`. `column_type` is either `TANGRAM_COLUMN_TYPE_NUMBER` or `TANGRAM_COLUMN_TYPE_STRING`. String columns are used for the model's enum and text columns. For number columns, `number_values` points to `n_rows` floats, and NaN values are treated as missing. For string columns, `string_values` points to `n_rows` C strings, and null pointers are treated as missing. `validity` may be null, or point to a bitmap with one bit for each row, least significant bit first, where a zero bit means the value is missing.
+typedef struct {
+	const char* name;
+	uint32_t column_type;
+	const float* number_values;
+	const char* const* string_values;
+	const uint8_t* validity;
+} tangram_column;
+
 /// Retrieve the version of libtangram that is in use. On success, a pointer to the C string with the version will be written to `version_ptr`. You must call `tangram_string_free` when you are done with it.
 int tangram_version(
 	char** version_ptr
 );
 
+/// Retrieve the message for the most recent error that occurred on the calling thread. On success, a pointer to the message as a C string will be written to `message_ptr`, or null if no error has occurred. You must call `tangram_string_free` when you are done with it.
+int tangram_last_error_message(
+	char** message_ptr
+);
+
 /// Load a model from the bytes pointed to by `model_data` with length `model_data_len`. On success, a pointer to the loaded model will be written to `model_ptr`. You must call `tangram_model_free` when you are done with it.
 int tangram_model_load(
 	const void* model_data,
@@ -23,7 +50,7 @@ int tangram_model_id(
 	char** id_ptr
 );
 
-/// Make a prediction! `model` should point to a model loaded with `tangram_model_load`. `input` should be a C string of a json serialized PredictInput. On success, a pointer to the output as a json serialized C string will be written to `output_ptr`. You must call `tangram_string_free` when you are done with it.
+/// Make a prediction! `model` should point to a model loaded with `tangram_model_load`. `input` should be a C string of a json serialized PredictInput. `options` may be null, or a C string of json serialized PredictOptions. On success, a pointer to the output as a json serialized C string will be written to `output_ptr`. You must call `tangram_string_free` when you are done with it.
 int tangram_model_predict(
 	tangram_model* model,
 	const char* input,
@@ -31,12 +58,34 @@ int tangram_model_predict(
 	char** output_ptr
 );
 
-/// Free the C string created by libtangram pointed to by `string`.
+/// Retrieve a description of the model as a json serialized C string, including its task, target column, the columns it expects as input with their types and enum options, its classes, its default threshold, and its metrics on the test set. On success, a pointer to the C string will be written to `metadata_ptr`. You must call `tangram_string_free` when you are done with it.
+int tangram_model_metadata(
+	tangram_model* model,
+	char** metadata_ptr
+);
+
+/// Retrieve the number of values `tangram_model_predict_columns` writes for each row. On success, the number will be written to `n_outputs_ptr`.
+int tangram_model_n_outputs(
+	tangram_model* model,
+	size_t* n_outputs_ptr
+);
+
+/// Make predictions for a batch of rows given as columns, without serializing them to json. `columns` should point to `n_columns` `tangram_column`s, each with `n_rows` values. Columns the model expects that are not present are treated as missing, and columns the model does not use are ignored. The predictions are written to `output`, which must point to `output_len` floats, where `output_len` is `n_rows` times the number of outputs retrieved with `tangram_model_n_outputs`. For regressors, each row's output is the predicted value. For binary classifiers, it is the probability of the positive class. For multiclass classifiers, it is the probability of each class, in the order the classes appear in the model. Feature contributions are not computed.
+int tangram_model_predict_columns(
+	tangram_model* model,
+	const tangram_column* columns,
+	size_t n_columns,
+	size_t n_rows,
+	float* output,
+	size_t output_len
+);
+
+/// Free the C string created by libtangram pointed to by `string`. Passing null does nothing.
 int tangram_string_free(
 	const char* string
 );
 
-/// Free the model pointed to by `model`.
+/// Free the model pointed to by `model`. Passing null does nothing.
 int tangram_model_free(
 	tangram_model* model
 );
@@ -0,0 +1,31 @@
+package tangram
+
+import (
+	"errors"
+	"io/ioutil"
+	"testing"
+)
+
+func TestLoadInvalidModel(t *testing.T) {
+	modelData, err := ioutil.ReadFile("examples/heart_disease.tangram")
+	if err != nil {
+		t.Fatal(err)
+	}
+	invalidModels := map[string][]byte{
+		"malformed": []byte("this is not a tangram model"),
+		"truncated": modelData[:len(modelData)/2],
+	}
+	for name, invalidModelData := range invalidModels {
+		model, err := LoadModel(invalidModelData, nil)
+		if model != nil {
+			t.Errorf("%s: expected no model to be loaded", name)
+		}
+		var tangramErr *Error
+		if !errors.As(err, &tangramErr) {
+			t.Fatalf("%s: expected a tangram error, got %v", name, err)
+		}
+		if tangramErr.Code != StatusInvalidModel {
+			t.Errorf("%s: expected status %d, got %d: %s", name, StatusInvalidModel, tangramErr.Code, tangramErr.Message)
+		}
+	}
+}
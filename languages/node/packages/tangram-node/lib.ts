@@ -3,20 +3,36 @@ let native = require("./build/Release/native.node")
 
 export type PredictOptions = {
 	threshold?: number
+	strict?: boolean
+	compute_feature_interactions?: boolean
 }
 
+export type PredictWarning =
+	| { type: "missing_column"; columnName: string }
+	| { type: "unknown_enum_option"; columnName: string; value: string }
+	| { type: "invalid_value"; columnName: string }
+	| { type: "unknown_column"; columnName: string }
+
 export type Input = {
 	[key: string]: string | number | boolean | null | undefined
 }
 
+export type ColumnarInput = {
+	[key: string]: Float32Array | Array<string | null | undefined>
+}
+
 export type RegressionOutput = {
 	value: number
+	lower: number | null
+	upper: number | null
+	warnings?: PredictWarning[]
 }
 
 export type MulticlassClassificationOutput<Input = string, Classes = string> = {
 	className: Classes
 	probabilities: { [K in keyof Classes]: number }
 	probability: number
+	warnings?: PredictWarning[]
 }
 
 export type Output = RegressionOutput | MulticlassClassificationOutput
@@ -38,6 +54,22 @@ export type TrueValueMonitorEvent = {
 	type: "true_value"
 }
 
+export type ModelMetadata = {
+	id: string
+	task: "regression" | "binary_classification" | "multiclass_classification"
+	targetColumnName: string
+	columns: ColumnMetadata[]
+	classes: string[] | null
+	defaultThreshold: number | null
+	testMetrics: { [key: string]: unknown }
+}
+
+export type ColumnMetadata =
+	| { type: "unknown"; name: string }
+	| { type: "number"; name: string }
+	| { type: "enum"; name: string; options: string[] }
+	| { type: "text"; name: string }
+
 export type ModelOptions = {
 	tangramUrl?: string
 }
@@ -70,6 +102,10 @@ export class Model<InputType extends Input, OutputType extends Output> {
 		return native.model_id(this.model)
 	}
 
+	public metadata(): ModelMetadata {
+		return JSON.parse(native.model_metadata(this.model))
+	}
+
 	public predictSync<PredictInput extends InputType | InputType[]>(
 		input: PredictInput,
 		options?: PredictOptions,
@@ -85,6 +121,11 @@ export class Model<InputType extends Input, OutputType extends Output> {
 			: OutputType
 	}
 
+	// Number columns are passed without copying. For multiclass classifiers, the output has the probability of each class for each row, one row after another.
+	public predictColumnsSync(input: ColumnarInput): Float32Array {
+		return native.model_predict_columns(this.model, input)
+	}
+
 	public async logPrediction(options: LogPredictionOptions): Promise<void> {
 		this.logEvent({
 			modelId: this.id(),
@@ -5,6 +5,18 @@
 #include <string.h>
 #include "tangram.h"
 
+// Throw a javascript error with the message for the most recent libtangram error.
+void throw_last_error(napi_env env, int result) {
+	char* message = NULL;
+	tangram_last_error_message(&message);
+	char code[16];
+	snprintf(code, sizeof(code), "%d", result);
+	napi_throw_error(env, code, message ? message : "tangram error");
+	if (message) {
+		tangram_string_free(message);
+	}
+}
+
 napi_value model_load_fn(napi_env env, napi_callback_info info) {
 	napi_status status;
 
@@ -35,7 +47,10 @@ napi_value model_load_fn(napi_env env, napi_callback_info info) {
 	// Call `tangram_model_load`.
 	tangram_model* model;
 	int load_result = tangram_model_load(model_data, model_data_length, &model);
-	assert(load_result == 0);
+	if (load_result != TANGRAM_OK) {
+		throw_last_error(env, load_result);
+		return NULL;
+	}
 
 	// Wrap the model in a node external value.
 	napi_value model_value;
@@ -77,7 +92,10 @@ napi_value model_id_fn(napi_env env, napi_callback_info info) {
 	// Call `tangram_model_id`.
 	char* id;
 	int id_result = tangram_model_id(model, &id);
-	assert(id_result == 0);
+	if (id_result != TANGRAM_OK) {
+		throw_last_error(env, id_result);
+		return NULL;
+	}
 
 	// Get the id.
 	napi_value id_value;
@@ -89,6 +107,50 @@ napi_value model_id_fn(napi_env env, napi_callback_info info) {
 	return id_value;
 }
 
+napi_value model_metadata_fn(napi_env env, napi_callback_info info) {
+	napi_status status;
+
+	// Get the args.
+	size_t argc = 1;
+	napi_value argv[1];
+	status = napi_get_cb_info(env, info, &argc, argv, NULL, NULL);
+	assert(status == napi_ok);
+	if (argc != 1) {
+		napi_throw_type_error(env, NULL, "tangram.model_metadata: incorrect number of arguments");
+		return NULL;
+	}
+
+	// Get the model arg.
+	napi_value model_arg = argv[0];
+	napi_valuetype model_arg_type;
+	status = napi_typeof(env, model_arg, &model_arg_type);
+	assert(status == napi_ok);
+	if (model_arg_type != napi_external) {
+		napi_throw_type_error(env, NULL, "tangram.model_metadata: the first argument must be a model");
+		return NULL;
+	}
+	void* model;
+	status = napi_get_value_external(env, argv[0], &model);
+	assert(status == napi_ok);
+
+	// Call `tangram_model_metadata`.
+	char* metadata;
+	int metadata_result = tangram_model_metadata(model, &metadata);
+	if (metadata_result != TANGRAM_OK) {
+		throw_last_error(env, metadata_result);
+		return NULL;
+	}
+
+	// Get the metadata.
+	napi_value metadata_value;
+	status = napi_create_string_utf8(env, metadata, strlen(metadata), &metadata_value);
+	assert(status == napi_ok);
+	int metadata_free_result = tangram_string_free(metadata);
+	assert(metadata_free_result == 0);
+
+	return metadata_value;
+}
+
 napi_value model_predict_fn(napi_env env, napi_callback_info info) {
 	napi_status status;
 
@@ -153,30 +215,194 @@ napi_value model_predict_fn(napi_env env, napi_callback_info info) {
 	// Call `tangram_model_predict`.
 	char* output;
 	int predict_result = tangram_model_predict(model, input, options, &output);
-	assert(predict_result == 0);
+	free(input);
+	if (options) {
+		free(options);
+	}
+	if (predict_result != TANGRAM_OK) {
+		throw_last_error(env, predict_result);
+		return NULL;
+	}
 
 	// Get the output string.
 	napi_value output_value;
 	status = napi_create_string_utf8(env, output, strlen(output), &output_value);
 	assert(status == napi_ok);
 
-	free(input);
-	if (options) {
-		free(options);
-	}
 	int output_free_result = tangram_string_free(output);
 	assert(output_free_result == 0);
 
 	return output_value;
 }
 
+napi_value model_predict_columns_fn(napi_env env, napi_callback_info info) {
+	napi_status status;
+
+	// Get the args.
+	size_t argc = 2;
+	napi_value argv[2];
+	status = napi_get_cb_info(env, info, &argc, argv, NULL, NULL);
+	assert(status == napi_ok);
+	if (argc != 2) {
+		napi_throw_type_error(env, NULL, "tangram.model_predict_columns: incorrect number of arguments");
+		return NULL;
+	}
+
+	// Get the model arg.
+	napi_valuetype model_arg_type;
+	status = napi_typeof(env, argv[0], &model_arg_type);
+	assert(status == napi_ok);
+	if (model_arg_type != napi_external) {
+		napi_throw_type_error(env, NULL, "tangram.model_predict_columns: the first argument must be a model");
+		return NULL;
+	}
+	void* model;
+	status = napi_get_value_external(env, argv[0], &model);
+	assert(status == napi_ok);
+
+	// Get the columns arg.
+	napi_value columns_arg = argv[1];
+	napi_valuetype columns_arg_type;
+	status = napi_typeof(env, columns_arg, &columns_arg_type);
+	assert(status == napi_ok);
+	if (columns_arg_type != napi_object) {
+		napi_throw_type_error(env, NULL, "tangram.model_predict_columns: the second argument must be an object");
+		return NULL;
+	}
+	napi_value column_names;
+	status = napi_get_property_names(env, columns_arg, &column_names);
+	assert(status == napi_ok);
+	uint32_t n_columns;
+	status = napi_get_array_length(env, column_names, &n_columns);
+	assert(status == napi_ok);
+
+	// Read each column. Float32Arrays are passed to libtangram without copying. Arrays are read as strings, with values that are not strings treated as missing.
+	tangram_column* columns = (tangram_column*)calloc(n_columns, sizeof(tangram_column));
+	size_t* column_lengths = (size_t*)calloc(n_columns, sizeof(size_t));
+	size_t n_rows = 0;
+	const char* error_message = NULL;
+	for (uint32_t column_index = 0; column_index < n_columns && !error_message; column_index++) {
+		tangram_column* column = &columns[column_index];
+		napi_value name_value;
+		status = napi_get_element(env, column_names, column_index, &name_value);
+		assert(status == napi_ok);
+		size_t name_n_bytes;
+		status = napi_get_value_string_utf8(env, name_value, NULL, 0, &name_n_bytes);
+		assert(status == napi_ok);
+		char* name = (char*)malloc(name_n_bytes + 1);
+		status = napi_get_value_string_utf8(env, name_value, name, name_n_bytes + 1, &name_n_bytes);
+		assert(status == napi_ok);
+		column->name = name;
+		napi_value values;
+		status = napi_get_property(env, columns_arg, name_value, &values);
+		assert(status == napi_ok);
+		size_t column_n_rows;
+		bool is_typedarray;
+		status = napi_is_typedarray(env, values, &is_typedarray);
+		assert(status == napi_ok);
+		bool is_array;
+		status = napi_is_array(env, values, &is_array);
+		assert(status == napi_ok);
+		if (is_typedarray) {
+			napi_typedarray_type typedarray_type;
+			void* data;
+			status = napi_get_typedarray_info(env, values, &typedarray_type, &column_n_rows, &data, NULL, NULL);
+			assert(status == napi_ok);
+			if (typedarray_type != napi_float32_array) {
+				error_message = "tangram.model_predict_columns: typed array columns must be Float32Arrays";
+				break;
+			}
+			column->column_type = TANGRAM_COLUMN_TYPE_NUMBER;
+			column->number_values = (const float*)data;
+		} else if (is_array) {
+			uint32_t length;
+			status = napi_get_array_length(env, values, &length);
+			assert(status == napi_ok);
+			column_n_rows = length;
+			column_lengths[column_index] = length;
+			char** string_values = (char**)calloc(length, sizeof(char*));
+			for (uint32_t row_index = 0; row_index < length; row_index++) {
+				napi_value value;
+				status = napi_get_element(env, values, row_index, &value);
+				assert(status == napi_ok);
+				napi_valuetype value_type;
+				status = napi_typeof(env, value, &value_type);
+				assert(status == napi_ok);
+				if (value_type != napi_string) {
+					continue;
+				}
+				size_t value_n_bytes;
+				status = napi_get_value_string_utf8(env, value, NULL, 0, &value_n_bytes);
+				assert(status == napi_ok);
+				string_values[row_index] = (char*)malloc(value_n_bytes + 1);
+				status = napi_get_value_string_utf8(env, value, string_values[row_index], value_n_bytes + 1, &value_n_bytes);
+				assert(status == napi_ok);
+			}
+			column->column_type = TANGRAM_COLUMN_TYPE_STRING;
+			column->string_values = (const char* const*)string_values;
+		} else {
+			error_message = "tangram.model_predict_columns: each column must be a Float32Array or an array of strings";
+			break;
+		}
+		if (column_index == 0) {
+			n_rows = column_n_rows;
+		} else if (column_n_rows != n_rows) {
+			error_message = "tangram.model_predict_columns: all columns must have the same length";
+		}
+	}
+
+	// Call `tangram_model_predict_columns`, writing the output directly into the buffer backing a Float32Array.
+	napi_value output_value = NULL;
+	if (!error_message) {
+		size_t n_outputs;
+		int n_outputs_result = tangram_model_n_outputs(model, &n_outputs);
+		if (n_outputs_result != TANGRAM_OK) {
+			throw_last_error(env, n_outputs_result);
+		} else {
+			size_t output_len = n_rows * n_outputs;
+			void* output;
+			napi_value output_buffer;
+			status = napi_create_arraybuffer(env, output_len * sizeof(float), &output, &output_buffer);
+			assert(status == napi_ok);
+			status = napi_create_typedarray(env, napi_float32_array, output_len, output_buffer, 0, &output_value);
+			assert(status == napi_ok);
+			int predict_result = tangram_model_predict_columns(model, columns, n_columns, n_rows, (float*)output, output_len);
+			if (predict_result != TANGRAM_OK) {
+				throw_last_error(env, predict_result);
+				output_value = NULL;
+			}
+		}
+	} else {
+		napi_throw_type_error(env, NULL, error_message);
+	}
+
+	// Free the memory allocated for the columns.
+	for (uint32_t column_index = 0; column_index < n_columns; column_index++) {
+		tangram_column* column = &columns[column_index];
+		free((char*)column->name);
+		if (column->column_type == TANGRAM_COLUMN_TYPE_STRING && column->string_values) {
+			char** string_values = (char**)column->string_values;
+			for (size_t row_index = 0; row_index < column_lengths[column_index]; row_index++) {
+				free(string_values[row_index]);
+			}
+			free(string_values);
+		}
+	}
+	free(columns);
+	free(column_lengths);
+
+	return output_value;
+}
+
 napi_value init(napi_env env, napi_value exports) {
 	napi_status status;
-	size_t n_prop_descs = 3;
+	size_t n_prop_descs = 5;
 	napi_property_descriptor prop_descs[] = {
 		{ "model_load", 0, model_load_fn, 0, 0, 0, napi_default, 0 },
 		{ "model_id", 0, model_id_fn, 0, 0, 0, napi_default, 0 },
-		{ "model_predict", 0, model_predict_fn, 0, 0, 0, napi_default, 0 }
+		{ "model_metadata", 0, model_metadata_fn, 0, 0, 0, napi_default, 0 },
+		{ "model_predict", 0, model_predict_fn, 0, 0, 0, napi_default, 0 },
+		{ "model_predict_columns", 0, model_predict_columns_fn, 0, 0, 0, napi_default, 0 }
 	};
 	status = napi_define_properties(env, exports, n_prop_descs, prop_descs);
 	assert(status == napi_ok);
@@ -1 +1,2 @@
+from tangram.libtangram import TangramError
 from tangram.model import Model
\ No newline at end of file
@@ -6,6 +6,14 @@ import platform
 ffi = FFI()
 ffi.cdef("""
 	typedef void tangram_model;
+	typedef struct {
+		const char* name;
+		uint32_t column_type;
+		const float* number_values;
+		const char* const* string_values;
+		const uint8_t* validity;
+	} tangram_column;
+	int tangram_last_error_message(char** message);
 	int tangram_model_load(
 		const void* model_data,
 		size_t model_data_len,
@@ -21,6 +29,22 @@ ffi.cdef("""
 		tangram_model* model,
 		char** output
 	);
+	int tangram_model_metadata(
+		tangram_model* model,
+		char** output
+	);
+	int tangram_model_n_outputs(
+		tangram_model* model,
+		size_t* n_outputs
+	);
+	int tangram_model_predict_columns(
+		tangram_model* model,
+		const tangram_column* columns,
+		size_t n_columns,
+		size_t n_rows,
+		float* output,
+		size_t output_len
+	);
 	int tangram_string_free(const char* ptr);
 	int tangram_model_free(tangram_model* model);
 """)
@@ -38,3 +62,22 @@ else:
 
 libtangram_path = os.path.join(os.path.abspath(os.path.dirname(__file__)), library_path)
 libtangram = ffi.dlopen(libtangram_path)
+
+# A TangramError is raised when a call to libtangram fails. The `code` attribute holds the status code returned by libtangram.
+class TangramError(Exception):
+	def __init__(self, code, message):
+		super().__init__(message)
+		self.code = code
+
+def check(result):
+	if result == 0:
+		return
+	message_ptr = ffi.new('char**')
+	libtangram.tangram_last_error_message(message_ptr)
+	message_ptr = message_ptr[0]
+	if message_ptr == ffi.NULL:
+		message = 'tangram error'
+	else:
+		message = ffi.string(message_ptr).decode('utf-8')
+		libtangram.tangram_string_free(message_ptr)
+	raise TangramError(result, message)
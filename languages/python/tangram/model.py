@@ -1,6 +1,12 @@
 import json
+import math
 import requests
-from tangram.libtangram import libtangram, ffi
+from tangram.libtangram import libtangram, ffi, check
+
+try:
+	import numpy as np
+except ImportError:
+	np = None
 
 class Model:
 
@@ -17,44 +23,97 @@ class Model:
 		self.tangram_url = tangram_url if tangram_url is not None else 'https://app.tangramhq.com.com'
 		self.log_queue = []
 		model_ptr = ffi.new('tangram_model**')
-		result = libtangram.tangram_model_load(model_data, len(model_data), model_ptr)
-		if result != 0:
-			raise 'tangram error'
+		check(libtangram.tangram_model_load(model_data, len(model_data), model_ptr))
 		self.model = model_ptr[0]
 
 	def __del__(self):
-		result = libtangram.tangram_model_free(self.model)
-		if result != 0:
-			raise 'tangram error'
+		if hasattr(self, 'model'):
+			check(libtangram.tangram_model_free(self.model))
 
 	def id(self):
 		id_ptr = ffi.new('char**')
-		result = libtangram.tangram_model_id(self.model, id_ptr)
-		if result != 0:
-			raise Exception('tangram error')
+		check(libtangram.tangram_model_id(self.model, id_ptr))
 		id_ptr = id_ptr[0]
 		id = ffi.string(id_ptr).decode('utf-8')
 		libtangram.tangram_string_free(id_ptr)
 		return id
 
+	# Retrieve a description of the model as a dict, including its task, target column, the columns it expects as input with their types and enum options, its classes, its default threshold, and its metrics on the test set.
+	def metadata(self):
+		metadata_ptr = ffi.new('char**')
+		check(libtangram.tangram_model_metadata(self.model, metadata_ptr))
+		metadata_ptr = metadata_ptr[0]
+		metadata = ffi.string(metadata_ptr).decode('utf-8')
+		check(libtangram.tangram_string_free(metadata_ptr))
+		return json.loads(metadata)
+
 	def predict(self, input, options=None):
 		is_array = isinstance(input, list)
 		input = input if is_array else [input]
 		input = json.dumps(input).encode('utf-8')
 		options = json.dumps(options).encode('utf-8') if options is not None else ffi.NULL
 		output_ptr = ffi.new('char**')
-		result = libtangram.tangram_model_predict(self.model, input, options, output_ptr)
-		if result != 0:
-			raise Exception('tangram error')
+		check(libtangram.tangram_model_predict(self.model, input, options, output_ptr))
 		output_ptr = output_ptr[0]
 		output = ffi.string(output_ptr).decode('utf-8')
-		result = libtangram.tangram_string_free(output_ptr)
-		if result != 0:
-			raise Exception('tangram error')
+		check(libtangram.tangram_string_free(output_ptr))
 		output = json.loads(output)
 		output = output if is_array else output[0]
 		return output
 
+	# Make predictions for a batch of rows given as columns. `columns` is a dict from column name to a sequence of values, one for each row, such as a list, a numpy array, or a pandas Series. Numeric numpy arrays and Series are converted to float32 without a per-value loop, and float32 arrays are passed to libtangram without copying. The masks of numpy masked arrays are passed to libtangram as validity bitmaps. Enum and text columns are sequences of strings. None and NaN values are treated as missing. For regressors this returns the predicted value for each row, for binary classifiers it returns the probability of the positive class for each row, and for multiclass classifiers it returns a list with the probability of each class for each row.
+	def predict_columns(self, columns):
+		columns = {name: _column_values(values) for name, values in columns.items()}
+		n_rows = None
+		for name, values in columns.items():
+			if n_rows is None:
+				n_rows = len(values)
+			elif len(values) != n_rows:
+				raise ValueError('all columns must have the same length')
+		n_rows = n_rows if n_rows is not None else 0
+		# Keep references to the memory passed to libtangram until the call returns.
+		keep_alive = []
+		c_columns = ffi.new('tangram_column[]', len(columns))
+		for c_column, (name, values) in zip(c_columns, columns.items()):
+			c_name = ffi.new('char[]', name.encode('utf-8'))
+			keep_alive.append(c_name)
+			c_column.name = c_name
+			if np is not None and isinstance(values, np.ndarray):
+				if np.ma.is_masked(values):
+					validity = np.packbits(~np.ma.getmaskarray(values), bitorder='little')
+					keep_alive.append(validity)
+					c_column.validity = ffi.from_buffer('uint8_t[]', validity)
+				# This does not copy arrays that are already contiguous float32.
+				values = np.ascontiguousarray(values.astype(np.float32, copy=False))
+				keep_alive.append(values)
+				c_values = ffi.from_buffer('float[]', values)
+				c_column.column_type = 0
+				c_column.number_values = c_values
+			elif _is_float32_buffer(values):
+				c_values = ffi.from_buffer('float[]', values)
+				c_column.column_type = 0
+				c_column.number_values = c_values
+			elif all(_is_missing(value) or isinstance(value, str) for value in values):
+				c_strings = [ffi.NULL if _is_missing(value) else ffi.new('char[]', value.encode('utf-8')) for value in values]
+				keep_alive.extend(c_strings)
+				c_values = ffi.new('char*[]', c_strings)
+				c_column.column_type = 1
+				c_column.string_values = c_values
+			else:
+				c_values = ffi.new('float[]', [math.nan if value is None else value for value in values])
+				c_column.column_type = 0
+				c_column.number_values = c_values
+			keep_alive.append(c_values)
+		n_outputs_ptr = ffi.new('size_t*')
+		check(libtangram.tangram_model_n_outputs(self.model, n_outputs_ptr))
+		n_outputs = n_outputs_ptr[0]
+		output = ffi.new('float[]', n_rows * n_outputs)
+		check(libtangram.tangram_model_predict_columns(self.model, c_columns, len(columns), n_rows, output, n_rows * n_outputs))
+		if n_outputs == 1:
+			return list(output)
+		else:
+			return [list(output[i * n_outputs:(i + 1) * n_outputs]) for i in range(n_rows)]
+
 	def log_prediction(self, identifier, input, output, options=None):
 		self.log_event(self.prediction_event(identifier, input, output, options))
 
@@ -103,3 +162,30 @@ class Model:
 			'identifier': identifier,
 			'trueValue': true_value,
 		})
+
+
+# Convert numeric pandas Series and numpy arrays to numpy arrays, and other pandas Series to lists with None for missing values.
+def _column_values(values):
+	is_series = hasattr(values, 'to_numpy') and hasattr(values, 'notna')
+	if np is not None and (is_series or isinstance(values, np.ndarray)) and values.dtype.kind in 'biuf':
+		if is_series:
+			return values.to_numpy(dtype=np.float32, na_value=np.nan)
+		return values
+	if is_series:
+		values = values.astype(object)
+		return values.where(values.notna(), None).tolist()
+	if np is not None and isinstance(values, np.ndarray):
+		return values.tolist()
+	return values
+
+
+def _is_missing(value):
+	return value is None or (isinstance(value, float) and math.isnan(value))
+
+
+def _is_float32_buffer(values):
+	try:
+		view = memoryview(values)
+	except TypeError:
+		return False
+	return view.format == 'f' and view.ndim == 1 and view.c_contiguous
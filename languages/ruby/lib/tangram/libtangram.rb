@@ -15,9 +15,36 @@ module LibTangram
 	end
 	extend FFI::Library
 	ffi_lib File.expand_path("#{library_path}", __dir__)
+	attach_function :tangram_last_error_message, [:pointer], :int
 	attach_function :tangram_model_load, [:pointer, :uint, :pointer], :int
 	attach_function :tangram_model_id, [:pointer, :pointer], :int
+	attach_function :tangram_model_metadata, [:pointer, :pointer], :int
 	attach_function :tangram_model_predict, [:pointer, :pointer, :pointer, :pointer], :int
 	attach_function :tangram_string_free, [:pointer], :int
 	attach_function :tangram_model_free, [:pointer], :int
 end
+
+module Tangram
+	# A Tangram::Error is raised when a call to libtangram fails. `code` holds the status code returned by libtangram.
+	class Error < StandardError
+		attr_reader :code
+
+		def initialize(code, message)
+			super(message)
+			@code = code
+		end
+	end
+
+	def self.check(result)
+		return if result == 0
+		message_ptr = FFI::MemoryPointer.new(:pointer)
+		LibTangram.tangram_last_error_message(message_ptr)
+		if message_ptr.read_pointer.null?
+			message = 'tangram error'
+		else
+			message = message_ptr.read_pointer.read_string.force_encoding('utf-8')
+			LibTangram.tangram_string_free(message_ptr.read_pointer)
+		end
+		raise Error.new(result, message)
+	end
+end
@@ -18,24 +18,27 @@ module Tangram
 			@tangram_url = tangram_url.nil? ? 'https://app.tangramhq.com.com' : tangram_url
 			@log_queue = []
 			model_ptr = FFI::MemoryPointer.new(:pointer)
-			result = LibTangram.tangram_model_load(model_data, model_data.size, model_ptr)
-			if result != 0
-				raise 'tangram error'
-			end
+			Tangram.check(LibTangram.tangram_model_load(model_data, model_data.size, model_ptr))
 			@model = FFI::AutoPointer.new(model_ptr.read_pointer, LibTangram.method(:tangram_model_free))
 		end
 
 		def id()
 			id_ptr = FFI::MemoryPointer.new(:pointer)
-			result = LibTangram.tangram_model_id(@model, id_ptr)
-			if result != 0
-				raise 'tangram error'
-			end
+			Tangram.check(LibTangram.tangram_model_id(@model, id_ptr))
 			id = id_ptr.read_pointer.read_string.force_encoding('utf-8')
 			LibTangram.tangram_string_free(id_ptr.read_pointer)
 			id
 		end
 
+		# Retrieve a description of the model as a hash, including its task, target column, the columns it expects as input with their types and enum options, its classes, its default threshold, and its metrics on the test set.
+		def metadata()
+			metadata_ptr = FFI::MemoryPointer.new(:pointer)
+			Tangram.check(LibTangram.tangram_model_metadata(@model, metadata_ptr))
+			metadata = metadata_ptr.read_pointer.read_string.force_encoding('utf-8')
+			LibTangram.tangram_string_free(metadata_ptr.read_pointer)
+			JSON.parse(metadata)
+		end
+
 		def predict(input, options: nil)
 			is_array = input.kind_of?(Array)
 			input = is_array ? input : [input]
@@ -46,10 +49,7 @@ module Tangram
 				options = nil
 			end
 			output_ptr = FFI::MemoryPointer.new(:pointer)
-			result = LibTangram.tangram_model_predict(@model, input, options, output_ptr)
-			if result != 0
-				raise 'tangram error'
-			end
+			Tangram.check(LibTangram.tangram_model_predict(@model, input, options, output_ptr))
 			output = output_ptr.read_pointer.read_string.force_encoding('utf-8')
 			LibTangram.tangram_string_free(output_ptr.read_pointer)
 			output = JSON.parse(output)
@@ -0,0 +1,36 @@
+fn main() -> tangram_rust::Result<()> {
+	// Get the path to the .tangram file.
+	let model_path = std::env::args()
+		.nth(1)
+		.unwrap_or_else(|| "heart_disease.tangram".to_owned());
+	// Load the model from the file.
+	let model = tangram_rust::Model::from_path(model_path)?;
+
+	// Create an example input matching the schema of the CSV file the model was trained on. Here the data is just hard-coded, but in your application you will probably get this from a database or user input.
+	let input = tangram_rust::PredictInputRow::new()
+		.number("age", 63.0)
+		.enum_option("chest_pain", "typical angina")
+		.number("cholesterol", 233.0)
+		.enum_option("exercise_induced_angina", "no")
+		.number("exercise_max_heart_rate", 150.0)
+		.number("exercise_st_depression", 2.3)
+		.enum_option("exercise_st_slope", "downsloping")
+		.enum_option("fasting_blood_sugar_greater_than_120", "true")
+		.number("fluoroscopy_vessels_colored", 0.0)
+		.enum_option("gender", "male")
+		.number("resting_blood_pressure", 145.0)
+		.enum_option(
+			"resting_ecg_result",
+			"probable or definite left ventricular hypertrophy",
+		)
+		.enum_option("thallium_stress_test", "fixed defect");
+
+	// Make the prediction!
+	let output = model.predict_rows(&[input.clone()], None)?;
+
+	// Print out the input and output.
+	println!("Input: {:?}", input);
+	println!("Output: {:?}", output);
+
+	Ok(())
+}
@@ -0,0 +1,119 @@
+/*!
+This crate is the Tangram library for Rust. It makes predictions with models trained with the Tangram CLI, and unlike `tangram_core`, it does not depend on any of the code used for training. Its API mirrors the libraries for the other languages.
+
+```no_run
+let model = tangram_rust::Model::from_path("heart_disease.tangram")?;
+let input = tangram_rust::PredictInputRow::new()
+	.number("age", 63.0)
+	.enum_option("gender", "male");
+let output = model.predict_rows(&[input], None)?;
+# Ok::<(), tangram_rust::Error>(())
+```
+*/
+
+use std::{convert::TryInto, path::Path};
+
+pub use tangram_core::{
+	counterfactual::{Counterfactual, CounterfactualChange, CounterfactualOptions, DesiredOutcome},
+	metadata::ModelMetadata,
+	predict::{
+		BinaryClassificationPredictOutput, Feature, FeatureContribution, FeatureContributions,
+		FeatureInteraction, MulticlassClassificationPredictOutput, PredictInput, PredictInputRow,
+		PredictInputValue, PredictOptions, PredictOutput, PredictWarning, RegressionPredictOutput,
+	},
+};
+pub use tangram_dataframe::DataFrame;
+pub use tangram_util::error::{Error, Result};
+
+/// A `Model` is a model trained with the Tangram CLI that is ready to make predictions.
+pub struct Model {
+	model: tangram_core::predict::Model,
+	metadata: ModelMetadata,
+}
+
+impl Model {
+	/// Load a model from the contents of a `.tangram` file.
+	pub fn from_slice(slice: &[u8]) -> Result<Model> {
+		let model = tangram_core::model::Model::from_slice(slice)?;
+		Model::from_model(model)
+	}
+
+	/// Load a model from the `.tangram` file at `path`.
+	pub fn from_path(path: impl AsRef<Path>) -> Result<Model> {
+		let model = tangram_core::model::Model::from_path(path.as_ref())?;
+		Model::from_model(model)
+	}
+
+	fn from_model(model: tangram_core::model::Model) -> Result<Model> {
+		let metadata = ModelMetadata::from(&model);
+		let model = model.try_into()?;
+		Ok(Model { model, metadata })
+	}
+
+	/// Retrieve the model's id.
+	pub fn id(&self) -> &str {
+		&self.metadata.id
+	}
+
+	/// Retrieve the model's metadata, which describes the columns it expects as input, its classes, and its test metrics.
+	pub fn metadata(&self) -> &ModelMetadata {
+		&self.metadata
+	}
+
+	/// Make predictions for rows of json input.
+	pub fn predict(
+		&self,
+		input: PredictInput,
+		options: Option<PredictOptions>,
+	) -> Result<PredictOutput> {
+		tangram_core::predict::predict(&self.model, input, options)
+	}
+
+	/// Make predictions for rows of typed input, which avoids building and parsing json values.
+	pub fn predict_rows(
+		&self,
+		input: &[PredictInputRow],
+		options: Option<PredictOptions>,
+	) -> Result<PredictOutput> {
+		tangram_core::predict::predict_rows(&self.model, input, options)
+	}
+
+	/// Search for the smallest changes to an example's number and enum columns that change the model's prediction to `desired_outcome`.
+	pub fn counterfactuals(
+		&self,
+		example: &serde_json::Map<String, serde_json::Value>,
+		desired_outcome: DesiredOutcome,
+		options: Option<CounterfactualOptions>,
+	) -> Result<Vec<Counterfactual>> {
+		tangram_core::counterfactual::compute_counterfactuals(
+			&self.model,
+			example,
+			desired_outcome,
+			options,
+		)
+	}
+
+	/// Create an empty `DataFrame` with one column for each of the model's input columns. Fill it with your input and pass it to [`Model::predict_dataframe`] or [`Model::predict_dataframe_into`] to make predictions in batches.
+	pub fn dataframe(&self) -> DataFrame {
+		self.model.dataframe()
+	}
+
+	/// Make predictions for a batch of columnar input.
+	pub fn predict_dataframe(
+		&self,
+		dataframe: &DataFrame,
+		options: Option<PredictOptions>,
+	) -> Result<PredictOutput> {
+		tangram_core::predict::predict_dataframe(&self.model, &dataframe.view(), options)
+	}
+
+	/// Make predictions for a batch of columnar input, writing only the output values to `output` and skipping feature contributions. `output` must have length `dataframe.nrows() * model.n_outputs()`.
+	pub fn predict_dataframe_into(&self, dataframe: &DataFrame, output: &mut [f32]) -> Result<()> {
+		tangram_core::predict::predict_dataframe_into(&self.model, &dataframe.view(), output)
+	}
+
+	/// Retrieve the number of values [`Model::predict_dataframe_into`] writes for each row.
+	pub fn n_outputs(&self) -> usize {
+		self.model.n_outputs()
+	}
+}
@@ -0,0 +1,46 @@
+let fs = require('fs')
+let path = require('path')
+let tangram = require('../../lib.js')
+
+async function main() {
+	// Instantiate libtangram from the tangram.wasm file produced by scripts/build. In a browser or edge worker, fetch it or use the module bound to the worker instead.
+	let wasm = fs.readFileSync(path.join(__dirname, '../../tangram.wasm'))
+	let libtangram = await tangram.Tangram.instantiate(wasm)
+
+	// Load the model from the file at the path passed as the first argument.
+	let modelPath = process.argv[2] ?? path.join(__dirname, 'heart_disease.tangram')
+	let modelData = fs.readFileSync(modelPath)
+	let model = libtangram.loadModel(modelData)
+
+	// Create an example input matching the schema of the CSV file the model was trained on.
+	let input = {
+		age: 63,
+		chest_pain: 'typical angina',
+		cholesterol: 233,
+		exercise_induced_angina: 'no',
+		exercise_max_heart_rate: 150,
+		exercise_st_depression: 2.3,
+		exercise_st_slope: 'downsloping',
+		fasting_blood_sugar_greater_than_120: 'true',
+		fluoroscopy_vessels_colored: 0,
+		gender: 'male',
+		resting_blood_pressure: 145,
+		resting_ecg_result: 'probable or definite left ventricular hypertrophy',
+		thallium_stress_test: 'fixed defect',
+	}
+
+	// Make the prediction!
+	let output = model.predict(input)
+
+	// Print out the input and output.
+	console.log('Input:', input)
+	console.log('Output:', output)
+
+	// Models live in wasm memory, so free the model when you are done with it.
+	model.free()
+}
+
+main().catch(error => {
+	console.error(error)
+	process.exit(1)
+})
@@ -0,0 +1,319 @@
+export type PredictOptions = {
+	threshold?: number
+	strict?: boolean
+}
+
+export type PredictWarning =
+	| { type: "missing_column"; columnName: string }
+	| { type: "unknown_enum_option"; columnName: string; value: string }
+	| { type: "invalid_value"; columnName: string }
+	| { type: "unknown_column"; columnName: string }
+
+export type Input = {
+	[key: string]: string | number | boolean | null | undefined
+}
+
+export type RegressionOutput = {
+	value: number
+	lower: number | null
+	upper: number | null
+	warnings?: PredictWarning[]
+}
+
+export type MulticlassClassificationOutput<Classes = string> = {
+	className: Classes
+	probabilities: { [K in keyof Classes]: number }
+	probability: number
+	warnings?: PredictWarning[]
+}
+
+export type Output = RegressionOutput | MulticlassClassificationOutput
+
+export type ModelMetadata = {
+	id: string
+	task: "regression" | "binary_classification" | "multiclass_classification"
+	targetColumnName: string
+	columns: ColumnMetadata[]
+	classes: string[] | null
+	defaultThreshold: number | null
+	testMetrics: { [key: string]: unknown }
+}
+
+export type ColumnMetadata =
+	| { type: "unknown"; name: string }
+	| { type: "number"; name: string }
+	| { type: "enum"; name: string; options: string[] }
+	| { type: "text"; name: string }
+
+// These are the status codes returned by libtangram.
+export const TANGRAM_OK = 0
+export const TANGRAM_ERROR = 1
+export const TANGRAM_INVALID_ARGUMENT = 2
+export const TANGRAM_INVALID_MODEL = 3
+export const TANGRAM_INVALID_INPUT = 4
+export const TANGRAM_PANIC = 5
+
+export class TangramError extends Error {
+	public code: number
+
+	constructor(message: string, code: number) {
+		super(message)
+		this.name = "TangramError"
+		this.code = code
+	}
+}
+
+// These are the functions exported by the wasm build of libtangram. Pointers and sizes are 32 bit on wasm32.
+type Exports = {
+	memory: WebAssembly.Memory
+	tangram_alloc(size: number, align: number): number
+	tangram_dealloc(ptr: number, size: number, align: number): void
+	tangram_version(versionPtr: number): number
+	tangram_last_error_message(messagePtr: number): number
+	tangram_model_load(
+		modelData: number,
+		modelDataLen: number,
+		modelPtr: number,
+	): number
+	tangram_model_id(model: number, idPtr: number): number
+	tangram_model_metadata(model: number, metadataPtr: number): number
+	tangram_model_predict(
+		model: number,
+		input: number,
+		options: number,
+		outputPtr: number,
+	): number
+	tangram_string_free(string: number): number
+	tangram_model_free(model: number): number
+}
+
+let encoder = new TextEncoder()
+let decoder = new TextDecoder()
+
+// `Tangram` is an instance of the libtangram wasm module. Create one with `Tangram.instantiate` and use it to load models.
+export class Tangram {
+	private exports: Exports
+
+	private constructor(instance: WebAssembly.Instance) {
+		this.exports = (instance.exports as unknown) as Exports
+	}
+
+	// Instantiate libtangram from the bytes of tangram.wasm or an already compiled module, such as one bound to a Cloudflare worker.
+	public static async instantiate(
+		source: BufferSource | WebAssembly.Module,
+	): Promise<Tangram> {
+		let instance =
+			source instanceof WebAssembly.Module
+				? await WebAssembly.instantiate(source, {})
+				: (await WebAssembly.instantiate(source, {})).instance
+		return new Tangram(instance)
+	}
+
+	public version(): string {
+		return this.withOutPtr(outPtr => {
+			this.check(this.call(() => this.exports.tangram_version(outPtr)))
+			return this.takeString(this.readPtr(outPtr))
+		})
+	}
+
+	public loadModel(data: ArrayBuffer | Uint8Array): Model {
+		let bytes = data instanceof Uint8Array ? data : new Uint8Array(data)
+		let dataPtr = this.exports.tangram_alloc(Math.max(bytes.length, 1), 1)
+		try {
+			new Uint8Array(this.exports.memory.buffer, dataPtr, bytes.length).set(
+				bytes,
+			)
+			let modelPtr = this.withOutPtr(outPtr => {
+				this.check(
+					this.call(() =>
+						this.exports.tangram_model_load(dataPtr, bytes.length, outPtr),
+					),
+				)
+				return this.readPtr(outPtr)
+			})
+			return new Model(this, modelPtr)
+		} finally {
+			this.exports.tangram_dealloc(dataPtr, Math.max(bytes.length, 1), 1)
+		}
+	}
+
+	/** @internal */
+	public modelId(model: number): string {
+		return this.withOutPtr(outPtr => {
+			this.check(this.call(() => this.exports.tangram_model_id(model, outPtr)))
+			return this.takeString(this.readPtr(outPtr))
+		})
+	}
+
+	/** @internal */
+	public modelMetadata(model: number): string {
+		return this.withOutPtr(outPtr => {
+			this.check(
+				this.call(() => this.exports.tangram_model_metadata(model, outPtr)),
+			)
+			return this.takeString(this.readPtr(outPtr))
+		})
+	}
+
+	/** @internal */
+	public modelPredict(
+		model: number,
+		inputJson: string,
+		optionsJson: string | undefined,
+	): string {
+		return this.withString(inputJson, inputPtr =>
+			this.withString(optionsJson, optionsPtr =>
+				this.withOutPtr(outPtr => {
+					this.check(
+						this.call(() =>
+							this.exports.tangram_model_predict(
+								model,
+								inputPtr,
+								optionsPtr,
+								outPtr,
+							),
+						),
+					)
+					return this.takeString(this.readPtr(outPtr))
+				}),
+			),
+		)
+	}
+
+	/** @internal */
+	public modelFree(model: number) {
+		this.exports.tangram_model_free(model)
+	}
+
+	// The wasm build of libtangram aborts on panic instead of unwinding, which surfaces here as a `WebAssembly.RuntimeError`.
+	private call(f: () => number): number {
+		try {
+			return f()
+		} catch (error) {
+			if (error instanceof WebAssembly.RuntimeError) {
+				throw new TangramError(
+					`libtangram panicked: ${error.message}`,
+					TANGRAM_PANIC,
+				)
+			}
+			throw error
+		}
+	}
+
+	private check(status: number) {
+		if (status === TANGRAM_OK) {
+			return
+		}
+		let message = this.withOutPtr(outPtr => {
+			this.exports.tangram_last_error_message(outPtr)
+			let messagePtr = this.readPtr(outPtr)
+			return messagePtr === 0 ? "tangram error" : this.takeString(messagePtr)
+		})
+		throw new TangramError(message, status)
+	}
+
+	// Allocate space for a pointer for libtangram to write to, call `f` with it, and free it.
+	private withOutPtr<T>(f: (outPtr: number) => T): T {
+		let outPtr = this.exports.tangram_alloc(4, 4)
+		try {
+			new DataView(this.exports.memory.buffer).setUint32(outPtr, 0, true)
+			return f(outPtr)
+		} finally {
+			this.exports.tangram_dealloc(outPtr, 4, 4)
+		}
+	}
+
+	// Copy `string` into wasm memory as a C string, call `f` with it, and free it. If `string` is undefined, `f` is called with a null pointer.
+	private withString<T>(
+		string: string | undefined,
+		f: (stringPtr: number) => T,
+	): T {
+		if (string === undefined) {
+			return f(0)
+		}
+		let bytes = encoder.encode(string)
+		let size = bytes.length + 1
+		let stringPtr = this.exports.tangram_alloc(size, 1)
+		try {
+			let memory = new Uint8Array(this.exports.memory.buffer, stringPtr, size)
+			memory.set(bytes)
+			memory[bytes.length] = 0
+			return f(stringPtr)
+		} finally {
+			this.exports.tangram_dealloc(stringPtr, size, 1)
+		}
+	}
+
+	// The memory buffer is replaced when wasm memory grows, so always read it again after calling into libtangram.
+	private readPtr(ptr: number): number {
+		return new DataView(this.exports.memory.buffer).getUint32(ptr, true)
+	}
+
+	// Read the C string at `stringPtr` that libtangram allocated and free it.
+	private takeString(stringPtr: number): string {
+		let memory = new Uint8Array(this.exports.memory.buffer)
+		let end = stringPtr
+		while (memory[end] !== 0) {
+			end += 1
+		}
+		let string = decoder.decode(memory.subarray(stringPtr, end))
+		this.exports.tangram_string_free(stringPtr)
+		return string
+	}
+}
+
+// A `Model` lives in the memory of the `Tangram` instance that loaded it. Call `free` when you are done with it, because wasm memory is not garbage collected.
+export class Model<
+	InputType extends Input = Input,
+	OutputType extends Output = Output
+> {
+	private tangram: Tangram
+	private model: number
+
+	/** @internal */
+	constructor(tangram: Tangram, model: number) {
+		this.tangram = tangram
+		this.model = model
+	}
+
+	public id(): string {
+		return this.tangram.modelId(this.ptr())
+	}
+
+	public metadata(): ModelMetadata {
+		return JSON.parse(this.tangram.modelMetadata(this.ptr()))
+	}
+
+	public predict<PredictInput extends InputType | InputType[]>(
+		input: PredictInput,
+		options?: PredictOptions,
+	): PredictInput extends InputType[] ? OutputType[] : OutputType {
+		let isArray = Array.isArray(input)
+		let inputJson = JSON.stringify(isArray ? input : [input])
+		let optionsJson = options ? JSON.stringify(options) : undefined
+		let outputJson = this.tangram.modelPredict(
+			this.ptr(),
+			inputJson,
+			optionsJson,
+		)
+		let output = JSON.parse(outputJson)
+		output = isArray ? output : output[0]
+		return output as PredictInput extends InputType[]
+			? OutputType[]
+			: OutputType
+	}
+
+	public free() {
+		if (this.model !== 0) {
+			this.tangram.modelFree(this.model)
+			this.model = 0
+		}
+	}
+
+	private ptr(): number {
+		if (this.model === 0) {
+			throw new TangramError("model has been freed", TANGRAM_INVALID_ARGUMENT)
+		}
+		return this.model
+	}
+}
@@ -0,0 +1,70 @@
+let assert = require('assert')
+let fs = require('fs')
+let path = require('path')
+let tangram = require('../lib.js')
+
+// These are the fixture models. For each one, scripts/test writes the native predictions for input.json to `<model>.json` in the directory passed as the first argument.
+let modelNames = [
+	'heart_disease_linear',
+	'heart_disease_multiclass',
+	'heart_disease_regressor',
+	'heart_disease_tree',
+]
+
+async function main() {
+	let nativeOutputDir = process.argv[2]
+	let wasm = fs.readFileSync(path.join(__dirname, '../tangram.wasm'))
+	let libtangram = await tangram.Tangram.instantiate(wasm)
+	let input = JSON.parse(fs.readFileSync(path.join(__dirname, 'input.json'), 'utf8'))
+
+	// Predictions made with the wasm build must match the native predictions.
+	for (let modelName of modelNames) {
+		let modelData = fs.readFileSync(path.join(__dirname, `${modelName}.tangram`))
+		let model = libtangram.loadModel(modelData)
+		let output = model.predict(input)
+		model.free()
+		let nativeOutput = JSON.parse(
+			fs.readFileSync(path.join(nativeOutputDir, `${modelName}.json`), 'utf8'),
+		)
+		assertClose(output, nativeOutput, modelName)
+		console.log(`${modelName}: ok`)
+	}
+
+	// Loading a truncated model must fail with TANGRAM_INVALID_MODEL.
+	let modelData = fs.readFileSync(path.join(__dirname, 'heart_disease_tree.tangram'))
+	assert.throws(
+		() => libtangram.loadModel(modelData.subarray(0, modelData.length / 2)),
+		error =>
+			error instanceof tangram.TangramError &&
+			error.code === tangram.TANGRAM_INVALID_MODEL,
+	)
+	console.log('truncated model: ok')
+}
+
+// Compare `actual` and `expected` recursively, allowing numbers to differ by a small tolerance because the wasm build may round differently than the native one.
+function assertClose(actual, expected, path) {
+	if (typeof expected === 'number') {
+		assert.strictEqual(typeof actual, 'number', path)
+		let tolerance = 1e-4 * Math.max(1, Math.abs(expected))
+		assert.ok(
+			Math.abs(actual - expected) <= tolerance,
+			`${path}: expected ${expected} but got ${actual}`,
+		)
+	} else if (Array.isArray(expected)) {
+		assert.ok(Array.isArray(actual), path)
+		assert.strictEqual(actual.length, expected.length, path)
+		expected.forEach((value, i) => assertClose(actual[i], value, `${path}[${i}]`))
+	} else if (expected !== null && typeof expected === 'object') {
+		assert.deepStrictEqual(Object.keys(actual).sort(), Object.keys(expected).sort(), path)
+		for (let key of Object.keys(expected)) {
+			assertClose(actual[key], expected[key], `${path}.${key}`)
+		}
+	} else {
+		assert.strictEqual(actual, expected, path)
+	}
+}
+
+main().catch(error => {
+	console.error(error)
+	process.exit(1)
+})
@@ -0,0 +1,22 @@
+/*!
+This example makes predictions natively, the same way `tangram_model_predict` does, and prints them as json. The wasm tests compare its output against the wasm build of libtangram.
+
+```
+cargo run -p libtangram --example predict -- <model> <input>
+```
+*/
+
+use std::convert::TryFrom;
+
+fn main() {
+	let mut args = std::env::args().skip(1);
+	let model_path = args.next().expect("usage: predict <model> <input>");
+	let input_path = args.next().expect("usage: predict <model> <input>");
+	let bytes = std::fs::read(model_path).unwrap();
+	let model = tangram_core::model::Model::from_slice(&bytes).unwrap();
+	let model = tangram_core::predict::Model::try_from(model).unwrap();
+	let input = std::fs::read_to_string(input_path).unwrap();
+	let input: tangram_core::predict::PredictInput = serde_json::from_str(&input).unwrap();
+	let output = tangram_core::predict::predict(&model, input, None).unwrap();
+	println!("{}", serde_json::to_string(&output).unwrap());
+}
@@ -1,28 +1,169 @@
 /*!
-This crate implements the C API for libtangram, the Tangram C library, which is used by the libraries for each programming language to make predictions using a model trained with the Tangram CLI. While the APIs for the language libraries are stable, this API is unstable and subject to change. All functions return a status code which will be zero on success or non-zero on error.
+This crate implements the C API for libtangram, the Tangram C library, which is used by the libraries for each programming language to make predictions using a model trained with the Tangram CLI. While the APIs for the language libraries are stable, this API is unstable and subject to change.
+
+All functions return a status code which will be `TANGRAM_OK` on success or one of the other status codes below on error. When a function returns an error, call `tangram_last_error_message` on the same thread to retrieve a description of it.
+
+| code | name                       | meaning                                                                        |
+|------|----------------------------|--------------------------------------------------------------------------------|
+| 0    | `TANGRAM_OK`               | The call succeeded.                                                            |
+| 1    | `TANGRAM_ERROR`            | An error not covered by the other codes occurred.                              |
+| 2    | `TANGRAM_INVALID_ARGUMENT` | A pointer was null or a string was not valid UTF-8.                            |
+| 3    | `TANGRAM_INVALID_MODEL`    | The model data could not be read or is not a valid model.                      |
+| 4    | `TANGRAM_INVALID_INPUT`    | The input or options could not be read, or the input failed strict validation. |
+| 5    | `TANGRAM_PANIC`            | libtangram panicked. This is a bug, please report it.                          |
 */
 
-#![allow(clippy::missing_safety_doc)]
+// The functions below are called from C, so they take raw pointers without being marked `unsafe`, and check them for null before dereferencing them.
+#![allow(clippy::missing_safety_doc, clippy::not_unsafe_ptr_arg_deref)]
 
 use std::{
 	alloc::{alloc, dealloc, Layout},
-	convert::TryInto,
+	cell::RefCell,
+	collections::HashMap,
+	convert::TryFrom,
 	ffi::{CStr, CString},
-	panic::catch_unwind,
+	num::NonZeroUsize,
+	panic::{catch_unwind, UnwindSafe},
 };
+use tangram_core::predict::Column;
+use tangram_dataframe::prelude::*;
+
+#[cfg(target_arch = "wasm32")]
+#[global_allocator]
+static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+
+pub const TANGRAM_OK: isize = 0;
+pub const TANGRAM_ERROR: isize = 1;
+pub const TANGRAM_INVALID_ARGUMENT: isize = 2;
+pub const TANGRAM_INVALID_MODEL: isize = 3;
+pub const TANGRAM_INVALID_INPUT: isize = 4;
+pub const TANGRAM_PANIC: isize = 5;
+
+pub const TANGRAM_COLUMN_TYPE_NUMBER: u32 = 0;
+pub const TANGRAM_COLUMN_TYPE_STRING: u32 = 1;
+
+/// A `TangramColumn` describes one column of input to `tangram_model_predict_columns`. No data is copied for number columns without a validity bitmap.
+#[repr(C)]
+pub struct TangramColumn {
+	/// This is the name of the column as a C string.
+	pub name: *const u8,
+	/// This is either `TANGRAM_COLUMN_TYPE_NUMBER` or `TANGRAM_COLUMN_TYPE_STRING`. String columns are used for the model's enum and text columns.
+	pub column_type: u32,
+	/// For number columns, this points to `n_rows` floats. NaN values are treated as missing.
+	pub number_values: *const f32,
+	/// For string columns, this points to `n_rows` C strings. Null pointers are treated as missing.
+	pub string_values: *const *const u8,
+	/// This may be null, or point to a bitmap with one bit for each row, least significant bit first, where a zero bit means the value is missing.
+	pub validity: *const u8,
+}
+
+/// `Model` is the opaque handle returned by `tangram_model_load`. It holds the model used to make predictions along with its metadata.
+pub struct Model {
+	model: tangram_core::predict::Model,
+	metadata: tangram_core::metadata::ModelMetadata,
+}
+
+thread_local! {
+	/// This is the message for the most recent error that occurred on this thread.
+	static LAST_ERROR_MESSAGE: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// An `Error` pairs one of the status codes with a message describing what went wrong.
+struct Error {
+	code: isize,
+	message: String,
+}
+
+impl Error {
+	fn new(code: isize, message: impl std::fmt::Display) -> Error {
+		Error {
+			code,
+			message: message.to_string(),
+		}
+	}
+}
+
+/// Run `f`, converting both returned errors and panics into a status code and recording the error message so it can be retrieved with `tangram_last_error_message`.
+fn call(f: impl FnOnce() -> Result<(), Error> + UnwindSafe) -> isize {
+	let error = match catch_unwind(f) {
+		Ok(Ok(())) => return TANGRAM_OK,
+		Ok(Err(error)) => error,
+		Err(panic) => {
+			let message = if let Some(message) = panic.downcast_ref::<&str>() {
+				(*message).to_owned()
+			} else if let Some(message) = panic.downcast_ref::<String>() {
+				message.clone()
+			} else {
+				"unknown panic".to_owned()
+			};
+			Error::new(TANGRAM_PANIC, format!("libtangram panicked: {}", message))
+		}
+	};
+	let Error { code, message } = error;
+	LAST_ERROR_MESSAGE.with(|last_error_message| {
+		last_error_message.replace(Some(message));
+	});
+	code
+}
+
+/// Read the C string pointed to by `ptr`, which must not be null and must be valid UTF-8.
+unsafe fn str_from_ptr<'a>(ptr: *const u8, name: &str) -> Result<&'a str, Error> {
+	if ptr.is_null() {
+		return Err(Error::new(
+			TANGRAM_INVALID_ARGUMENT,
+			format!("{} must not be null", name),
+		));
+	}
+	CStr::from_ptr(ptr as *const i8).to_str().map_err(|_| {
+		Error::new(
+			TANGRAM_INVALID_ARGUMENT,
+			format!("{} is not valid utf-8", name),
+		)
+	})
+}
+
+/// Check that the out parameter `ptr` is not null.
+fn check_out_ptr<T>(ptr: *mut T, name: &str) -> Result<(), Error> {
+	if ptr.is_null() {
+		Err(Error::new(
+			TANGRAM_INVALID_ARGUMENT,
+			format!("{} must not be null", name),
+		))
+	} else {
+		Ok(())
+	}
+}
+
+/// Move `string` into a C string owned by the caller.
+fn into_c_string(string: String) -> Result<*const u8, Error> {
+	let string = CString::new(string).map_err(|error| Error::new(TANGRAM_ERROR, error))?;
+	Ok(CString::into_raw(string) as *const u8)
+}
 
 /// Retrieve the version of libtangram that is in use. On success, a pointer to the C string with the version will be written to `version_ptr`. You must call `tangram_string_free` when you are done with it.
 #[no_mangle]
 pub extern "C" fn tangram_version(version_ptr: *mut *const u8) -> isize {
-	let result = catch_unwind(|| unsafe {
+	call(|| unsafe {
+		check_out_ptr(version_ptr, "version_ptr")?;
 		let version = env!("CARGO_PKG_VERSION");
-		let version = CString::new(version).unwrap();
-		*version_ptr = CString::into_raw(version) as *const u8;
-	});
-	match result {
-		Ok(_) => 0,
-		Err(_) => 1,
-	}
+		*version_ptr = into_c_string(version.to_owned())?;
+		Ok(())
+	})
+}
+
+/// Retrieve the message for the most recent error that occurred on the calling thread. On success, a pointer to the message as a C string will be written to `message_ptr`, or null if no error has occurred. You must call `tangram_string_free` when you are done with it.
+#[no_mangle]
+pub extern "C" fn tangram_last_error_message(message_ptr: *mut *const u8) -> isize {
+	call(|| unsafe {
+		check_out_ptr(message_ptr, "message_ptr")?;
+		let message =
+			LAST_ERROR_MESSAGE.with(|last_error_message| last_error_message.borrow().clone());
+		*message_ptr = match message {
+			Some(message) => into_c_string(message)?,
+			None => std::ptr::null(),
+		};
+		Ok(())
+	})
 }
 
 /// Load a model from the bytes pointed to by `model_data` with length `model_data_len`. On success, a pointer to the loaded model will be written to `model_ptr`. You must call `tangram_model_free` when you are done with it.
@@ -30,96 +171,341 @@ pub extern "C" fn tangram_version(version_ptr: *mut *const u8) -> isize {
 pub extern "C" fn tangram_model_load(
 	model_data: *const u8,
 	model_data_len: usize,
-	model_ptr: *mut *const tangram_core::predict::Model,
+	model_ptr: *mut *const Model,
 ) -> isize {
-	let result = catch_unwind(|| unsafe {
-		assert!(!model_ptr.is_null());
+	call(|| unsafe {
+		check_out_ptr(model_ptr, "model_ptr")?;
+		if model_data.is_null() {
+			return Err(Error::new(
+				TANGRAM_INVALID_ARGUMENT,
+				"model_data must not be null",
+			));
+		}
 		let bytes = std::slice::from_raw_parts(model_data, model_data_len);
-		let model = tangram_core::model::Model::from_slice(bytes).unwrap();
-		let model: tangram_core::predict::Model = model.try_into().unwrap();
-		let model = Box::new(model);
+		let model = tangram_core::model::Model::from_slice(bytes)
+			.map_err(|error| Error::new(TANGRAM_INVALID_MODEL, error.error()))?;
+		let metadata = tangram_core::metadata::ModelMetadata::from(&model);
+		let model = tangram_core::predict::Model::try_from(model)
+			.map_err(|error| Error::new(TANGRAM_INVALID_MODEL, error.error()))?;
+		let model = Box::new(Model { model, metadata });
 		*model_ptr = Box::into_raw(model);
-	});
-	match result {
-		Ok(_) => 0,
-		Err(_) => 1,
-	}
+		Ok(())
+	})
 }
 
 /// Retrieve the id of the model. On success, a pointer to the model id as a C string will be written to `id_ptr`. You must call `tangram_string_free` when you are done with it.
 #[no_mangle]
-pub extern "C" fn tangram_model_id(
-	model: *const tangram_core::predict::Model,
-	id_ptr: *mut *const u8,
-) -> isize {
-	let result = catch_unwind(|| unsafe {
-		let model = model.as_ref().unwrap();
-		let id = match model {
+pub extern "C" fn tangram_model_id(model: *const Model, id_ptr: *mut *const u8) -> isize {
+	call(|| unsafe {
+		check_out_ptr(id_ptr, "id_ptr")?;
+		let model = model
+			.as_ref()
+			.ok_or_else(|| Error::new(TANGRAM_INVALID_ARGUMENT, "model must not be null"))?;
+		let id = match &model.model {
 			tangram_core::predict::Model::Regressor(model) => &model.id,
 			tangram_core::predict::Model::BinaryClassifier(model) => &model.id,
 			tangram_core::predict::Model::MulticlassClassifier(model) => &model.id,
 		};
-		let id = CString::new(id.clone()).unwrap();
-		*id_ptr = CString::into_raw(id) as *const u8;
-	});
-	match result {
-		Ok(_) => 0,
-		Err(_) => 1,
-	}
+		*id_ptr = into_c_string(id.clone())?;
+		Ok(())
+	})
 }
 
-/// Make a prediction! `model` should point to a model loaded with `tangram_model_load`. `input_ptr` should be a C string of a json serialized PredictInput. On success, a pointer to the output as a json serialized C string will be written to `output_ptr`. You must call `tangram_string_free` when you are done with it.
+/// Make a prediction! `model` should point to a model loaded with `tangram_model_load`. `input_ptr` should be a C string of a json serialized PredictInput. `options` may be null, or a C string of json serialized PredictOptions. On success, a pointer to the output as a json serialized C string will be written to `output_ptr`. You must call `tangram_string_free` when you are done with it.
 #[no_mangle]
 pub extern "C" fn tangram_model_predict(
-	model: *const tangram_core::predict::Model,
+	model: *const Model,
 	input: *const u8,
 	options: *const u8,
 	output_ptr: *mut *const u8,
 ) -> isize {
-	let result = catch_unwind(|| unsafe {
-		let model = model.as_ref().unwrap();
-		let input = CStr::from_ptr(input as *const i8).to_str().unwrap();
-		let input: tangram_core::predict::PredictInput = serde_json::from_str(input).unwrap();
-		let options: Option<tangram_core::predict::PredictOptions> =
-			options.as_ref().map(|options_ptr| {
-				let options = CStr::from_ptr(options_ptr as *const u8 as *const i8)
-					.to_str()
-					.unwrap();
-				serde_json::from_str(options).unwrap()
-			});
-		let output = tangram_core::predict::predict(model, input, options);
-		let output = serde_json::to_string(&output).unwrap();
-		let output = CString::new(output).unwrap();
-		*output_ptr = CString::into_raw(output) as *const u8;
-	});
-	match result {
-		Ok(_) => 0,
-		Err(_) => 1,
+	call(|| unsafe {
+		check_out_ptr(output_ptr, "output_ptr")?;
+		let model = model
+			.as_ref()
+			.ok_or_else(|| Error::new(TANGRAM_INVALID_ARGUMENT, "model must not be null"))?;
+		let input = str_from_ptr(input, "input")?;
+		let input: tangram_core::predict::PredictInput =
+			serde_json::from_str(input).map_err(|error| {
+				Error::new(TANGRAM_INVALID_INPUT, format!("invalid input: {}", error))
+			})?;
+		let options: Option<tangram_core::predict::PredictOptions> = if options.is_null() {
+			None
+		} else {
+			let options = str_from_ptr(options, "options")?;
+			let options = serde_json::from_str(options).map_err(|error| {
+				Error::new(TANGRAM_INVALID_INPUT, format!("invalid options: {}", error))
+			})?;
+			Some(options)
+		};
+		let output = tangram_core::predict::predict(&model.model, input, options)
+			.map_err(|error| Error::new(TANGRAM_INVALID_INPUT, error.error()))?;
+		let output =
+			serde_json::to_string(&output).map_err(|error| Error::new(TANGRAM_ERROR, error))?;
+		*output_ptr = into_c_string(output)?;
+		Ok(())
+	})
+}
+
+/// Retrieve a description of the model as a json serialized C string, including its task, target column, the columns it expects as input with their types and enum options, its classes, its default threshold, and its metrics on the test set. On success, a pointer to the C string will be written to `metadata_ptr`. You must call `tangram_string_free` when you are done with it.
+#[no_mangle]
+pub extern "C" fn tangram_model_metadata(
+	model: *const Model,
+	metadata_ptr: *mut *const u8,
+) -> isize {
+	call(|| unsafe {
+		check_out_ptr(metadata_ptr, "metadata_ptr")?;
+		let model = model
+			.as_ref()
+			.ok_or_else(|| Error::new(TANGRAM_INVALID_ARGUMENT, "model must not be null"))?;
+		let metadata = serde_json::to_string(&model.metadata)
+			.map_err(|error| Error::new(TANGRAM_ERROR, error))?;
+		*metadata_ptr = into_c_string(metadata)?;
+		Ok(())
+	})
+}
+
+/// Retrieve the number of values `tangram_model_predict_columns` writes for each row. On success, the number will be written to `n_outputs_ptr`.
+#[no_mangle]
+pub extern "C" fn tangram_model_n_outputs(model: *const Model, n_outputs_ptr: *mut usize) -> isize {
+	call(|| unsafe {
+		check_out_ptr(n_outputs_ptr, "n_outputs_ptr")?;
+		let model = model
+			.as_ref()
+			.ok_or_else(|| Error::new(TANGRAM_INVALID_ARGUMENT, "model must not be null"))?;
+		*n_outputs_ptr = model.model.n_outputs();
+		Ok(())
+	})
+}
+
+/// Make predictions for a batch of rows given as columns, without serializing them to json. `columns` should point to `n_columns` `TangramColumn`s, each with `n_rows` values. Columns the model expects that are not present are treated as missing, and columns the model does not use are ignored. The predictions are written to `output`, which must point to `output_len` floats, where `output_len` is `n_rows` times the number of outputs retrieved with `tangram_model_n_outputs`. For regressors, each row's output is the predicted value. For binary classifiers, it is the probability of the positive class. For multiclass classifiers, it is the probability of each class, in the order the classes appear in the model. Feature contributions are not computed.
+#[no_mangle]
+pub extern "C" fn tangram_model_predict_columns(
+	model: *const Model,
+	columns: *const TangramColumn,
+	n_columns: usize,
+	n_rows: usize,
+	output: *mut f32,
+	output_len: usize,
+) -> isize {
+	call(|| unsafe {
+		let model = model
+			.as_ref()
+			.ok_or_else(|| Error::new(TANGRAM_INVALID_ARGUMENT, "model must not be null"))?;
+		let model = &model.model;
+		if columns.is_null() && n_columns > 0 {
+			return Err(Error::new(
+				TANGRAM_INVALID_ARGUMENT,
+				"columns must not be null",
+			));
+		}
+		if output.is_null() && output_len > 0 {
+			return Err(Error::new(
+				TANGRAM_INVALID_ARGUMENT,
+				"output must not be null",
+			));
+		}
+		let columns = if n_columns > 0 {
+			std::slice::from_raw_parts(columns, n_columns)
+		} else {
+			&[]
+		};
+		let output = if output_len > 0 {
+			std::slice::from_raw_parts_mut(output, output_len)
+		} else {
+			&mut []
+		};
+		// Index the input columns by name.
+		let mut input_columns = HashMap::new();
+		for column in columns.iter() {
+			let name = str_from_ptr(column.name, "column name")?;
+			input_columns.insert(name, column);
+		}
+		// Convert each of the model's columns into the storage the dataframe expects. Number columns without a validity bitmap are borrowed directly from the caller.
+		let column_data = model
+			.columns()
+			.iter()
+			.map(|column| {
+				let input_column = input_columns.get(column.name()).copied();
+				read_column(column, input_column, n_rows)
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+		let dataframe_columns = model
+			.columns()
+			.iter()
+			.zip(column_data.iter())
+			.map(|(column, column_data)| {
+				let name = Some(column.name());
+				match (column, column_data) {
+					(Column::Enum(column), ColumnData::Enum(data)) => DataFrameColumnView::Enum(
+						EnumDataFrameColumnView::new(name, column.options(), data),
+					),
+					(_, ColumnData::Number(data)) => {
+						DataFrameColumnView::Number(NumberDataFrameColumnView::new(name, &**data))
+					}
+					(_, ColumnData::Text(data)) => {
+						DataFrameColumnView::Text(TextDataFrameColumnView::new(name, data))
+					}
+					(_, _) => {
+						DataFrameColumnView::Unknown(UnknownDataFrameColumnView::new(name, n_rows))
+					}
+				}
+			})
+			.collect();
+		let dataframe = DataFrameView::from_columns(dataframe_columns);
+		tangram_core::predict::predict_dataframe_into(model, &dataframe, output)
+			.map_err(|error| Error::new(TANGRAM_INVALID_INPUT, error.error()))?;
+		Ok(())
+	})
+}
+
+/// `ColumnData` holds the values for one of the model's columns in the form the dataframe expects.
+enum ColumnData<'a> {
+	Unknown,
+	Number(std::borrow::Cow<'a, [f32]>),
+	Enum(Vec<Option<NonZeroUsize>>),
+	Text(Vec<String>),
+}
+
+/// Read the values for the model's column `column` from `input_column`, treating every value as missing if `input_column` is `None`.
+unsafe fn read_column<'a>(
+	column: &Column,
+	input_column: Option<&'a TangramColumn>,
+	n_rows: usize,
+) -> Result<ColumnData<'a>, Error> {
+	let input_column = match (column, input_column) {
+		(Column::Unknown(_), _) => return Ok(ColumnData::Unknown),
+		(Column::Number(_), None) => {
+			return Ok(ColumnData::Number(vec![std::f32::NAN; n_rows].into()))
+		}
+		(Column::Enum(_), None) => return Ok(ColumnData::Enum(vec![None; n_rows])),
+		(Column::Text(_), None) => return Ok(ColumnData::Text(vec![String::new(); n_rows])),
+		(_, Some(input_column)) => input_column,
+	};
+	let expected_column_type = match column {
+		Column::Number(_) => TANGRAM_COLUMN_TYPE_NUMBER,
+		_ => TANGRAM_COLUMN_TYPE_STRING,
+	};
+	if input_column.column_type != expected_column_type {
+		return Err(Error::new(
+			TANGRAM_INVALID_INPUT,
+			format!(
+				"column \"{}\" must be a {} column",
+				column.name(),
+				if expected_column_type == TANGRAM_COLUMN_TYPE_NUMBER {
+					"number"
+				} else {
+					"string"
+				}
+			),
+		));
+	}
+	let is_valid = |row_index: usize| {
+		input_column.validity.is_null()
+			|| *input_column.validity.add(row_index / 8) & (1 << (row_index % 8)) != 0
+	};
+	match column {
+		Column::Number(_) => {
+			if input_column.number_values.is_null() && n_rows > 0 {
+				return Err(Error::new(
+					TANGRAM_INVALID_ARGUMENT,
+					format!(
+						"number_values for column \"{}\" must not be null",
+						column.name()
+					),
+				));
+			}
+			let values = if n_rows > 0 {
+				std::slice::from_raw_parts(input_column.number_values, n_rows)
+			} else {
+				&[]
+			};
+			if input_column.validity.is_null() {
+				Ok(ColumnData::Number(values.into()))
+			} else {
+				let values = values
+					.iter()
+					.enumerate()
+					.map(|(row_index, value)| {
+						if is_valid(row_index) {
+							*value
+						} else {
+							std::f32::NAN
+						}
+					})
+					.collect::<Vec<_>>();
+				Ok(ColumnData::Number(values.into()))
+			}
+		}
+		Column::Enum(_) | Column::Text(_) => {
+			if input_column.string_values.is_null() && n_rows > 0 {
+				return Err(Error::new(
+					TANGRAM_INVALID_ARGUMENT,
+					format!(
+						"string_values for column \"{}\" must not be null",
+						column.name()
+					),
+				));
+			}
+			let mut values = Vec::with_capacity(n_rows);
+			for row_index in 0..n_rows {
+				let value = *input_column.string_values.add(row_index);
+				let value = if value.is_null() || !is_valid(row_index) {
+					None
+				} else {
+					Some(str_from_ptr(value, "string value")?)
+				};
+				values.push(value);
+			}
+			match column {
+				Column::Enum(column) => {
+					let options: HashMap<&str, NonZeroUsize> = column
+						.options()
+						.iter()
+						.enumerate()
+						.map(|(index, option)| {
+							(option.as_str(), NonZeroUsize::new(index + 1).unwrap())
+						})
+						.collect();
+					Ok(ColumnData::Enum(
+						values
+							.into_iter()
+							.map(|value| value.and_then(|value| options.get(value).copied()))
+							.collect(),
+					))
+				}
+				_ => Ok(ColumnData::Text(
+					values
+						.into_iter()
+						.map(|value| value.unwrap_or("").to_owned())
+						.collect(),
+				)),
+			}
+		}
+		Column::Unknown(_) => unreachable!(),
 	}
 }
 
-/// Free the C string created by libtangram pointed to by `string`.
+/// Free the C string created by libtangram pointed to by `string`. Passing null does nothing.
 #[no_mangle]
 pub extern "C" fn tangram_string_free(string: *mut u8) -> isize {
-	let result = catch_unwind(|| unsafe {
-		drop(CString::from_raw(string as *mut i8));
-	});
-	match result {
-		Ok(_) => 0,
-		Err(_) => 1,
-	}
+	call(|| unsafe {
+		if !string.is_null() {
+			drop(CString::from_raw(string as *mut i8));
+		}
+		Ok(())
+	})
 }
 
-/// Free the model pointed to by `model`.
+/// Free the model pointed to by `model`. Passing null does nothing.
 #[no_mangle]
-pub extern "C" fn tangram_model_free(model: *mut tangram_core::predict::Model) -> isize {
-	let result = catch_unwind(|| unsafe {
-		drop(Box::from_raw(model));
-	});
-	match result {
-		Ok(_) => 0,
-		Err(_) => 1,
-	}
+pub extern "C" fn tangram_model_free(model: *mut Model) -> isize {
+	call(|| unsafe {
+		if !model.is_null() {
+			drop(Box::from_raw(model));
+		}
+		Ok(())
+	})
 }
 
 /// This function exposes the allocator used by libtangram. It is used by the wasm build of libtangram because WebAssembly does not yet include its own allocator.
@@ -1,13 +1,19 @@
-use super::{
-	shap::{compute_shap_values_for_example, ComputeShapValuesForExampleOutput},
-	train_early_stopping_split, EarlyStoppingMonitor, TrainOptions, TrainProgress,
-};
+use super::shap::{compute_shap_values_for_example, ComputeShapValuesForExampleOutput};
+#[cfg(feature = "train")]
+use super::{train_early_stopping_split, EarlyStoppingMonitor, TrainOptions, TrainProgress};
 use ndarray::prelude::*;
+#[cfg(feature = "train")]
 use num_traits::{clamp, ToPrimitive};
+#[cfg(feature = "train")]
 use rayon::prelude::*;
-use std::{num::NonZeroUsize, ops::Neg};
+#[cfg(feature = "train")]
+use std::num::NonZeroUsize;
+use std::ops::Neg;
+#[cfg(feature = "train")]
 use tangram_dataframe::prelude::*;
+#[cfg(feature = "train")]
 use tangram_metrics::{BinaryCrossEntropy, BinaryCrossEntropyInput, StreamingMetric};
+#[cfg(feature = "train")]
 use tangram_util::{progress_counter::ProgressCounter, pzip, super_unsafe::SuperUnsafe, zip};
 
 /// This struct describes a linear binary classifier model. You can train one by calling `BinaryClassifier::train`.
@@ -22,6 +28,7 @@ pub struct BinaryClassifier {
 }
 
 /// This struct is returned by `BinaryClassifier::train`.
+#[cfg(feature = "train")]
 pub struct BinaryClassifierTrainOutput {
 	/// This is the model you just trained.
 	pub model: BinaryClassifier,
@@ -31,6 +38,7 @@ pub struct BinaryClassifierTrainOutput {
 	pub feature_importances: Option<Vec<f32>>,
 }
 
+#[cfg(feature = "train")]
 impl BinaryClassifier {
 	/// Train a linear binary classifier.
 	pub fn train(
@@ -221,7 +229,9 @@ impl BinaryClassifier {
 		.finalize()
 		.unwrap()
 	}
+}
 
+impl BinaryClassifier {
 	/// Write predicted probabilities into `probabilities` for the input `features`.
 	pub fn predict(&self, features: ArrayView2<f32>, mut probabilities: ArrayViewMut1<f32>) {
 		probabilities.fill(self.bias);
@@ -4,7 +4,9 @@ This crate is an implementation of linear machine learning models for regression
 To make training faster on multicore processors, we allow simultaneous read/write access to the model parameters from multiple threads. This means each thread will be reading weights partially updated by other threads and the weights it writes may be clobbered by other threads. Unsafe sharing is implmented using the [super_unsafe](docs.io/crates/super_unsafe) crate. This makes training nondeterministic, but in practice we observe little variation in the outcome, because there is feedback control: the change in loss is monitored after each epoch, and training terminates when the loss has stabilized.
 */
 
+#[cfg(feature = "train")]
 use ndarray::prelude::*;
+#[cfg(feature = "train")]
 use num_traits::ToPrimitive;
 
 mod binary_classifier;
@@ -17,6 +19,7 @@ pub use multiclass_classifier::MulticlassClassifier;
 pub use regressor::Regressor;
 
 /// These are the options passed to `Regressor::train`, `BinaryClassifier::train`, and `MulticlassClassifier::train`.
+#[cfg(feature = "train")]
 #[derive(Clone, Debug)]
 pub struct TrainOptions {
 	/// If true, the model will include the loss on the training data after each epoch.
@@ -33,6 +36,7 @@ pub struct TrainOptions {
 	pub n_examples_per_batch: usize,
 }
 
+#[cfg(feature = "train")]
 impl Default for TrainOptions {
 	fn default() -> TrainOptions {
 		TrainOptions {
@@ -47,6 +51,7 @@ impl Default for TrainOptions {
 }
 
 /// The parameters in this struct control how to determine whether training should stop early after each epoch.
+#[cfg(feature = "train")]
 #[derive(Clone, Debug)]
 pub struct EarlyStoppingOptions {
 	/// This is the fraction of the dataset that is set aside to compute the early stopping metric.
@@ -58,10 +63,12 @@ pub struct EarlyStoppingOptions {
 }
 
 /// This is the training progress, which tracks the current epoch.
+#[cfg(feature = "train")]
 #[derive(Debug)]
 pub struct TrainProgress(pub tangram_util::progress_counter::ProgressCounter);
 
 /// This function splits the `features` and `labels` arrays into training and early stopping arrays, where the size of the early stopping stopping array will be `features.len() * early_stopping_fraction`.
+#[cfg(feature = "train")]
 fn train_early_stopping_split<'features, 'labels, Label>(
 	features: ArrayView2<'features, f32>,
 	labels: ArrayView1<'labels, Label>,
@@ -88,6 +95,7 @@ fn train_early_stopping_split<'features, 'labels, Label>(
 /**
 The `EarlyStoppingMonitor` keeps track of the values of an early stopping metric for each epoch, and if enough epochs have passed without a significant improvement in the metric, the `update()` function will return `true` to indicate that training should be stopped.
 */
+#[cfg(feature = "train")]
 struct EarlyStoppingMonitor {
 	threshold: f32,
 	epochs: usize,
@@ -95,6 +103,7 @@ struct EarlyStoppingMonitor {
 	previous_epoch_metric_value: Option<f32>,
 }
 
+#[cfg(feature = "train")]
 impl EarlyStoppingMonitor {
 	// Create a new `EarlyStoppingMonitor`.
 	pub fn new(threshold: f32, epochs: usize) -> EarlyStoppingMonitor {
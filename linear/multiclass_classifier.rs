@@ -1,14 +1,20 @@
-use super::{
-	shap::{compute_shap_values_for_example, ComputeShapValuesForExampleOutput},
-	train_early_stopping_split, EarlyStoppingMonitor, TrainOptions, TrainProgress,
-};
+use super::shap::{compute_shap_values_for_example, ComputeShapValuesForExampleOutput};
+#[cfg(feature = "train")]
+use super::{train_early_stopping_split, EarlyStoppingMonitor, TrainOptions, TrainProgress};
 use ndarray::prelude::*;
+#[cfg(feature = "train")]
 use num_traits::{clamp, ToPrimitive};
+#[cfg(feature = "train")]
 use rayon::prelude::*;
+#[cfg(feature = "train")]
 use std::num::NonZeroUsize;
+#[cfg(feature = "train")]
 use tangram_dataframe::prelude::*;
+#[cfg(feature = "train")]
 use tangram_metrics::{CrossEntropy, CrossEntropyInput, StreamingMetric};
-use tangram_util::{progress_counter::ProgressCounter, pzip, super_unsafe::SuperUnsafe, zip};
+use tangram_util::zip;
+#[cfg(feature = "train")]
+use tangram_util::{progress_counter::ProgressCounter, pzip, super_unsafe::SuperUnsafe};
 
 /// This struct describes a linear multiclass classifier model. You can train one by calling `MulticlassClassifier::train`.
 #[derive(Debug)]
@@ -22,6 +28,7 @@ pub struct MulticlassClassifier {
 }
 
 /// This struct is returned by `MulticlassClassifier::train`.
+#[cfg(feature = "train")]
 pub struct MulticlassClassifierTrainOutput {
 	/// This is the model you just trained.
 	pub model: MulticlassClassifier,
@@ -31,6 +38,7 @@ pub struct MulticlassClassifierTrainOutput {
 	pub feature_importances: Option<Vec<f32>>,
 }
 
+#[cfg(feature = "train")]
 impl MulticlassClassifier {
 	/// Train a linear multiclass classifier.
 	pub fn train(
@@ -246,7 +254,9 @@ impl MulticlassClassifier {
 		.0
 		.unwrap()
 	}
+}
 
+impl MulticlassClassifier {
 	/// Write predicted probabilities into `probabilities` for the input `features`.
 	pub fn predict(&self, features: ArrayView2<f32>, mut probabilities: ArrayViewMut2<f32>) {
 		for mut row in probabilities.axis_iter_mut(Axis(0)) {
@@ -263,12 +273,12 @@ impl MulticlassClassifier {
 		features
 			.axis_iter(Axis(0))
 			.map(|features| {
-				zip!(self.weights.axis_iter(Axis(0)), self.biases.view())
+				zip!(self.weights.axis_iter(Axis(1)), self.biases.view())
 					.map(|(weights, bias)| {
 						compute_shap_values_for_example(
 							features.as_slice().unwrap(),
 							*bias,
-							weights.as_slice().unwrap(),
+							&weights.to_vec(),
 							&self.means,
 						)
 					})
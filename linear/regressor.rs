@@ -1,12 +1,16 @@
-use super::{
-	shap::{compute_shap_values_for_example, ComputeShapValuesForExampleOutput},
-	train_early_stopping_split, EarlyStoppingMonitor, TrainOptions,
-};
+use super::shap::{compute_shap_values_for_example, ComputeShapValuesForExampleOutput};
+#[cfg(feature = "train")]
+use super::{train_early_stopping_split, EarlyStoppingMonitor, TrainOptions};
 use ndarray::prelude::*;
+#[cfg(feature = "train")]
 use num_traits::ToPrimitive;
+#[cfg(feature = "train")]
 use rayon::prelude::*;
+#[cfg(feature = "train")]
 use tangram_dataframe::prelude::*;
+#[cfg(feature = "train")]
 use tangram_metrics::{MeanSquaredError, StreamingMetric};
+#[cfg(feature = "train")]
 use tangram_util::{progress_counter::ProgressCounter, pzip, super_unsafe::SuperUnsafe, zip};
 
 /// This struct describes a linear regressor model. You can train one by calling `Regressor::train`.
@@ -21,6 +25,7 @@ pub struct Regressor {
 }
 
 /// This struct is returned by `Regressor::train`.
+#[cfg(feature = "train")]
 pub struct RegressorTrainOutput {
 	/// This is the model you just trained.
 	pub model: Regressor,
@@ -30,6 +35,7 @@ pub struct RegressorTrainOutput {
 	pub feature_importances: Option<Vec<f32>>,
 }
 
+#[cfg(feature = "train")]
 impl Regressor {
 	/// Train a linear regressor.
 	pub fn train(
@@ -193,7 +199,9 @@ impl Regressor {
 		.finalize()
 		.unwrap()
 	}
+}
 
+impl Regressor {
 	/// Write predictions into `predictions` for the input `features`.
 	pub fn predict(&self, features: ArrayView2<f32>, mut predictions: ArrayViewMut1<f32>) {
 		predictions.fill(self.bias);
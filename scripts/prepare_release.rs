@@ -210,6 +210,15 @@ pub async fn prepare_release() -> Result<()> {
 	.await;
 	fs::remove_file("dist/tangram.lib").await.unwrap();
 
+	// Create the wasm library archive.
+	sh("unzip dist/tangram-wasm -d dist");
+	sh(format!(
+		"zip -qj dist/tangram-wasm-{}.zip dist/tangram.wasm",
+		version
+	));
+	copy("dist/tangram.wasm", "languages/wasm/tangram.wasm").await;
+	fs::remove_file("dist/tangram.wasm").await.unwrap();
+
 	// Delete all the downloaded artifacts.
 	for artifact in artifacts.iter() {
 		let artifact_path = dist_path.join(&artifact.name);
@@ -126,6 +126,8 @@ fn generate_fake_prediction_mpg(target_value: &serde_json::Value) -> PredictOutp
 	let target_value = target_value + rng.gen::<f64>() * 5.0;
 	PredictOutput::Regression(RegressionPredictOutput {
 		value: target_value.to_f32().unwrap(),
+		lower: None,
+		upper: None,
 	})
 }
 
@@ -168,6 +170,8 @@ fn generate_fake_prediction_boston(target_value: &serde_json::Value) -> PredictO
 	let target_value = target_value + rng.gen::<f64>() * 5.0;
 	PredictOutput::Regression(RegressionPredictOutput {
 		value: target_value.to_f32().unwrap(),
+		lower: None,
+		upper: None,
 	})
 }
 
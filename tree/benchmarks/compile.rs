@@ -0,0 +1,96 @@
+use maplit::btreemap;
+use ndarray::prelude::*;
+use serde_json::json;
+use std::path::Path;
+use std::time::Instant;
+use tangram_dataframe::prelude::*;
+use tangram_util::zip;
+
+fn main() {
+	// Load the data.
+	let csv_file_path_train = Path::new("data/higgs_train.csv");
+	let csv_file_path_test = Path::new("data/higgs_test.csv");
+	let target_column_index = 0;
+	let signal_options = ["false", "true"].iter().map(ToString::to_string).collect();
+	let options = tangram_dataframe::FromCsvOptions {
+		column_types: Some(btreemap! {
+			"signal".to_owned() => DataFrameColumnType::Enum { options: signal_options },
+			"lepton_pt".to_owned() => DataFrameColumnType::Number,
+			"lepton_eta".to_owned() => DataFrameColumnType::Number,
+			"lepton_phi".to_owned() => DataFrameColumnType::Number,
+			"missing_energy_magnitude".to_owned() => DataFrameColumnType::Number,
+			"missing_energy_phi".to_owned() => DataFrameColumnType::Number,
+			"jet_1_pt".to_owned() => DataFrameColumnType::Number,
+			"jet_1_eta".to_owned() => DataFrameColumnType::Number,
+			"jet_1_phi".to_owned() => DataFrameColumnType::Number,
+			"jet_1_b_tag".to_owned() => DataFrameColumnType::Number,
+			"jet_2_pt".to_owned() => DataFrameColumnType::Number,
+			"jet_2_eta".to_owned() => DataFrameColumnType::Number,
+			"jet_2_phi".to_owned() => DataFrameColumnType::Number,
+			"jet_2_b_tag".to_owned() => DataFrameColumnType::Number,
+			"jet_3_pt".to_owned() => DataFrameColumnType::Number,
+			"jet_3_eta".to_owned() => DataFrameColumnType::Number,
+			"jet_3_phi".to_owned() => DataFrameColumnType::Number,
+			"jet_3_b_tag".to_owned() => DataFrameColumnType::Number,
+			"jet_4_pt".to_owned() => DataFrameColumnType::Number,
+			"jet_4_eta".to_owned() => DataFrameColumnType::Number,
+			"jet_4_phi".to_owned() => DataFrameColumnType::Number,
+			"jet_4_b_tag".to_owned() => DataFrameColumnType::Number,
+			"m_jj".to_owned() => DataFrameColumnType::Number,
+			"m_jjj".to_owned() => DataFrameColumnType::Number,
+			"m_lv".to_owned() => DataFrameColumnType::Number,
+			"m_jlv".to_owned() => DataFrameColumnType::Number,
+			"m_bb".to_owned() => DataFrameColumnType::Number,
+			"m_wbb".to_owned() => DataFrameColumnType::Number,
+			"m_wwbb".to_owned() => DataFrameColumnType::Number,
+		}),
+		..Default::default()
+	};
+	let mut features_train =
+		DataFrame::from_path(csv_file_path_train, options.clone(), |_| {}).unwrap();
+	let labels_train = features_train.columns_mut().remove(target_column_index);
+	let mut features_test =
+		DataFrame::from_path(csv_file_path_test, options.clone(), |_| {}).unwrap();
+	features_test.columns_mut().remove(target_column_index);
+	let labels_train = labels_train.as_enum().unwrap();
+
+	// Train the model.
+	let train_options = tangram_tree::TrainOptions {
+		binned_features_layout: tangram_tree::BinnedFeaturesLayout::RowMajor,
+		learning_rate: 0.1,
+		max_leaf_nodes: 255,
+		max_rounds: 100,
+		..Default::default()
+	};
+	let train_output = tangram_tree::BinaryClassifier::train(
+		features_train.view(),
+		labels_train.view(),
+		&train_options,
+		&mut |_| {},
+	);
+	let model = train_output.model;
+	let compiled_model = model.compile();
+
+	// Make predictions on the test data with the trees and with the compiled model.
+	let features_test = features_test.to_rows();
+	let mut probabilities = Array::zeros(features_test.nrows());
+	let start = Instant::now();
+	model.predict(features_test.view(), probabilities.view_mut());
+	let tree_duration = start.elapsed();
+	let mut compiled_probabilities = Array::zeros(features_test.nrows());
+	let start = Instant::now();
+	compiled_model.predict(features_test.view(), compiled_probabilities.view_mut());
+	let compiled_duration = start.elapsed();
+
+	// The compiled model must make the same predictions.
+	let max_difference = zip!(probabilities.iter(), compiled_probabilities.iter())
+		.map(|(a, b)| (a - b).abs())
+		.fold(0.0f32, f32::max);
+
+	let output = json!({
+		"tree_duration": format!("{:?}", tree_duration),
+		"compiled_duration": format!("{:?}", compiled_duration),
+		"max_difference": max_difference,
+	});
+	println!("{}", output);
+}
@@ -1,16 +1,29 @@
 use crate::{
-	shap::{compute_shap_values_for_example, ComputeShapValuesForExampleOutput},
+	shap::{
+		compute_shap_interaction_values_for_example, compute_shap_values_for_example,
+		ComputeShapInteractionValuesForExampleOutput, ComputeShapValuesForExampleOutput,
+	},
+	Tree,
+};
+#[cfg(feature = "train")]
+use crate::{
 	train::{train, Task, TrainOutput},
 	train_tree::TrainTree,
-	TrainOptions, TrainProgress, Tree,
+	TrainOptions, TrainProgress,
 };
 use ndarray::prelude::*;
+#[cfg(feature = "train")]
 use num_traits::{clamp, ToPrimitive};
+#[cfg(any(feature = "train", not(target_arch = "wasm32")))]
 use rayon::prelude::*;
+#[cfg(feature = "train")]
 use std::num::NonZeroUsize;
 use std::ops::Neg;
 use tangram_dataframe::prelude::*;
-use tangram_util::{pzip, zip};
+#[cfg(any(feature = "train", not(target_arch = "wasm32")))]
+use tangram_util::pzip;
+#[cfg(any(feature = "train", target_arch = "wasm32"))]
+use tangram_util::zip;
 
 /// `BinaryClassifier`s predict binary target values, for example whether a patient has heart disease or not.
 #[derive(Debug)]
@@ -22,6 +35,7 @@ pub struct BinaryClassifier {
 }
 
 /// This struct is returned by `BinaryClassifier::train`.
+#[cfg(feature = "train")]
 #[derive(Debug)]
 pub struct BinaryClassifierTrainOutput {
 	/// This is the model you just trained.
@@ -34,6 +48,7 @@ pub struct BinaryClassifierTrainOutput {
 
 impl BinaryClassifier {
 	/// Train a binary classifier.
+	#[cfg(feature = "train")]
 	pub fn train(
 		features: DataFrameView,
 		labels: EnumDataFrameColumnView,
@@ -63,15 +78,28 @@ impl BinaryClassifier {
 		probabilities.fill(self.bias);
 		let probabilities = probabilities.as_slice_mut().unwrap();
 		for tree in self.trees.iter() {
+			// Rayon cannot spawn threads in WebAssembly, so make predictions on the current thread.
+			#[cfg(not(target_arch = "wasm32"))]
 			pzip!(features.axis_iter(Axis(0)), probabilities.par_iter_mut()).for_each(
 				|(example, logit)| {
 					*logit += tree.predict(example.as_slice().unwrap());
 				},
 			);
+			#[cfg(target_arch = "wasm32")]
+			zip!(features.axis_iter(Axis(0)), probabilities.iter_mut()).for_each(
+				|(example, logit)| {
+					*logit += tree.predict(example.as_slice().unwrap());
+				},
+			);
 		}
+		#[cfg(not(target_arch = "wasm32"))]
 		probabilities.par_iter_mut().for_each(|probability| {
 			*probability = 1.0 / (probability.neg().exp() + 1.0);
 		});
+		#[cfg(target_arch = "wasm32")]
+		probabilities.iter_mut().for_each(|probability| {
+			*probability = 1.0 / (probability.neg().exp() + 1.0);
+		});
 	}
 
 	/// Compute SHAP values.
@@ -87,9 +115,28 @@ impl BinaryClassifier {
 			})
 			.collect()
 	}
+
+	/// Compute SHAP interaction values.
+	pub fn compute_feature_interactions(
+		&self,
+		features: ArrayView2<DataFrameValue>,
+	) -> Vec<ComputeShapInteractionValuesForExampleOutput> {
+		let trees = ArrayView1::from_shape(self.trees.len(), &self.trees).unwrap();
+		features
+			.axis_iter(Axis(0))
+			.map(|features| {
+				compute_shap_interaction_values_for_example(
+					features.as_slice().unwrap(),
+					trees,
+					self.bias,
+				)
+			})
+			.collect()
+	}
 }
 
 /// This function is used by the common train function to update the logits after each tree is trained for binary classification.
+#[cfg(feature = "train")]
 pub fn update_logits(
 	trees_for_round: &[TrainTree],
 	binned_features: ArrayView2<DataFrameValue>,
@@ -105,6 +152,7 @@ pub fn update_logits(
 }
 
 /// This function is used by the common train function to compute the loss after each tree is trained for binary classification.
+#[cfg(feature = "train")]
 pub fn compute_loss(logits: ArrayView2<f32>, labels: ArrayView1<Option<NonZeroUsize>>) -> f32 {
 	let mut total = 0.0;
 	for (label, logit) in zip!(labels.iter(), logits) {
@@ -118,6 +166,7 @@ pub fn compute_loss(logits: ArrayView2<f32>, labels: ArrayView1<Option<NonZeroUs
 }
 
 /// This function is used by the common train function to compute the biases for binary classification.
+#[cfg(feature = "train")]
 pub fn compute_biases(labels: ArrayView1<Option<NonZeroUsize>>) -> Array1<f32> {
 	let pos_count = labels
 		.iter()
@@ -129,6 +178,7 @@ pub fn compute_biases(labels: ArrayView1<Option<NonZeroUsize>>) -> Array1<f32> {
 }
 
 /// This function is used by the common train function to compute the gradients and hessian after each round.
+#[cfg(feature = "train")]
 pub fn compute_gradients_and_hessians(
 	// (n_examples)
 	gradients: &mut [f32],
@@ -152,6 +202,7 @@ pub fn compute_gradients_and_hessians(
 	);
 }
 
+#[cfg(feature = "train")]
 fn sigmoid(value: f32) -> f32 {
 	1.0 / (value.neg().exp() + 1.0)
 }
@@ -0,0 +1,764 @@
+/*!
+This module lowers trained tree models into a flattened layout that is faster to evaluate than `Tree::predict`, and can also emit standalone Rust or C source code for a model.
+
+In the flattened layout, the nodes of all the trees in a model are stored in a single contiguous `Vec<CompiledNode>`, one tree after another. Within each tree, the nodes are stored in preorder, so a branch's left child always immediately follows it and only the index of its right child needs to be stored. Each node is 16 bytes, so four fit in a cache line, and the walk down the left side of a tree reads memory sequentially. Predictions are made in blocks of examples, evaluating each tree for every example in the block before moving on to the next tree, so a tree's nodes stay in cache while they are in use.
+
+Compiled models take the same features as the models they were compiled from, and produce the same predictions.
+*/
+
+use crate::{
+	BinaryClassifier, BranchNode, BranchSplit, BranchSplitContinuous, BranchSplitDiscrete,
+	LeafNode, MulticlassClassifier, Node, Regressor, SplitDirection, Tree,
+};
+use ndarray::prelude::*;
+use num_traits::ToPrimitive;
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
+use std::{fmt::Write, ops::Neg};
+use tangram_dataframe::DataFrameValue;
+#[cfg(not(target_arch = "wasm32"))]
+use tangram_util::pzip;
+use tangram_util::zip;
+
+/// This is the number of examples each tree is evaluated for before moving on to the next tree.
+const BLOCK_SIZE: usize = 64;
+
+/// This is the value of `CompiledNode::feature_index` for leaves.
+const LEAF: u32 = u32::MAX;
+
+/// This is the value of `CompiledNode::directions_offset` for continuous splits.
+const CONTINUOUS: u32 = u32::MAX;
+
+/// A `CompiledNode` is a branch or leaf in the flattened layout.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct CompiledNode {
+	/// For branches, this is the index of the feature to get the value for. For leaves, it is `u32::MAX`.
+	pub feature_index: u32,
+	/// For branches, this is the index of the right child. The left child is the next node.
+	pub right_child_index: u32,
+	/// For continuous splits, this is the split value. For leaves, this is the value to output.
+	pub value: f32,
+	/// For discrete splits, this is the index of the first word of the bitset in `CompiledTrees::directions` that has a set bit for each enum option that is sent left. For continuous splits, it is `u32::MAX`.
+	pub directions_offset: u32,
+}
+
+/// `CompiledTrees` holds the trees of a compiled model in the flattened layout.
+#[derive(Debug)]
+pub struct CompiledTrees {
+	/// These are the nodes of all the trees, one tree after another.
+	pub nodes: Vec<CompiledNode>,
+	/// These are the indexes of the root node of each tree in `nodes`.
+	pub roots: Vec<u32>,
+	/// These are the bitsets for the discrete splits.
+	pub directions: Vec<u64>,
+}
+
+/// A `CompiledRegressor` is a `Regressor` lowered into the flattened layout. Create one with `Regressor::compile`.
+#[derive(Debug)]
+pub struct CompiledRegressor {
+	pub bias: f32,
+	pub trees: CompiledTrees,
+}
+
+/// A `CompiledBinaryClassifier` is a `BinaryClassifier` lowered into the flattened layout. Create one with `BinaryClassifier::compile`.
+#[derive(Debug)]
+pub struct CompiledBinaryClassifier {
+	pub bias: f32,
+	pub trees: CompiledTrees,
+}
+
+/// A `CompiledMulticlassClassifier` is a `MulticlassClassifier` lowered into the flattened layout. Create one with `MulticlassClassifier::compile`.
+#[derive(Debug)]
+pub struct CompiledMulticlassClassifier {
+	pub biases: Vec<f32>,
+	/// The trees are stored with shape (n_rounds, n_classes), like in `MulticlassClassifier`.
+	pub trees: CompiledTrees,
+	pub n_classes: usize,
+}
+
+impl Regressor {
+	/// Lower this model into the flattened layout for faster predictions.
+	pub fn compile(&self) -> CompiledRegressor {
+		CompiledRegressor {
+			bias: self.bias,
+			trees: CompiledTrees::new(&self.trees),
+		}
+	}
+}
+
+impl BinaryClassifier {
+	/// Lower this model into the flattened layout for faster predictions.
+	pub fn compile(&self) -> CompiledBinaryClassifier {
+		CompiledBinaryClassifier {
+			bias: self.bias,
+			trees: CompiledTrees::new(&self.trees),
+		}
+	}
+}
+
+impl MulticlassClassifier {
+	/// Lower this model into the flattened layout for faster predictions.
+	pub fn compile(&self) -> CompiledMulticlassClassifier {
+		CompiledMulticlassClassifier {
+			biases: self.biases.clone(),
+			trees: CompiledTrees::new(&self.trees),
+			n_classes: self.n_classes,
+		}
+	}
+}
+
+impl CompiledTrees {
+	fn new(trees: &[Tree]) -> CompiledTrees {
+		let mut compiled_trees = CompiledTrees {
+			nodes: Vec::new(),
+			roots: Vec::with_capacity(trees.len()),
+			directions: Vec::new(),
+		};
+		for tree in trees {
+			compiled_trees
+				.roots
+				.push(compiled_trees.nodes.len().to_u32().unwrap());
+			compiled_trees.push_tree(tree);
+		}
+		compiled_trees
+	}
+
+	/// Push the nodes of `tree` in preorder.
+	fn push_tree(&mut self, tree: &Tree) {
+		// Each entry on the stack is the index of a node in `tree` and, for right children, the index of the compiled parent whose `right_child_index` should be set to this node.
+		let mut stack = vec![(0, None)];
+		while let Some((node_index, parent_index)) = stack.pop() {
+			let compiled_node_index = self.nodes.len();
+			if let Some(parent_index) = parent_index {
+				let parent: &mut CompiledNode = &mut self.nodes[parent_index];
+				parent.right_child_index = compiled_node_index.to_u32().unwrap();
+			}
+			match &tree.nodes[node_index] {
+				Node::Leaf(LeafNode { value, .. }) => {
+					self.nodes.push(CompiledNode {
+						feature_index: LEAF,
+						right_child_index: 0,
+						value: value.to_f32().unwrap(),
+						directions_offset: 0,
+					});
+				}
+				Node::Branch(BranchNode {
+					left_child_index,
+					right_child_index,
+					split,
+					..
+				}) => {
+					let compiled_node = match split {
+						BranchSplit::Continuous(BranchSplitContinuous {
+							feature_index,
+							split_value,
+							..
+						}) => CompiledNode {
+							feature_index: feature_index.to_u32().unwrap(),
+							right_child_index: 0,
+							value: *split_value,
+							directions_offset: CONTINUOUS,
+						},
+						BranchSplit::Discrete(BranchSplitDiscrete {
+							feature_index,
+							directions,
+						}) => {
+							let directions_offset = self.directions.len().to_u32().unwrap();
+							self.directions
+								.extend(std::iter::repeat(0).take(n_words(directions.len())));
+							for (bin_index, direction) in directions.iter().enumerate() {
+								if *direction == SplitDirection::Left {
+									self.directions[directions_offset as usize + bin_index / 64] |=
+										1 << (bin_index % 64);
+								}
+							}
+							CompiledNode {
+								feature_index: feature_index.to_u32().unwrap(),
+								right_child_index: 0,
+								value: 0.0,
+								directions_offset,
+							}
+						}
+					};
+					self.nodes.push(compiled_node);
+					// Push the right child first so the left child is popped and pushed immediately after this node.
+					stack.push((*right_child_index, Some(compiled_node_index)));
+					stack.push((*left_child_index, None));
+				}
+			}
+		}
+	}
+
+	/// Evaluate the tree whose root is at index `root` for a single example.
+	#[inline(always)]
+	fn evaluate(&self, root: u32, features: &[f32]) -> f32 {
+		let mut node_index = root as usize;
+		loop {
+			// The indexes are valid because they were computed in `push_tree`.
+			let node = unsafe { self.nodes.get_unchecked(node_index) };
+			if node.feature_index == LEAF {
+				return node.value;
+			}
+			let feature = unsafe { *features.get_unchecked(node.feature_index as usize) };
+			let left = if node.directions_offset == CONTINUOUS {
+				feature <= node.value
+			} else {
+				let bin_index = feature as usize;
+				self.directions
+					.get(node.directions_offset as usize + bin_index / 64)
+					.map(|word| word & (1 << (bin_index % 64)) != 0)
+					.unwrap_or(false)
+			};
+			node_index = if left {
+				node_index + 1
+			} else {
+				node.right_child_index as usize
+			};
+		}
+	}
+
+	/// Add the output of the trees at `roots` to the logits for each example in a block. `features` has shape (n_examples, n_features), and `logits` has shape (n_examples, n_outputs). Tree `i` contributes to output `i % n_outputs`.
+	fn predict_block(&self, features: ArrayView2<DataFrameValue>, mut logits: ArrayViewMut2<f32>) {
+		let n_outputs = logits.ncols();
+		let features = flatten_features(features);
+		for (tree_index, root) in self.roots.iter().enumerate() {
+			let output_index = tree_index % n_outputs;
+			for (example, logit) in
+				zip!(features.axis_iter(Axis(0)), logits.column_mut(output_index))
+			{
+				*logit += self.evaluate(*root, example.as_slice().unwrap());
+			}
+		}
+	}
+
+	/// Compute the logits for each example, processing the examples in blocks in parallel.
+	fn predict(&self, features: ArrayView2<DataFrameValue>, mut logits: ArrayViewMut2<f32>) {
+		// Rayon cannot spawn threads in WebAssembly, so make predictions on the current thread.
+		#[cfg(not(target_arch = "wasm32"))]
+		pzip!(
+			features.axis_chunks_iter(Axis(0), BLOCK_SIZE),
+			logits.axis_chunks_iter_mut(Axis(0), BLOCK_SIZE),
+		)
+		.for_each(|(features, logits)| self.predict_block(features, logits));
+		#[cfg(target_arch = "wasm32")]
+		zip!(
+			features.axis_chunks_iter(Axis(0), BLOCK_SIZE),
+			logits.axis_chunks_iter_mut(Axis(0), BLOCK_SIZE),
+		)
+		.for_each(|(features, logits)| self.predict_block(features, logits));
+	}
+}
+
+impl CompiledRegressor {
+	/// Make predictions.
+	pub fn predict(
+		&self,
+		features: ArrayView2<DataFrameValue>,
+		mut predictions: ArrayViewMut1<f32>,
+	) {
+		predictions.fill(self.bias);
+		let n_examples = predictions.len();
+		let logits = predictions.into_shape((n_examples, 1)).unwrap();
+		self.trees.predict(features, logits);
+	}
+
+	/// Generate Rust source for a function named `name` that makes a prediction for a single example. It takes the features as `f32`s, with enum features as the index of their option and 0 for invalid values.
+	pub fn to_rust_source(&self, name: &str) -> String {
+		to_rust_source(name, &self.trees, &[self.bias], Activation::None)
+	}
+
+	/// Generate C source for a function named `name` that makes a prediction for a single example. It takes the features as `float`s, with enum features as the index of their option and 0 for invalid values.
+	pub fn to_c_source(&self, name: &str) -> String {
+		to_c_source(name, &self.trees, &[self.bias], Activation::None)
+	}
+}
+
+impl CompiledBinaryClassifier {
+	/// Make predictions.
+	pub fn predict(
+		&self,
+		features: ArrayView2<DataFrameValue>,
+		mut probabilities: ArrayViewMut1<f32>,
+	) {
+		probabilities.fill(self.bias);
+		let n_examples = probabilities.len();
+		let mut logits = probabilities.into_shape((n_examples, 1)).unwrap();
+		self.trees.predict(features, logits.view_mut());
+		for probability in logits.iter_mut() {
+			*probability = 1.0 / (probability.neg().exp() + 1.0);
+		}
+	}
+
+	/// Generate Rust source for a function named `name` that computes the probability of the positive class for a single example. It takes the features as `f32`s, with enum features as the index of their option and 0 for invalid values.
+	pub fn to_rust_source(&self, name: &str) -> String {
+		to_rust_source(name, &self.trees, &[self.bias], Activation::Sigmoid)
+	}
+
+	/// Generate C source for a function named `name` that computes the probability of the positive class for a single example. It takes the features as `float`s, with enum features as the index of their option and 0 for invalid values.
+	pub fn to_c_source(&self, name: &str) -> String {
+		to_c_source(name, &self.trees, &[self.bias], Activation::Sigmoid)
+	}
+}
+
+impl CompiledMulticlassClassifier {
+	/// Make predictions.
+	pub fn predict(
+		&self,
+		features: ArrayView2<DataFrameValue>,
+		mut probabilities: ArrayViewMut2<f32>,
+	) {
+		let biases = ArrayView1::from(self.biases.as_slice());
+		for mut logits in probabilities.axis_iter_mut(Axis(0)) {
+			logits.assign(&biases);
+		}
+		self.trees.predict(features, probabilities.view_mut());
+		for mut logits in probabilities.axis_iter_mut(Axis(0)) {
+			softmax(logits.as_slice_mut().unwrap());
+		}
+	}
+
+	/// Generate Rust source for a function named `name` that computes the probability of each class for a single example. It takes the features as `f32`s, with enum features as the index of their option and 0 for invalid values.
+	pub fn to_rust_source(&self, name: &str) -> String {
+		to_rust_source(name, &self.trees, &self.biases, Activation::Softmax)
+	}
+
+	/// Generate C source for a function named `name` that computes the probability of each class for a single example. It takes the features as `float`s, with enum features as the index of their option and 0 for invalid values.
+	pub fn to_c_source(&self, name: &str) -> String {
+		to_c_source(name, &self.trees, &self.biases, Activation::Softmax)
+	}
+}
+
+/// Convert a block of features to `f32`s. Enum features become the index of their option, with 0 for invalid values, which is the bin index used by discrete splits.
+fn flatten_features(features: ArrayView2<DataFrameValue>) -> Array2<f32> {
+	features.map(|feature| match feature {
+		DataFrameValue::Number(value) => *value,
+		DataFrameValue::Enum(value) => value
+			.map(|value| value.get())
+			.unwrap_or(0)
+			.to_f32()
+			.unwrap(),
+		_ => unreachable!(),
+	})
+}
+
+fn n_words(n_bits: usize) -> usize {
+	(n_bits + 63) / 64
+}
+
+fn softmax(logits: &mut [f32]) {
+	let max = logits.iter().fold(std::f32::MIN, |a, &b| f32::max(a, b));
+	for logit in logits.iter_mut() {
+		*logit = (*logit - max).exp();
+	}
+	let sum = logits.iter().sum::<f32>();
+	for logit in logits.iter_mut() {
+		*logit /= sum;
+	}
+}
+
+#[derive(Clone, Copy)]
+enum Activation {
+	None,
+	Sigmoid,
+	Softmax,
+}
+
+/**
+Generate Rust source for a function named `name` that evaluates the trees with nested `if` expressions. The function takes a slice with the value of each feature as an `f32`, where enum features are the index of their option, with 0 for invalid values. For regressors and binary classifiers, it returns an `f32`. For multiclass classifiers, it returns an array with the probability of each class.
+*/
+fn to_rust_source(
+	name: &str,
+	trees: &CompiledTrees,
+	biases: &[f32],
+	activation: Activation,
+) -> String {
+	let mut source = String::new();
+	let n_outputs = biases.len();
+	let offsets = directions_offsets(trees);
+	let return_type = match activation {
+		Activation::None | Activation::Sigmoid => "f32".to_owned(),
+		Activation::Softmax => format!("[f32; {}]", n_outputs),
+	};
+	writeln!(source, "#[allow(clippy::all)]").unwrap();
+	writeln!(
+		source,
+		"pub fn {}(features: &[f32]) -> {} {{",
+		name, return_type
+	)
+	.unwrap();
+	writeln!(source, "\tlet mut logits = {};", rust_f32_array(biases)).unwrap();
+	for (tree_index, root) in trees.roots.iter().enumerate() {
+		write!(source, "\tlogits[{}] += ", tree_index % n_outputs).unwrap();
+		write_rust_node(&mut source, trees, &offsets, *root as usize, 1);
+		writeln!(source, ";").unwrap();
+	}
+	match activation {
+		Activation::None => writeln!(source, "\tlogits[0]").unwrap(),
+		Activation::Sigmoid => writeln!(source, "\t1.0 / ((-logits[0]).exp() + 1.0)").unwrap(),
+		Activation::Softmax => {
+			writeln!(
+				source,
+				"\tlet max = logits.iter().fold(f32::MIN, |a, &b| a.max(b));"
+			)
+			.unwrap();
+			writeln!(source, "\tlet mut sum = 0.0;").unwrap();
+			writeln!(source, "\tfor logit in logits.iter_mut() {{").unwrap();
+			writeln!(source, "\t\t*logit = (*logit - max).exp();").unwrap();
+			writeln!(source, "\t\tsum += *logit;").unwrap();
+			writeln!(source, "\t}}").unwrap();
+			writeln!(source, "\tfor logit in logits.iter_mut() {{").unwrap();
+			writeln!(source, "\t\t*logit /= sum;").unwrap();
+			writeln!(source, "\t}}").unwrap();
+			writeln!(source, "\tlogits").unwrap();
+		}
+	}
+	writeln!(source, "}}").unwrap();
+	source
+}
+
+fn write_rust_node(
+	source: &mut String,
+	trees: &CompiledTrees,
+	offsets: &[usize],
+	node_index: usize,
+	depth: usize,
+) {
+	let node = trees.nodes[node_index];
+	if node.feature_index == LEAF {
+		write!(source, "{}", rust_f32(node.value)).unwrap();
+		return;
+	}
+	let condition = if node.directions_offset == CONTINUOUS {
+		format!(
+			"features[{}] <= {}",
+			node.feature_index,
+			rust_f32(node.value)
+		)
+	} else {
+		let left_bins = left_bins(trees, offsets, &node)
+			.iter()
+			.map(|bin_index| bin_index.to_string())
+			.collect::<Vec<_>>();
+		if left_bins.is_empty() {
+			"false".to_owned()
+		} else {
+			format!(
+				"matches!(features[{}] as usize, {})",
+				node.feature_index,
+				left_bins.join(" | ")
+			)
+		}
+	};
+	let indent = "\t".repeat(depth + 1);
+	writeln!(source, "if {} {{", condition).unwrap();
+	source.push_str(&indent);
+	write_rust_node(source, trees, offsets, node_index + 1, depth + 1);
+	writeln!(source).unwrap();
+	write!(source, "{}}} else {{\n{}", &indent[1..], indent).unwrap();
+	write_rust_node(
+		source,
+		trees,
+		offsets,
+		node.right_child_index as usize,
+		depth + 1,
+	);
+	write!(source, "\n{}}}", &indent[1..]).unwrap();
+}
+
+/**
+Generate C source for a function named `name` that evaluates the trees with nested conditional expressions. The function takes a pointer to the value of each feature as a `float`, where enum features are the index of their option, with 0 or NaN for invalid values. For regressors and binary classifiers, it returns a `float`. For multiclass classifiers, it takes a second pointer to `n_classes` floats that it writes the probability of each class to.
+*/
+fn to_c_source(
+	name: &str,
+	trees: &CompiledTrees,
+	biases: &[f32],
+	activation: Activation,
+) -> String {
+	let mut source = String::new();
+	let n_outputs = biases.len();
+	let offsets = directions_offsets(trees);
+	writeln!(source, "#include <math.h>").unwrap();
+	writeln!(source).unwrap();
+	match activation {
+		Activation::None | Activation::Sigmoid => {
+			writeln!(source, "float {}(const float* features) {{", name).unwrap();
+		}
+		Activation::Softmax => {
+			writeln!(
+				source,
+				"void {}(const float* features, float* probabilities) {{",
+				name
+			)
+			.unwrap();
+		}
+	}
+	writeln!(
+		source,
+		"\tfloat logits[{}] = {{{}}};",
+		n_outputs,
+		biases
+			.iter()
+			.map(|bias| c_float(*bias))
+			.collect::<Vec<_>>()
+			.join(", ")
+	)
+	.unwrap();
+	for (tree_index, root) in trees.roots.iter().enumerate() {
+		write!(source, "\tlogits[{}] += ", tree_index % n_outputs).unwrap();
+		write_c_node(&mut source, trees, &offsets, *root as usize, 1);
+		writeln!(source, ";").unwrap();
+	}
+	match activation {
+		Activation::None => writeln!(source, "\treturn logits[0];").unwrap(),
+		Activation::Sigmoid => {
+			writeln!(source, "\treturn 1.0f / (expf(-logits[0]) + 1.0f);").unwrap()
+		}
+		Activation::Softmax => {
+			writeln!(source, "\tfloat max = logits[0];").unwrap();
+			writeln!(source, "\tfor (int i = 1; i < {}; i++) {{", n_outputs).unwrap();
+			writeln!(source, "\t\tif (logits[i] > max) {{").unwrap();
+			writeln!(source, "\t\t\tmax = logits[i];").unwrap();
+			writeln!(source, "\t\t}}").unwrap();
+			writeln!(source, "\t}}").unwrap();
+			writeln!(source, "\tfloat sum = 0.0f;").unwrap();
+			writeln!(source, "\tfor (int i = 0; i < {}; i++) {{", n_outputs).unwrap();
+			writeln!(source, "\t\tprobabilities[i] = expf(logits[i] - max);").unwrap();
+			writeln!(source, "\t\tsum += probabilities[i];").unwrap();
+			writeln!(source, "\t}}").unwrap();
+			writeln!(source, "\tfor (int i = 0; i < {}; i++) {{", n_outputs).unwrap();
+			writeln!(source, "\t\tprobabilities[i] /= sum;").unwrap();
+			writeln!(source, "\t}}").unwrap();
+		}
+	}
+	writeln!(source, "}}").unwrap();
+	source
+}
+
+fn write_c_node(
+	source: &mut String,
+	trees: &CompiledTrees,
+	offsets: &[usize],
+	node_index: usize,
+	depth: usize,
+) {
+	let node = trees.nodes[node_index];
+	if node.feature_index == LEAF {
+		write!(source, "{}", c_float(node.value)).unwrap();
+		return;
+	}
+	let condition = if node.directions_offset == CONTINUOUS {
+		format!(
+			"features[{}] <= {}",
+			node.feature_index,
+			c_float(node.value)
+		)
+	} else {
+		let left_bins = left_bins(trees, offsets, &node);
+		let comparisons = left_bins
+			.iter()
+			.map(|bin_index| format!("(int)features[{}] == {}", node.feature_index, bin_index))
+			.collect::<Vec<_>>();
+		if comparisons.is_empty() {
+			"0".to_owned()
+		} else {
+			// Casting NaN to an int is undefined behavior in C, so check for it first and treat it as an invalid value, which is bin 0.
+			let nan_is_left = left_bins.first() == Some(&0);
+			format!(
+				"(isnan(features[{}]) ? {} : ({}))",
+				node.feature_index,
+				if nan_is_left { 1 } else { 0 },
+				comparisons.join(" || ")
+			)
+		}
+	};
+	let indent = "\t".repeat(depth + 1);
+	write!(source, "({}\n{}? ", condition, indent).unwrap();
+	write_c_node(source, trees, offsets, node_index + 1, depth + 1);
+	write!(source, "\n{}: ", indent).unwrap();
+	write_c_node(
+		source,
+		trees,
+		offsets,
+		node.right_child_index as usize,
+		depth + 1,
+	);
+	write!(source, ")").unwrap();
+}
+
+/// Retrieve the bin indexes that a discrete split sends left. `offsets` are the distinct values of `directions_offset` for the discrete splits, in ascending order. The bitsets are stored one after another, so each ends where the next one starts.
+fn left_bins(trees: &CompiledTrees, offsets: &[usize], node: &CompiledNode) -> Vec<usize> {
+	let start = node.directions_offset as usize;
+	let end = match offsets.binary_search(&start) {
+		Ok(index) => offsets
+			.get(index + 1)
+			.cloned()
+			.unwrap_or(trees.directions.len()),
+		Err(_) => start,
+	};
+	let mut left_bins = Vec::new();
+	for (word_index, word) in trees.directions[start..end].iter().enumerate() {
+		for bit in 0..64 {
+			if word & (1 << bit) != 0 {
+				left_bins.push(word_index * 64 + bit);
+			}
+		}
+	}
+	left_bins
+}
+
+/// Retrieve the distinct offsets of the discrete splits' bitsets in ascending order, for use with `left_bins`.
+fn directions_offsets(trees: &CompiledTrees) -> Vec<usize> {
+	let mut offsets = trees
+		.nodes
+		.iter()
+		.filter(|node| node.feature_index != LEAF && node.directions_offset != CONTINUOUS)
+		.map(|node| node.directions_offset as usize)
+		.collect::<Vec<_>>();
+	offsets.sort_unstable();
+	offsets.dedup();
+	offsets
+}
+
+/// Format an `f32` as a Rust literal that round trips exactly.
+fn rust_f32(value: f32) -> String {
+	if value.is_finite() {
+		format!("{:?}f32", value)
+	} else if value.is_nan() {
+		"f32::NAN".to_owned()
+	} else if value > 0.0 {
+		"f32::INFINITY".to_owned()
+	} else {
+		"f32::NEG_INFINITY".to_owned()
+	}
+}
+
+fn rust_f32_array(values: &[f32]) -> String {
+	format!(
+		"[{}]",
+		values
+			.iter()
+			.map(|value| rust_f32(*value))
+			.collect::<Vec<_>>()
+			.join(", ")
+	)
+}
+
+/// Format an `f32` as a C literal that round trips exactly.
+fn c_float(value: f32) -> String {
+	if value.is_finite() {
+		let literal = format!("{:?}", value);
+		if literal.contains('.') || literal.contains('e') {
+			format!("{}f", literal)
+		} else {
+			format!("{}.0f", literal)
+		}
+	} else if value.is_nan() {
+		"NAN".to_owned()
+	} else if value > 0.0 {
+		"INFINITY".to_owned()
+	} else {
+		"-INFINITY".to_owned()
+	}
+}
+
+#[cfg(feature = "train")]
+#[test]
+fn test_compiled_predictions_match() {
+	use crate::TrainOptions;
+	use std::num::NonZeroUsize;
+	use tangram_dataframe::prelude::*;
+	// Create a dataset with a number feature and an enum feature, both with invalid values, so the trees have continuous and discrete splits.
+	let n_examples = 1000;
+	let number_feature = (0..n_examples)
+		.map(|i| {
+			if i % 17 == 0 {
+				std::f32::NAN
+			} else {
+				((i * 37) % 101).to_f32().unwrap() / 10.0
+			}
+		})
+		.collect::<Vec<_>>();
+	let enum_feature = (0..n_examples)
+		.map(|i| {
+			if i % 13 == 0 {
+				None
+			} else {
+				NonZeroUsize::new((i * 7) % 5 + 1)
+			}
+		})
+		.collect::<Vec<_>>();
+	let enum_feature_options = ["a", "b", "c", "d", "e"]
+		.iter()
+		.map(ToString::to_string)
+		.collect();
+	let features = DataFrame::from_columns(vec![
+		DataFrameColumn::Number(NumberDataFrameColumn::new(
+			Some("number".to_owned()),
+			number_feature.clone(),
+		)),
+		DataFrameColumn::Enum(EnumDataFrameColumn::new(
+			Some("enum".to_owned()),
+			enum_feature_options,
+			enum_feature.clone(),
+		)),
+	]);
+	let scores = zip!(number_feature.iter(), enum_feature.iter())
+		.map(|(number, option)| {
+			let number = if number.is_nan() { 5.0 } else { *number };
+			let option = option.map(|option| option.get()).unwrap_or(0);
+			number + option.to_f32().unwrap() * 2.0
+		})
+		.collect::<Vec<_>>();
+	let train_options = TrainOptions {
+		max_rounds: 20,
+		..Default::default()
+	};
+	let rows = features.to_rows();
+
+	// Regressor.
+	let labels = NumberDataFrameColumn::new(None, scores.clone());
+	let model = Regressor::train(features.view(), labels.view(), &train_options, &mut |_| {}).model;
+	let mut expected = Array::zeros(n_examples);
+	model.predict(rows.view(), expected.view_mut());
+	let compiled_model = model.compile();
+	assert!(!compiled_model.trees.directions.is_empty());
+	let mut actual = Array::zeros(n_examples);
+	compiled_model.predict(rows.view(), actual.view_mut());
+	assert_eq!(actual, expected);
+
+	// Binary classifier.
+	let labels = EnumDataFrameColumn::new(
+		None,
+		vec!["false".to_owned(), "true".to_owned()],
+		scores
+			.iter()
+			.map(|score| NonZeroUsize::new(if *score > 10.0 { 2 } else { 1 }))
+			.collect(),
+	);
+	let model =
+		BinaryClassifier::train(features.view(), labels.view(), &train_options, &mut |_| {}).model;
+	let mut expected = Array::zeros(n_examples);
+	model.predict(rows.view(), expected.view_mut());
+	let mut actual = Array::zeros(n_examples);
+	model.compile().predict(rows.view(), actual.view_mut());
+	assert_eq!(actual, expected);
+
+	// Multiclass classifier.
+	let labels = EnumDataFrameColumn::new(
+		None,
+		vec!["low".to_owned(), "medium".to_owned(), "high".to_owned()],
+		scores
+			.iter()
+			.map(|score| NonZeroUsize::new((*score / 6.0).to_usize().unwrap().min(2) + 1))
+			.collect(),
+	);
+	let model =
+		MulticlassClassifier::train(features.view(), labels.view(), &train_options, &mut |_| {})
+			.model;
+	let mut expected = Array::zeros((n_examples, 3));
+	model.predict(rows.view(), expected.view_mut());
+	let mut actual = Array::zeros((n_examples, 3));
+	model.compile().predict(rows.view(), actual.view_mut());
+	assert_eq!(actual, expected);
+}
@@ -5,25 +5,41 @@ For an example of regression, see `benchmarks/boston.rs`.rs`. For an example of
 */
 
 mod binary_classifier;
+#[cfg(feature = "train")]
 mod choose_best_split;
+mod compile;
+#[cfg(feature = "train")]
 mod compute_bin_stats;
+#[cfg(feature = "train")]
 mod compute_binned_features;
+#[cfg(feature = "train")]
 mod compute_binning_instructions;
+#[cfg(feature = "train")]
 mod compute_feature_importances;
 mod multiclass_classifier;
+#[cfg(feature = "train")]
 mod rearrange_examples_index;
 mod regressor;
 mod shap;
 #[cfg(feature = "timing")]
 mod timing;
+#[cfg(feature = "train")]
 mod train;
+#[cfg(feature = "train")]
 mod train_tree;
 
 pub use binary_classifier::BinaryClassifier;
+pub use compile::{
+	CompiledBinaryClassifier, CompiledMulticlassClassifier, CompiledNode, CompiledRegressor,
+	CompiledTrees,
+};
+#[cfg(feature = "train")]
+pub use compute_binning_instructions::{compute_binning_instructions, BinningInstruction};
 pub use multiclass_classifier::MulticlassClassifier;
 pub use regressor::Regressor;
 
 /// These are the options passed to `Regressor::train`, `BinaryClassifier::train`, and `MulticlassClassifier::train`.
+#[cfg(feature = "train")]
 #[derive(Clone, Debug)]
 pub struct TrainOptions {
 	/// This option controls whether binned features will be layed out in row major or column major order. Each will produce the same result, but row major will be faster for datasets with more rows and fewer columns, while column major will be faster for datasets with fewer rows and more columns.
@@ -58,6 +74,7 @@ pub struct TrainOptions {
 	pub supplemental_l2_regularization_for_discrete_splits: f32,
 }
 
+#[cfg(feature = "train")]
 impl Default for TrainOptions {
 	fn default() -> TrainOptions {
 		TrainOptions {
@@ -81,6 +98,7 @@ impl Default for TrainOptions {
 }
 
 /// This enum defines whether binned features will be layed out in row major or column major order.
+#[cfg(feature = "train")]
 #[derive(Clone, Copy, Debug)]
 pub enum BinnedFeaturesLayout {
 	RowMajor,
@@ -88,6 +106,7 @@ pub enum BinnedFeaturesLayout {
 }
 
 /// The parameters in this struct control how to determine whether training should stop early after each round. `early_stopping_fraction` is the fraction of the dataset that is set aside to compute the early stopping metric. If `early_stopping_rounds` rounds pass by without an improvement of at least `early_stopping_threshold` in the early stopping metric over the previous round, training will be stopped early.
+#[cfg(feature = "train")]
 #[derive(Clone, Debug)]
 pub struct EarlyStoppingOptions {
 	pub early_stopping_fraction: f32,
@@ -96,6 +115,7 @@ pub struct EarlyStoppingOptions {
 }
 
 /// This struct reports the training progress.
+#[cfg(feature = "train")]
 #[derive(Debug)]
 pub enum TrainProgress {
 	Initializing(tangram_util::progress_counter::ProgressCounter),
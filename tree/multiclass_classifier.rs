@@ -1,15 +1,23 @@
 use crate::{
-	shap::{compute_shap_values_for_example, ComputeShapValuesForExampleOutput},
-	train::TrainOutput,
-	train_tree::TrainTree,
-	TrainOptions, TrainProgress, Tree,
+	shap::{
+		compute_shap_interaction_values_for_example, compute_shap_values_for_example,
+		ComputeShapInteractionValuesForExampleOutput, ComputeShapValuesForExampleOutput,
+	},
+	Tree,
 };
+#[cfg(feature = "train")]
+use crate::{train::TrainOutput, train_tree::TrainTree, TrainOptions, TrainProgress};
 use ndarray::prelude::*;
+#[cfg(feature = "train")]
 use num_traits::{clamp, ToPrimitive};
+#[cfg(any(feature = "train", not(target_arch = "wasm32")))]
 use rayon::prelude::*;
+#[cfg(feature = "train")]
 use std::num::NonZeroUsize;
 use tangram_dataframe::prelude::*;
-use tangram_util::{pzip, zip};
+#[cfg(any(feature = "train", not(target_arch = "wasm32")))]
+use tangram_util::pzip;
+use tangram_util::zip;
 
 /// `MulticlasClassifier`s predict multiclass target values, for example which of several species a flower is.
 #[derive(Debug)]
@@ -25,6 +33,7 @@ pub struct MulticlassClassifier {
 }
 
 /// This struct is returned by `MulticlassClassifier::train`.
+#[cfg(feature = "train")]
 #[derive(Debug)]
 pub struct MulticlassClassifierTrainOutput {
 	/// This is the model you just trained.
@@ -37,6 +46,7 @@ pub struct MulticlassClassifierTrainOutput {
 
 impl MulticlassClassifier {
 	// Train a multiclass classifier.
+	#[cfg(feature = "train")]
 	pub fn train(
 		features: DataFrameView,
 		labels: EnumDataFrameColumnView,
@@ -69,19 +79,29 @@ impl MulticlassClassifier {
 		let n_classes = self.n_classes;
 		let trees = ArrayView2::from_shape((n_rounds, n_classes), &self.trees).unwrap();
 		let biases = ArrayView1::from_shape(n_classes, &self.biases).unwrap();
+		let predict_example =
+			|(mut logits, example): (ArrayViewMut1<f32>, ArrayView1<DataFrameValue>)| {
+				logits.assign(&biases);
+				for trees in trees.axis_iter(Axis(0)) {
+					for (logit, tree) in zip!(logits.iter_mut(), trees.iter()) {
+						*logit += tree.predict(&example.as_slice().unwrap());
+					}
+				}
+				softmax(logits.as_slice_mut().unwrap());
+			};
+		// Rayon cannot spawn threads in WebAssembly, so make predictions on the current thread.
+		#[cfg(not(target_arch = "wasm32"))]
 		pzip!(
 			probabilities.axis_iter_mut(Axis(0)),
 			features.axis_iter(Axis(0))
 		)
-		.for_each(|(mut logits, example)| {
-			logits.assign(&biases);
-			for trees in trees.axis_iter(Axis(0)) {
-				for (logit, tree) in zip!(logits.iter_mut(), trees.iter()) {
-					*logit += tree.predict(&example.as_slice().unwrap());
-				}
-			}
-			softmax(logits.as_slice_mut().unwrap());
-		});
+		.for_each(predict_example);
+		#[cfg(target_arch = "wasm32")]
+		zip!(
+			probabilities.axis_iter_mut(Axis(0)),
+			features.axis_iter(Axis(0))
+		)
+		.for_each(predict_example);
 	}
 
 	/// Compute SHAP values.
@@ -104,9 +124,35 @@ impl MulticlassClassifier {
 			})
 			.collect()
 	}
+
+	/// Compute SHAP interaction values.
+	pub fn compute_feature_interactions(
+		&self,
+		features: ArrayView2<DataFrameValue>,
+	) -> Vec<Vec<ComputeShapInteractionValuesForExampleOutput>> {
+		let n_rounds = self.n_rounds;
+		let n_classes = self.n_classes;
+		let trees = ArrayView2::from_shape((n_rounds, n_classes), &self.trees).unwrap();
+		let biases = ArrayView1::from_shape(n_classes, &self.biases).unwrap();
+		features
+			.axis_iter(Axis(0))
+			.map(|features| {
+				zip!(trees.axis_iter(Axis(1)), biases.iter())
+					.map(|(tree, bias)| {
+						compute_shap_interaction_values_for_example(
+							features.as_slice().unwrap(),
+							tree,
+							*bias,
+						)
+					})
+					.collect()
+			})
+			.collect()
+	}
 }
 
 /// This function is used by the common train function to update the logits after each round of trees is trained for multiclass classification.
+#[cfg(feature = "train")]
 pub fn update_logits(
 	trees_for_round: &[TrainTree],
 	binned_features: ArrayView2<DataFrameValue>,
@@ -122,6 +168,7 @@ pub fn update_logits(
 }
 
 /// This function is used by the common train function to compute the loss after each tree is trained for multiclass classification.
+#[cfg(feature = "train")]
 pub fn compute_loss(logits: ArrayView2<f32>, labels: ArrayView1<Option<NonZeroUsize>>) -> f32 {
 	let mut loss = 0.0;
 	for (label, logits) in zip!(labels.into_iter(), logits.axis_iter(Axis(0))) {
@@ -138,6 +185,7 @@ pub fn compute_loss(logits: ArrayView2<f32>, labels: ArrayView1<Option<NonZeroUs
 }
 
 /// This function is used by the common train function to compute the biases for multiclass classification.
+#[cfg(feature = "train")]
 pub fn compute_biases(
 	labels: ArrayView1<Option<NonZeroUsize>>,
 	n_trees_per_round: usize,
@@ -157,6 +205,7 @@ pub fn compute_biases(
 }
 
 /// This function is used by the common train function to compute the gradients and hessian after each round.
+#[cfg(feature = "train")]
 pub fn compute_gradients_and_hessians(
 	class_index: usize,
 	// (n_examples)
@@ -117,10 +117,10 @@ unsafe fn rearrange_examples_index_serial_continuous<T>(
 			*left += 1;
 		} else {
 			*right -= 1;
-			std::ptr::swap_nonoverlapping(
+			// `left` and `right` are equal when this is the last example, so the pointers may overlap.
+			std::ptr::swap(
 				examples_index.as_mut_ptr().add(*left),
 				examples_index.as_mut_ptr().add(*right),
-				1,
 			);
 		}
 	}
@@ -146,10 +146,10 @@ unsafe fn rearrange_examples_index_serial_discrete<T>(
 			*left += 1;
 		} else {
 			*right -= 1;
-			std::ptr::swap_nonoverlapping(
+			// `left` and `right` are equal when this is the last example, so the pointers may overlap.
+			std::ptr::swap(
 				examples_index.as_mut_ptr().add(*left),
 				examples_index.as_mut_ptr().add(*right),
-				1,
 			);
 		}
 	}
@@ -1,14 +1,26 @@
 use crate::{
-	shap::{compute_shap_values_for_example, ComputeShapValuesForExampleOutput},
+	shap::{
+		compute_shap_interaction_values_for_example, compute_shap_values_for_example,
+		ComputeShapInteractionValuesForExampleOutput, ComputeShapValuesForExampleOutput,
+	},
+	Tree,
+};
+#[cfg(feature = "train")]
+use crate::{
 	train::{train, Task, TrainOutput},
 	train_tree::TrainTree,
-	TrainOptions, TrainProgress, Tree,
+	TrainOptions, TrainProgress,
 };
 use ndarray::prelude::*;
+#[cfg(feature = "train")]
 use num_traits::ToPrimitive;
+#[cfg(any(feature = "train", not(target_arch = "wasm32")))]
 use rayon::prelude::*;
 use tangram_dataframe::prelude::*;
-use tangram_util::{pzip, zip};
+#[cfg(any(feature = "train", not(target_arch = "wasm32")))]
+use tangram_util::pzip;
+#[cfg(any(feature = "train", target_arch = "wasm32"))]
+use tangram_util::zip;
 
 /// `Regressor`s predict continuous target values, for example the selling price of a home.
 #[derive(Debug)]
@@ -20,6 +32,7 @@ pub struct Regressor {
 }
 
 /// This struct is returned by `Regressor::train`.
+#[cfg(feature = "train")]
 #[derive(Debug)]
 pub struct RegressorTrainOutput {
 	/// This is the model you just trained.
@@ -32,6 +45,7 @@ pub struct RegressorTrainOutput {
 
 impl Regressor {
 	/// Train a regressor.
+	#[cfg(feature = "train")]
 	pub fn train(
 		features: DataFrameView,
 		labels: NumberDataFrameColumnView,
@@ -61,11 +75,19 @@ impl Regressor {
 		predictions.fill(self.bias);
 		let predictions = predictions.as_slice_mut().unwrap();
 		for tree in self.trees.iter() {
+			// Rayon cannot spawn threads in WebAssembly, so make predictions on the current thread.
+			#[cfg(not(target_arch = "wasm32"))]
 			pzip!(features.axis_iter(Axis(0)), predictions.par_iter_mut()).for_each(
 				|(example, prediction)| {
 					*prediction += tree.predict(example.as_slice().unwrap());
 				},
-			)
+			);
+			#[cfg(target_arch = "wasm32")]
+			zip!(features.axis_iter(Axis(0)), predictions.iter_mut()).for_each(
+				|(example, prediction)| {
+					*prediction += tree.predict(example.as_slice().unwrap());
+				},
+			);
 		}
 	}
 
@@ -82,9 +104,28 @@ impl Regressor {
 			})
 			.collect()
 	}
+
+	/// Compute SHAP interaction values.
+	pub fn compute_feature_interactions(
+		&self,
+		features: ArrayView2<DataFrameValue>,
+	) -> Vec<ComputeShapInteractionValuesForExampleOutput> {
+		let trees = ArrayView1::from_shape(self.trees.len(), &self.trees).unwrap();
+		features
+			.axis_iter(Axis(0))
+			.map(|features| {
+				compute_shap_interaction_values_for_example(
+					features.as_slice().unwrap(),
+					trees,
+					self.bias,
+				)
+			})
+			.collect()
+	}
 }
 
 /// This function is used by the common train function to update the logits after each round of trees is trained for regression.
+#[cfg(feature = "train")]
 pub fn update_logits(
 	trees_for_round: &[TrainTree],
 	features: ArrayView2<DataFrameValue>,
@@ -98,6 +139,7 @@ pub fn update_logits(
 }
 
 /// This function is used by the common train function to compute the loss after each tree is trained for regression.
+#[cfg(feature = "train")]
 pub fn compute_loss(predictions: ArrayView2<f32>, labels: ArrayView1<f32>) -> f32 {
 	let mut loss = 0.0;
 	for (label, prediction) in zip!(labels, predictions) {
@@ -107,11 +149,13 @@ pub fn compute_loss(predictions: ArrayView2<f32>, labels: ArrayView1<f32>) -> f3
 }
 
 /// This function is used by the common train function to compute the biases for regression.
+#[cfg(feature = "train")]
 pub fn compute_biases(labels: ArrayView1<f32>) -> Array1<f32> {
 	arr1(&[labels.mean().unwrap()])
 }
 
 /// This function is used by the common train function to compute the gradients and hessian after each round.
+#[cfg(feature = "train")]
 pub fn compute_gradients_and_hessians(
 	// (n_examples)
 	gradients: &mut [f32],
@@ -22,7 +22,12 @@ pub fn compute_shap_values_for_example(
 	}
 	let mut feature_contribution_values = vec![0.0; example.len()];
 	for tree in trees {
-		tree_shap(example, tree, feature_contribution_values.as_mut_slice());
+		tree_shap(
+			example,
+			tree,
+			feature_contribution_values.as_mut_slice(),
+			Condition::None,
+		);
 	}
 	let output_value = baseline_value + feature_contribution_values.iter().sum::<f64>();
 	ComputeShapValuesForExampleOutput {
@@ -35,8 +40,110 @@ pub fn compute_shap_values_for_example(
 	}
 }
 
+pub struct ComputeShapInteractionValuesForExampleOutput {
+	pub baseline_value: f32,
+	pub output_value: f32,
+	/// These are the indexes of the features the trees split on, in increasing order. The SHAP interaction values for every other feature are zero, so they are not stored.
+	pub feature_indexes: Vec<usize>,
+	/// These are the SHAP interaction values for each pair of features in `feature_indexes`, in row major order. The value at (i, i) is the main effect of feature i, the value at (i, j) is half of the interaction between features i and j, and each row sums to the feature's SHAP value.
+	pub shap_interaction_values: Vec<f32>,
+}
+
+/// Compute the SHAP interaction values for a single class for a single example.
+pub fn compute_shap_interaction_values_for_example(
+	example: &[tangram_dataframe::DataFrameValue],
+	trees: ArrayView1<Tree>,
+	bias: f32,
+) -> ComputeShapInteractionValuesForExampleOutput {
+	let mut baseline_value = bias as f64;
+	for tree in trees {
+		baseline_value += compute_expectation(tree, 0);
+	}
+	let tree_feature_indexes = trees
+		.iter()
+		.map(compute_feature_indexes)
+		.collect::<Vec<_>>();
+	let mut feature_indexes = tree_feature_indexes
+		.iter()
+		.flatten()
+		.cloned()
+		.collect::<Vec<_>>();
+	feature_indexes.sort_unstable();
+	feature_indexes.dedup();
+	let n_features = feature_indexes.len();
+	let mut shap_interaction_values = vec![0.0; n_features * n_features];
+	let mut phi = vec![0.0; example.len()];
+	let mut phi_on = vec![0.0; example.len()];
+	let mut phi_off = vec![0.0; example.len()];
+	for (tree, tree_feature_indexes) in trees.iter().zip(tree_feature_indexes.iter()) {
+		// Start each feature's main effect at its SHAP value. The interactions are subtracted from it below.
+		phi.iter_mut().for_each(|value| *value = 0.0);
+		tree_shap(example, tree, phi.as_mut_slice(), Condition::None);
+		for (i, feature_index) in feature_indexes.iter().enumerate() {
+			shap_interaction_values[i * n_features + i] += phi[*feature_index];
+		}
+		// The interaction between features i and j is half the difference between the SHAP values of feature j with feature i always present and with feature i always missing. Conditioning on a feature the tree does not split on changes nothing, so those features are skipped.
+		for (i, feature_index) in feature_indexes.iter().enumerate() {
+			if tree_feature_indexes.binary_search(feature_index).is_err() {
+				continue;
+			}
+			phi_on.iter_mut().for_each(|value| *value = 0.0);
+			phi_off.iter_mut().for_each(|value| *value = 0.0);
+			tree_shap(
+				example,
+				tree,
+				phi_on.as_mut_slice(),
+				Condition::On(*feature_index),
+			);
+			tree_shap(
+				example,
+				tree,
+				phi_off.as_mut_slice(),
+				Condition::Off(*feature_index),
+			);
+			for (j, other_feature_index) in feature_indexes.iter().enumerate() {
+				let value = (phi_on[*other_feature_index] - phi_off[*other_feature_index]) / 2.0;
+				shap_interaction_values[i * n_features + j] += value;
+				shap_interaction_values[i * n_features + i] -= value;
+			}
+		}
+	}
+	let output_value = baseline_value + shap_interaction_values.iter().sum::<f64>();
+	ComputeShapInteractionValuesForExampleOutput {
+		baseline_value: baseline_value.to_f32().unwrap(),
+		output_value: output_value.to_f32().unwrap(),
+		feature_indexes,
+		shap_interaction_values: shap_interaction_values
+			.iter()
+			.map(|value| value.to_f32().unwrap())
+			.collect(),
+	}
+}
+
+/// This determines whether `tree_shap` computes SHAP values as usual, or with one feature always present or always missing, which is used to compute SHAP interaction values.
+#[derive(Clone, Copy)]
+enum Condition {
+	None,
+	On(usize),
+	Off(usize),
+}
+
+impl Condition {
+	fn feature_index(self) -> Option<usize> {
+		match self {
+			Condition::None => None,
+			Condition::On(feature_index) | Condition::Off(feature_index) => Some(feature_index),
+		}
+	}
+}
+
 /// This function, and the helper functions below it, are a direct port from https://github.com/slundberg/shap.
-fn tree_shap(example: &[tangram_dataframe::DataFrameValue], tree: &Tree, phi: &mut [f64]) {
+fn tree_shap(
+	example: &[tangram_dataframe::DataFrameValue],
+	tree: &Tree,
+	phi: &mut [f64],
+	condition: Condition,
+) {
 	let max_depth = max_depth(tree, 0, 0) + 2;
 	let mut unique_path = vec![PathItem::new(); max_depth * (max_depth + 1) / 2];
 	tree_shap_recursive(TreeShapRecursiveOptions {
@@ -49,6 +156,8 @@ fn tree_shap(example: &[tangram_dataframe::DataFrameValue], tree: &Tree, phi: &m
 		parent_zero_fraction: 1.0,
 		parent_one_fraction: 1.0,
 		parent_feature_index: None,
+		condition,
+		condition_fraction: 1.0,
 	});
 }
 
@@ -72,6 +181,8 @@ impl PathItem {
 }
 
 struct TreeShapRecursiveOptions<'a> {
+	condition: Condition,
+	condition_fraction: f64,
 	example: &'a [tangram_dataframe::DataFrameValue<'a>],
 	node_index: usize,
 	parent_feature_index: Option<usize>,
@@ -85,6 +196,8 @@ struct TreeShapRecursiveOptions<'a> {
 
 fn tree_shap_recursive(options: TreeShapRecursiveOptions) {
 	let TreeShapRecursiveOptions {
+		condition,
+		condition_fraction,
 		example,
 		node_index,
 		parent_feature_index,
@@ -95,13 +208,19 @@ fn tree_shap_recursive(options: TreeShapRecursiveOptions) {
 		unique_depth,
 		unique_path,
 	} = options;
-	extend_path(ExtendPathOptions {
-		unique_path,
-		unique_depth,
-		zero_fraction: parent_zero_fraction,
-		one_fraction: parent_one_fraction,
-		feature_index: parent_feature_index,
-	});
+	if condition_fraction == 0.0 {
+		return;
+	}
+	// The feature that is conditioned on is never added to the path.
+	if condition.feature_index().is_none() || condition.feature_index() != parent_feature_index {
+		extend_path(ExtendPathOptions {
+			unique_path,
+			unique_depth,
+			zero_fraction: parent_zero_fraction,
+			one_fraction: parent_one_fraction,
+			feature_index: parent_feature_index,
+		});
+	}
 	let mut unique_depth = unique_depth;
 	let node = &tree.nodes[node_index];
 	match node {
@@ -110,7 +229,8 @@ fn tree_shap_recursive(options: TreeShapRecursiveOptions) {
 				let weight = unwound_path_sum(unique_path, unique_depth, path_index);
 				let path_item = &unique_path[path_index];
 				let scale = weight * (path_item.one_fraction - path_item.zero_fraction);
-				phi[path_item.feature_index.unwrap()] += scale * node.value as f64;
+				phi[path_item.feature_index.unwrap()] +=
+					scale * node.value as f64 * condition_fraction;
 			}
 		}
 		Node::Branch(node) => {
@@ -131,27 +251,51 @@ fn tree_shap_recursive(options: TreeShapRecursiveOptions) {
 				unique_depth -= 1;
 			};
 			let feature_index = node.split.feature_index();
+			// Divide the condition fraction among the children. If this node splits on the feature that is conditioned on, the children do not add it to the path, so their depth is not incremented.
+			let mut hot_condition_fraction = condition_fraction;
+			let mut cold_condition_fraction = condition_fraction;
+			let mut child_unique_depth = unique_depth + 1;
+			match condition {
+				Condition::On(condition_feature_index)
+					if condition_feature_index == feature_index =>
+				{
+					cold_condition_fraction = 0.0;
+					child_unique_depth = unique_depth;
+				}
+				Condition::Off(condition_feature_index)
+					if condition_feature_index == feature_index =>
+				{
+					hot_condition_fraction *= hot_zero_fraction;
+					cold_condition_fraction *= cold_zero_fraction;
+					child_unique_depth = unique_depth;
+				}
+				_ => {}
+			}
 			let (parent_path, child_path) = unique_path.split_at_mut(unique_depth + 1);
 			child_path[0..parent_path.len()].clone_from_slice(parent_path);
 			tree_shap_recursive(TreeShapRecursiveOptions {
+				condition,
+				condition_fraction: hot_condition_fraction,
 				phi,
 				example,
 				tree,
 				node_index: hot_child_index,
 				unique_path: child_path,
-				unique_depth: unique_depth + 1,
+				unique_depth: child_unique_depth,
 				parent_zero_fraction: hot_zero_fraction * incoming_zero_fraction,
 				parent_one_fraction: incoming_one_fraction,
 				parent_feature_index: Some(feature_index),
 			});
 			child_path[0..parent_path.len()].clone_from_slice(parent_path);
 			tree_shap_recursive(TreeShapRecursiveOptions {
+				condition,
+				condition_fraction: cold_condition_fraction,
 				phi,
 				example,
 				tree,
 				node_index: cold_child_index,
 				unique_path: child_path,
-				unique_depth: unique_depth + 1,
+				unique_depth: child_unique_depth,
 				parent_zero_fraction: cold_zero_fraction * incoming_zero_fraction,
 				parent_one_fraction: 0.0,
 				parent_feature_index: Some(feature_index),
@@ -270,7 +414,9 @@ fn compute_hot_cold_child(
 			directions,
 		}) => match example[*feature_index] {
 			tangram_dataframe::DataFrameValue::Enum(value) => {
-				if *directions.get(value.unwrap().get()).unwrap() == SplitDirection::Left {
+				// Missing enum values go to bin 0, the same as in `Tree::predict`.
+				let bin_index = value.map(|value| value.get()).unwrap_or(0);
+				if *directions.get(bin_index).unwrap() == SplitDirection::Left {
 					(node.left_child_index, node.right_child_index)
 				} else {
 					(node.right_child_index, node.left_child_index)
@@ -294,6 +440,19 @@ fn max_depth(tree: &Tree, node_index: usize, depth: usize) -> usize {
 	usize::max(left_depth, right_depth) + 1
 }
 
+/// Retrieve the indexes of the features `tree` splits on, in increasing order.
+fn compute_feature_indexes(tree: &Tree) -> Vec<usize> {
+	let mut feature_indexes = tree
+		.nodes
+		.iter()
+		.filter_map(|node| node.as_branch())
+		.map(|node| node.split.feature_index())
+		.collect::<Vec<_>>();
+	feature_indexes.sort_unstable();
+	feature_indexes.dedup();
+	feature_indexes
+}
+
 fn compute_expectation(tree: &Tree, node_index: usize) -> f64 {
 	let current_node = &tree.nodes[node_index];
 	if let Node::Leaf(n) = current_node {
@@ -310,3 +469,116 @@ fn compute_expectation(tree: &Tree, node_index: usize) -> f64 {
 		+ (right_child.examples_fraction() as f64 / current_node.examples_fraction as f64)
 			* right_value
 }
+
+#[cfg(test)]
+fn test_branch(
+	feature_index: usize,
+	split_value: f32,
+	left_child_index: usize,
+	right_child_index: usize,
+	examples_fraction: f32,
+) -> Node {
+	Node::Branch(BranchNode {
+		left_child_index,
+		right_child_index,
+		split: BranchSplit::Continuous(BranchSplitContinuous {
+			feature_index,
+			split_value,
+			invalid_values_direction: SplitDirection::Left,
+		}),
+		examples_fraction,
+	})
+}
+
+#[cfg(test)]
+fn test_leaf(value: f64, examples_fraction: f32) -> Node {
+	Node::Leaf(crate::LeafNode {
+		value,
+		examples_fraction,
+	})
+}
+
+/// The first tree splits on feature 0 and then on feature 1, with leaf values that make the features interact. The second tree splits on feature 2, and no tree splits on feature 3.
+#[cfg(test)]
+fn test_trees() -> Array1<Tree> {
+	Array::from(vec![
+		Tree {
+			nodes: vec![
+				test_branch(0, 0.5, 1, 2, 1.0),
+				test_branch(1, 0.5, 3, 4, 0.6),
+				test_branch(1, 0.5, 5, 6, 0.4),
+				test_leaf(1.0, 0.3),
+				test_leaf(2.0, 0.3),
+				test_leaf(3.0, 0.1),
+				test_leaf(8.0, 0.3),
+			],
+		},
+		Tree {
+			nodes: vec![
+				test_branch(2, 1.0, 1, 2, 1.0),
+				test_leaf(-1.0, 0.5),
+				test_leaf(1.0, 0.5),
+			],
+		},
+	])
+}
+
+#[cfg(test)]
+fn test_example() -> Vec<tangram_dataframe::DataFrameValue<'static>> {
+	vec![
+		tangram_dataframe::DataFrameValue::Number(1.0),
+		tangram_dataframe::DataFrameValue::Number(0.0),
+		tangram_dataframe::DataFrameValue::Number(2.0),
+		tangram_dataframe::DataFrameValue::Number(7.0),
+	]
+}
+
+#[test]
+fn test_shap_interaction_values_rows_sum_to_shap_values() {
+	let trees = test_trees();
+	let example = test_example();
+	let shap_values = compute_shap_values_for_example(&example, trees.view(), 0.5);
+	let output = compute_shap_interaction_values_for_example(&example, trees.view(), 0.5);
+	assert_eq!(output.feature_indexes, vec![0, 1, 2]);
+	let n_features = output.feature_indexes.len();
+	for (i, feature_index) in output.feature_indexes.iter().enumerate() {
+		let row_sum = output.shap_interaction_values[i * n_features..(i + 1) * n_features]
+			.iter()
+			.sum::<f32>();
+		assert!(f32::abs(row_sum - shap_values.feature_contribution_values[*feature_index]) < 1e-5);
+	}
+	// Feature 3 is not split on, so its SHAP value is zero.
+	assert!(f32::abs(shap_values.feature_contribution_values[3]) < f32::EPSILON);
+}
+
+#[test]
+fn test_shap_interaction_values_sum_to_prediction() {
+	let trees = test_trees();
+	let example = test_example();
+	let output = compute_shap_interaction_values_for_example(&example, trees.view(), 0.5);
+	let prediction = 0.5 + trees.iter().map(|tree| tree.predict(&example)).sum::<f32>();
+	let total = output.shap_interaction_values.iter().sum::<f32>();
+	assert!(f32::abs(output.baseline_value + total - prediction) < 1e-5);
+	assert!(f32::abs(output.output_value - prediction) < 1e-5);
+	// The baseline is the bias plus the expected value of each tree, and the second tree's expected value is zero.
+	let baseline_value = 0.5 + (0.3 * 1.0 + 0.3 * 2.0 + 0.1 * 3.0 + 0.3 * 8.0);
+	assert!(f32::abs(output.baseline_value - baseline_value) < 1e-5);
+}
+
+#[test]
+fn test_shap_interaction_values_are_symmetric() {
+	let trees = test_trees();
+	let example = test_example();
+	let output = compute_shap_interaction_values_for_example(&example, trees.view(), 0.5);
+	let n_features = output.feature_indexes.len();
+	let values = &output.shap_interaction_values;
+	for i in 0..n_features {
+		for j in 0..n_features {
+			assert!(f32::abs(values[i * n_features + j] - values[j * n_features + i]) < 1e-5);
+		}
+	}
+	// Features 0 and 1 interact, and neither interacts with feature 2, which is in a different tree.
+	assert!(f32::abs(values[1]) > 1e-3);
+	assert!(f32::abs(values[2]) < 1e-5);
+	assert!(f32::abs(values[n_features + 2]) < 1e-5);
+}
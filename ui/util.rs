@@ -1,4 +1,4 @@
-use num_traits::Num;
+use num_traits::{Num, ToPrimitive};
 
 pub fn format_number<T: Num + std::fmt::Display>(value: T) -> String {
 	value.to_string()
@@ -16,6 +16,21 @@ pub fn format_percent_with_precision(value: f32, precision: usize) -> String {
 	}
 }
 
+/// Format a value with the margin of error of its confidence interval, as in "0.87 ± 0.02". The value and the margin are rounded to the first significant digit of the margin. If there is no margin, only the value is formatted.
+pub fn format_number_with_margin(value: f32, margin: Option<f32>) -> String {
+	match margin {
+		Some(margin) if margin.is_finite() => {
+			let precision = if margin > 0.0 {
+				(-margin.log10().floor()).max(0.0).to_usize().unwrap()
+			} else {
+				2
+			};
+			format!("{:.2$} ± {:.2$}", value, margin, precision)
+		}
+		_ => format_number(value),
+	}
+}
+
 #[test]
 fn test_format_percent() {
 	assert_eq!(format_percent(0.0), "0.00%");
@@ -23,3 +38,15 @@ fn test_format_percent() {
 	assert_eq!(format_percent_with_precision(0.424292, 3), "42.429%");
 	assert_eq!(format_percent(1.00), "100%");
 }
+
+#[test]
+fn test_format_number_with_margin() {
+	assert_eq!(
+		format_number_with_margin(0.8734, Some(0.0213)),
+		"0.87 ± 0.02"
+	);
+	assert_eq!(format_number_with_margin(1234.5, Some(12.3)), "1234 ± 12");
+	assert_eq!(format_number_with_margin(1.0, Some(0.0)), "1.00 ± 0.00");
+	assert_eq!(format_number_with_margin(0.5, None), "0.5");
+	assert_eq!(format_number_with_margin(0.5, Some(f32::NAN)), "0.5");
+}
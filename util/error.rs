@@ -1,4 +1,4 @@
-use tangram_deps::backtrace::Backtrace;
+use backtrace::Backtrace;
 
 #[macro_export]
 macro_rules! err {
@@ -2,8 +2,6 @@
 Tangram uses the `Id` type to uniquely identify models, users, and anything that needs a primary key. This type is almost identical to a UUID v4, except there are no bits reserved to specify the version, and the string representation has no dashes. We do not like dashes in our unique identifiers.
 */
 
-use tangram_deps::{rand, serde};
-
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Id(u128);
 
@@ -5,6 +5,7 @@ pub mod id;
 pub mod pool;
 pub mod progress_counter;
 pub mod pzip;
+#[cfg(feature = "serve")]
 pub mod serve;
 pub mod super_unsafe;
 pub mod table;
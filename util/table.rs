@@ -1,5 +1,5 @@
 use crate::zip;
-use tangram_deps::ndarray::prelude::*;
+use ndarray::prelude::*;
 
 pub struct Table<'a> {
 	padding: usize,